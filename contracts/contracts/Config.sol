@@ -40,12 +40,21 @@ contract Config {
     /// If set to 0 validator can revert blocks at any time.
     uint256 constant EXPECT_VERIFICATION_IN = 0 hours / BLOCK_PERIOD;
 
+    /// @notice Max time difference between the block timestamp and the moment of the block commitment
+    /// (block can't be committed if its timestamp is older than that)
+    uint256 constant COMMIT_TIMESTAMP_NOT_OLDER = 8 hours;
+
+    /// @notice Max allowed time difference between the block timestamp and the Ethereum block timestamp
+    /// in the future (to account for the clock differences)
+    uint256 constant COMMIT_TIMESTAMP_APPROXIMATION_DELTA = 15 minutes;
+
     uint256 constant NOOP_BYTES = 1 * CHUNK_BYTES;
     uint256 constant DEPOSIT_BYTES = 6 * CHUNK_BYTES;
     uint256 constant TRANSFER_TO_NEW_BYTES = 6 * CHUNK_BYTES;
     uint256 constant PARTIAL_EXIT_BYTES = 6 * CHUNK_BYTES;
     uint256 constant TRANSFER_BYTES = 2 * CHUNK_BYTES;
     uint256 constant FORCED_EXIT_BYTES = 6 * CHUNK_BYTES;
+    uint256 constant SWAP_BYTES = 5 * CHUNK_BYTES;
 
     /// @notice Full exit operation length
     uint256 constant FULL_EXIT_BYTES = 6 * CHUNK_BYTES;
@@ -16,7 +16,8 @@ library Operations {
         Transfer,
         FullExit,
         ChangePubKey,
-        ForcedExit
+        ForcedExit,
+        Swap
     }
 
     // Byte lengths
@@ -251,7 +251,7 @@ contract ZkSync is UpgradeableMaster, Storage, Config, Events, ReentrancyGuard {
     /// @notice Commit block - collect onchain operations, create its commitment, emit BlockCommit event
     /// @param _blockNumber Block number
     /// @param _feeAccount Account to collect fees
-    /// @param _newBlockInfo New state of the block. (first element is the account tree root hash, rest of the array is reserved for the future)
+    /// @param _newBlockInfo New state of the block. (first element is the account tree root hash, second element is the block timestamp, rest of the array is reserved for the future)
     /// @param _publicData Operations pubdata
     /// @param _ethWitness Data passed to ethereum outside pubdata of the circuit.
     /// @param _ethWitnessSizes Amount of eth witness bytes for the corresponding operation.
@@ -266,7 +266,11 @@ contract ZkSync is UpgradeableMaster, Storage, Config, Events, ReentrancyGuard {
         requireActive();
         require(_blockNumber == totalBlocksCommitted + 1, "fck11"); // only commit next block
         governance.requireActiveValidator(msg.sender);
-        require(_newBlockInfo.length == 1, "fck13"); // This version of the contract expects only account tree root hash
+        require(_newBlockInfo.length == 2, "fck13"); // This version of the contract expects account tree root hash and block timestamp
+
+        uint256 timestamp = uint256(_newBlockInfo[1]);
+        require(timestamp >= block.timestamp.sub(COMMIT_TIMESTAMP_NOT_OLDER), "fck14"); // Block is too old
+        require(timestamp <= block.timestamp.add(COMMIT_TIMESTAMP_APPROXIMATION_DELTA), "fck15"); // Block timestamp is in the future
 
         bytes memory publicData = _publicData;
 
@@ -282,6 +286,7 @@ contract ZkSync is UpgradeableMaster, Storage, Config, Events, ReentrancyGuard {
             _blockNumber,
             _feeAccount,
             _newBlockInfo[0],
+            timestamp,
             publicData,
             withdrawalsDataHash,
             nPriorityRequestProcessed
@@ -443,11 +448,13 @@ contract ZkSync is UpgradeableMaster, Storage, Config, Events, ReentrancyGuard {
     }
 
     /// @notice Store committed block structure to the storage.
+    /// @param _timestamp - block timestamp
     /// @param _nCommittedPriorityRequests - number of priority requests in block
     function createCommittedBlock(
         uint32 _blockNumber,
         uint32 _feeAccount,
         bytes32 _newRoot,
+        uint256 _timestamp,
         bytes memory _publicData,
         bytes32 _withdrawalDataHash,
         uint64 _nCommittedPriorityRequests
@@ -459,7 +466,14 @@ contract ZkSync is UpgradeableMaster, Storage, Config, Events, ReentrancyGuard {
 
         // Create block commitment for verification proof
         bytes32 commitment =
-            createBlockCommitment(_blockNumber, _feeAccount, blocks[_blockNumber - 1].stateRoot, _newRoot, _publicData);
+            createBlockCommitment(
+                _blockNumber,
+                _feeAccount,
+                blocks[_blockNumber - 1].stateRoot,
+                _newRoot,
+                _timestamp,
+                _publicData
+            );
 
         blocks[_blockNumber] = Block(
             uint32(block.number), // committed at
@@ -536,6 +550,8 @@ contract ZkSync is UpgradeableMaster, Storage, Config, Events, ReentrancyGuard {
                 pubDataPtr += NOOP_BYTES;
             } else if (opType == Operations.OpType.TransferToNew) {
                 pubDataPtr += TRANSFER_TO_NEW_BYTES;
+            } else if (opType == Operations.OpType.Swap) {
+                pubDataPtr += SWAP_BYTES;
             } else {
                 // other operations processing
 
@@ -687,6 +703,7 @@ contract ZkSync is UpgradeableMaster, Storage, Config, Events, ReentrancyGuard {
     /// @param _feeAccount Account to collect fees
     /// @param _oldRoot Old tree root
     /// @param _newRoot New tree root
+    /// @param _timestamp Block timestamp
     /// @param _publicData Operations pubdata
     /// @return block commitment
     function createBlockCommitment(
@@ -694,11 +711,13 @@ contract ZkSync is UpgradeableMaster, Storage, Config, Events, ReentrancyGuard {
         uint32 _feeAccount,
         bytes32 _oldRoot,
         bytes32 _newRoot,
+        uint256 _timestamp,
         bytes memory _publicData
     ) internal view returns (bytes32 commitment) {
         bytes32 hash = sha256(abi.encodePacked(uint256(_blockNumber), uint256(_feeAccount)));
         hash = sha256(abi.encodePacked(hash, uint256(_oldRoot)));
         hash = sha256(abi.encodePacked(hash, uint256(_newRoot)));
+        hash = sha256(abi.encodePacked(hash, _timestamp));
 
         /// The code below is equivalent to `commitment = sha256(abi.encodePacked(hash, _publicData))`
 
@@ -0,0 +1,242 @@
+//! Deterministic replay of the committed blocks.
+//!
+//! Operations of the stored blocks are executed once again on top of the state of the
+//! preceding block. Account updates made by every operation are compared with the stored
+//! ones, so if the resulting root hash of the block doesn't match the stored one, the first
+//! operation that caused the divergence can be found.
+//!
+//! Transactions of the same batch are executed together, since the batch is applied atomically:
+//! failure of any transaction reverts the whole batch.
+
+// Built-in deps
+use std::fmt::{self, Display};
+// Workspace deps
+use zksync_crypto::Fr;
+use zksync_state::state::{OpSuccess, ZkSyncState};
+use zksync_types::{
+    block::{Block, ExecutedTx},
+    AccountId, AccountMap, AccountUpdate, AccountUpdates, BlockNumber, ExecutedOperations,
+    SignedZkSyncTx,
+};
+
+#[cfg(test)]
+mod tests;
+
+/// Result of the block operation which differs from the stored one.
+#[derive(Debug)]
+pub struct Divergence {
+    pub block_number: BlockNumber,
+    /// Index of the operation in the block. `None` if the divergence wasn't caused
+    /// by a certain operation (e.g. by the collected fees or the root hash).
+    pub op_index: Option<usize>,
+    /// Human-readable description of the divergence.
+    pub reason: String,
+    /// Account updates obtained during the replay.
+    pub replayed_updates: AccountUpdates,
+    /// Account updates stored in the database.
+    pub stored_updates: AccountUpdates,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.op_index {
+            Some(op_index) => writeln!(
+                f,
+                "Block #{}, operation #{}: {}",
+                self.block_number, op_index, self.reason
+            )?,
+            None => writeln!(f, "Block #{}: {}", self.block_number, self.reason)?,
+        }
+        writeln!(f, "Replayed account updates: {:#?}", self.replayed_updates)?;
+        write!(f, "Stored account updates: {:#?}", self.stored_updates)
+    }
+}
+
+/// Re-executes the stored blocks one by one, starting from the given state.
+pub struct BlockReplayer {
+    state: ZkSyncState,
+}
+
+impl BlockReplayer {
+    /// Creates a replayer for the blocks following the `block_number`, which resulted in `accounts`.
+    pub fn new(block_number: BlockNumber, accounts: AccountMap) -> Self {
+        Self {
+            state: ZkSyncState::from_acc_map(accounts, block_number + 1),
+        }
+    }
+
+    /// Returns the root hash of the replayed state.
+    pub fn root_hash(&self) -> Fr {
+        self.state.root_hash()
+    }
+
+    /// Executes operations of the block, checking that they produce exactly the `stored_updates`
+    /// and result in the stored root hash.
+    ///
+    /// Blocks must be replayed in order. Once the divergence is found, the state of the replayer
+    /// is no longer consistent, so the further blocks can't be replayed.
+    pub fn replay_block(
+        &mut self,
+        block: &Block,
+        stored_updates: &[(AccountId, AccountUpdate)],
+    ) -> Result<(), Box<Divergence>> {
+        assert_eq!(
+            block.block_number, self.state.block_number,
+            "Blocks must be replayed in order"
+        );
+
+        let divergence = |op_index, reason: String, replayed_updates, stored_updates: &[_]| {
+            Box::new(Divergence {
+                block_number: block.block_number,
+                op_index,
+                reason,
+                replayed_updates,
+                stored_updates: stored_updates.to_vec(),
+            })
+        };
+
+        let mut stored_updates = stored_updates;
+        let mut collected_fees = Vec::new();
+        let mut first_op_index = 0;
+        while first_op_index < block.block_transactions.len() {
+            let operations = &block.block_transactions[first_op_index..];
+            let operations = &operations[..Self::batch_len(operations)];
+            let results = self.execute_operations(operations);
+
+            for (op_index, result) in (first_op_index..).zip(results) {
+                let (fee, updates) = match result {
+                    Ok(Some(OpSuccess { fee, updates, .. })) => (fee, updates),
+                    // Operation failed both during the execution and the replay.
+                    Ok(None) => continue,
+                    Err(reason) => {
+                        return Err(divergence(
+                            Some(op_index),
+                            reason,
+                            Vec::new(),
+                            stored_updates,
+                        ))
+                    }
+                };
+
+                let (op_updates, rest) =
+                    stored_updates.split_at(updates.len().min(stored_updates.len()));
+                if updates.as_slice() != op_updates {
+                    return Err(divergence(
+                        Some(op_index),
+                        "account updates don't match".to_string(),
+                        updates,
+                        op_updates,
+                    ));
+                }
+                stored_updates = rest;
+                collected_fees.extend(fee);
+            }
+            first_op_index += operations.len();
+        }
+
+        let fee_updates = self.state.collect_fee(&collected_fees, block.fee_account);
+        if fee_updates.as_slice() != stored_updates {
+            return Err(divergence(
+                None,
+                "collected fees don't match".to_string(),
+                fee_updates,
+                stored_updates,
+            ));
+        }
+
+        let root_hash = self.state.root_hash();
+        if root_hash != block.new_root_hash {
+            return Err(divergence(
+                None,
+                format!(
+                    "root hash mismatch: replayed {}, stored {}",
+                    root_hash, block.new_root_hash
+                ),
+                Vec::new(),
+                &[],
+            ));
+        }
+
+        self.state.block_number += 1;
+        Ok(())
+    }
+
+    /// Returns the number of the leading operations which have to be executed together:
+    /// the length of the transactions batch, or 1 for the operation outside of the batch.
+    fn batch_len(operations: &[ExecutedOperations]) -> usize {
+        let batch_id = match &operations[0] {
+            ExecutedOperations::Tx(tx) => tx.batch_id,
+            ExecutedOperations::PriorityOp(_) => None,
+        };
+
+        match batch_id {
+            Some(batch_id) => operations
+                .iter()
+                .take_while(|operation| match operation {
+                    ExecutedOperations::Tx(tx) => tx.batch_id == Some(batch_id),
+                    ExecutedOperations::PriorityOp(_) => false,
+                })
+                .count(),
+            None => 1,
+        }
+    }
+
+    /// Executes the stored operations and returns the result for each of them, or `None` if
+    /// the operation has failed as expected. Returns an error if the outcome doesn't match
+    /// the stored one.
+    ///
+    /// Operations are either a single operation, or the transactions of the same batch.
+    fn execute_operations(
+        &mut self,
+        operations: &[ExecutedOperations],
+    ) -> Vec<Result<Option<OpSuccess>, String>> {
+        match operations {
+            [ExecutedOperations::PriorityOp(op)] => vec![Ok(Some(
+                self.state.execute_priority_op(op.priority_op.data.clone()),
+            ))],
+            [ExecutedOperations::Tx(tx)] if tx.batch_id.is_none() => {
+                let result = self.state.execute_tx(tx.signed_tx.tx.clone());
+                vec![Self::check_tx_outcome(tx, result)]
+            }
+            _ => {
+                let txs: Vec<&ExecutedTx> = operations
+                    .iter()
+                    .map(|operation| match operation {
+                        ExecutedOperations::Tx(tx) => tx.as_ref(),
+                        ExecutedOperations::PriorityOp(_) => {
+                            panic!("Priority operation can't be a part of the batch")
+                        }
+                    })
+                    .collect();
+                let signed_txs: Vec<SignedZkSyncTx> =
+                    txs.iter().map(|tx| tx.signed_tx.clone()).collect();
+
+                let results = self.state.execute_txs_batch(&signed_txs);
+                txs.into_iter()
+                    .zip(results)
+                    .map(|(tx, result)| Self::check_tx_outcome(tx, result))
+                    .collect()
+            }
+        }
+    }
+
+    /// Compares the result of the transaction replay with the stored one.
+    fn check_tx_outcome<E: Display>(
+        tx: &ExecutedTx,
+        result: Result<OpSuccess, E>,
+    ) -> Result<Option<OpSuccess>, String> {
+        match (result, tx.success) {
+            (Ok(op_success), true) => Ok(Some(op_success)),
+            (Err(_), false) => Ok(None),
+            (Ok(_), false) => Err(format!(
+                "transaction {} has failed, but succeeds during the replay",
+                tx.signed_tx.hash().to_string()
+            )),
+            (Err(e), true) => Err(format!(
+                "transaction {} has succeeded, but fails during the replay: {}",
+                tx.signed_tx.hash().to_string(),
+                e
+            )),
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use anyhow::{ensure, format_err};
+use structopt::StructOpt;
+use zksync_block_replay::BlockReplayer;
+use zksync_storage::ConnectionPool;
+use zksync_types::BlockNumber;
+
+#[derive(StructOpt)]
+#[structopt(
+    name = "Block replay",
+    author = "Matter Labs",
+    rename_all = "snake_case"
+)]
+struct Opt {
+    /// First block to be replayed
+    #[structopt(long)]
+    from: BlockNumber,
+
+    /// Last block to be replayed. Defaults to the last committed block
+    #[structopt(long)]
+    to: Option<BlockNumber>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    env_logger::init();
+    let opt = Opt::from_args();
+    ensure!(opt.from > 0, "Genesis block can't be replayed");
+
+    let connection_pool = ConnectionPool::new(Some(1));
+    let mut storage = connection_pool.access_storage().await?;
+
+    let to = match opt.to {
+        Some(to) => to,
+        None => {
+            storage
+                .chain()
+                .block_schema()
+                .get_last_committed_block()
+                .await?
+        }
+    };
+    ensure!(
+        opt.from <= to,
+        "Nothing to replay: the last block #{} precedes the first block #{}",
+        to,
+        opt.from
+    );
+
+    let (state_block, accounts) = storage
+        .chain()
+        .state_schema()
+        .load_committed_state(Some(opt.from - 1))
+        .await?;
+    ensure!(
+        state_block == opt.from - 1,
+        "State for the block #{} is not found",
+        opt.from - 1
+    );
+
+    log::info!("Replaying blocks #{}..=#{}", opt.from, to);
+    let mut replayer = BlockReplayer::new(state_block, accounts);
+    for block_number in opt.from..=to {
+        let block = storage
+            .chain()
+            .block_schema()
+            .get_block(block_number)
+            .await?
+            .ok_or_else(|| format_err!("Block #{} is not found", block_number))?;
+        let stored_updates = storage
+            .chain()
+            .state_schema()
+            .load_state_diff_for_block(block_number)
+            .await?;
+
+        if let Err(divergence) = replayer.replay_block(&block, &stored_updates) {
+            println!("{}", divergence);
+            anyhow::bail!("Block #{} diverged during the replay", block_number);
+        }
+        println!("Block #{}: OK", block_number);
+    }
+
+    println!(
+        "All blocks are replayed successfully, root hash: {}",
+        replayer.root_hash()
+    );
+    Ok(())
+}
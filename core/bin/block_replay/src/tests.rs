@@ -0,0 +1,212 @@
+use super::*;
+use chrono::Utc;
+use num::BigUint;
+use zksync_crypto::{
+    ff::Field,
+    priv_key_from_fs,
+    rand::{Rng, SeedableRng, XorShiftRng},
+};
+use zksync_types::{
+    block::ExecutedPriorityOp, priority_ops::Deposit, Account, AccountUpdate, Address, PriorityOp,
+    PubKeyHash, Transfer, ZkSyncPriorityOp, ZkSyncTx,
+};
+
+const FEE_ACCOUNT_ID: AccountId = 0;
+
+/// Initial state containing only the fee account.
+fn initial_accounts() -> AccountMap {
+    let mut accounts = AccountMap::default();
+    accounts.insert(
+        FEE_ACCOUNT_ID,
+        Account::default_with_address(&Address::zero()),
+    );
+    accounts
+}
+
+fn deposit(serial_id: u64, to: Address) -> PriorityOp {
+    PriorityOp {
+        serial_id,
+        data: ZkSyncPriorityOp::Deposit(Deposit {
+            from: to,
+            to,
+            amount: BigUint::from(100u32),
+            token: 0,
+        }),
+        deadline_block: 0,
+        eth_hash: Vec::new(),
+        eth_block: 0,
+    }
+}
+
+/// Executes deposits to the random addresses and creates a block as the state keeper does.
+fn create_block(
+    state: &mut ZkSyncState,
+    rng: &mut XorShiftRng,
+    deposits_count: u64,
+) -> (Block, AccountUpdates) {
+    let mut block_transactions = Vec::new();
+    let mut updates = Vec::new();
+    for serial_id in 0..deposits_count {
+        let priority_op = deposit(serial_id, Address::from_slice(&rng.gen::<[u8; 20]>()));
+        let op_success = state.execute_priority_op(priority_op.data.clone());
+        updates.extend(op_success.updates);
+        block_transactions.push(ExecutedOperations::PriorityOp(Box::new(
+            ExecutedPriorityOp {
+                priority_op,
+                op: op_success.executed_op,
+                block_index: serial_id as u32,
+                created_at: Utc::now(),
+            },
+        )));
+    }
+    updates.extend(state.collect_fee(&[], FEE_ACCOUNT_ID));
+
+    let block = Block::new(
+        state.block_number,
+        state.root_hash(),
+        FEE_ACCOUNT_ID,
+        block_transactions,
+        (0, deposits_count),
+        10,
+        1_000_000.into(),
+        1_500_000.into(),
+        0,
+    );
+    state.block_number += 1;
+
+    (block, updates)
+}
+
+/// Checks that the correctly stored blocks are replayed without divergences.
+#[test]
+fn replay_matching_blocks() {
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    let mut state = ZkSyncState::from_acc_map(initial_accounts(), 1);
+    let mut replayer = BlockReplayer::new(0, initial_accounts());
+
+    for _ in 0..3 {
+        let (block, updates) = create_block(&mut state, &mut rng, 2);
+        replayer
+            .replay_block(&block, &updates)
+            .expect("Block replay diverged");
+    }
+    assert_eq!(replayer.root_hash(), state.root_hash());
+}
+
+/// Checks that the first operation with the changed account updates is reported.
+#[test]
+fn replay_diverged_updates() {
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    let mut state = ZkSyncState::from_acc_map(initial_accounts(), 1);
+    let mut replayer = BlockReplayer::new(0, initial_accounts());
+
+    let (block, mut updates) = create_block(&mut state, &mut rng, 2);
+    // Alter the balance set by the second deposit.
+    let (_, update) = updates.last_mut().unwrap();
+    match update {
+        AccountUpdate::UpdateBalance { balance_update, .. } => {
+            balance_update.2 = BigUint::from(200u32)
+        }
+        _ => panic!("Unexpected account update: {:?}", update),
+    }
+
+    let divergence = replayer
+        .replay_block(&block, &updates)
+        .expect_err("Block replay didn't diverge");
+    assert_eq!(divergence.block_number, block.block_number);
+    assert_eq!(divergence.op_index, Some(1));
+    assert_ne!(divergence.replayed_updates, divergence.stored_updates);
+}
+
+/// Checks that the root hash mismatch is reported when the account updates are the same.
+#[test]
+fn replay_diverged_root_hash() {
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    let mut state = ZkSyncState::from_acc_map(initial_accounts(), 1);
+    let mut replayer = BlockReplayer::new(0, initial_accounts());
+
+    let (mut block, updates) = create_block(&mut state, &mut rng, 1);
+    block.new_root_hash = Fr::zero();
+
+    let divergence = replayer
+        .replay_block(&block, &updates)
+        .expect_err("Block replay didn't diverge");
+    assert_eq!(divergence.op_index, None);
+    assert!(divergence.reason.starts_with("root hash mismatch"));
+}
+
+/// Checks that the transactions of the failed batch are replayed together, so the valid
+/// transaction of the batch is reverted along with the invalid one.
+#[test]
+fn replay_failed_batch() {
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    let sk = priv_key_from_fs(rng.gen());
+    let mut sender = Account::default_with_address(&Address::from_slice(&rng.gen::<[u8; 20]>()));
+    sender.pub_key_hash = PubKeyHash::from_privkey(&sk);
+    sender.set_balance(0, BigUint::from(100u32));
+    let recipient = Account::default_with_address(&Address::from_slice(&rng.gen::<[u8; 20]>()));
+
+    let mut accounts = initial_accounts();
+    accounts.insert(1, sender.clone());
+    accounts.insert(2, recipient.clone());
+    let mut state = ZkSyncState::from_acc_map(accounts.clone(), 1);
+    let mut replayer = BlockReplayer::new(0, accounts);
+
+    // The first transfer is valid on its own, but the second one exceeds the sender balance.
+    let batch: Vec<SignedZkSyncTx> = (0..2)
+        .map(|nonce| {
+            let transfer = Transfer::new_signed(
+                1,
+                sender.address,
+                recipient.address,
+                0,
+                BigUint::from(60u32),
+                BigUint::from(0u32),
+                nonce,
+                Default::default(),
+                &sk,
+            )
+            .unwrap();
+            ZkSyncTx::from(transfer).into()
+        })
+        .collect();
+
+    let block_transactions = batch
+        .iter()
+        .cloned()
+        .zip(state.execute_txs_batch(&batch))
+        .enumerate()
+        .map(|(tx_index, (signed_tx, result))| {
+            let error = result.expect_err("Batch didn't fail");
+            ExecutedOperations::Tx(Box::new(ExecutedTx {
+                signed_tx,
+                success: false,
+                op: None,
+                fail_reason: Some(error.to_string()),
+                fail_code: Some(error.tx_error(tx_index)),
+                block_index: None,
+                created_at: Utc::now(),
+                batch_id: Some(1),
+                trace: None,
+            }))
+        })
+        .collect();
+    let updates = state.collect_fee(&[], FEE_ACCOUNT_ID);
+
+    let block = Block::new(
+        state.block_number,
+        state.root_hash(),
+        FEE_ACCOUNT_ID,
+        block_transactions,
+        (0, 0),
+        10,
+        1_000_000.into(),
+        1_500_000.into(),
+        0,
+    );
+
+    replayer
+        .replay_block(&block, &updates)
+        .expect("Block replay diverged");
+    assert_eq!(replayer.root_hash(), state.root_hash());
+}
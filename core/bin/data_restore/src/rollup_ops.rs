@@ -13,6 +13,8 @@ pub struct RollupOpsBlock {
     pub ops: Vec<ZkSyncOp>,
     /// Fee account
     pub fee_account: u32,
+    /// Block timestamp, zero if it is unknown
+    pub timestamp: u64,
 }
 
 impl RollupOpsBlock {
@@ -31,12 +33,13 @@ impl RollupOpsBlock {
         let input_data = get_input_data_from_ethereum_transaction(&transaction)?;
 
         let fee_account_argument_id = 1;
+        let new_block_info_argument_id = 2;
         let public_data_argument_id = 3;
         let decoded_commitment_parameters = ethabi::decode(
             vec![
                 ParamType::Uint(32),                                   // uint32 _blockNumber,
                 ParamType::Uint(32),                                   // uint32 _feeAccount,
-                ParamType::Array(Box::new(ParamType::FixedBytes(32))), // bytes32[] _newRoots,
+                ParamType::Array(Box::new(ParamType::FixedBytes(32))), // bytes32[] _newBlockInfo,
                 ParamType::Bytes, // bytes calldata _publicData,
                 ParamType::Bytes, // bytes calldata _ethWitness,
                 ParamType::Array(Box::new(ParamType::Uint(32))), // uint32[] calldata _ethWitnessSizes
@@ -51,17 +54,30 @@ impl RollupOpsBlock {
             )))
         })?;
 
-        if let (ethabi::Token::Uint(fee_acc), ethabi::Token::Bytes(public_data)) = (
+        if let (
+            ethabi::Token::Uint(fee_acc),
+            ethabi::Token::Array(new_block_info),
+            ethabi::Token::Bytes(public_data),
+        ) = (
             &decoded_commitment_parameters[fee_account_argument_id],
+            &decoded_commitment_parameters[new_block_info_argument_id],
             &decoded_commitment_parameters[public_data_argument_id],
         ) {
             let ops = RollupOpsBlock::get_rollup_ops_from_data(public_data.as_slice())?;
             let fee_account = fee_acc.as_u32();
+            // Block info consists of the new root hash followed by the block timestamp.
+            let timestamp = match new_block_info.get(1) {
+                Some(ethabi::Token::FixedBytes(timestamp)) => {
+                    web3::types::U256::from_big_endian(timestamp).as_u64()
+                }
+                _ => 0,
+            };
 
             let block = RollupOpsBlock {
                 block_num: event_data.block_num,
                 ops,
                 fee_account,
+                timestamp,
             };
             Ok(block)
         } else {
@@ -103,8 +119,8 @@ impl RollupOpsBlock {
 mod test {
     use crate::rollup_ops::RollupOpsBlock;
     use num::BigUint;
-    use zksync_types::operations::ChangePubKeyOp;
-    use zksync_types::tx::{ChangePubKey, TxSignature};
+    use zksync_types::operations::{ChangePubKeyOp, SwapOp};
+    use zksync_types::tx::{ChangePubKey, Order, Swap, TxSignature};
     use zksync_types::{
         Close, CloseOp, Deposit, DepositOp, FullExit, FullExitOp, PubKeyHash, Transfer, TransferOp,
         TransferToNewOp, Withdraw, WithdrawOp, ZkSyncOp,
@@ -141,6 +157,7 @@ mod test {
             20u32.into(),
             10u32.into(),
             2,
+            Default::default(),
             None,
         );
         let op1 = ZkSyncOp::Withdraw(Box::new(WithdrawOp { tx, account_id: 3 }));
@@ -203,6 +220,7 @@ mod test {
             20u32.into(),
             20u32.into(),
             3,
+            Default::default(),
             None,
         );
         let op1 = ZkSyncOp::TransferToNew(Box::new(TransferToNewOp {
@@ -229,6 +247,7 @@ mod test {
             20u32.into(),
             10u32.into(),
             3,
+            Default::default(),
             None,
         );
         let op1 = ZkSyncOp::Transfer(Box::new(TransferOp {
@@ -271,6 +290,7 @@ mod test {
             0,
             Default::default(),
             3,
+            Default::default(),
             None,
             None,
         );
@@ -283,4 +303,45 @@ mod test {
         let pub_data2 = op2.public_data();
         assert_eq!(pub_data1, pub_data2);
     }
+
+    #[test]
+    fn test_swap() {
+        let order = |account_id, token_sell, token_buy, amount: u32| {
+            Order::new(
+                account_id,
+                "7777777777777777777777777777777777777777".parse().unwrap(),
+                0,
+                token_sell,
+                token_buy,
+                (1u32.into(), 1u32.into()),
+                amount.into(),
+                amount.into(),
+                None,
+            )
+        };
+        let tx = Swap::new(
+            5,
+            "8888888888888888888888888888888888888888".parse().unwrap(),
+            2,
+            (order(3, 1, 2, 20), order(4, 2, 1, 30)),
+            (20u32.into(), 30u32.into()),
+            1,
+            10u32.into(),
+            Default::default(),
+            None,
+        );
+        let op1 = ZkSyncOp::Swap(Box::new(SwapOp {
+            tx,
+            submitter: 5,
+            accounts: (3, 4),
+            recipients: (6, 7),
+        }));
+        let pub_data1 = op1.public_data();
+        let op2 = RollupOpsBlock::get_rollup_ops_from_data(&pub_data1)
+            .expect("cant get ops from data")
+            .pop()
+            .expect("empty ops array");
+        let pub_data2 = op2.public_data();
+        assert_eq!(pub_data1, pub_data2);
+    }
 }
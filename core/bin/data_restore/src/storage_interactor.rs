@@ -137,5 +137,7 @@ pub fn stored_ops_block_into_ops_block(op_block: &StoredRollupOpsBlock) -> Rollu
         block_num: op_block.block_num,
         ops: op_block.ops.clone(),
         fee_account: op_block.fee_account,
+        // Timestamp is not stored along with the operations.
+        timestamp: 0,
     }
 }
@@ -37,7 +37,17 @@ fn create_withdraw_operations(
     amount: u32,
 ) -> ExecutedOperations {
     let withdraw_op = ZkSyncOp::Withdraw(Box::new(WithdrawOp {
-        tx: Withdraw::new(account_id, from, to, 0, amount.into(), 0u32.into(), 0, None),
+        tx: Withdraw::new(
+            account_id,
+            from,
+            to,
+            0,
+            amount.into(),
+            0u32.into(),
+            0,
+            Default::default(),
+            None,
+        ),
         account_id,
     }));
     let executed_tx = ExecutedTx {
@@ -45,9 +55,11 @@ fn create_withdraw_operations(
         success: false,
         op: Some(withdraw_op),
         fail_reason: None,
+        fail_code: None,
         block_index: None,
         created_at: Utc::now(),
         batch_id: None,
+        trace: None,
     };
     ExecutedOperations::Tx(Box::new(executed_tx))
 }
@@ -88,19 +100,20 @@ fn create_block(block_number: u32, transactions: Vec<ExecutedOperations>) -> Blo
         100,
         1_000_000.into(),
         1_500_000.into(),
+        0,
     )
 }
 
 fn create_transaction(number: u32, block: Block) -> Transaction {
     let hash: H256 = u32_to_32bytes(number).into();
-    let root = block.get_eth_encoded_root();
+    let new_block_info = block.get_eth_new_block_info();
     let public_data = block.get_eth_public_data();
     let witness_data = block.get_eth_witness_data();
     let fake_data = [0u8; 4];
     let params = (
         u64::from(block.block_number),
         u64::from(block.fee_account),
-        vec![root],
+        new_block_info,
         public_data,
         witness_data.0,
         witness_data.1,
@@ -11,7 +11,7 @@ use zksync_types::block::{Block, ExecutedOperations, ExecutedPriorityOp, Execute
 use zksync_types::operations::ZkSyncOp;
 use zksync_types::priority_ops::PriorityOp;
 use zksync_types::priority_ops::ZkSyncPriorityOp;
-use zksync_types::tx::{ChangePubKey, Close, ForcedExit, Transfer, Withdraw, ZkSyncTx};
+use zksync_types::tx::{ChangePubKey, Close, ForcedExit, Swap, Transfer, Withdraw, ZkSyncTx};
 use zksync_types::{AccountId, AccountMap, AccountUpdates};
 
 /// Rollup accounts states
@@ -218,6 +218,54 @@ impl TreeState {
                         &mut ops,
                     );
                 }
+                ZkSyncOp::Swap(mut op) => {
+                    // Swap op comes with empty Account Addresses and Nonces fields
+                    let get_account = |account_id, name| {
+                        self.state
+                            .get_account(account_id)
+                            .ok_or_else(|| format_err!("Swap fail: Nonexistent {} account", name))
+                    };
+                    let submitter_account = get_account(op.submitter, "submitter")?;
+                    let account_0 = get_account(op.accounts.0, "first order")?;
+                    let account_1 = get_account(op.accounts.1, "second order")?;
+                    let recipient_0 = get_account(op.recipients.0, "first recipient")?;
+                    let recipient_1 = get_account(op.recipients.1, "second recipient")?;
+
+                    // Set the fields unknown from the pubdata.
+                    // Submitter nonce is incremented before the orders nonces are checked.
+                    let submitter_id = op.submitter;
+                    let order_nonce = |account_id, account: &Account| {
+                        if account_id == submitter_id {
+                            account.nonce + 1
+                        } else {
+                            account.nonce
+                        }
+                    };
+                    op.tx.submitter_address = submitter_account.address;
+                    op.tx.nonce = submitter_account.nonce;
+                    op.tx.orders.0.nonce = order_nonce(op.accounts.0, &account_0);
+                    op.tx.orders.0.recipient_address = recipient_0.address;
+                    op.tx.orders.1.nonce = order_nonce(op.accounts.1, &account_1);
+                    op.tx.orders.1.recipient_address = recipient_1.address;
+
+                    let tx = ZkSyncTx::Swap(Box::new(op.tx.clone()));
+                    let (fee, updates) =
+                        <ZkSyncState as TxHandler<Swap>>::apply_op(&mut self.state, &op)
+                            .map_err(|e| format_err!("Swap fail: {}", e))?;
+                    let tx_result = OpSuccess {
+                        fee,
+                        updates,
+                        executed_op: ZkSyncOp::Swap(op),
+                    };
+                    current_op_block_index = self.update_from_tx(
+                        tx,
+                        tx_result,
+                        &mut fees,
+                        &mut accounts_updated,
+                        current_op_block_index,
+                        &mut ops,
+                    );
+                }
                 ZkSyncOp::Close(mut op) => {
                     // Close op comes with empty Account Address and Nonce fields
                     let account = self
@@ -311,6 +359,7 @@ impl TreeState {
             &self.available_block_chunk_sizes,
             gas_limit,
             gas_limit,
+            ops_block.timestamp,
         );
 
         self.state.block_number += 1;
@@ -398,9 +447,11 @@ impl TreeState {
             success: true,
             op: Some(executed_op),
             fail_reason: None,
+            fail_code: None,
             block_index: Some(block_index),
             created_at: chrono::Utc::now(),
             batch_id: None, // Currently `data_restore` is unable to restore `transaction <--> batch` relation
+            trace: None,
         };
         ops.push(ExecutedOperations::Tx(Box::new(exec_result)));
         current_op_block_index + 1
@@ -458,6 +509,7 @@ mod test {
             block_num: 1,
             ops: ops1,
             fee_account: 0,
+            timestamp: 0,
         };
 
         // Withdraw 20 with 1 fee from 7 to 10
@@ -469,6 +521,7 @@ mod test {
             BigUint::from(20u32),
             BigUint::from(1u32),
             1,
+            Default::default(),
             None,
         );
         let op2 = ZkSyncOp::Withdraw(Box::new(WithdrawOp {
@@ -482,6 +535,7 @@ mod test {
             block_num: 2,
             ops: ops2,
             fee_account: 0,
+            timestamp: 0,
         };
 
         // Transfer 40 with 1 fee from 7 to 8
@@ -493,6 +547,7 @@ mod test {
             BigUint::from(40u32),
             BigUint::from(1u32),
             3,
+            Default::default(),
             None,
         );
         let op3 = ZkSyncOp::TransferToNew(Box::new(TransferToNewOp {
@@ -507,6 +562,7 @@ mod test {
             block_num: 3,
             ops: ops3,
             fee_account: 0,
+            timestamp: 0,
         };
 
         // Transfer 19 with 1 fee from 8 to 7
@@ -518,6 +574,7 @@ mod test {
             BigUint::from(19u32),
             BigUint::from(1u32),
             1,
+            Default::default(),
             None,
         );
         let op4 = ZkSyncOp::Transfer(Box::new(TransferOp {
@@ -532,6 +589,7 @@ mod test {
             block_num: 4,
             ops: ops4,
             fee_account: 0,
+            timestamp: 0,
         };
 
         let pub_key_hash_7 = PubKeyHash::from_hex("sync:8888888888888888888888888888888888888888")
@@ -543,6 +601,7 @@ mod test {
             1,
             BigUint::from(1u32),
             2,
+            Default::default(),
             None,
             None,
         );
@@ -557,6 +616,7 @@ mod test {
             block_num: 5,
             ops: ops5,
             fee_account: 0,
+            timestamp: 0,
         };
 
         // Full exit for 8
@@ -576,10 +636,19 @@ mod test {
             block_num: 5,
             ops: ops6,
             fee_account: 0,
+            timestamp: 0,
         };
 
         // Forced exit for 7
-        let tx7 = ForcedExit::new(0, [7u8; 20].into(), 1, BigUint::from(1u32), 1, None);
+        let tx7 = ForcedExit::new(
+            0,
+            [7u8; 20].into(),
+            1,
+            BigUint::from(1u32),
+            1,
+            Default::default(),
+            None,
+        );
         let op7 = ZkSyncOp::ForcedExit(Box::new(ForcedExitOp {
             tx: tx7,
             target_account_id: 0,
@@ -592,6 +661,7 @@ mod test {
             block_num: 7,
             ops: ops7,
             fee_account: 1,
+            timestamp: 0,
         };
         // This transaction have to be deleted, do not uncomment. Delete it after removing the corresponding code        // let tx6 = Close {
         //     account: Address::from_hex("sync:8888888888888888888888888888888888888888").unwrap(),
@@ -685,6 +755,7 @@ mod test {
             BigUint::from(20u32),
             BigUint::from(1u32),
             1,
+            Default::default(),
             None,
         );
         let op2 = ZkSyncOp::Withdraw(Box::new(WithdrawOp {
@@ -701,6 +772,7 @@ mod test {
             BigUint::from(40u32),
             BigUint::from(1u32),
             3,
+            Default::default(),
             None,
         );
         let op3 = ZkSyncOp::TransferToNew(Box::new(TransferToNewOp {
@@ -718,6 +790,7 @@ mod test {
             BigUint::from(19u32),
             BigUint::from(1u32),
             1,
+            Default::default(),
             None,
         );
         let op4 = ZkSyncOp::Transfer(Box::new(TransferOp {
@@ -736,6 +809,7 @@ mod test {
             1,
             BigUint::from(1u32),
             2,
+            Default::default(),
             None,
             None,
         );
@@ -756,7 +830,15 @@ mod test {
         }));
         let pub_data6 = op6.public_data();
 
-        let tx7 = ForcedExit::new(0, [7u8; 20].into(), 1, BigUint::from(1u32), 1, None);
+        let tx7 = ForcedExit::new(
+            0,
+            [7u8; 20].into(),
+            1,
+            BigUint::from(1u32),
+            1,
+            Default::default(),
+            None,
+        );
         let op7 = ZkSyncOp::ForcedExit(Box::new(ForcedExitOp {
             tx: tx7,
             target_account_id: 0,
@@ -779,6 +861,7 @@ mod test {
             block_num: 1,
             ops,
             fee_account: 0,
+            timestamp: 0,
         };
 
         let mut tree = TreeState::new(vec![50]);
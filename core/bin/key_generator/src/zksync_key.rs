@@ -87,9 +87,17 @@ fn zksync_circuit(block_chunks: usize) -> impl Circuit<Engine> + Clone {
             a: None,
             b: None,
             amount_packed: None,
+            second_amount_packed: None,
+            special_eth_addresses: vec![None; 2],
+            special_tokens: vec![None; 3],
+            special_accounts: vec![None; 5],
+            special_prices: vec![None; 4],
+            special_amounts: vec![None; 4],
             full_amount: None,
             fee: None,
             pub_nonce: None,
+            valid_from: None,
+            valid_until: None,
             new_pub_key_hash: None,
             eth_address: None,
         },
@@ -130,6 +138,7 @@ fn zksync_circuit(block_chunks: usize) -> impl Circuit<Engine> + Clone {
         initial_used_subtree_root: None,
         validator_address: None,
         block_number: None,
+        block_timestamp: None,
         pub_data_commitment: None,
         validator_balances: vec![None; params::total_tokens()],
         validator_audit_path: vec![None; params::account_tree_depth()],
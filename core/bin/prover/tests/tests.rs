@@ -127,7 +127,7 @@ fn new_test_data_for_prover() -> ProverData {
     let fee_account = Account::default_with_address(&Address::default());
     circuit_account_tree.insert(fee_account_id, CircuitAccount::from(fee_account));
 
-    let mut witness_accum = WitnessBuilder::new(&mut circuit_account_tree, fee_account_id, 1);
+    let mut witness_accum = WitnessBuilder::new(&mut circuit_account_tree, fee_account_id, 1, 0);
 
     let empty_account_id = 1;
     let empty_account_address = [7u8; 20].into();
@@ -160,6 +160,8 @@ fn new_test_data_for_prover() -> ProverData {
         new_root: witness_accum.root_after_fees.unwrap(),
         validator_address: Fr::from_str(&witness_accum.fee_account_id.to_string())
             .expect("failed to parse"),
+        block_timestamp: Fr::from_str(&witness_accum.block_timestamp.to_string())
+            .expect("failed to parse"),
         operations: witness_accum.operations,
         validator_balances: witness_accum.fee_account_balances.unwrap(),
         validator_audit_path: witness_accum.fee_account_audit_path.unwrap(),
@@ -13,10 +13,12 @@ use actix_web_httpauth::middleware::HttpAuthentication;
 use futures::channel::mpsc;
 use jsonwebtoken::errors::Error as JwtError;
 use jsonwebtoken::{decode, DecodingKey, Validation};
+use num::Zero;
 use serde::{Deserialize, Serialize};
 
 // Local uses
-use zksync_types::{tokens, Address, TokenId};
+use crate::core_api_client::CoreApiClient;
+use zksync_types::{tokens, Address, FeeParams, TokenId};
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -31,6 +33,7 @@ struct PayloadAuthToken {
 struct AppState {
     secret_auth: String,
     connection_pool: zksync_storage::ConnectionPool,
+    core_api_client: CoreApiClient,
 }
 
 impl AppState {
@@ -42,6 +45,35 @@ impl AppState {
     }
 }
 
+/// Converts an error of the request to the Core API into the server error.
+fn core_api_error(e: anyhow::Error) -> actix_web::Error {
+    vlog::warn!("Core API request failed: {}", e);
+    actix_web::error::ErrorInternalServerError("core api error")
+}
+
+/// Converts an error of the storage layer into the server error.
+fn storage_error(e: anyhow::Error) -> actix_web::Error {
+    vlog::warn!("Storage request failed: {}", e);
+    actix_web::error::ErrorInternalServerError("storage layer error")
+}
+
+/// Maximum number of the fee parameters versions returned at once.
+const MAX_FEE_PARAMS_HISTORY_LIMIT: u32 = 100;
+
+/// Query of the fee parameters history request.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+struct FeeParamsHistoryQuery {
+    /// Number of the latest versions to return.
+    limit: u32,
+}
+
+/// Response to the forced block sealing request.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+struct SealBlockResponse {
+    /// Whether the block was sealed. Empty pending block is not sealed.
+    sealed: bool,
+}
+
 /// Token that contains information to add to the server
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 struct AddTokenRequest {
@@ -125,6 +157,113 @@ async fn add_token(
     Ok(HttpResponse::Ok().json(token))
 }
 
+async fn pause_block_proposer(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mode = data
+        .core_api_client
+        .pause_block_proposer()
+        .await
+        .map_err(core_api_error)?;
+
+    vlog::info!("Block production is paused by the admin request");
+    Ok(HttpResponse::Ok().json(mode))
+}
+
+async fn resume_block_proposer(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mode = data
+        .core_api_client
+        .resume_block_proposer()
+        .await
+        .map_err(core_api_error)?;
+
+    vlog::info!("Block production is resumed by the admin request");
+    Ok(HttpResponse::Ok().json(mode))
+}
+
+async fn seal_block(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let sealed = data
+        .core_api_client
+        .seal_block()
+        .await
+        .map_err(core_api_error)?;
+
+    if sealed {
+        vlog::info!("Pending block is sealed by the admin request");
+    } else {
+        vlog::info!("Pending block is empty, nothing to seal by the admin request");
+    }
+    Ok(HttpResponse::Ok().json(SealBlockResponse { sealed }))
+}
+
+async fn fee_params(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let mut storage = data.access_storage().await?;
+
+    let params = storage
+        .tokens_schema()
+        .load_fee_params()
+        .await
+        .map_err(storage_error)?;
+
+    Ok(HttpResponse::Ok().json(params))
+}
+
+/// Stores the new version of the fee parameters.
+/// Fee ticker starts using them once it reloads the parameters from the database.
+/// If the ZKP cost is derived from the prover statistics, the provided one is only a fallback.
+async fn update_fee_params(
+    data: web::Data<AppState>,
+    params: web::Json<FeeParams>,
+) -> actix_web::Result<HttpResponse> {
+    if params.zkp_cost_chunk_usd.is_zero() {
+        return Err(actix_web::error::ErrorBadRequest(
+            "ZKP cost of a chunk must be positive",
+        ));
+    }
+    if params
+        .tokens_risk_factors
+        .values()
+        .any(|risk_factor| risk_factor.is_zero())
+    {
+        return Err(actix_web::error::ErrorBadRequest(
+            "token risk factors must be positive",
+        ));
+    }
+
+    let mut storage = data.access_storage().await?;
+    let stored = storage
+        .tokens_schema()
+        .store_fee_params(&params)
+        .await
+        .map_err(storage_error)?;
+
+    vlog::info!(
+        "Fee parameters are updated to version {} by the admin request: {:?}",
+        stored.version,
+        stored.params
+    );
+    Ok(HttpResponse::Ok().json(stored))
+}
+
+async fn fee_params_history(
+    data: web::Data<AppState>,
+    query: web::Query<FeeParamsHistoryQuery>,
+) -> actix_web::Result<HttpResponse> {
+    if query.limit == 0 || query.limit > MAX_FEE_PARAMS_HISTORY_LIMIT {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "limit should be within [1, {}]",
+            MAX_FEE_PARAMS_HISTORY_LIMIT
+        )));
+    }
+
+    let mut storage = data.access_storage().await?;
+    let history = storage
+        .tokens_schema()
+        .load_fee_params_history(query.limit)
+        .await
+        .map_err(storage_error)?;
+
+    Ok(HttpResponse::Ok().json(history))
+}
+
 async fn run_server(app_state: AppState, bind_to: SocketAddr) {
     HttpServer::new(move || {
         let auth = HttpAuthentication::bearer(move |req, credentials| async {
@@ -142,6 +281,18 @@ async fn run_server(app_state: AppState, bind_to: SocketAddr) {
             .wrap(auth)
             .data(app_state.clone())
             .route("/tokens", web::post().to(add_token))
+            .route(
+                "/block_proposer/pause",
+                web::post().to(pause_block_proposer),
+            )
+            .route(
+                "/block_proposer/resume",
+                web::post().to(resume_block_proposer),
+            )
+            .route("/seal_block", web::post().to(seal_block))
+            .route("/fee_params", web::get().to(fee_params))
+            .route("/fee_params", web::post().to(update_fee_params))
+            .route("/fee_params/history", web::get().to(fee_params_history))
     })
     .workers(1)
     .bind(&bind_to)
@@ -155,6 +306,7 @@ pub fn start_admin_server(
     bind_to: SocketAddr,
     secret_auth: String,
     connection_pool: zksync_storage::ConnectionPool,
+    core_api_client: CoreApiClient,
     panic_notify: mpsc::Sender<bool>,
 ) {
     thread::Builder::new()
@@ -165,6 +317,7 @@ pub fn start_admin_server(
                 let app_state = AppState {
                     connection_pool,
                     secret_auth,
+                    core_api_client,
                 };
 
                 run_server(app_state, bind_to).await;
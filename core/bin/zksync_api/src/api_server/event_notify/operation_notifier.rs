@@ -124,11 +124,14 @@ impl OperationNotifier {
                         executed: true,
                         success: Some(tx.success),
                         fail_reason: tx.fail_reason,
+                        fail_code: tx.fail_code,
                         block: Some(BlockInfo {
                             block_number: i64::from(block_number),
                             committed: true,
                             verified: action == ActionType::VERIFY,
                         }),
+                        evicted: false,
+                        replaced_by: None,
                     };
                     self.tx_subs.notify(hash, action, resp);
                 }
@@ -141,6 +144,7 @@ impl OperationNotifier {
                             committed: true,
                             verified: action == ActionType::VERIFY,
                         }),
+                        evicted: false,
                     };
                     self.prior_op_subs.notify(id, action, resp);
                 }
@@ -237,11 +241,14 @@ impl OperationNotifier {
                 executed: true,
                 success: Some(receipt.success),
                 fail_reason: receipt.fail_reason,
+                fail_code: receipt.fail_code,
                 block: Some(BlockInfo {
                     block_number: receipt.block_number,
                     committed: receipt.success,
                     verified: receipt.verified,
                 }),
+                evicted: false,
+                replaced_by: None,
             };
             match action {
                 ActionType::COMMIT => {
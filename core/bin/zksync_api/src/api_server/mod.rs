@@ -13,6 +13,7 @@ use futures::channel::mpsc;
 use zksync_config::{AdminServerOptions, ApiServerOptions, ConfigurationOptions};
 use zksync_storage::ConnectionPool;
 // Local uses
+use crate::core_api_client::CoreApiClient;
 use crate::fee_ticker::TickerRequest;
 use crate::signature_checker;
 
@@ -68,6 +69,7 @@ pub fn start_api_server(
         admin_server_opts.admin_http_server_address,
         admin_server_opts.secret_auth,
         connection_pool.clone(),
+        CoreApiClient::new(api_server_opts.core_server_url.clone()),
         panic_notify.clone(),
     );
 
@@ -0,0 +1,966 @@
+//! Accounts part of API implementation.
+
+// Built-in uses
+use std::{collections::BTreeMap, fmt, str::FromStr};
+
+// External uses
+use actix_web::{
+    web::{self, Json},
+    Scope,
+};
+use anyhow::{ensure, format_err};
+use chrono::{DateTime, Utc};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+// Workspace uses
+use zksync_storage::{
+    chain::operations_ext::{records::TransactionsHistoryItem, SearchDirection},
+    ConnectionPool, QueryResult, StorageProcessor,
+};
+use zksync_types::{
+    tx::TxHash, Account, AccountId, Address, BlockNumber, Nonce, PubKeyHash, SignedZkSyncTx,
+    ZkSyncTx, H256,
+};
+use zksync_utils::BigUintSerdeWrapper;
+
+// Local uses
+use super::{
+    check_limit,
+    client::{Client, ClientError},
+    transactions::TxReceipt,
+    Error as ApiError, JsonResult,
+};
+use crate::utils::token_db_cache::TokenDBCache;
+
+/// Shared data between `api/v1/accounts` endpoints.
+#[derive(Debug, Clone)]
+struct ApiAccountsData {
+    pool: ConnectionPool,
+    tokens: TokenDBCache,
+}
+
+impl ApiAccountsData {
+    fn new(pool: ConnectionPool, tokens: TokenDBCache) -> Self {
+        Self { pool, tokens }
+    }
+
+    /// Resolves the account address, returns `None` if there is no account with the given ID.
+    async fn account_address(
+        storage: &mut StorageProcessor<'_>,
+        account: AccountQuery,
+    ) -> QueryResult<Option<Address>> {
+        match account {
+            AccountQuery::Address(address) => Ok(Some(address)),
+            AccountQuery::Id(id) => Ok(storage
+                .chain()
+                .account_schema()
+                .last_committed_state_for_account(id)
+                .await?
+                .map(|account| account.address)),
+        }
+    }
+
+    async fn account_state(&self, account: Account) -> QueryResult<AccountState> {
+        let mut balances = BTreeMap::new();
+        for (token_id, balance) in account.get_nonzero_balances() {
+            let symbol = if token_id == 0 {
+                "ETH".to_string()
+            } else {
+                self.tokens
+                    .get_token(token_id)
+                    .await?
+                    .ok_or_else(|| format_err!("Unknown token with id {}", token_id))?
+                    .symbol
+            };
+            balances.insert(symbol, balance);
+        }
+
+        Ok(AccountState {
+            balances,
+            nonce: account.nonce,
+            pub_key_hash: account.pub_key_hash,
+        })
+    }
+
+    async fn account_info(&self, account: AccountQuery) -> QueryResult<Option<AccountInfo>> {
+        let mut storage = self.pool.access_storage().await?;
+
+        let address = match Self::account_address(&mut storage, account).await? {
+            Some(address) => address,
+            None => return Ok(None),
+        };
+
+        let account_state = storage
+            .chain()
+            .account_schema()
+            .account_state_by_address(&address)
+            .await?;
+
+        let (id, committed) = match account_state.committed {
+            Some(committed) => committed,
+            None => return Ok(None),
+        };
+        let committed = self.account_state(committed).await?;
+        let verified = match account_state.verified {
+            Some((_, verified)) => Some(self.account_state(verified).await?),
+            None => None,
+        };
+
+        Ok(Some(AccountInfo {
+            address,
+            id,
+            committed,
+            verified,
+        }))
+    }
+
+    async fn account_txs(
+        &self,
+        account: AccountQuery,
+        pagination: TxPagination,
+        limit: u32,
+    ) -> QueryResult<Vec<AccountTxInfo>> {
+        let mut storage = self.pool.access_storage().await?;
+
+        let address = match Self::account_address(&mut storage, account).await? {
+            Some(address) => address,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut schema = storage.chain().operations_ext_schema();
+        let txs = match pagination {
+            TxPagination::Last => {
+                schema
+                    .get_account_transactions_history(&address, 0, limit.into())
+                    .await?
+            }
+            TxPagination::Before(location) => {
+                schema
+                    .get_account_transactions_history_from(
+                        &address,
+                        location.into_tx_id(),
+                        SearchDirection::Older,
+                        limit.into(),
+                    )
+                    .await?
+            }
+            TxPagination::After(location) => {
+                schema
+                    .get_account_transactions_history_from(
+                        &address,
+                        location.into_tx_id(),
+                        SearchDirection::Newer,
+                        limit.into(),
+                    )
+                    .await?
+            }
+        };
+
+        txs.into_iter().map(AccountTxInfo::try_from_item).collect()
+    }
+
+    async fn account_priority_ops(
+        &self,
+        account: AccountQuery,
+        before: Option<u64>,
+        limit: u32,
+    ) -> QueryResult<Vec<AccountPriorityOp>> {
+        let mut storage = self.pool.access_storage().await?;
+
+        let address = match Self::account_address(&mut storage, account).await? {
+            Some(address) => address,
+            None => return Ok(Vec::new()),
+        };
+
+        let ops = storage
+            .chain()
+            .operations_schema()
+            .get_account_executed_priority_operations(&address, before, limit.into())
+            .await?;
+        if ops.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let last_committed = storage
+            .chain()
+            .block_schema()
+            .get_last_committed_block()
+            .await?;
+        let last_verified = storage
+            .chain()
+            .block_schema()
+            .get_last_verified_confirmed_block()
+            .await?;
+
+        Ok(ops
+            .into_iter()
+            .map(|op| {
+                let block = op.block_number as BlockNumber;
+                let status = if block <= last_verified {
+                    TxReceipt::Verified { block }
+                } else if block <= last_committed {
+                    TxReceipt::Committed { block }
+                } else {
+                    TxReceipt::Executed
+                };
+
+                AccountPriorityOp {
+                    serial_id: op.priority_op_serialid as u64,
+                    eth_hash: H256::from_slice(&op.eth_hash),
+                    eth_block: op.eth_block as u64,
+                    index: op.block_index as u32,
+                    op: op.operation,
+                    status,
+                    created_at: op.created_at,
+                }
+            })
+            .collect())
+    }
+
+    async fn pending_txs(&self, address: Address) -> QueryResult<PendingTransactions> {
+        let mut storage = self.pool.access_storage().await?;
+
+        let committed_nonce = storage
+            .chain()
+            .account_schema()
+            .account_state_by_address(&address)
+            .await?
+            .committed
+            .map(|(_, account)| account.nonce)
+            .unwrap_or_default();
+
+        let txs = storage
+            .chain()
+            .mempool_schema()
+            .get_account_txs(address)
+            .await?;
+
+        Ok(PendingTransactions::new(address, committed_nonce, txs))
+    }
+}
+
+// Data transfer objects.
+
+/// Account identifier: either the account ID or its address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountQuery {
+    /// ID of the account in the zkSync network.
+    Id(AccountId),
+    /// Address of the account.
+    Address(Address),
+}
+
+impl From<AccountId> for AccountQuery {
+    fn from(id: AccountId) -> Self {
+        Self::Id(id)
+    }
+}
+
+impl From<Address> for AccountQuery {
+    fn from(address: Address) -> Self {
+        Self::Address(address)
+    }
+}
+
+impl fmt::Display for AccountQuery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccountQuery::Id(id) => write!(f, "{}", id),
+            AccountQuery::Address(address) => write!(f, "{:#x}", address),
+        }
+    }
+}
+
+impl FromStr for AccountQuery {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(id) = AccountId::from_str(s) {
+            return Ok(Self::Id(id));
+        }
+
+        let address = s.strip_prefix("0x").unwrap_or(s);
+        Address::from_str(address)
+            .map(Self::Address)
+            .map_err(|_| format_err!("Account should be specified by its ID or address"))
+    }
+}
+
+/// Account state at the certain moment.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountState {
+    /// Non-zero balances of the account by the token symbols.
+    pub balances: BTreeMap<String, BigUintSerdeWrapper>,
+    pub nonce: Nonce,
+    pub pub_key_hash: PubKeyHash,
+}
+
+/// Account information in the committed and the verified state.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountInfo {
+    pub address: Address,
+    pub id: AccountId,
+    pub committed: AccountState,
+    /// Account state in the last verified block, if the account has already been verified.
+    pub verified: Option<AccountState>,
+}
+
+/// Location of the transaction in the chain.
+///
+/// It is used as a cursor to paginate through the account transactions and is represented
+/// as a `block,index` string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxLocation {
+    pub block: BlockNumber,
+    /// Index of the transaction in the block. Failed transactions don't have it.
+    pub index: Option<u32>,
+}
+
+impl TxLocation {
+    fn into_tx_id(self) -> (u64, u64) {
+        (self.block.into(), self.index.unwrap_or_default().into())
+    }
+}
+
+impl fmt::Display for TxLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.index {
+            Some(index) => write!(f, "{},{}", self.block, index),
+            None => write!(f, "{}", self.block),
+        }
+    }
+}
+
+impl FromStr for TxLocation {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s.split(',').collect::<Vec<_>>();
+        ensure!(
+            !parts.is_empty() && parts.len() <= 2,
+            "Transaction location should be in the `block,index` format"
+        );
+
+        Ok(Self {
+            block: parts[0].parse()?,
+            index: parts.get(1).map(|index| index.parse()).transpose()?,
+        })
+    }
+}
+
+impl Serialize for TxLocation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TxLocation {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+/// Transactions pagination request.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TxPagination {
+    /// Request to return transactions older than specified (not including itself).
+    Before(TxLocation),
+    /// Request to return transactions newer than specified (not including itself).
+    After(TxLocation),
+    /// Request to return the latest transactions.
+    Last,
+}
+
+impl TxPagination {
+    fn into_query(self, limit: u32) -> AccountTxsQuery {
+        match self {
+            TxPagination::Before(before) => AccountTxsQuery {
+                before: Some(before),
+                after: None,
+                limit,
+            },
+            TxPagination::After(after) => AccountTxsQuery {
+                before: None,
+                after: Some(after),
+                limit,
+            },
+            TxPagination::Last => AccountTxsQuery {
+                before: None,
+                after: None,
+                limit,
+            },
+        }
+    }
+}
+
+/// Account transactions query representation:
+///
+/// `?limit=..&[before={block,index}|after={block,index}]`
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq)]
+struct AccountTxsQuery {
+    before: Option<TxLocation>,
+    after: Option<TxLocation>,
+    limit: u32,
+}
+
+impl AccountTxsQuery {
+    fn into_inner(self) -> Result<(TxPagination, u32), ApiError> {
+        let pagination = match (self.before, self.after) {
+            (Some(before), None) => TxPagination::Before(before),
+            (None, Some(after)) => TxPagination::After(after),
+            (None, None) => TxPagination::Last,
+            (Some(_), Some(_)) => {
+                return Err(ApiError::bad_request("Incorrect pagination query")
+                    .detail("Pagination query contains both `before` and `after` values."))
+            }
+        };
+
+        check_limit(self.limit)?;
+        Ok((pagination, self.limit))
+    }
+}
+
+/// Account priority operations query representation:
+///
+/// `?limit=..&[before={serial_id}]`
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq)]
+struct AccountOpsQuery {
+    before: Option<u64>,
+    limit: u32,
+}
+
+/// Transaction or priority operation related to the account.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountTxInfo {
+    pub tx_id: TxLocation,
+    /// Hash of the transaction, or the Ethereum transaction hash for priority operations.
+    pub hash: Option<String>,
+    pub eth_block: Option<u64>,
+    /// Serial ID of the priority operation.
+    pub pq_id: Option<u64>,
+    pub tx: Value,
+    pub success: Option<bool>,
+    pub fail_reason: Option<String>,
+    pub committed: bool,
+    pub verified: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl AccountTxInfo {
+    fn try_from_item(item: TransactionsHistoryItem) -> QueryResult<Self> {
+        Ok(Self {
+            tx_id: item.tx_id.parse()?,
+            hash: item.hash,
+            eth_block: item.eth_block.map(|block| block as u64),
+            pq_id: item.pq_id.map(|id| id as u64),
+            tx: item.tx,
+            success: item.success,
+            fail_reason: item.fail_reason,
+            committed: item.commited,
+            verified: item.verified,
+            created_at: item.created_at,
+        })
+    }
+}
+
+/// Executed priority operation related to the account.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountPriorityOp {
+    pub serial_id: u64,
+    pub eth_hash: H256,
+    pub eth_block: u64,
+    /// Index of the operation in the block.
+    pub index: u32,
+    pub op: Value,
+    #[serde(flatten)]
+    pub status: TxReceipt,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Transaction waiting in the mempool to be included into a block.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingTx {
+    pub tx_hash: TxHash,
+    pub nonce: Nonce,
+    pub tx: ZkSyncTx,
+}
+
+/// Transactions of the account waiting in the mempool.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingTransactions {
+    pub address: Address,
+    /// Account nonce in the committed state.
+    pub committed_nonce: Nonce,
+    /// The lowest nonce that is neither committed nor used by pending transactions.
+    ///
+    /// This value is set only if there are pending transactions with the greater nonce:
+    /// these transactions can't be executed until the transaction with the missing
+    /// nonce is sent.
+    pub missing_nonce: Option<Nonce>,
+    /// Pending transactions sorted by nonce.
+    pub txs: Vec<PendingTx>,
+}
+
+impl PendingTransactions {
+    pub fn new(address: Address, committed_nonce: Nonce, txs: Vec<SignedZkSyncTx>) -> Self {
+        let txs: Vec<_> = txs
+            .into_iter()
+            .map(|tx| PendingTx {
+                tx_hash: tx.hash(),
+                nonce: tx.nonce(),
+                tx: tx.tx,
+            })
+            .collect();
+
+        let mut next_nonce = committed_nonce;
+        let mut missing_nonce = None;
+        for tx in &txs {
+            if tx.nonce > next_nonce {
+                missing_nonce = Some(next_nonce);
+                break;
+            }
+            next_nonce = next_nonce.max(tx.nonce + 1);
+        }
+
+        Self {
+            address,
+            committed_nonce,
+            missing_nonce,
+            txs,
+        }
+    }
+}
+
+// Client implementation
+
+/// Accounts API part.
+impl Client {
+    /// Gets information about the account with the specified ID or address.
+    pub async fn account_info(
+        &self,
+        account: impl Into<AccountQuery>,
+    ) -> Result<Option<AccountInfo>, ClientError> {
+        self.get(&format!("accounts/{}", account.into()))
+            .send()
+            .await
+    }
+
+    /// Gets transactions and priority operations related to the account.
+    pub async fn account_txs(
+        &self,
+        account: impl Into<AccountQuery>,
+        from: TxPagination,
+        limit: u32,
+    ) -> Result<Vec<AccountTxInfo>, ClientError> {
+        self.get(&format!("accounts/{}/transactions", account.into()))
+            .query(&from.into_query(limit))
+            .send()
+            .await
+    }
+
+    /// Gets executed priority operations related to the account, starting from the newest one.
+    pub async fn account_priority_ops(
+        &self,
+        account: impl Into<AccountQuery>,
+        before: Option<u64>,
+        limit: u32,
+    ) -> Result<Vec<AccountPriorityOp>, ClientError> {
+        self.get(&format!("accounts/{}/operations", account.into()))
+            .query(&AccountOpsQuery { before, limit })
+            .send()
+            .await
+    }
+
+    /// Gets transactions of the account waiting in the mempool.
+    pub async fn pending_txs(&self, address: Address) -> Result<PendingTransactions, ClientError> {
+        self.get(&format!(
+            "accounts/0x{}/pending_transactions",
+            hex::encode(address)
+        ))
+        .send()
+        .await
+    }
+}
+
+// Server implementation
+
+fn parse_account_query(account: &str) -> Result<AccountQuery, ApiError> {
+    account
+        .parse()
+        .map_err(|err| ApiError::bad_request("Incorrect account identifier").detail(err))
+}
+
+async fn account_info(
+    data: web::Data<ApiAccountsData>,
+    web::Path(account): web::Path<String>,
+) -> JsonResult<Option<AccountInfo>> {
+    let account = parse_account_query(&account)?;
+    let info = data
+        .account_info(account)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(info))
+}
+
+async fn account_txs(
+    data: web::Data<ApiAccountsData>,
+    web::Path(account): web::Path<String>,
+    web::Query(query): web::Query<AccountTxsQuery>,
+) -> JsonResult<Vec<AccountTxInfo>> {
+    let account = parse_account_query(&account)?;
+    let (pagination, limit) = query.into_inner()?;
+
+    let txs = data
+        .account_txs(account, pagination, limit)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(txs))
+}
+
+async fn account_priority_ops(
+    data: web::Data<ApiAccountsData>,
+    web::Path(account): web::Path<String>,
+    web::Query(query): web::Query<AccountOpsQuery>,
+) -> JsonResult<Vec<AccountPriorityOp>> {
+    let account = parse_account_query(&account)?;
+    check_limit(query.limit)?;
+
+    let ops = data
+        .account_priority_ops(account, query.before, query.limit)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(ops))
+}
+
+async fn pending_txs(
+    data: web::Data<ApiAccountsData>,
+    web::Path(address): web::Path<Address>,
+) -> JsonResult<PendingTransactions> {
+    let pending_txs = data
+        .pending_txs(address)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(pending_txs))
+}
+
+pub fn api_scope(pool: ConnectionPool, tokens: TokenDBCache) -> Scope {
+    let data = ApiAccountsData::new(pool, tokens);
+
+    web::scope("accounts")
+        .data(data)
+        .route("{account}", web::get().to(account_info))
+        .route("{account}/transactions", web::get().to(account_txs))
+        .route("{account}/operations", web::get().to(account_priority_ops))
+        .route("{address}/pending_transactions", web::get().to(pending_txs))
+}
+
+#[cfg(test)]
+mod tests {
+    use zksync_test_account::ZkSyncAccount;
+    use zksync_types::ExecutedOperations;
+
+    use super::{
+        super::test_utils::{TestServerConfig, COMMITTED_OP_SERIAL_ID, VERIFIED_OP_SERIAL_ID},
+        *,
+    };
+
+    fn accounts_scope(cfg: &TestServerConfig) -> Scope {
+        api_scope(cfg.pool.clone(), TokenDBCache::new(cfg.pool.clone()))
+    }
+
+    #[actix_rt::test]
+    async fn test_account_info() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let (client, server) = cfg.start_server(accounts_scope);
+
+        // Take some account from the committed state.
+        let (account_id, account) = {
+            let mut storage = cfg.pool.access_storage().await?;
+            let (_, accounts) = storage
+                .chain()
+                .state_schema()
+                .load_committed_state(None)
+                .await?;
+            accounts
+                .into_iter()
+                .next()
+                .expect("There are no accounts in the committed state")
+        };
+
+        let info = client
+            .account_info(account_id)
+            .await?
+            .expect("Account should exist");
+        assert_eq!(info.address, account.address);
+        assert_eq!(info.id, account_id);
+        assert_eq!(info.committed.nonce, account.nonce);
+        assert_eq!(info.committed.pub_key_hash, account.pub_key_hash);
+        assert_eq!(info.committed.balances["ETH"].0, account.get_balance(0));
+
+        // The same account can be requested by its address.
+        let info = client
+            .account_info(account.address)
+            .await?
+            .expect("Account should exist");
+        assert_eq!(info.id, account_id);
+
+        // Unknown account.
+        let unknown_account = ZkSyncAccount::rand();
+        assert!(client
+            .account_info(unknown_account.address)
+            .await?
+            .is_none());
+
+        server.stop().await;
+        Ok(())
+    }
+
+    #[actix_rt::test]
+    async fn test_account_history() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let (client, server) = cfg.start_server(accounts_scope);
+
+        // Take the account which sent transactions in the first block.
+        let address = {
+            let mut storage = cfg.pool.access_storage().await?;
+            let ops = storage
+                .chain()
+                .block_schema()
+                .get_block_executed_ops(1)
+                .await?;
+            match &ops[0] {
+                ExecutedOperations::Tx(tx) => tx.signed_tx.account(),
+                ExecutedOperations::PriorityOp(_) => panic!("Block should contain transactions"),
+            }
+        };
+
+        let txs = client.account_txs(address, TxPagination::Last, 10).await?;
+        assert_eq!(txs.len(), 2);
+        assert!(txs.iter().all(|tx| tx.tx_id.block == 1 && tx.committed));
+
+        let txs_limited = client.account_txs(address, TxPagination::Last, 1).await?;
+        assert_eq!(txs_limited.as_slice(), &txs[..1]);
+
+        // There are no transactions before the first block.
+        let first_block = TxLocation {
+            block: 1,
+            index: Some(0),
+        };
+        let older_txs = client
+            .account_txs(address, TxPagination::Before(first_block), 10)
+            .await?;
+        assert!(older_txs.is_empty());
+
+        let genesis = TxLocation {
+            block: 0,
+            index: Some(0),
+        };
+        let newer_txs = client
+            .account_txs(address, TxPagination::After(genesis), 10)
+            .await?;
+        assert_eq!(newer_txs, txs);
+
+        // Incorrect limit.
+        assert!(client
+            .account_txs(address, TxPagination::Last, 0)
+            .await
+            .is_err());
+
+        // Priority operations.
+        let ops = client
+            .account_priority_ops(Address::default(), None, 10)
+            .await?;
+        assert_eq!(
+            ops.iter()
+                .map(|op| (op.serial_id, op.status.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (COMMITTED_OP_SERIAL_ID, TxReceipt::Committed { block: 4 }),
+                (VERIFIED_OP_SERIAL_ID, TxReceipt::Verified { block: 2 }),
+            ]
+        );
+
+        let ops = client
+            .account_priority_ops(Address::default(), Some(COMMITTED_OP_SERIAL_ID), 10)
+            .await?;
+        assert_eq!(ops.len(), 1);
+        assert_eq!(ops[0].serial_id, VERIFIED_OP_SERIAL_ID);
+
+        server.stop().await;
+        Ok(())
+    }
+
+    #[test]
+    fn account_query_parse() {
+        let address = Address::repeat_byte(0x42);
+
+        assert_eq!("42".parse::<AccountQuery>().unwrap(), AccountQuery::Id(42));
+        for s in &[format!("{:#x}", address), hex::encode(address)] {
+            assert_eq!(
+                s.parse::<AccountQuery>().unwrap(),
+                AccountQuery::Address(address)
+            );
+        }
+        assert!("foo".parse::<AccountQuery>().is_err());
+
+        let query = AccountQuery::Address(address);
+        assert_eq!(query.to_string().parse::<AccountQuery>().unwrap(), query);
+    }
+
+    #[test]
+    fn tx_location_parse() {
+        let cases = vec![
+            (
+                "5,3",
+                Some(TxLocation {
+                    block: 5,
+                    index: Some(3),
+                }),
+            ),
+            (
+                "5",
+                Some(TxLocation {
+                    block: 5,
+                    index: None,
+                }),
+            ),
+            ("5,3,1", None),
+            ("", None),
+            ("a,b", None),
+        ];
+
+        for (s, expected) in cases {
+            let location = s.parse::<TxLocation>().ok();
+            assert_eq!(location, expected, "input: {:?}", s);
+            if let Some(location) = location {
+                assert_eq!(location.to_string(), s);
+            }
+        }
+    }
+
+    #[actix_rt::test]
+    async fn test_accounts_scope() -> anyhow::Result<()> {
+        let cfg = TestServerConfig::default();
+        cfg.fill_database().await?;
+
+        let (client, server) = cfg.start_server(accounts_scope);
+
+        let from = ZkSyncAccount::rand();
+        from.set_account_id(Some(0xbeef));
+        let to = ZkSyncAccount::rand();
+
+        // Account without pending transactions.
+        let pending_txs = client.pending_txs(from.address).await?;
+        assert_eq!(pending_txs.committed_nonce, 0);
+        assert_eq!(pending_txs.missing_nonce, None);
+        assert!(pending_txs.txs.is_empty());
+
+        // Pending transactions with the nonce gap.
+        let txs = [0, 2]
+            .iter()
+            .map(|&nonce| {
+                from.sign_transfer(
+                    0,
+                    "ETH",
+                    1_u64.into(),
+                    1_u64.into(),
+                    &to.address,
+                    Some(nonce),
+                    false,
+                )
+                .0
+            })
+            .collect::<Vec<_>>();
+        {
+            let mut storage = cfg.pool.access_storage().await?;
+            for tx in txs.iter().rev() {
+                storage
+                    .chain()
+                    .mempool_schema()
+                    .insert_tx(&ZkSyncTx::from(tx.clone()).into())
+                    .await?;
+            }
+        }
+
+        let pending_txs = client.pending_txs(from.address).await?;
+        assert_eq!(pending_txs.address, from.address);
+        assert_eq!(pending_txs.committed_nonce, 0);
+        assert_eq!(pending_txs.missing_nonce, Some(1));
+        assert_eq!(
+            pending_txs
+                .txs
+                .iter()
+                .map(|tx| tx.nonce)
+                .collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+        assert_eq!(
+            pending_txs.txs[0].tx_hash,
+            ZkSyncTx::from(txs[0].clone()).hash()
+        );
+
+        server.stop().await;
+        Ok(())
+    }
+
+    #[test]
+    fn pending_txs_missing_nonce() {
+        let account = ZkSyncAccount::rand();
+        account.set_account_id(Some(0xbeef));
+        let to = ZkSyncAccount::rand();
+        let txs = |nonces: &[Nonce]| -> Vec<SignedZkSyncTx> {
+            nonces
+                .iter()
+                .map(|&nonce| {
+                    let tx = account
+                        .sign_transfer(
+                            0,
+                            "ETH",
+                            1_u64.into(),
+                            1_u64.into(),
+                            &to.address,
+                            Some(nonce),
+                            false,
+                        )
+                        .0;
+                    ZkSyncTx::from(tx).into()
+                })
+                .collect()
+        };
+
+        let cases: Vec<(Nonce, Vec<Nonce>, Option<Nonce>)> = vec![
+            (3, vec![], None),
+            (3, vec![3, 4, 5], None),
+            (3, vec![4, 5], Some(3)),
+            (3, vec![3, 5], Some(4)),
+            // Transactions with outdated nonces don't affect the result.
+            (3, vec![1, 3, 4], None),
+        ];
+
+        for (committed_nonce, nonces, expected) in cases {
+            let pending_txs =
+                PendingTransactions::new(account.address, committed_nonce, txs(&nonces));
+            assert_eq!(
+                pending_txs.missing_nonce, expected,
+                "committed nonce: {}, pending nonces: {:?}",
+                committed_nonce, nonces
+            );
+        }
+    }
+}
@@ -15,7 +15,7 @@ use serde_json::Value;
 use zksync_config::ApiServerOptions;
 use zksync_crypto::{convert::FeConvert, serialization::FrSerde, Fr};
 use zksync_storage::{chain::block::records, ConnectionPool, QueryResult};
-use zksync_types::{tx::TxHash, BlockNumber};
+use zksync_types::{tx::TxHash, AccountId, BlockNumber};
 
 // Local uses
 use super::{
@@ -110,6 +110,8 @@ pub struct BlockInfo {
     #[serde(with = "FrSerde")]
     pub new_state_root: Fr,
     pub block_size: u64,
+    /// Account which received the fees collected in the block.
+    pub fee_account: AccountId,
     pub commit_tx_hash: Option<TxHash>,
     pub verify_tx_hash: Option<TxHash>,
     pub committed_at: DateTime<Utc>,
@@ -137,6 +139,7 @@ impl From<records::BlockDetails> for BlockInfo {
                 )
             }),
             block_size: inner.block_size as u64,
+            fee_account: inner.fee_account_id as AccountId,
             commit_tx_hash: inner.commit_tx_hash.map(|bytes| {
                 TxHash::from_slice(&bytes).unwrap_or_else(|| {
                     panic!(
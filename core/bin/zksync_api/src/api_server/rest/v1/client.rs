@@ -2,8 +2,12 @@
 
 // Public uses
 pub use super::{
+    accounts::{
+        AccountInfo, AccountPriorityOp, AccountQuery, AccountState, AccountTxInfo, TxLocation,
+        TxPagination,
+    },
     blocks::{BlockInfo, TransactionInfo},
-    config::Contracts,
+    config::{Contracts, NetworkInfo},
     operations::PriorityOpReceipt,
     tokens::TokenPriceKind,
     transactions::{SumbitErrorCode, TxReceipt},
@@ -4,16 +4,24 @@
 
 // External uses
 use actix_web::{web, Scope};
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt,
+};
 use serde::{Deserialize, Serialize};
 
 // Workspace uses
 use zksync_config::ConfigurationOptions;
-use zksync_types::{network::Network, Address};
+use zksync_types::{block::BlockProductionMode, network::Network, Address};
 
 // Local uses
 use super::{
     client::{self, Client},
-    Json,
+    Error as ApiError, Json, JsonResult,
+};
+use crate::{
+    core_api_client::CoreApiClient,
+    fee_ticker::{ActiveFeeParams, TickerRequest},
 };
 
 /// Shared data between `api/v1/config` endpoints.
@@ -22,16 +30,36 @@ struct ApiConfigData {
     contract_address: Address,
     deposit_confirmations: u64,
     network: Network,
+    core_api_client: CoreApiClient,
+    fee_ticker: mpsc::Sender<TickerRequest>,
 }
 
 impl ApiConfigData {
-    fn new(env_options: &ConfigurationOptions) -> Self {
+    fn new(
+        env_options: &ConfigurationOptions,
+        core_api_client: CoreApiClient,
+        fee_ticker: mpsc::Sender<TickerRequest>,
+    ) -> Self {
         Self {
             contract_address: env_options.contract_eth_addr,
             deposit_confirmations: env_options.confirmations_for_eth_event,
             network: env_options.eth_network.parse().unwrap(),
+            core_api_client,
+            fee_ticker,
         }
     }
+
+    async fn fee_params(&self) -> anyhow::Result<ActiveFeeParams> {
+        let (params_sender, params_receiver) = oneshot::channel();
+        self.fee_ticker
+            .clone()
+            .send(TickerRequest::GetFeeParams {
+                response: params_sender,
+            })
+            .await?;
+
+        Ok(params_receiver.await?)
+    }
 }
 
 // Data transfer objects.
@@ -41,6 +69,14 @@ pub struct Contracts {
     pub contract: Address,
 }
 
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkInfo {
+    pub network: Network,
+    /// Whether the new blocks are being produced at the moment.
+    pub block_production_mode: BlockProductionMode,
+}
+
 // Client implementation
 
 /// Configuration API part.
@@ -53,9 +89,13 @@ impl Client {
         self.get("config/deposit_confirmations").send().await
     }
 
-    pub async fn network(&self) -> client::Result<String> {
+    pub async fn network(&self) -> client::Result<NetworkInfo> {
         self.get("config/network").send().await
     }
+
+    pub async fn fee_params(&self) -> client::Result<ActiveFeeParams> {
+        self.get("config/fee_params").send().await
+    }
 }
 
 // Server implementation
@@ -70,17 +110,36 @@ async fn deposit_confirmations(data: web::Data<ApiConfigData>) -> Json<u64> {
     Json(data.deposit_confirmations)
 }
 
-async fn network(data: web::Data<ApiConfigData>) -> Json<Network> {
-    Json(data.network)
+async fn network(data: web::Data<ApiConfigData>) -> JsonResult<NetworkInfo> {
+    let block_production_mode = data
+        .core_api_client
+        .block_production_mode()
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(NetworkInfo {
+        network: data.network,
+        block_production_mode,
+    }))
 }
 
-pub fn api_scope(env_options: &ConfigurationOptions) -> Scope {
-    let data = ApiConfigData::new(env_options);
+async fn fee_params(data: web::Data<ApiConfigData>) -> JsonResult<ActiveFeeParams> {
+    let params = data.fee_params().await.map_err(ApiError::internal)?;
+    Ok(Json(params))
+}
+
+pub fn api_scope(
+    env_options: &ConfigurationOptions,
+    core_api_client: CoreApiClient,
+    fee_ticker: mpsc::Sender<TickerRequest>,
+) -> Scope {
+    let data = ApiConfigData::new(env_options, core_api_client, fee_ticker);
 
     web::scope("config")
         .data(data)
         .route("contracts", web::get().to(contracts))
         .route("network", web::get().to(network))
+        .route("fee_params", web::get().to(fee_params))
         .route(
             "deposit_confirmations",
             web::get().to(deposit_confirmations),
@@ -89,27 +148,93 @@ pub fn api_scope(env_options: &ConfigurationOptions) -> Scope {
 
 #[cfg(test)]
 mod tests {
+    use actix_web::App;
+    use futures::StreamExt;
+    use num::{rational::Ratio, BigUint};
+    use zksync_types::FeeParams;
+
     use super::{super::test_utils::TestServerConfig, *};
 
+    fn block_proposer_loopback() -> (CoreApiClient, actix_web::test::TestServer) {
+        async fn block_production_mode() -> Json<BlockProductionMode> {
+            Json(BlockProductionMode::Paused)
+        }
+
+        let server = actix_web::test::start(move || {
+            App::new().route("block_proposer/mode", web::get().to(block_production_mode))
+        });
+
+        let mut url = server.url("");
+        url.pop(); // Pop last '/' symbol.
+
+        (CoreApiClient::new(url), server)
+    }
+
+    fn dummy_fee_ticker(params: ActiveFeeParams) -> mpsc::Sender<TickerRequest> {
+        let (sender, mut receiver) = mpsc::channel(10);
+
+        actix_rt::spawn(async move {
+            while let Some(item) = receiver.next().await {
+                match item {
+                    TickerRequest::GetFeeParams { response } => {
+                        response
+                            .send(params.clone())
+                            .expect("Unable to send response");
+                    }
+                    _ => unreachable!("Unsupported request"),
+                }
+            }
+        });
+
+        sender
+    }
+
     #[actix_rt::test]
     async fn test_config_scope() -> anyhow::Result<()> {
+        let (core_client, core_server) = block_proposer_loopback();
+        let fee_params = ActiveFeeParams {
+            version: Some(3),
+            params: FeeParams {
+                zkp_cost_chunk_usd: Ratio::new(BigUint::from(1u32), BigUint::from(1000u32)),
+                tokens_risk_factors: vec![(
+                    1,
+                    Ratio::new(BigUint::from(5u32), BigUint::from(2u32)),
+                )]
+                .into_iter()
+                .collect(),
+            },
+            effective_zkp_cost_chunk_usd: Ratio::new(BigUint::from(3u32), BigUint::from(1000u32)),
+            is_zkp_cost_dynamic: true,
+        };
+        let fee_ticker = dummy_fee_ticker(fee_params.clone());
+
         let cfg = TestServerConfig::default();
-        let (client, server) = cfg.start_server(|cfg| api_scope(&cfg.env_options));
+        let (client, server) = cfg.start_server(move |cfg| {
+            api_scope(&cfg.env_options, core_client.clone(), fee_ticker.clone())
+        });
 
         assert_eq!(
             client.deposit_confirmations().await?,
             cfg.env_options.confirmations_for_eth_event
         );
 
-        assert_eq!(client.network().await?, cfg.env_options.eth_network);
+        assert_eq!(
+            client.network().await?,
+            NetworkInfo {
+                network: cfg.env_options.eth_network.parse().unwrap(),
+                block_production_mode: BlockProductionMode::Paused,
+            }
+        );
         assert_eq!(
             client.contracts().await?,
             Contracts {
                 contract: cfg.env_options.contract_eth_addr
             },
         );
+        assert_eq!(client.fee_params().await?, fee_params);
 
         server.stop().await;
+        core_server.stop().await;
 
         Ok(())
     }
@@ -1,7 +1,10 @@
 //! First stable API implementation.
 
 // Public uses
-pub use self::error::{Error, ErrorBody};
+pub use self::{
+    accounts::{PendingTransactions, PendingTx},
+    error::{Error, ErrorBody},
+};
 
 // Built-in uses
 
@@ -19,6 +22,7 @@ use zksync_types::BlockNumber;
 // Local uses
 use crate::api_server::tx_sender::TxSender;
 
+mod accounts;
 mod blocks;
 pub mod client;
 mod config;
@@ -41,7 +45,15 @@ pub(crate) fn api_scope(
     api_server_options: ApiServerOptions,
 ) -> Scope {
     web::scope("/api/v1")
-        .service(config::api_scope(&env_options))
+        .service(accounts::api_scope(
+            tx_sender.pool.clone(),
+            tx_sender.tokens.clone(),
+        ))
+        .service(config::api_scope(
+            &env_options,
+            tx_sender.core_api_client.clone(),
+            tx_sender.ticker_requests.clone(),
+        ))
         .service(blocks::api_scope(
             &api_server_options,
             tx_sender.pool.clone(),
@@ -109,18 +121,24 @@ impl PaginationQuery {
                 .detail("Pagination query contains both `before` and `after` values.")),
         }?;
 
-        if limit == 0 {
-            return Err(Error::bad_request("Incorrect pagination query")
-                .detail("Limit should be greater than zero"));
-        }
+        check_limit(limit)?;
+        Ok((pagination, limit))
+    }
+}
 
-        if limit > MAX_LIMIT {
-            return Err(Error::bad_request("Incorrect pagination query")
-                .detail(format!("Limit should be lower than {}", MAX_LIMIT)));
-        }
+/// Checks that the limit value of the pagination query is within the allowed range.
+fn check_limit(limit: u32) -> Result<(), Error> {
+    if limit == 0 {
+        return Err(Error::bad_request("Incorrect pagination query")
+            .detail("Limit should be greater than zero"));
+    }
 
-        Ok((pagination, limit))
+    if limit > MAX_LIMIT {
+        return Err(Error::bad_request("Incorrect pagination query")
+            .detail(format!("Limit should be lower than {}", MAX_LIMIT)));
     }
+
+    Ok(())
 }
 
 impl Pagination {
@@ -104,9 +104,11 @@ impl TestServerConfig {
                 success: true,
                 op: Some(zksync_op),
                 fail_reason: None,
+                fail_code: None,
                 block_index: None,
                 created_at: chrono::Utc::now(),
                 batch_id: None,
+                trace: None,
             };
 
             txs.push((
@@ -131,9 +133,11 @@ impl TestServerConfig {
                 success: true,
                 op: Some(zksync_op),
                 fail_reason: None,
+                fail_code: None,
                 block_index: None,
                 created_at: chrono::Utc::now(),
                 batch_id: None,
+                trace: None,
             };
 
             txs.push((
@@ -301,8 +305,8 @@ impl TestServerConfig {
                 block_number: 2,
                 block_index: 2,
                 operation: Default::default(),
-                from_account: Default::default(),
-                to_account: Default::default(),
+                from_account: Address::default().as_bytes().to_vec(),
+                to_account: Address::default().as_bytes().to_vec(),
                 priority_op_serialid: VERIFIED_OP_SERIAL_ID as i64,
                 deadline_block: 100,
                 eth_hash: H256::default().as_bytes().to_vec(),
@@ -314,8 +318,8 @@ impl TestServerConfig {
                 block_number: VERIFIED_BLOCKS_COUNT as i64 + 1,
                 block_index: 1,
                 operation: Default::default(),
-                from_account: Default::default(),
-                to_account: Default::default(),
+                from_account: Address::default().as_bytes().to_vec(),
+                to_account: Address::default().as_bytes().to_vec(),
                 priority_op_serialid: COMMITTED_OP_SERIAL_ID as i64,
                 deadline_block: 200,
                 eth_hash: H256::default().as_bytes().to_vec(),
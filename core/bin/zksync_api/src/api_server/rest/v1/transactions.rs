@@ -14,7 +14,10 @@ use zksync_storage::{
     chain::operations_ext::records::TxReceiptResponse, QueryResult, StorageProcessor,
 };
 use zksync_types::{
-    tx::{TxEthSignature, TxHash},
+    tx::{
+        TxCancelRequest, TxEthSignature, TxExecutionError, TxExecutionTrace, TxHash, TxSignature,
+        TxSimulationResult,
+    },
     BlockNumber, SignedZkSyncTx, ZkSyncTx,
 };
 
@@ -32,6 +35,9 @@ pub enum SumbitErrorCode {
     IncorrectTx = 104,
     TxAdd = 105,
     InappropriateFeeToken = 106,
+    TxCancel = 107,
+    FeeQuoteExpired = 108,
+    InvalidFeeQuote = 109,
 
     Internal = 110,
     CommunicationCoreServer = 111,
@@ -46,7 +52,10 @@ impl SumbitErrorCode {
             SubmitError::UnsupportedFastProcessing => Self::UnsupportedFastProcessing,
             SubmitError::IncorrectTx(_) => Self::IncorrectTx,
             SubmitError::TxAdd(_) => Self::TxAdd,
+            SubmitError::TxCancel(_) => Self::TxCancel,
             SubmitError::InappropriateFeeToken => Self::InappropriateFeeToken,
+            SubmitError::FeeQuoteExpired => Self::FeeQuoteExpired,
+            SubmitError::InvalidFeeQuote(_) => Self::InvalidFeeQuote,
             SubmitError::CommunicationCoreServer(_) => Self::CommunicationCoreServer,
             SubmitError::Internal(_) => Self::Internal,
             SubmitError::Other(_) => Self::Other,
@@ -106,11 +115,19 @@ impl ApiTransactionsData {
                     .contains_tx(tx_hash)
                     .await?;
 
-                let tx_receipt = if tx_in_mempool {
-                    Some(TxReceipt::Pending)
-                } else {
-                    None
-                };
+                if tx_in_mempool {
+                    return Ok(Some(TxReceipt::Pending));
+                }
+
+                let evicted_tx = storage
+                    .chain()
+                    .mempool_schema()
+                    .get_evicted_tx(tx_hash)
+                    .await?;
+                let tx_receipt = evicted_tx.map(|tx| match tx.replacement_hash() {
+                    Some(replaced_by) => TxReceipt::Replaced { replaced_by },
+                    None => TxReceipt::Evicted { reason: tx.reason },
+                });
                 return Ok(tx_receipt);
             }
         };
@@ -120,6 +137,7 @@ impl ApiTransactionsData {
         if !tx_receipt.success {
             return Ok(Some(TxReceipt::Rejected {
                 reason: tx_receipt.fail_reason,
+                code: tx_receipt.fail_code,
             }));
         }
 
@@ -178,6 +196,23 @@ impl ApiTransactionsData {
             storage.chain().mempool_schema().get_tx(tx_hash).await
         }
     }
+
+    async fn tx_trace(&self, tx_hash: TxHash) -> QueryResult<Option<TxExecutionTrace>> {
+        let mut storage = self.tx_sender.pool.access_storage().await?;
+
+        let operation = storage
+            .chain()
+            .operations_schema()
+            .get_executed_operation(tx_hash.as_ref())
+            .await?;
+
+        // Traces are recorded only for the successfully executed transactions.
+        let trace = operation
+            .and_then(|op| op.trace)
+            .map(serde_json::from_value)
+            .transpose()?;
+        Ok(trace)
+    }
 }
 
 // Data transfer objects.
@@ -193,6 +228,9 @@ struct FastProcessingQuery {
 struct IncomingTx {
     tx: ZkSyncTx,
     signature: Option<TxEthSignature>,
+    /// Identifier of the fee quote to be honoured instead of the actual fee.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fee_quote: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -201,6 +239,11 @@ struct IncomingTxBatch {
     signature: Option<TxEthSignature>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct IncomingTxCancel {
+    signature: TxSignature,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(tag = "status", rename_all = "camelCase")]
 pub enum TxReceipt {
@@ -214,7 +257,17 @@ pub enum TxReceipt {
     /// The block which contains this transaction has been verified.
     Verified { block: BlockNumber },
     /// The transaction has been rejected for some reasons.
-    Rejected { reason: Option<String> },
+    Rejected {
+        reason: Option<String>,
+        /// Machine-readable reason of the rejection.
+        code: Option<TxExecutionError>,
+    },
+    /// The transaction has been removed from the memorypool without being executed
+    /// (e.g. it was expired or replaced by transactions paying higher fee).
+    Evicted { reason: String },
+    /// The transaction has been replaced in the memorypool by the transaction with the same
+    /// nonce paying higher fee.
+    Replaced { replaced_by: TxHash },
 }
 
 // Client implementation
@@ -227,10 +280,27 @@ impl Client {
         tx: ZkSyncTx,
         signature: Option<TxEthSignature>,
         fast_processing: Option<bool>,
+    ) -> Result<TxHash, ClientError> {
+        self.submit_tx_with_fee_quote(tx, signature, fast_processing, None)
+            .await
+    }
+
+    /// Sends a new transaction to the memory pool, paying the fee fixed by the
+    /// given fee quote, if any.
+    pub async fn submit_tx_with_fee_quote(
+        &self,
+        tx: ZkSyncTx,
+        signature: Option<TxEthSignature>,
+        fast_processing: Option<bool>,
+        fee_quote: Option<String>,
     ) -> Result<TxHash, ClientError> {
         self.post("transactions/submit")
             .query(&FastProcessingQuery { fast_processing })
-            .body(&IncomingTx { tx, signature })
+            .body(&IncomingTx {
+                tx,
+                signature,
+                fee_quote,
+            })
             .send()
             .await
     }
@@ -254,6 +324,35 @@ impl Client {
             .await
     }
 
+    /// Cancels the pending transaction.
+    pub async fn cancel_tx(&self, request: TxCancelRequest) -> Result<(), ClientError> {
+        self.post(&format!(
+            "transactions/{}/cancel",
+            request.tx_hash.to_string()
+        ))
+        .body(&IncomingTxCancel {
+            signature: request.signature,
+        })
+        .send()
+        .await
+    }
+
+    /// Executes the transaction against the current network state without submitting it.
+    pub async fn simulate_tx(&self, tx: ZkSyncTx) -> Result<TxSimulationResult, ClientError> {
+        self.post("transactions/simulate").body(&tx).send().await
+    }
+
+    /// Executes the transactions batch against the current network state without submitting it.
+    pub async fn simulate_tx_batch(
+        &self,
+        txs: Vec<ZkSyncTx>,
+    ) -> Result<Vec<TxSimulationResult>, ClientError> {
+        self.post("transactions/simulate/batch")
+            .body(&txs)
+            .send()
+            .await
+    }
+
     /// Gets transaction content.
     pub async fn tx_data(&self, tx_hash: TxHash) -> Result<Option<SignedZkSyncTx>, ClientError> {
         self.get(&format!("transactions/{}/data", tx_hash.to_string()))
@@ -261,6 +360,13 @@ impl Client {
             .await
     }
 
+    /// Gets the execution trace of the transaction.
+    pub async fn tx_trace(&self, tx_hash: TxHash) -> Result<Option<TxExecutionTrace>, ClientError> {
+        self.get(&format!("transactions/{}/trace", tx_hash.to_string()))
+            .send()
+            .await
+    }
+
     /// Gets transaction receipt by ID.
     pub async fn tx_receipt_by_id(
         &self,
@@ -310,6 +416,15 @@ async fn tx_data(
     Ok(Json(tx_data))
 }
 
+async fn tx_trace(
+    data: web::Data<ApiTransactionsData>,
+    web::Path(tx_hash): web::Path<TxHash>,
+) -> JsonResult<Option<TxExecutionTrace>> {
+    let tx_trace = data.tx_trace(tx_hash).await.map_err(ApiError::internal)?;
+
+    Ok(Json(tx_trace))
+}
+
 async fn tx_receipt_by_id(
     data: web::Data<ApiTransactionsData>,
     web::Path((tx_hash, receipt_id)): web::Path<(TxHash, u32)>,
@@ -353,7 +468,12 @@ async fn submit_tx(
 ) -> JsonResult<TxHash> {
     let tx_hash = data
         .tx_sender
-        .submit_tx(body.tx, body.signature, query.fast_processing)
+        .submit_tx(
+            body.tx,
+            body.signature,
+            query.fast_processing,
+            body.fee_quote,
+        )
         .await
         .map_err(ApiError::from)?;
 
@@ -375,6 +495,49 @@ async fn submit_tx_batch(
     Ok(Json(tx_hashes))
 }
 
+async fn cancel_tx(
+    data: web::Data<ApiTransactionsData>,
+    web::Path(tx_hash): web::Path<TxHash>,
+    Json(body): Json<IncomingTxCancel>,
+) -> JsonResult<()> {
+    let request = TxCancelRequest {
+        tx_hash,
+        signature: body.signature,
+    };
+    data.tx_sender
+        .cancel_tx(request)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(()))
+}
+
+async fn simulate_tx(
+    data: web::Data<ApiTransactionsData>,
+    Json(tx): Json<ZkSyncTx>,
+) -> JsonResult<TxSimulationResult> {
+    let mut results = data
+        .tx_sender
+        .simulate_txs(vec![tx])
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(results.remove(0)))
+}
+
+async fn simulate_tx_batch(
+    data: web::Data<ApiTransactionsData>,
+    Json(txs): Json<Vec<ZkSyncTx>>,
+) -> JsonResult<Vec<TxSimulationResult>> {
+    let results = data
+        .tx_sender
+        .simulate_txs(txs)
+        .await
+        .map_err(ApiError::from)?;
+
+    Ok(Json(results))
+}
+
 pub fn api_scope(tx_sender: TxSender) -> Scope {
     let data = ApiTransactionsData::new(tx_sender);
 
@@ -382,13 +545,17 @@ pub fn api_scope(tx_sender: TxSender) -> Scope {
         .data(data)
         .route("{tx_hash}", web::get().to(tx_status))
         .route("{tx_hash}/data", web::get().to(tx_data))
+        .route("{tx_hash}/trace", web::get().to(tx_trace))
         .route(
             "{tx_hash}/receipts/{receipt_id}",
             web::get().to(tx_receipt_by_id),
         )
         .route("{tx_hash}/receipts", web::get().to(tx_receipts))
+        .route("{tx_hash}/cancel", web::post().to(cancel_tx))
         .route("submit", web::post().to(submit_tx))
         .route("submit/batch", web::post().to(submit_tx_batch))
+        .route("simulate", web::post().to(simulate_tx))
+        .route("simulate/batch", web::post().to(simulate_tx_batch))
 }
 
 #[cfg(test)]
@@ -396,11 +563,19 @@ mod tests {
     use actix_web::App;
 
     use bigdecimal::BigDecimal;
+    use chrono::Duration;
     use futures::{channel::mpsc, prelude::*};
     use num::BigUint;
-    use zksync_storage::ConnectionPool;
+    use zksync_config::ApiServerOptions;
+    use zksync_storage::{chain::operations::records::NewExecutedTransaction, ConnectionPool};
     use zksync_test_account::ZkSyncAccount;
-    use zksync_types::{tokens::TokenLike, tx::PackedEthSignature, SignedZkSyncTx};
+    use zksync_types::{
+        codec,
+        mempool::SignedTxsBatch,
+        tokens::TokenLike,
+        tx::{PackedEthSignature, TracedFee},
+        ExecutedOperations, SignedZkSyncTx, TxFeeTypes,
+    };
 
     use super::{
         super::test_utils::{TestServerConfig, TestTransactions},
@@ -409,25 +584,41 @@ mod tests {
     use crate::{
         api_server::rest::helpers::try_parse_tx_hash,
         core_api_client::CoreApiClient,
-        fee_ticker::{Fee, OutputFeeType::Withdraw, TickerRequest},
+        fee_ticker::{Fee, FeeQuoteSigner, OutputFeeType::Withdraw, TickerRequest},
         signature_checker::{VerifiedTx, VerifyTxSignatureRequest},
     };
 
     fn submit_txs_loopback() -> (CoreApiClient, actix_web::test::TestServer) {
-        async fn send_tx(_tx: Json<SignedZkSyncTx>) -> Json<Result<(), ()>> {
+        async fn send_tx(body: web::Bytes) -> Json<Result<(), ()>> {
+            codec::decode::<SignedZkSyncTx>(&body).expect("Malformed transaction");
+            Json(Ok(()))
+        }
+
+        async fn send_txs_batch(body: web::Bytes) -> Json<Result<(), ()>> {
+            codec::decode::<SignedTxsBatch>(&body).expect("Malformed transactions batch");
             Json(Ok(()))
         }
 
-        async fn send_txs_batch(
-            _txs: Json<(Vec<SignedZkSyncTx>, Option<TxEthSignature>)>,
-        ) -> Json<Result<(), ()>> {
+        async fn cancel_tx(_tx_hash: Json<TxHash>) -> Json<Result<(), ()>> {
             Json(Ok(()))
         }
 
+        async fn simulate_tx(
+            txs: Json<Vec<SignedZkSyncTx>>,
+        ) -> Json<Result<Vec<TxSimulationResult>, ()>> {
+            let results = txs
+                .iter()
+                .map(|tx| TxSimulationResult::success(Vec::new(), tx.min_chunks(), None))
+                .collect();
+            Json(Ok(results))
+        }
+
         let server = actix_web::test::start(move || {
             App::new()
                 .route("new_tx", web::post().to(send_tx))
                 .route("new_txs_batch", web::post().to(send_txs_batch))
+                .route("cancel_tx", web::post().to(cancel_tx))
+                .route("simulate_tx", web::post().to(simulate_tx))
         });
 
         let mut url = server.url("");
@@ -438,6 +629,7 @@ mod tests {
 
     fn dummy_fee_ticker() -> mpsc::Sender<TickerRequest> {
         let (sender, mut receiver) = mpsc::channel(10);
+        let mut quote_signer = FeeQuoteSigner::from_options(&ApiServerOptions::from_env());
 
         actix_rt::spawn(async move {
             while let Some(item) = receiver.next().await {
@@ -447,6 +639,7 @@ mod tests {
                             Withdraw,
                             BigUint::from(1_u64).into(),
                             BigUint::from(1_u64).into(),
+                            BigUint::from(1_u64).into(),
                             1_u64.into(),
                             1_u64.into(),
                         ));
@@ -467,6 +660,27 @@ mod tests {
                         };
                         response.send(Ok(!is_phnx)).unwrap_or_default();
                     }
+                    TickerRequest::VerifyFeeQuote {
+                        quote_id,
+                        tx_type,
+                        address,
+                        token,
+                        response,
+                    } => {
+                        let fee = quote_signer.verify(&quote_id, tx_type, address, token);
+                        response.send(fee).unwrap_or_default();
+                    }
+                    TickerRequest::RedeemFeeQuote {
+                        quote_id,
+                        tx_type,
+                        address,
+                        token,
+                        response,
+                    } => {
+                        let fee = quote_signer.redeem(&quote_id, tx_type, address, token);
+                        response.send(fee).unwrap_or_default();
+                    }
+                    _ => unreachable!("Unsupported request"),
                 }
             }
         });
@@ -542,7 +756,11 @@ mod tests {
         };
 
         core_client.send_tx(signed_tx.clone()).await??;
-        core_client.send_txs_batch(vec![signed_tx], None).await??;
+        core_client
+            .send_txs_batch(vec![signed_tx.clone()], None)
+            .await??;
+        core_client.cancel_tx(TxHash::default()).await??;
+        assert_eq!(core_client.simulate_tx(vec![signed_tx]).await?.len(), 1);
 
         core_server.stop().await;
         Ok(())
@@ -617,6 +835,37 @@ mod tests {
             committed_tx_hash
         );
 
+        // Tx trace for executed transaction.
+        let (traced_tx_hash, expected_trace) = {
+            let mut storage = server.pool.access_storage().await?;
+
+            let (tx, op) = TestServerConfig::gen_zk_txs(1_u64).txs[1].clone();
+            let mut executed_tx = match op {
+                ExecutedOperations::Tx(executed_tx) => *executed_tx,
+                ExecutedOperations::PriorityOp(_) => unreachable!(),
+            };
+            let trace = TxExecutionTrace {
+                block_index: 0,
+                chunks: tx.min_chunks(),
+                fee: Some(TracedFee {
+                    token: 0,
+                    amount: 1_u64.into(),
+                }),
+                updates: vec![],
+            };
+            executed_tx.trace = Some(trace.clone());
+            storage
+                .chain()
+                .operations_schema()
+                .store_executed_tx(NewExecutedTransaction::prepare_stored_tx(executed_tx, 1))
+                .await?;
+
+            (tx.hash(), trace)
+        };
+        assert_eq!(client.tx_trace(traced_tx_hash).await?, Some(expected_trace));
+        assert_eq!(client.tx_trace(committed_tx_hash).await?, None);
+        assert_eq!(client.tx_trace(unknown_tx_hash).await?, None);
+
         // Tx status and data for pending transaction.
         let tx_hash = {
             let mut storage = server.pool.access_storage().await?;
@@ -637,11 +886,80 @@ mod tests {
         assert_eq!(client.tx_status(tx_hash).await?, Some(TxReceipt::Pending));
         assert_eq!(client.tx_data(tx_hash).await?.unwrap().hash(), tx_hash);
 
+        // Tx status for evicted transaction.
+        {
+            let mut storage = server.pool.access_storage().await?;
+            storage
+                .chain()
+                .mempool_schema()
+                .evict_txs(&[tx_hash], "Transaction expired")
+                .await?;
+        }
+        assert_eq!(
+            client.tx_status(tx_hash).await?,
+            Some(TxReceipt::Evicted {
+                reason: "Transaction expired".to_owned()
+            })
+        );
+
+        // Tx status for replaced transaction.
+        let replaced_by = TestServerConfig::gen_zk_txs(1_u64).txs[1].0.hash();
+        {
+            let mut storage = server.pool.access_storage().await?;
+            storage
+                .chain()
+                .mempool_schema()
+                .replace_tx(tx_hash, replaced_by, "Replaced by fee")
+                .await?;
+        }
+        assert_eq!(
+            client.tx_status(tx_hash).await?,
+            Some(TxReceipt::Replaced { replaced_by })
+        );
+
         // Tx status for unknown transaction.
         let tx_hash = TestServerConfig::gen_zk_txs(1_u64).txs[1].0.hash();
         assert_eq!(client.tx_status(tx_hash).await?, None);
         assert!(client.tx_data(tx_hash).await?.is_none());
 
+        // Cancel pending transaction.
+        let TestTransactions { acc, txs } = TestServerConfig::gen_zk_txs(1_u64);
+        let pending_tx_hash = {
+            let mut storage = server.pool.access_storage().await?;
+
+            let tx = txs[1].0.clone();
+            let tx_hash = tx.hash();
+            storage
+                .chain()
+                .mempool_schema()
+                .insert_tx(&SignedZkSyncTx {
+                    tx,
+                    eth_sign_data: None,
+                })
+                .await?;
+
+            tx_hash
+        };
+        let wrong_key = ZkSyncAccount::rand().private_key;
+        assert!(client
+            .cancel_tx(TxCancelRequest::new_signed(pending_tx_hash, &wrong_key))
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("Cancel request signature is incorrect"));
+        client
+            .cancel_tx(TxCancelRequest::new_signed(
+                pending_tx_hash,
+                &acc.private_key,
+            ))
+            .await?;
+        assert!(client
+            .cancel_tx(TxCancelRequest::new_signed(tx_hash, &acc.private_key))
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("Tx is not pending in the mempool"));
+
         // Submit correct transaction.
         let tx = TestServerConfig::gen_zk_txs(1_00).txs[0].0.clone();
         let expected_tx_hash = tx.hash();
@@ -656,6 +974,22 @@ mod tests {
             .to_string()
             .contains("Transaction fee is too low"));
 
+        // Simulate transaction and transactions batch.
+        let txs = TestServerConfig::gen_zk_txs(1_00).txs;
+        let result = client.simulate_tx(txs[0].0.clone()).await?;
+        assert!(result.success);
+        assert_eq!(result.chunks, txs[0].0.min_chunks());
+        let results = client
+            .simulate_tx_batch(txs.iter().map(|(tx, _op)| tx.clone()).collect())
+            .await?;
+        assert_eq!(results.len(), txs.len());
+        assert!(client
+            .simulate_tx_batch(vec![])
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("Transaction batch is empty"));
+
         // Submit correct transactions batch.
         let TestTransactions { acc, txs } = TestServerConfig::gen_zk_txs(1_00);
         let (txs, tx_hashes): (Vec<_>, Vec<_>) = txs
@@ -851,4 +1185,111 @@ mod tests {
         server.stop().await;
         Ok(())
     }
+
+    /// This test checks the following criteria:
+    ///
+    /// - Attempt to submit transaction with a valid fee quote but without the Ethereum signature
+    ///   fails and doesn't redeem the quote.
+    /// - Transaction paying less than the actual fee is accepted with the valid fee quote.
+    /// - Attempt to submit another transaction with the same fee quote fails.
+    /// - Attempt to submit transaction with an expired fee quote fails.
+    /// - Attempt to submit transaction with a fee quote signed by someone else fails.
+    /// - Attempt to submit transaction with a fee quote issued for another transaction fails.
+    #[actix_rt::test]
+    async fn test_fee_quotes() -> anyhow::Result<()> {
+        let (client, server) = TestServer::new().await?;
+
+        let from = ZkSyncAccount::rand();
+        from.set_account_id(Some(0xdead));
+        let to = ZkSyncAccount::rand();
+
+        // Dummy fee ticker requires the fee of 2 wei.
+        let (tx, eth_sig) = from.sign_transfer(
+            0,
+            "ETH",
+            10_u64.into(),
+            1_u64.into(),
+            &to.address,
+            None,
+            false,
+        );
+        let submit_tx_with_signature = |fee_quote: Option<String>, eth_sig| {
+            client.submit_tx_with_fee_quote(
+                ZkSyncTx::Transfer(Box::new(tx.clone())),
+                eth_sig,
+                None,
+                fee_quote,
+            )
+        };
+        let submit_tx = |fee_quote: Option<String>| {
+            submit_tx_with_signature(
+                fee_quote,
+                Some(TxEthSignature::EthereumSignature(eth_sig.clone())),
+            )
+        };
+
+        assert!(submit_tx(None)
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("Transaction fee is too low"));
+
+        let options = ApiServerOptions::from_env();
+        let quote = FeeQuoteSigner::from_options(&options).issue(
+            TxFeeTypes::Transfer,
+            to.address,
+            0,
+            1_u64.into(),
+        );
+        assert!(submit_tx_with_signature(Some(quote.id.clone()), None)
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("MissingEthSignature"));
+        submit_tx(Some(quote.id.clone())).await?;
+        assert!(submit_tx(Some(quote.id))
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("already used"));
+
+        let quote = FeeQuoteSigner::new(&options.fee_quote_secret, Duration::seconds(-60)).issue(
+            TxFeeTypes::Transfer,
+            to.address,
+            0,
+            1_u64.into(),
+        );
+        assert!(submit_tx(Some(quote.id))
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("Fee quote is expired"));
+
+        let quote = FeeQuoteSigner::new("other secret", Duration::seconds(60)).issue(
+            TxFeeTypes::Transfer,
+            to.address,
+            0,
+            1_u64.into(),
+        );
+        assert!(submit_tx(Some(quote.id))
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("incorrect signature"));
+
+        let quote = FeeQuoteSigner::from_options(&options).issue(
+            TxFeeTypes::Transfer,
+            from.address,
+            0,
+            1_u64.into(),
+        );
+        assert!(submit_tx(Some(quote.id))
+            .await
+            .unwrap_err()
+            .to_string()
+            .contains("issued for another transaction"));
+
+        server.stop().await;
+        Ok(())
+    }
 }
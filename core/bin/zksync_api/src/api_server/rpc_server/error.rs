@@ -10,6 +10,8 @@ pub enum RpcErrorCodes {
     IncorrectTx = 103,
     FeeTooLow = 104,
     InappropriateFeeToken = 105,
+    FeeQuoteExpired = 106,
+    InvalidFeeQuote = 107,
 
     MissingEthSignature = 200,
     EIP1271SignatureVerificationFail = 201,
@@ -20,6 +22,7 @@ pub enum RpcErrorCodes {
     AccountCloseDisabled = 301,
     OperationsLimitReached = 302,
     UnsupportedFastProcessing = 303,
+    TxCancelFailed = 304,
 }
 
 impl From<TxAddError> for RpcErrorCodes {
@@ -38,6 +41,13 @@ impl From<TxAddError> for RpcErrorCodes {
             TxAddError::EmptyBatch => Self::Other,
             TxAddError::BatchTooBig => Self::Other,
             TxAddError::BatchWithdrawalsOverload => Self::Other,
+            TxAddError::MempoolIsFull => Self::OperationsLimitReached,
+            TxAddError::TooManyPendingTxs => Self::OperationsLimitReached,
+            TxAddError::ReplacementFeeTooLow => Self::FeeTooLow,
+            TxAddError::BatchTxReplacement => Self::NonceMismatch,
+            TxAddError::BatchNonceConflict => Self::NonceMismatch,
+            TxAddError::NonceTooHigh => Self::NonceMismatch,
+            TxAddError::InvalidTimeRange => Self::IncorrectTx,
         }
     }
 }
@@ -74,11 +84,26 @@ impl From<SubmitError> for jsonrpc_core::Error {
                 message: inner.to_string(),
                 data: None,
             },
+            SubmitError::TxCancel(inner) => Self {
+                code: RpcErrorCodes::TxCancelFailed.into(),
+                message: inner.to_string(),
+                data: None,
+            },
             SubmitError::InappropriateFeeToken => Self {
                 code: RpcErrorCodes::InappropriateFeeToken.into(),
                 message: inner.to_string(),
                 data: None,
             },
+            SubmitError::FeeQuoteExpired => Self {
+                code: RpcErrorCodes::FeeQuoteExpired.into(),
+                message: inner.to_string(),
+                data: None,
+            },
+            SubmitError::InvalidFeeQuote(_) => Self {
+                code: RpcErrorCodes::InvalidFeeQuote.into(),
+                message: inner.to_string(),
+                data: None,
+            },
             SubmitError::CommunicationCoreServer(reason) => Self {
                 code: RpcErrorCodes::Other.into(),
                 message: "Error communicating core server".to_string(),
@@ -11,12 +11,13 @@ use jsonrpc_http_server::ServerBuilder;
 use zksync_config::{ApiServerOptions, ConfigurationOptions};
 use zksync_storage::{
     chain::{
-        block::records::BlockDetails, operations::records::StoredExecutedPriorityOperation,
+        block::records::BlockDetails, mempool::records::MempoolEvictedTx,
+        operations::records::StoredExecutedPriorityOperation,
         operations_ext::records::TxReceiptResponse,
     },
     ConnectionPool, StorageProcessor,
 };
-use zksync_types::{tx::TxHash, Address, PriorityOp, TokenLike, TxFeeTypes};
+use zksync_types::{tx::TxHash, Address, PriorityOp, SignedZkSyncTx, TokenLike, TxFeeTypes};
 // Local uses
 use crate::{
     core_api_client::{CoreApiClient, EthBlockId},
@@ -246,6 +247,44 @@ impl RpcApp {
         Ok(res)
     }
 
+    async fn get_account_pending_txs(&self, address: Address) -> Result<Vec<SignedZkSyncTx>> {
+        let start = Instant::now();
+        let mut storage = self.access_storage().await?;
+        let txs = storage
+            .chain()
+            .mempool_schema()
+            .get_account_txs(address)
+            .await
+            .map_err(|err| {
+                vlog::warn!("Internal Server Error: '{}'; input: {:?}", err, address);
+                Error::internal_error()
+            })?;
+
+        metrics::histogram!("api.rpc.get_account_pending_txs", start.elapsed());
+        Ok(txs)
+    }
+
+    async fn get_evicted_tx(&self, tx_hash: TxHash) -> Result<Option<MempoolEvictedTx>> {
+        let start = Instant::now();
+        let mut storage = self.access_storage().await?;
+        let evicted_tx = storage
+            .chain()
+            .mempool_schema()
+            .get_evicted_tx(tx_hash)
+            .await
+            .map_err(|err| {
+                vlog::warn!(
+                    "Internal Server Error: '{}'; input: {}",
+                    err,
+                    tx_hash.to_string()
+                );
+                Error::internal_error()
+            })?;
+
+        metrics::histogram!("api.rpc.get_evicted_tx", start.elapsed());
+        Ok(evicted_tx)
+    }
+
     async fn token_allowed_for_fees(
         mut ticker_request_sender: mpsc::Sender<TickerRequest>,
         token: TokenLike,
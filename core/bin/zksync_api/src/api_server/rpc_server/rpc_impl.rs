@@ -6,13 +6,13 @@ use num::BigUint;
 // Workspace uses
 use zksync_types::{
     helpers::closest_packable_fee_amount,
-    tx::{TxEthSignature, TxHash},
+    tx::{TxCancelRequest, TxEthSignature, TxHash, TxSignature, TxSimulationResult},
     Address, Token, TokenLike, TxFeeTypes, ZkSyncTx,
 };
 
 // Local uses
 use crate::{
-    api_server::tx_sender::SubmitError,
+    api_server::{tx_sender::SubmitError, v1::PendingTransactions},
     fee_ticker::{BatchFee, Fee, TokenPriceRequestType},
 };
 use bigdecimal::BigDecimal;
@@ -83,18 +83,34 @@ impl RpcApp {
                 executed: true,
                 success: Some(stored_receipt.success),
                 fail_reason: stored_receipt.fail_reason,
+                fail_code: stored_receipt.fail_code,
                 block: Some(BlockInfo {
                     block_number: stored_receipt.block_number,
                     committed: true,
                     verified: stored_receipt.verified,
                 }),
+                evicted: false,
+                replaced_by: None,
+            }
+        } else if let Some(evicted_tx) = self.get_evicted_tx(tx_hash).await? {
+            TransactionInfoResp {
+                executed: false,
+                success: None,
+                replaced_by: evicted_tx.replacement_hash(),
+                fail_reason: Some(evicted_tx.reason),
+                fail_code: None,
+                block: None,
+                evicted: true,
             }
         } else {
             TransactionInfoResp {
                 executed: false,
                 success: None,
                 fail_reason: None,
+                fail_code: None,
                 block: None,
+                evicted: false,
+                replaced_by: None,
             }
         })
     }
@@ -104,17 +120,31 @@ impl RpcApp {
         tx: Box<ZkSyncTx>,
         signature: Box<Option<TxEthSignature>>,
         fast_processing: Option<bool>,
+        fee_quote: Option<String>,
     ) -> Result<TxHash> {
         let start = Instant::now();
         let result = self
             .tx_sender
-            .submit_tx(*tx, *signature, fast_processing)
+            .submit_tx(*tx, *signature, fast_processing, fee_quote)
             .await
             .map_err(Error::from);
         metrics::histogram!("api.rpc.tx_submit", start.elapsed());
         result
     }
 
+    pub async fn _impl_tx_cancel(self, tx_hash: TxHash, signature: TxSignature) -> Result<bool> {
+        let start = Instant::now();
+        let request = TxCancelRequest { tx_hash, signature };
+        let result = self
+            .tx_sender
+            .cancel_tx(request)
+            .await
+            .map(|_| true)
+            .map_err(Error::from);
+        metrics::histogram!("api.rpc.tx_cancel", start.elapsed());
+        result
+    }
+
     pub async fn _impl_submit_txs_batch(
         self,
         txs: Vec<TxWithSignature>,
@@ -131,6 +161,42 @@ impl RpcApp {
         result
     }
 
+    pub async fn _impl_pending_txs(self, address: Address) -> Result<PendingTransactions> {
+        let start = Instant::now();
+
+        let account_state = self.get_account_state(&address).await?;
+        let txs = self.get_account_pending_txs(address).await?;
+
+        metrics::histogram!("api.rpc.pending_txs", start.elapsed());
+        Ok(PendingTransactions::new(
+            address,
+            account_state.committed.nonce,
+            txs,
+        ))
+    }
+
+    pub async fn _impl_tx_simulate(self, tx: Box<ZkSyncTx>) -> Result<TxSimulationResult> {
+        let start = Instant::now();
+        let result = self
+            .tx_sender
+            .simulate_txs(vec![*tx])
+            .await
+            .map(|mut results| results.remove(0))
+            .map_err(Error::from);
+        metrics::histogram!("api.rpc.tx_simulate", start.elapsed());
+        result
+    }
+
+    pub async fn _impl_simulate_txs_batch(
+        self,
+        txs: Vec<ZkSyncTx>,
+    ) -> Result<Vec<TxSimulationResult>> {
+        let start = Instant::now();
+        let result = self.tx_sender.simulate_txs(txs).await.map_err(Error::from);
+        metrics::histogram!("api.rpc.simulate_txs_batch", start.elapsed());
+        result
+    }
+
     pub async fn _impl_contract_address(self) -> Result<ContractAddressResp> {
         let start = Instant::now();
         let mut storage = self.access_storage().await?;
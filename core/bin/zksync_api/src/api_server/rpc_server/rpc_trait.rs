@@ -5,12 +5,15 @@ use jsonrpc_core::Error;
 use jsonrpc_derive::rpc;
 // Workspace uses
 use zksync_types::{
-    tx::{TxEthSignature, TxHash},
+    tx::{TxEthSignature, TxHash, TxSignature, TxSimulationResult},
     Address, Token, TokenLike, TxFeeTypes, ZkSyncTx,
 };
 
 // Local uses
-use crate::fee_ticker::{BatchFee, Fee};
+use crate::{
+    api_server::v1::PendingTransactions,
+    fee_ticker::{BatchFee, Fee},
+};
 use bigdecimal::BigDecimal;
 
 use super::{types::*, RpcApp};
@@ -34,6 +37,7 @@ pub trait Rpc {
         tx: Box<ZkSyncTx>,
         signature: Box<Option<TxEthSignature>>,
         fast_processing: Option<bool>,
+        fee_quote: Option<String>,
     ) -> FutureResp<TxHash>;
 
     #[rpc(name = "submit_txs_batch", returns = "Vec<TxHash>")]
@@ -43,6 +47,18 @@ pub trait Rpc {
         eth_signature: Option<TxEthSignature>,
     ) -> FutureResp<Vec<TxHash>>;
 
+    #[rpc(name = "tx_cancel", returns = "bool")]
+    fn tx_cancel(&self, hash: TxHash, signature: TxSignature) -> FutureResp<bool>;
+
+    #[rpc(name = "pending_txs", returns = "PendingTransactions")]
+    fn pending_txs(&self, addr: Address) -> FutureResp<PendingTransactions>;
+
+    #[rpc(name = "tx_simulate", returns = "TxSimulationResult")]
+    fn tx_simulate(&self, tx: Box<ZkSyncTx>) -> FutureResp<TxSimulationResult>;
+
+    #[rpc(name = "simulate_txs_batch", returns = "Vec<TxSimulationResult>")]
+    fn simulate_txs_batch(&self, txs: Vec<ZkSyncTx>) -> FutureResp<Vec<TxSimulationResult>>;
+
     #[rpc(name = "contract_address", returns = "ContractAddressResp")]
     fn contract_address(&self) -> FutureResp<ContractAddressResp>;
 
@@ -108,12 +124,13 @@ impl Rpc for RpcApp {
         tx: Box<ZkSyncTx>,
         signature: Box<Option<TxEthSignature>>,
         fast_processing: Option<bool>,
+        fee_quote: Option<String>,
     ) -> FutureResp<TxHash> {
         let handle = self.runtime_handle.clone();
         let self_ = self.clone();
         let resp = async move {
             handle
-                .spawn(self_._impl_tx_submit(tx, signature, fast_processing))
+                .spawn(self_._impl_tx_submit(tx, signature, fast_processing, fee_quote))
                 .await
                 .unwrap()
         };
@@ -136,6 +153,44 @@ impl Rpc for RpcApp {
         Box::new(resp.boxed().compat())
     }
 
+    fn tx_cancel(&self, hash: TxHash, signature: TxSignature) -> FutureResp<bool> {
+        let handle = self.runtime_handle.clone();
+        let self_ = self.clone();
+        let resp = async move {
+            handle
+                .spawn(self_._impl_tx_cancel(hash, signature))
+                .await
+                .unwrap()
+        };
+        Box::new(resp.boxed().compat())
+    }
+
+    fn pending_txs(&self, addr: Address) -> FutureResp<PendingTransactions> {
+        let handle = self.runtime_handle.clone();
+        let self_ = self.clone();
+        let resp = async move { handle.spawn(self_._impl_pending_txs(addr)).await.unwrap() };
+        Box::new(resp.boxed().compat())
+    }
+
+    fn tx_simulate(&self, tx: Box<ZkSyncTx>) -> FutureResp<TxSimulationResult> {
+        let handle = self.runtime_handle.clone();
+        let self_ = self.clone();
+        let resp = async move { handle.spawn(self_._impl_tx_simulate(tx)).await.unwrap() };
+        Box::new(resp.boxed().compat())
+    }
+
+    fn simulate_txs_batch(&self, txs: Vec<ZkSyncTx>) -> FutureResp<Vec<TxSimulationResult>> {
+        let handle = self.runtime_handle.clone();
+        let self_ = self.clone();
+        let resp = async move {
+            handle
+                .spawn(self_._impl_simulate_txs_batch(txs))
+                .await
+                .unwrap()
+        };
+        Box::new(resp.boxed().compat())
+    }
+
     fn contract_address(&self) -> FutureResp<ContractAddressResp> {
         let handle = self.runtime_handle.clone();
         let self_ = self.clone();
@@ -5,8 +5,8 @@ use num::{BigUint, ToPrimitive};
 use serde::{Deserialize, Serialize};
 // Workspace uses
 use zksync_types::{
-    tx::TxEthSignature, Account, AccountId, Address, Nonce, PriorityOp, PubKeyHash,
-    ZkSyncPriorityOp, ZkSyncTx,
+    tx::{TxEthSignature, TxExecutionError, TxHash},
+    Account, AccountId, Address, Nonce, PriorityOp, PubKeyHash, ZkSyncPriorityOp, ZkSyncTx,
 };
 use zksync_utils::{BigUintSerdeAsRadix10Str, BigUintSerdeWrapper};
 // Local uses
@@ -136,7 +136,17 @@ pub struct TransactionInfoResp {
     pub executed: bool,
     pub success: Option<bool>,
     pub fail_reason: Option<String>,
+    /// Machine-readable reason of the execution failure.
+    #[serde(default)]
+    pub fail_code: Option<TxExecutionError>,
     pub block: Option<BlockInfo>,
+    /// Transaction was removed from the mempool without being executed,
+    /// the reason of eviction is reported in the `fail_reason` field.
+    #[serde(default)]
+    pub evicted: bool,
+    /// Hash of the transaction with the same nonce that replaced the evicted one.
+    #[serde(default)]
+    pub replaced_by: Option<TxHash>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
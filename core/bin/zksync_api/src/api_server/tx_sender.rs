@@ -10,7 +10,7 @@ use futures::{
     channel::{mpsc, oneshot},
     prelude::*,
 };
-use num::bigint::ToBigInt;
+use num::{bigint::ToBigInt, BigUint};
 use thiserror::Error;
 
 // Workspace uses
@@ -18,16 +18,16 @@ use zksync_config::ApiServerOptions;
 use zksync_storage::ConnectionPool;
 use zksync_types::{
     tx::EthSignData,
-    tx::{SignedZkSyncTx, TxEthSignature, TxHash},
+    tx::{SignedZkSyncTx, TxCancelRequest, TxEthSignature, TxHash, TxSimulationResult},
     Address, Token, TokenId, TokenLike, TxFeeTypes, ZkSyncTx,
 };
 
 // Local uses
 use crate::{
     core_api_client::CoreApiClient,
-    fee_ticker::{Fee, TickerRequest, TokenPriceRequestType},
+    fee_ticker::{Fee, FeeQuoteError, TickerRequest, TokenPriceRequestType},
     signature_checker::{TxVariant, VerifiedTx, VerifyTxSignatureRequest},
-    tx_error::TxAddError,
+    tx_error::{TxAddError, TxCancelError},
     utils::token_db_cache::TokenDBCache,
 };
 
@@ -56,8 +56,14 @@ pub enum SubmitError {
     IncorrectTx(String),
     #[error("Transaction adding error: {0}.")]
     TxAdd(TxAddError),
+    #[error("Transaction cancel error: {0}.")]
+    TxCancel(TxCancelError),
     #[error("Chosen token is not suitable for paying fees.")]
     InappropriateFeeToken,
+    #[error("Fee quote is expired.")]
+    FeeQuoteExpired,
+    #[error("{0}.")]
+    InvalidFeeQuote(FeeQuoteError),
 
     #[error("Communication error with the core server: {0}.")]
     CommunicationCoreServer(String),
@@ -143,6 +149,7 @@ impl TxSender {
         mut tx: ZkSyncTx,
         signature: Option<TxEthSignature>,
         fast_processing: Option<bool>,
+        fee_quote: Option<String>,
     ) -> Result<TxHash, SubmitError> {
         if tx.is_close() {
             return Err(SubmitError::AccountCloseDisabled);
@@ -178,9 +185,12 @@ impl TxSender {
         let sign_verify_channel = self.sign_verify_requests.clone();
         let ticker_request_sender = self.ticker_requests.clone();
 
+        // Quote is redeemed only once the transaction is signed correctly, so the rejected
+        // submission doesn't prevent the quote from being used with the corrected one.
+        let mut quote_to_redeem = None;
         if let Some((tx_type, token, address, provided_fee)) = tx_fee_info {
-            let should_enforce_fee =
-                !matches!(tx_type, TxFeeTypes::ChangePubKey{..}) || self.enforce_pubkey_change_fee;
+            let should_enforce_fee = !matches!(tx_type, TxFeeTypes::ChangePubKey { .. })
+                || self.enforce_pubkey_change_fee;
 
             let fee_allowed =
                 Self::token_allowed_for_fees(ticker_request_sender.clone(), token.clone()).await?;
@@ -189,11 +199,26 @@ impl TxSender {
                 return Err(SubmitError::InappropriateFeeToken);
             }
 
-            let required_fee =
+            let required_fee = if let Some(quote_id) = fee_quote {
+                // The quoted fee is honoured until the quote expires, regardless of the actual one.
+                let quoted_fee = Self::fee_quote_request(
+                    ticker_request_sender.clone(),
+                    quote_id.clone(),
+                    tx_type,
+                    address,
+                    &token,
+                    false,
+                )
+                .await?;
+                quote_to_redeem = Some((quote_id, tx_type, address, token.clone()));
+                quoted_fee
+            } else {
                 Self::ticker_request(ticker_request_sender, tx_type, address, token.clone())
-                    .await?;
+                    .await?
+                    .total_fee
+            };
             // Converting `BitUint` to `BigInt` is safe.
-            let required_fee: BigDecimal = required_fee.total_fee.to_bigint().unwrap().into();
+            let required_fee: BigDecimal = required_fee.to_bigint().unwrap().into();
             let provided_fee: BigDecimal = provided_fee.to_bigint().unwrap().into();
             // Scaling the fee required since the price may change between signing the transaction and sending it to the server.
             let scaled_provided_fee = scale_user_fee_up(provided_fee.clone());
@@ -220,6 +245,18 @@ impl TxSender {
         .await?
         .unwrap_tx();
 
+        if let Some((quote_id, tx_type, address, token)) = quote_to_redeem {
+            Self::fee_quote_request(
+                self.ticker_requests.clone(),
+                quote_id,
+                tx_type,
+                address,
+                &token,
+                true,
+            )
+            .await?;
+        }
+
         // Send verified transactions to the mempool.
         self.core_api_client
             .send_tx(verified_tx)
@@ -230,6 +267,62 @@ impl TxSender {
         Ok(tx.hash())
     }
 
+    /// Removes the pending transaction from the mempool.
+    /// Cancel request must be signed with the same zkSync key as the transaction itself.
+    pub async fn cancel_tx(&self, request: TxCancelRequest) -> Result<(), SubmitError> {
+        let mut storage = self
+            .pool
+            .access_storage()
+            .await
+            .map_err(SubmitError::internal)?;
+        let tx = storage
+            .chain()
+            .mempool_schema()
+            .get_tx(request.tx_hash)
+            .await
+            .map_err(SubmitError::internal)?
+            .ok_or(SubmitError::TxCancel(TxCancelError::NotPending))?;
+
+        let signer = request.verify_signature();
+        if signer.is_none() || signer != tx.tx.verify_signature() {
+            return Err(SubmitError::TxCancel(TxCancelError::IncorrectSignature));
+        }
+
+        self.core_api_client
+            .cancel_tx(request.tx_hash)
+            .await
+            .map_err(SubmitError::communication_core_server)?
+            .map_err(SubmitError::TxCancel)
+    }
+
+    /// Executes the transactions against the current network state without submitting them.
+    /// Several transactions are executed as a batch, the result is returned for each of them.
+    /// Batch is subject to the same size limits as the submitted ones.
+    ///
+    /// Transactions must be signed with the zkSync key, but the Ethereum signature is not required.
+    pub async fn simulate_txs(
+        &self,
+        txs: Vec<ZkSyncTx>,
+    ) -> Result<Vec<TxSimulationResult>, SubmitError> {
+        if txs.is_empty() {
+            return Err(SubmitError::TxAdd(TxAddError::EmptyBatch));
+        }
+
+        let txs = txs
+            .into_iter()
+            .map(|tx| SignedZkSyncTx {
+                tx,
+                eth_sign_data: None,
+            })
+            .collect();
+
+        self.core_api_client
+            .simulate_tx(txs)
+            .await
+            .map_err(SubmitError::communication_core_server)?
+            .map_err(SubmitError::TxAdd)
+    }
+
     pub async fn submit_txs_batch(
         &self,
         txs: Vec<(ZkSyncTx, Option<TxEthSignature>)>,
@@ -423,6 +516,53 @@ impl TxSender {
             .ok_or_else(|| SubmitError::other("Token not found in the DB"))
     }
 
+    /// Checks the fee quote provided along with the transaction and returns the quoted fee.
+    /// If `redeem` is set, the quote is also marked as used. Fee ticker is the only one
+    /// redeeming the quotes, so every quote is used at most once.
+    async fn fee_quote_request(
+        mut ticker_request_sender: mpsc::Sender<TickerRequest>,
+        quote_id: String,
+        tx_type: TxFeeTypes,
+        address: Address,
+        token: &TokenLike,
+        redeem: bool,
+    ) -> Result<BigUint, SubmitError> {
+        // Fee token of the transaction is always specified by its ID.
+        let token = match token {
+            TokenLike::Id(token) => *token,
+            _ => return Err(SubmitError::InvalidFeeQuote(FeeQuoteError::Mismatch)),
+        };
+
+        let req = oneshot::channel();
+        let request = if redeem {
+            TickerRequest::RedeemFeeQuote {
+                quote_id,
+                tx_type,
+                address,
+                token,
+                response: req.0,
+            }
+        } else {
+            TickerRequest::VerifyFeeQuote {
+                quote_id,
+                tx_type,
+                address,
+                token,
+                response: req.0,
+            }
+        };
+        ticker_request_sender
+            .send(request)
+            .await
+            .map_err(SubmitError::internal)?;
+
+        let resp = req.1.await.map_err(SubmitError::internal)?;
+        resp.map_err(|err| match err {
+            FeeQuoteError::Expired => SubmitError::FeeQuoteExpired,
+            err => SubmitError::InvalidFeeQuote(err),
+        })
+    }
+
     async fn ticker_request(
         mut ticker_request_sender: mpsc::Sender<TickerRequest>,
         tx_type: TxFeeTypes,
@@ -1,5 +1,11 @@
-use crate::tx_error::TxAddError;
-use zksync_types::{tx::TxEthSignature, Address, PriorityOp, SignedZkSyncTx, H256};
+use crate::tx_error::{TxAddError, TxCancelError};
+use zksync_types::{
+    block::BlockProductionMode,
+    codec,
+    mempool::SignedTxsBatch,
+    tx::{TxEthSignature, TxHash, TxSimulationResult},
+    Address, PriorityOp, SignedZkSyncTx, H256,
+};
 
 /// `CoreApiClient` is capable of interacting with a private zkSync Core API.
 #[derive(Debug, Clone)]
@@ -21,7 +27,7 @@ impl CoreApiClient {
     /// Sends a new transaction to the Core mempool.
     pub async fn send_tx(&self, tx: SignedZkSyncTx) -> anyhow::Result<Result<(), TxAddError>> {
         let endpoint = format!("{}/new_tx", self.addr);
-        self.post(&endpoint, tx).await
+        self.post_binary(&endpoint, codec::encode(&tx)).await
     }
 
     /// Sends a new transactions batch to the Core mempool.
@@ -31,9 +37,30 @@ impl CoreApiClient {
         eth_signature: Option<TxEthSignature>,
     ) -> anyhow::Result<Result<(), TxAddError>> {
         let endpoint = format!("{}/new_txs_batch", self.addr);
-        let data = (txs, eth_signature);
+        // Batch ID is assigned by the mempool.
+        let batch = SignedTxsBatch {
+            txs,
+            batch_id: 0,
+            eth_signature,
+        };
 
-        self.post(&endpoint, data).await
+        self.post_binary(&endpoint, codec::encode(&batch)).await
+    }
+
+    /// Removes a pending transaction from the Core mempool.
+    pub async fn cancel_tx(&self, tx_hash: TxHash) -> anyhow::Result<Result<(), TxCancelError>> {
+        let endpoint = format!("{}/cancel_tx", self.addr);
+        self.post(&endpoint, tx_hash).await
+    }
+
+    /// Executes transactions against the current Core state without applying their changes.
+    /// Several transactions are executed as a batch.
+    pub async fn simulate_tx(
+        &self,
+        txs: Vec<SignedZkSyncTx>,
+    ) -> anyhow::Result<Result<Vec<TxSimulationResult>, TxAddError>> {
+        let endpoint = format!("{}/simulate_tx", self.addr);
+        self.post(&endpoint, txs).await
     }
 
     /// Queries information about unconfirmed deposit operations for a certain address from a Core.
@@ -62,6 +89,31 @@ impl CoreApiClient {
         self.get(&endpoint).await
     }
 
+    /// Stops the Core block proposer, so no new blocks are produced until it's resumed.
+    pub async fn pause_block_proposer(&self) -> anyhow::Result<BlockProductionMode> {
+        let endpoint = format!("{}/block_proposer/pause", self.addr);
+        self.post(&endpoint, ()).await
+    }
+
+    /// Resumes the Core block proposer.
+    pub async fn resume_block_proposer(&self) -> anyhow::Result<BlockProductionMode> {
+        let endpoint = format!("{}/block_proposer/resume", self.addr);
+        self.post(&endpoint, ()).await
+    }
+
+    /// Queries the current mode of the block production from a Core.
+    pub async fn block_production_mode(&self) -> anyhow::Result<BlockProductionMode> {
+        let endpoint = format!("{}/block_proposer/mode", self.addr);
+        self.get(&endpoint).await
+    }
+
+    /// Makes the Core seal the pending block immediately.
+    /// Returns `false` if the pending block was empty, so no block was sealed.
+    pub async fn seal_block(&self) -> anyhow::Result<bool> {
+        let endpoint = format!("{}/seal_block", self.addr);
+        self.post(&endpoint, ()).await
+    }
+
     async fn get<T: serde::de::DeserializeOwned>(&self, url: &str) -> anyhow::Result<T> {
         let response = self.client.get(url).send().await?.json().await?;
 
@@ -84,4 +136,23 @@ impl CoreApiClient {
 
         Ok(response)
     }
+
+    /// Sends the request encoded with the zkSync binary codec, the response is still JSON.
+    async fn post_binary<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+        body: Vec<u8>,
+    ) -> anyhow::Result<T> {
+        let response = self
+            .client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, codec::CONTENT_TYPE)
+            .body(body)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        Ok(response)
+    }
 }
@@ -1,7 +1,7 @@
 use zksync_types::{
     config::MAX_WITHDRAWALS_TO_COMPLETE_IN_A_CALL,
     gas_counter::{CommitCost, GasCounter, VerifyCost},
-    ChangePubKeyOp, TransferOp, TransferToNewOp, WithdrawOp,
+    ChangePubKeyOp, SwapOp, TransferOp, TransferToNewOp, WithdrawOp,
 };
 
 // Base operation costs estimated via `gas_price` test.
@@ -24,6 +24,8 @@ pub(crate) const BASE_CHANGE_PUBKEY_OFFCHAIN_COST: u64 = CommitCost::CHANGE_PUBK
 pub(crate) const BASE_CHANGE_PUBKEY_ONCHAIN_COST: u64 = CommitCost::CHANGE_PUBKEY_COST_ONCHAIN
     + zksync_types::gas_counter::VerifyCost::CHANGE_PUBKEY_COST
     + 1000 * (ChangePubKeyOp::CHUNKS as u64);
+pub(crate) const BASE_SWAP_COST: u64 =
+    VerifyCost::SWAP_COST + CommitCost::SWAP_COST + 1000 * (SwapOp::CHUNKS as u64);
 
 // The Subsidized cost of operations.
 // Represent the cost of performing operations after recursion is introduced to mainnet.
@@ -31,3 +33,4 @@ pub(crate) const SUBSIDY_TRANSFER_COST: u64 = 550;
 pub(crate) const SUBSIDY_TRANSFER_TO_NEW_COST: u64 = 550 * 3;
 pub(crate) const SUBSIDY_WITHDRAW_COST: u64 = 45000;
 pub(crate) const SUBSIDY_CHANGE_PUBKEY_OFFCHAIN_COST: u64 = 10000;
+pub(crate) const SUBSIDY_SWAP_COST: u64 = 550 * 2;
@@ -1,10 +1,14 @@
 // Built-in deps
 // External deps
+use chrono::{DateTime, Utc};
 use num::{rational::Ratio, BigUint};
 use serde::{Deserialize, Serialize};
 // Workspace deps
-use zksync_types::helpers::{pack_fee_amount, unpack_fee_amount};
-use zksync_utils::{round_precision, BigUintSerdeAsRadix10Str};
+use zksync_types::{
+    helpers::{pack_fee_amount, unpack_fee_amount},
+    FeeParams,
+};
+use zksync_utils::{round_precision, BigUintSerdeAsRadix10Str, UnsignedRatioSerializeAsDecimal};
 // Local deps
 
 /// Type of the fee calculation pattern.
@@ -23,12 +27,16 @@ pub enum OutputFeeType {
         #[serde(rename = "onchainPubkeyAuth")]
         onchain_pubkey_auth: bool,
     },
+    Swap,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Fee {
     pub fee_type: OutputFeeType,
+    /// Cost of proving a single chunk in USD, used to calculate the `zkp_fee`.
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub zkp_cost_chunk_usd: Ratio<BigUint>,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub gas_tx_amount: BigUint,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
@@ -39,6 +47,21 @@ pub struct Fee {
     pub zkp_fee: BigUint,
     #[serde(with = "BigUintSerdeAsRadix10Str")]
     pub total_fee: BigUint,
+    /// Quote guaranteeing that the `total_fee` is accepted until it expires.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote: Option<FeeQuote>,
+}
+
+/// Fee quote signed by the server.
+///
+/// If the quote identifier is provided along with the transaction, the server accepts
+/// the quoted fee instead of the actual one until the quote expires.
+/// Each quote can be used by a single transaction only.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeQuote {
+    pub id: String,
+    pub valid_until: DateTime<Utc>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -48,9 +71,28 @@ pub struct BatchFee {
     pub total_fee: BigUint,
 }
 
+/// Fee parameters currently used by the fee ticker.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveFeeParams {
+    /// Version of the parameters set by the server operator,
+    /// or `None` if the defaults from the server configuration are used.
+    pub version: Option<i64>,
+    /// Parameters set by the server operator. If the ZKP cost is derived from the prover
+    /// statistics, the ZKP cost from the parameters is only used as a fallback.
+    #[serde(flatten)]
+    pub params: FeeParams,
+    /// ZKP cost of a chunk actually used in the fees, in USD.
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub effective_zkp_cost_chunk_usd: Ratio<BigUint>,
+    /// Whether the effective ZKP cost is derived from the prover statistics.
+    pub is_zkp_cost_dynamic: bool,
+}
+
 impl Fee {
     pub fn new(
         fee_type: OutputFeeType,
+        zkp_cost_chunk_usd: Ratio<BigUint>,
         zkp_fee: Ratio<BigUint>,
         gas_fee: Ratio<BigUint>,
         gas_tx_amount: BigUint,
@@ -65,11 +107,13 @@ impl Fee {
 
         Self {
             fee_type,
+            zkp_cost_chunk_usd,
             gas_tx_amount,
             gas_price_wei,
             gas_fee,
             zkp_fee,
             total_fee,
+            quote: None,
         }
     }
 }
@@ -0,0 +1,270 @@
+//! Fee quotes signed by the server.
+//!
+//! Quote is a JWT fixing the fee for the transaction of a certain type, sent to a certain
+//! address and paid in a certain token. While the quote is not expired, the server accepts
+//! the quoted fee even if the actual one has grown because of the token price change.
+//!
+//! Every quote can be used only once: it's redeemed by the first transaction submitted with it
+//! that passes the signature checks, even if the transaction is rejected by the mempool later.
+//! Redeemed quotes are tracked in memory until they expire, so after the server restart
+//! an unexpired quote can be used once again.
+
+// Built-in deps
+use std::collections::HashMap;
+// External deps
+use chrono::{Duration, TimeZone, Utc};
+use jsonwebtoken::{
+    decode, encode, errors::ErrorKind, DecodingKey, EncodingKey, Header, Validation,
+};
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+// Workspace deps
+use zksync_config::ApiServerOptions;
+use zksync_crypto::rand::random;
+use zksync_types::{Address, TokenId, TxFeeTypes};
+use zksync_utils::BigUintSerdeAsRadix10Str;
+// Local deps
+use super::FeeQuote;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum FeeQuoteError {
+    #[error("Fee quote is expired")]
+    Expired,
+    #[error("Fee quote is malformed or has an incorrect signature")]
+    Invalid,
+    #[error("Fee quote was issued for another transaction")]
+    Mismatch,
+    #[error("Fee quote was already used by another transaction")]
+    AlreadyUsed,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FeeQuoteClaims {
+    tx_type: TxFeeTypes,
+    address: Address,
+    token: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    total_fee: BigUint,
+    /// Expiration time of the quote as a UNIX timestamp, checked by the `jsonwebtoken`.
+    exp: i64,
+    /// Random identifier of the quote, so the quotes issued for the same fee
+    /// at the same second are still distinct.
+    jti: u64,
+}
+
+/// Issues the fee quotes and checks the quotes provided along with the transactions.
+#[derive(Debug, Clone)]
+pub struct FeeQuoteSigner {
+    secret: Vec<u8>,
+    validity: Duration,
+    /// Redeemed quotes along with their expiration timestamps.
+    used_quotes: HashMap<String, i64>,
+}
+
+impl FeeQuoteSigner {
+    pub fn new(secret: impl AsRef<[u8]>, validity: Duration) -> Self {
+        Self {
+            secret: secret.as_ref().to_vec(),
+            validity,
+            used_quotes: HashMap::new(),
+        }
+    }
+
+    pub fn from_options(options: &ApiServerOptions) -> Self {
+        let validity = Duration::from_std(options.fee_quote_validity)
+            .expect("Unable to convert std::Duration to chrono::Duration");
+
+        Self::new(&options.fee_quote_secret, validity)
+    }
+
+    /// Signs the quote for the `total_fee`, valid for the configured period.
+    pub fn issue(
+        &self,
+        tx_type: TxFeeTypes,
+        address: Address,
+        token: TokenId,
+        total_fee: BigUint,
+    ) -> FeeQuote {
+        // Expiration time is stored with the precision of seconds.
+        let valid_until = Utc.timestamp((Utc::now() + self.validity).timestamp(), 0);
+        let claims = FeeQuoteClaims {
+            tx_type,
+            address,
+            token,
+            total_fee,
+            exp: valid_until.timestamp(),
+            jti: random(),
+        };
+        let id = encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(&self.secret),
+        )
+        .expect("Failed to sign the fee quote");
+
+        FeeQuote { id, valid_until }
+    }
+
+    /// Checks that the quote is issued by this server for the given transaction and is not expired.
+    /// Returns the quoted fee.
+    pub fn verify(
+        &self,
+        quote_id: &str,
+        tx_type: TxFeeTypes,
+        address: Address,
+        token: TokenId,
+    ) -> Result<BigUint, FeeQuoteError> {
+        self.verify_claims(quote_id, tx_type, address, token)
+            .map(|claims| claims.total_fee)
+    }
+
+    /// Verifies the quote and marks it as used, so it can't be provided with another transaction.
+    /// Returns the quoted fee.
+    pub fn redeem(
+        &mut self,
+        quote_id: &str,
+        tx_type: TxFeeTypes,
+        address: Address,
+        token: TokenId,
+    ) -> Result<BigUint, FeeQuoteError> {
+        let claims = self.verify_claims(quote_id, tx_type, address, token)?;
+
+        // Expired quotes are rejected anyway, so there is no need to keep them.
+        let now = Utc::now().timestamp();
+        self.used_quotes.retain(|_, exp| *exp >= now);
+        if self
+            .used_quotes
+            .insert(quote_id.to_owned(), claims.exp)
+            .is_some()
+        {
+            return Err(FeeQuoteError::AlreadyUsed);
+        }
+
+        Ok(claims.total_fee)
+    }
+
+    fn verify_claims(
+        &self,
+        quote_id: &str,
+        tx_type: TxFeeTypes,
+        address: Address,
+        token: TokenId,
+    ) -> Result<FeeQuoteClaims, FeeQuoteError> {
+        let claims = decode::<FeeQuoteClaims>(
+            quote_id,
+            &DecodingKey::from_secret(&self.secret),
+            &Validation::default(),
+        )
+        .map_err(|err| match err.kind() {
+            ErrorKind::ExpiredSignature => FeeQuoteError::Expired,
+            _ => FeeQuoteError::Invalid,
+        })?
+        .claims;
+
+        if claims.tx_type != tx_type || claims.address != address || claims.token != token {
+            return Err(FeeQuoteError::Mismatch);
+        }
+
+        Ok(claims)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue_quote(signer: &FeeQuoteSigner) -> FeeQuote {
+        signer.issue(
+            TxFeeTypes::Transfer,
+            Address::repeat_byte(1),
+            1,
+            1000u32.into(),
+        )
+    }
+
+    #[test]
+    fn quote_roundtrip() {
+        let signer = FeeQuoteSigner::new("secret", Duration::seconds(60));
+        let quote = issue_quote(&signer);
+        assert!(quote.valid_until > Utc::now());
+
+        let total_fee = signer
+            .verify(&quote.id, TxFeeTypes::Transfer, Address::repeat_byte(1), 1)
+            .unwrap();
+        assert_eq!(total_fee, 1000u32.into());
+    }
+
+    #[test]
+    fn redeemed_quote() {
+        let mut signer = FeeQuoteSigner::new("secret", Duration::seconds(60));
+        let quote = issue_quote(&signer);
+        let other_quote = issue_quote(&signer);
+        assert_ne!(quote.id, other_quote.id);
+
+        let mut redeem = |quote_id: &str| {
+            signer.redeem(quote_id, TxFeeTypes::Transfer, Address::repeat_byte(1), 1)
+        };
+        assert_eq!(redeem(&quote.id).unwrap(), 1000u32.into());
+        assert_eq!(redeem(&quote.id).unwrap_err(), FeeQuoteError::AlreadyUsed);
+        // The same fee quoted again can be used.
+        assert_eq!(redeem(&other_quote.id).unwrap(), 1000u32.into());
+    }
+
+    #[test]
+    fn expired_quote() {
+        let signer = FeeQuoteSigner::new("secret", Duration::seconds(-60));
+        let quote = issue_quote(&signer);
+
+        let err = signer
+            .verify(&quote.id, TxFeeTypes::Transfer, Address::repeat_byte(1), 1)
+            .unwrap_err();
+        assert_eq!(err, FeeQuoteError::Expired);
+    }
+
+    #[test]
+    fn tampered_quote() {
+        let signer = FeeQuoteSigner::new("secret", Duration::seconds(60));
+        let verify = |quote_id: &str| {
+            signer.verify(quote_id, TxFeeTypes::Transfer, Address::repeat_byte(1), 1)
+        };
+
+        // Quote issued by another server.
+        let quote = issue_quote(&FeeQuoteSigner::new("other", Duration::seconds(60)));
+        assert_eq!(verify(&quote.id).unwrap_err(), FeeQuoteError::Invalid);
+
+        // Quote with the replaced claims.
+        let quote = issue_quote(&signer);
+        let cheap_quote = signer.issue(
+            TxFeeTypes::Transfer,
+            Address::repeat_byte(1),
+            1,
+            1u32.into(),
+        );
+        let mut parts: Vec<_> = quote.id.split('.').collect();
+        parts[1] = cheap_quote.id.split('.').nth(1).unwrap();
+        let forged_id = parts.join(".");
+        assert_eq!(verify(&forged_id).unwrap_err(), FeeQuoteError::Invalid);
+
+        assert_eq!(verify("not a quote").unwrap_err(), FeeQuoteError::Invalid);
+    }
+
+    #[test]
+    fn quote_for_another_tx() {
+        let signer = FeeQuoteSigner::new("secret", Duration::seconds(60));
+        let quote = issue_quote(&signer);
+
+        let mismatched = [
+            (TxFeeTypes::Withdraw, Address::repeat_byte(1), 1),
+            (TxFeeTypes::Transfer, Address::repeat_byte(2), 1),
+            (TxFeeTypes::Transfer, Address::repeat_byte(1), 0),
+        ];
+        for &(tx_type, address, token) in &mismatched {
+            let err = signer
+                .verify(&quote.id, tx_type, address, token)
+                .unwrap_err();
+            assert_eq!(err, FeeQuoteError::Mismatch);
+        }
+    }
+}
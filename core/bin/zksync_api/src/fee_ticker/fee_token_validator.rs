@@ -3,13 +3,21 @@
 
 // Built-in uses
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+// External uses
+use num::BigUint;
+use tokio::sync::Mutex;
 // Workspace uses
 use zksync_types::{
     tokens::{Token, TokenLike},
     Address,
 };
 // Local uses
-use crate::utils::token_db_cache::TokenDBCache;
+use crate::{fee_ticker::ticker_api::uniswap::UniswapV2API, utils::token_db_cache::TokenDBCache};
+
+/// Time during which the measured token liquidity is considered actual.
+const LIQUIDITY_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
 
 /// Fee token validator decides whether certain ERC20 token is suitable for paying fees.
 #[derive(Debug, Clone)]
@@ -17,6 +25,11 @@ pub(crate) struct FeeTokenValidator {
     tokens_cache: TokenCacheWrapper,
     /// List of tokens that aren't accepted to pay fees in.
     disabled_tokens: HashSet<Address>,
+    /// DEX used to measure the token liquidity and the minimal amount of wei
+    /// the token pair must hold to accept the token for paying fees.
+    liquidity_checker: Option<(Arc<UniswapV2API>, BigUint)>,
+    /// Measured liquidity of the tokens and the time it was measured at.
+    liquidity_cache: Arc<Mutex<HashMap<Address, (BigUint, Instant)>>>,
 }
 
 impl FeeTokenValidator {
@@ -27,9 +40,22 @@ impl FeeTokenValidator {
         Self {
             tokens_cache: cache.into(),
             disabled_tokens,
+            liquidity_checker: None,
+            liquidity_cache: Default::default(),
         }
     }
 
+    /// Makes the validator reject tokens having less than `min_liquidity` wei
+    /// in their pair with WETH. Measured liquidity is cached for `LIQUIDITY_CACHE_TTL`.
+    pub(crate) fn with_liquidity_check(
+        mut self,
+        dex: Arc<UniswapV2API>,
+        min_liquidity: BigUint,
+    ) -> Self {
+        self.liquidity_checker = Some((dex, min_liquidity));
+        self
+    }
+
     /// Returns `true` if token can be used to pay fees.
     pub(crate) async fn token_allowed(&self, token: TokenLike) -> anyhow::Result<bool> {
         let token = self.resolve_token(token).await?;
@@ -42,17 +68,46 @@ impl FeeTokenValidator {
     }
 
     async fn check_token(&self, token: Option<Token>) -> anyhow::Result<bool> {
-        // Currently we add tokens in zkSync manually, thus we can decide whether token is acceptable in before.
-        // Later we'll check Uniswap trading volume for tokens. That's why this function is already `async` even
-        // though it's not really `async` at this moment.
-
-        if let Some(token) = token {
-            let not_acceptable = self.disabled_tokens.contains(&token.address);
-            Ok(!not_acceptable)
-        } else {
+        // Tokens are added in zkSync manually, thus some of them may be disabled in before.
+        // If the liquidity check is enabled, the rest are accepted only if they are traded on the DEX
+        // with enough liquidity, so their prices can't be easily manipulated.
+
+        let token = match token {
+            Some(token) => token,
             // Unknown tokens aren't suitable for our needs, obviously.
-            Ok(false)
+            None => return Ok(false),
+        };
+        if self.disabled_tokens.contains(&token.address) {
+            return Ok(false);
+        }
+
+        match &self.liquidity_checker {
+            // ETH is always accepted, its liquidity can't be measured in ETH anyway.
+            Some(_) if token.id == 0 => Ok(true),
+            Some((dex, min_liquidity)) => {
+                let liquidity = self.token_liquidity(dex, &token).await?;
+                Ok(&liquidity >= min_liquidity)
+            }
+            None => Ok(true),
+        }
+    }
+
+    /// Returns the cached token liquidity, querying the DEX only if the cached value is outdated.
+    async fn token_liquidity(&self, dex: &UniswapV2API, token: &Token) -> anyhow::Result<BigUint> {
+        if let Some((liquidity, measured_at)) =
+            self.liquidity_cache.lock().await.get(&token.address)
+        {
+            if measured_at.elapsed() < LIQUIDITY_CACHE_TTL {
+                return Ok(liquidity.clone());
+            }
         }
+
+        let liquidity = dex.token_liquidity(token).await?;
+        self.liquidity_cache
+            .lock()
+            .await
+            .insert(token.address, (liquidity.clone(), Instant::now()));
+        Ok(liquidity)
     }
 }
 
@@ -86,6 +141,7 @@ impl TokenCacheWrapper {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fee_ticker::ticker_api::uniswap;
     use std::collections::HashSet;
     use std::str::FromStr;
 
@@ -118,4 +174,50 @@ mod tests {
         assert_eq!(dai_allowed, true);
         assert_eq!(phnx_allowed, false);
     }
+
+    #[actix_rt::test]
+    async fn check_tokens_liquidity() {
+        let eth = BigUint::from(1_000_000_000_000_000_000u64);
+        let server = uniswap::tests::mock_node();
+        let dex = Arc::new(uniswap::tests::uniswap_api(
+            &server,
+            std::time::Duration::from_secs(0),
+        ));
+        let token_allowed = |min_liquidity: BigUint, symbol: &str| {
+            let validator = FeeTokenValidator::new(uniswap::tests::tokens(), HashSet::new())
+                .with_liquidity_check(dex.clone(), min_liquidity);
+            let symbol = symbol.to_string();
+            async move { validator.token_allowed(TokenLike::Symbol(symbol)).await }
+        };
+
+        // LTT/WETH pair holds 5 ETH.
+        assert!(token_allowed(eth.clone() * 5u32, "LTT").await.unwrap());
+        assert!(!token_allowed(eth.clone() * 6u32, "LTT").await.unwrap());
+        assert!(!token_allowed(eth.clone(), "NOPE").await.unwrap());
+        // ETH is accepted without the DEX queries.
+        assert!(token_allowed(eth.clone() * 1000u32, "ETH").await.unwrap());
+        assert!(!token_allowed(eth.clone(), "UNKNOWN").await.unwrap());
+
+        // Once measured, the liquidity is taken from the cache.
+        let validator = FeeTokenValidator::new(uniswap::tests::tokens(), HashSet::new())
+            .with_liquidity_check(dex.clone(), eth.clone());
+        let ltt = TokenLike::Symbol("LTT".to_string());
+        assert!(validator.token_allowed(ltt.clone()).await.unwrap());
+        drop(server);
+        assert!(validator.token_allowed(ltt.clone()).await.unwrap());
+
+        // Outdated liquidity is measured again.
+        let ltt_address = validator
+            .resolve_token(ltt.clone())
+            .await
+            .unwrap()
+            .unwrap()
+            .address;
+        validator
+            .liquidity_cache
+            .lock()
+            .await
+            .insert(ltt_address, (eth, Instant::now() - LIQUIDITY_CACHE_TTL));
+        assert!(validator.token_allowed(ltt).await.is_err());
+    }
 }
@@ -5,45 +5,53 @@
 
 // Built-in deps
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 // External deps
 use bigdecimal::BigDecimal;
+use chrono::Utc;
 use futures::{
     channel::{mpsc::Receiver, oneshot},
     StreamExt,
 };
-use num::{
-    rational::Ratio,
-    traits::{Inv, Pow},
-    BigUint,
-};
+use num::{rational::Ratio, traits::Pow, BigUint};
 use serde::{Deserialize, Serialize};
 use tokio::task::JoinHandle;
 // Workspace deps
-use zksync_config::{FeeTickerOptions, TokenPriceSource};
+use zksync_config::{
+    DynamicZkpCostOptions, FeeTickerOptions, ProverOptions, TokenPriceSource, UniswapV2Options,
+};
 use zksync_storage::ConnectionPool;
 use zksync_types::{
-    Address, ChangePubKeyOp, Token, TokenId, TokenLike, TransferOp, TransferToNewOp, TxFeeTypes,
-    WithdrawOp,
+    Address, ChangePubKeyOp, FeeParams, SwapOp, Token, TokenId, TokenLike, TransferOp,
+    TransferToNewOp, TxFeeTypes, WithdrawOp,
 };
 use zksync_utils::ratio_to_big_decimal;
 // Local deps
 use crate::fee_ticker::{
     fee_token_validator::FeeTokenValidator,
     ticker_api::{
-        coingecko::CoinGeckoAPI, coinmarkercap::CoinMarketCapAPI, FeeTickerAPI, TickerApi,
-        CONNECTION_TIMEOUT,
+        coingecko::CoinGeckoAPI,
+        coinmarkercap::CoinMarketCapAPI,
+        median::{BoxedTokenPriceAPI, MedianTokenPriceAPI},
+        uniswap::UniswapV2API,
+        FeeTickerAPI, TickerApi, CONNECTION_TIMEOUT,
     },
     ticker_info::{FeeTickerInfo, TickerInfo},
+    zkp_cost::zkp_cost_chunk_usd,
 };
 use crate::utils::token_db_cache::TokenDBCache;
 
 pub use self::fee::*;
+pub use self::fee_quote::{FeeQuoteError, FeeQuoteSigner};
 
 mod constants;
 mod fee;
+mod fee_quote;
 mod fee_token_validator;
 mod ticker_api;
 mod ticker_info;
+mod zkp_cost;
 
 #[cfg(test)]
 mod tests;
@@ -93,6 +101,7 @@ impl GasOperationsCost {
                 },
                 constants::BASE_CHANGE_PUBKEY_ONCHAIN_COST.into(),
             ),
+            (OutputFeeType::Swap, constants::BASE_SWAP_COST.into()),
         ]
         .into_iter()
         .collect::<HashMap<_, _>>();
@@ -126,6 +135,7 @@ impl GasOperationsCost {
                 },
                 constants::BASE_CHANGE_PUBKEY_ONCHAIN_COST.into(),
             ),
+            (OutputFeeType::Swap, constants::SUBSIDY_SWAP_COST.into()),
         ]
         .into_iter()
         .collect::<HashMap<_, _>>();
@@ -139,9 +149,8 @@ impl GasOperationsCost {
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TickerConfig {
-    zkp_cost_chunk_usd: Ratio<BigUint>,
+    fee_params: FeeParams,
     gas_cost_tx: GasOperationsCost,
-    tokens_risk_factors: HashMap<TokenId, Ratio<BigUint>>,
     not_subsidized_tokens: HashSet<Address>,
 }
 
@@ -168,32 +177,76 @@ pub enum TickerRequest {
         token: TokenLike,
         response: oneshot::Sender<Result<bool, anyhow::Error>>,
     },
+    GetFeeParams {
+        response: oneshot::Sender<ActiveFeeParams>,
+    },
+    /// Checks the fee quote provided along with the transaction without marking it as used.
+    /// Responds with the quoted fee.
+    VerifyFeeQuote {
+        quote_id: String,
+        tx_type: TxFeeTypes,
+        address: Address,
+        token: TokenId,
+        response: oneshot::Sender<Result<BigUint, FeeQuoteError>>,
+    },
+    /// Checks the fee quote provided along with the transaction and marks it as used.
+    /// Responds with the quoted fee.
+    RedeemFeeQuote {
+        quote_id: String,
+        tx_type: TxFeeTypes,
+        address: Address,
+        token: TokenId,
+        response: oneshot::Sender<Result<BigUint, FeeQuoteError>>,
+    },
 }
 
+/// Interval between the checks whether the fee parameters were changed by the server operator.
+const FEE_PARAMS_UPDATE_INTERVAL: Duration = Duration::from_secs(10);
+/// Interval between the updates of the ZKP cost derived from the prover statistics.
+const ZKP_COST_UPDATE_INTERVAL: Duration = Duration::from_secs(60);
+
 struct FeeTicker<API, INFO> {
     api: API,
     info: INFO,
     requests: Receiver<TickerRequest>,
     config: TickerConfig,
     validator: FeeTokenValidator,
+    /// Signer of the fee quotes. If not set, quotes are not issued.
+    quote_signer: Option<FeeQuoteSigner>,
+    /// Version of the fee parameters set by the server operator, if any.
+    fee_params_version: Option<i64>,
+    /// Last time the fee parameters were loaded from the database.
+    fee_params_checked_at: Option<Instant>,
+    /// Configuration of the ZKP cost derived from the prover statistics, if enabled.
+    dynamic_zkp_cost: Option<DynamicZkpCostOptions>,
+    /// ZKP cost of a chunk derived from the prover statistics, if there are enough of them.
+    zkp_cost_chunk_usd: Option<Ratio<BigUint>>,
+    /// Last time the prover statistics were loaded from the database.
+    zkp_cost_checked_at: Option<Instant>,
 }
 
 #[must_use]
 pub fn run_ticker_task(
     db_pool: ConnectionPool,
     tricker_requests: Receiver<TickerRequest>,
+    quote_signer: FeeQuoteSigner,
 ) -> JoinHandle<()> {
     let config = FeeTickerOptions::from_env();
+    let idle_provers = ProverOptions::from_env().idle_provers;
 
     let ticker_config = TickerConfig {
-        zkp_cost_chunk_usd: Ratio::from_integer(BigUint::from(10u32).pow(3u32)).inv(),
+        fee_params: config.fee_params,
         gas_cost_tx: GasOperationsCost::from_constants(config.fast_processing_coeff),
-        tokens_risk_factors: HashMap::new(),
         not_subsidized_tokens: config.not_subsidized_tokens,
     };
 
     let cache = TokenDBCache::new(db_pool.clone());
-    let validator = FeeTokenValidator::new(cache, config.disabled_tokens);
+    let mut validator = FeeTokenValidator::new(cache.clone(), config.disabled_tokens);
+    if let Some(min_liquidity) = config.min_fee_token_liquidity_wei {
+        let dex = UniswapV2API::new(UniswapV2Options::from_env(), cache.clone())
+            .expect("failed to init Uniswap client");
+        validator = validator.with_liquidity_check(Arc::new(dex), min_liquidity.into());
+    }
 
     let client = reqwest::ClientBuilder::new()
         .timeout(CONNECTION_TIMEOUT)
@@ -205,14 +258,16 @@ pub fn run_ticker_task(
             let token_price_api = CoinMarketCapAPI::new(client, base_url);
 
             let ticker_api = TickerApi::new(db_pool.clone(), token_price_api);
-            let ticker_info = TickerInfo::new(db_pool);
+            let ticker_info = TickerInfo::new(db_pool, idle_provers);
             let fee_ticker = FeeTicker::new(
                 ticker_api,
                 ticker_info,
                 tricker_requests,
                 ticker_config,
                 validator,
-            );
+            )
+            .with_quote_signer(quote_signer)
+            .with_dynamic_zkp_cost(config.dynamic_zkp_cost);
 
             tokio::spawn(fee_ticker.run())
         }
@@ -221,14 +276,83 @@ pub fn run_ticker_task(
                 CoinGeckoAPI::new(client, base_url).expect("failed to init CoinGecko client");
 
             let ticker_api = TickerApi::new(db_pool.clone(), token_price_api);
-            let ticker_info = TickerInfo::new(db_pool);
+            let ticker_info = TickerInfo::new(db_pool, idle_provers);
+            let fee_ticker = FeeTicker::new(
+                ticker_api,
+                ticker_info,
+                tricker_requests,
+                ticker_config,
+                validator,
+            )
+            .with_quote_signer(quote_signer)
+            .with_dynamic_zkp_cost(config.dynamic_zkp_cost);
+
+            tokio::spawn(fee_ticker.run())
+        }
+        TokenPriceSource::Median {
+            sources,
+            max_deviation,
+            quorum,
+        } => {
+            let sources = sources
+                .into_iter()
+                .map(|source| -> (&'static str, BoxedTokenPriceAPI) {
+                    match source {
+                        TokenPriceSource::CoinMarketCap { base_url } => (
+                            "coinmarketcap",
+                            Box::new(CoinMarketCapAPI::new(client.clone(), base_url)),
+                        ),
+                        TokenPriceSource::CoinGecko { base_url } => (
+                            "coingecko",
+                            Box::new(
+                                CoinGeckoAPI::new(client.clone(), base_url)
+                                    .expect("failed to init CoinGecko client"),
+                            ),
+                        ),
+                        TokenPriceSource::UniswapV2(options) => (
+                            "uniswapv2",
+                            Box::new(
+                                UniswapV2API::new(options, cache.clone())
+                                    .expect("failed to init Uniswap client"),
+                            ),
+                        ),
+                        TokenPriceSource::Median { .. } => {
+                            panic!("Median token price source can't be nested")
+                        }
+                    }
+                })
+                .collect();
+            let token_price_api = MedianTokenPriceAPI::new(sources, max_deviation, quorum);
+
+            let ticker_api = TickerApi::new(db_pool.clone(), token_price_api);
+            let ticker_info = TickerInfo::new(db_pool, idle_provers);
             let fee_ticker = FeeTicker::new(
                 ticker_api,
                 ticker_info,
                 tricker_requests,
                 ticker_config,
                 validator,
-            );
+            )
+            .with_quote_signer(quote_signer)
+            .with_dynamic_zkp_cost(config.dynamic_zkp_cost);
+
+            tokio::spawn(fee_ticker.run())
+        }
+        TokenPriceSource::UniswapV2(options) => {
+            let token_price_api =
+                UniswapV2API::new(options, cache).expect("failed to init Uniswap client");
+
+            let ticker_api = TickerApi::new(db_pool.clone(), token_price_api);
+            let ticker_info = TickerInfo::new(db_pool, idle_provers);
+            let fee_ticker = FeeTicker::new(
+                ticker_api,
+                ticker_info,
+                tricker_requests,
+                ticker_config,
+                validator,
+            )
+            .with_quote_signer(quote_signer)
+            .with_dynamic_zkp_cost(config.dynamic_zkp_cost);
 
             tokio::spawn(fee_ticker.run())
         }
@@ -249,9 +373,27 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo> FeeTicker<API, INFO> {
             requests,
             config,
             validator,
+            quote_signer: None,
+            fee_params_version: None,
+            fee_params_checked_at: None,
+            dynamic_zkp_cost: None,
+            zkp_cost_chunk_usd: None,
+            zkp_cost_checked_at: None,
         }
     }
 
+    fn with_quote_signer(mut self, quote_signer: FeeQuoteSigner) -> Self {
+        self.quote_signer = Some(quote_signer);
+        self
+    }
+
+    /// Enables the ZKP cost derived from the prover statistics.
+    /// If `None` is provided, the ZKP cost from the fee parameters is used.
+    fn with_dynamic_zkp_cost(mut self, options: Option<DynamicZkpCostOptions>) -> Self {
+        self.dynamic_zkp_cost = options;
+        self
+    }
+
     async fn run(mut self) {
         while let Some(request) = self.requests.next().await {
             match request {
@@ -278,10 +420,132 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo> FeeTicker<API, INFO> {
                     let allowed = self.validator.token_allowed(token).await;
                     response.send(allowed).unwrap_or_default();
                 }
+                TickerRequest::GetFeeParams { response } => {
+                    let params = self.active_fee_params().await;
+                    response.send(params).unwrap_or_default();
+                }
+                TickerRequest::VerifyFeeQuote {
+                    quote_id,
+                    tx_type,
+                    address,
+                    token,
+                    response,
+                } => {
+                    let fee = match &self.quote_signer {
+                        Some(quote_signer) => {
+                            quote_signer.verify(&quote_id, tx_type, address, token)
+                        }
+                        // Quotes aren't issued, so none of them can be valid.
+                        None => Err(FeeQuoteError::Invalid),
+                    };
+                    response.send(fee).unwrap_or_default();
+                }
+                TickerRequest::RedeemFeeQuote {
+                    quote_id,
+                    tx_type,
+                    address,
+                    token,
+                    response,
+                } => {
+                    let fee = match &mut self.quote_signer {
+                        Some(quote_signer) => {
+                            quote_signer.redeem(&quote_id, tx_type, address, token)
+                        }
+                        // Quotes aren't issued, so none of them can be valid.
+                        None => Err(FeeQuoteError::Invalid),
+                    };
+                    response.send(fee).unwrap_or_default();
+                }
             }
         }
     }
 
+    /// Reloads the fee parameters set by the server operator,
+    /// unless they were checked less than `FEE_PARAMS_UPDATE_INTERVAL` ago.
+    async fn update_fee_params(&mut self) {
+        let is_outdated = self
+            .fee_params_checked_at
+            .map(|checked_at| checked_at.elapsed() >= FEE_PARAMS_UPDATE_INTERVAL)
+            .unwrap_or(true);
+        if !is_outdated {
+            return;
+        }
+        self.fee_params_checked_at = Some(Instant::now());
+
+        match self.info.fee_params().await {
+            Ok(Some(stored)) if Some(stored.version) != self.fee_params_version => {
+                log::info!("Fee parameters are updated to version {}", stored.version);
+                self.config.fee_params = stored.params;
+                self.fee_params_version = Some(stored.version);
+            }
+            Ok(_) => {}
+            Err(err) => log::warn!("Failed to load fee parameters: {}", err),
+        }
+    }
+
+    /// Returns the fee parameters along with the ZKP cost actually used in the fees.
+    async fn active_fee_params(&mut self) -> ActiveFeeParams {
+        self.update_fee_params().await;
+        self.update_zkp_cost().await;
+
+        ActiveFeeParams {
+            version: self.fee_params_version,
+            params: self.config.fee_params.clone(),
+            effective_zkp_cost_chunk_usd: self.effective_zkp_cost_chunk_usd(),
+            is_zkp_cost_dynamic: self.zkp_cost_chunk_usd.is_some(),
+        }
+    }
+
+    /// Returns the ZKP cost derived from the prover statistics if there are enough of them,
+    /// and the one from the fee parameters otherwise.
+    fn effective_zkp_cost_chunk_usd(&self) -> Ratio<BigUint> {
+        self.zkp_cost_chunk_usd
+            .clone()
+            .unwrap_or_else(|| self.config.fee_params.zkp_cost_chunk_usd.clone())
+    }
+
+    /// Recalculates the ZKP cost from the prover statistics,
+    /// unless it was updated less than `ZKP_COST_UPDATE_INTERVAL` ago.
+    async fn update_zkp_cost(&mut self) {
+        let options = match &self.dynamic_zkp_cost {
+            Some(options) => options.clone(),
+            None => return,
+        };
+        let is_outdated = self
+            .zkp_cost_checked_at
+            .map(|checked_at| checked_at.elapsed() >= ZKP_COST_UPDATE_INTERVAL)
+            .unwrap_or(true);
+        if !is_outdated {
+            return;
+        }
+        self.zkp_cost_checked_at = Some(Instant::now());
+
+        let since = Utc::now()
+            - chrono::Duration::from_std(options.stats_period)
+                .expect("Unable to convert std::Duration to chrono::Duration");
+        match self.info.prover_stats(since).await {
+            Ok(stats) => {
+                metrics::gauge!("ticker.zkp_cost.pending_jobs", stats.pending_jobs as f64);
+                metrics::gauge!(
+                    "ticker.zkp_cost.provers_required",
+                    stats.provers_required as f64
+                );
+
+                self.zkp_cost_chunk_usd = zkp_cost_chunk_usd(&options, &stats);
+                match &self.zkp_cost_chunk_usd {
+                    Some(cost) => log::debug!(
+                        "ZKP cost of a chunk is updated to {} USD",
+                        ratio_to_big_decimal(cost, 18)
+                    ),
+                    None => log::debug!(
+                        "No blocks were proven recently, using the default ZKP cost of a chunk"
+                    ),
+                }
+            }
+            Err(err) => log::warn!("Failed to load prover statistics: {}", err),
+        }
+    }
+
     async fn get_token_price(
         &self,
         token: TokenLike,
@@ -317,10 +581,14 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo> FeeTicker<API, INFO> {
         token: TokenLike,
         recipient: Address,
     ) -> Result<Fee, anyhow::Error> {
-        let zkp_cost_chunk = self.config.zkp_cost_chunk_usd.clone();
+        self.update_fee_params().await;
+        self.update_zkp_cost().await;
+
+        let zkp_cost_chunk = self.effective_zkp_cost_chunk_usd();
         let token = self.api.get_token(token).await?;
         let token_risk_factor = self
             .config
+            .fee_params
             .tokens_risk_factors
             .get(&token.id)
             .cloned()
@@ -344,6 +612,7 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo> FeeTicker<API, INFO> {
                 },
                 ChangePubKeyOp::CHUNKS,
             ),
+            TxFeeTypes::Swap => (OutputFeeType::Swap, SwapOp::CHUNKS),
         };
         // Convert chunks amount to `BigUint`.
         let op_chunks = BigUint::from(op_chunks);
@@ -376,18 +645,25 @@ impl<API: FeeTickerAPI, INFO: FeeTickerInfo> FeeTicker<API, INFO> {
             .usd_price
             / BigUint::from(10u32).pow(u32::from(token.decimals));
 
-        let zkp_fee =
-            (zkp_cost_chunk * op_chunks) * token_risk_factor.clone() / token_price_usd.clone();
+        let zkp_fee = (zkp_cost_chunk.clone() * op_chunks) * token_risk_factor.clone()
+            / token_price_usd.clone();
         let gas_fee = (wei_price_usd * gas_tx_amount.clone() * gas_price_wei.clone())
             * token_risk_factor
             / token_price_usd;
 
-        Ok(Fee::new(
+        let mut fee = Fee::new(
             fee_type,
+            zkp_cost_chunk,
             zkp_fee,
             gas_fee,
             gas_tx_amount,
             gas_price_wei,
-        ))
+        );
+        if let Some(quote_signer) = &self.quote_signer {
+            fee.quote =
+                Some(quote_signer.issue(tx_type, recipient, token.id, fee.total_fee.clone()));
+        }
+
+        Ok(fee)
     }
 }
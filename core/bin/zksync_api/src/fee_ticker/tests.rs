@@ -5,9 +5,12 @@ use chrono::Utc;
 use futures::channel::mpsc;
 use futures::executor::block_on;
 use std::str::FromStr;
-use zksync_types::{Address, Token, TokenId, TokenPrice};
+use zksync_storage::prover::records::ProvingTimeStats;
+use zksync_types::{Address, FeeParamsVersion, Token, TokenId, TokenPrice};
 use zksync_utils::{ratio_to_big_decimal, UnsignedRatioSerializeAsDecimal};
 
+use crate::fee_ticker::zkp_cost::ProverStats;
+
 const TEST_FAST_WITHDRAW_COEFF: f64 = 10.0;
 
 #[derive(Debug, Clone)]
@@ -77,16 +80,20 @@ impl TestToken {
 
 fn get_test_ticker_config() -> TickerConfig {
     TickerConfig {
-        zkp_cost_chunk_usd: UnsignedRatioSerializeAsDecimal::deserialize_from_str_with_dot("0.001")
+        fee_params: FeeParams {
+            zkp_cost_chunk_usd: UnsignedRatioSerializeAsDecimal::deserialize_from_str_with_dot(
+                "0.001",
+            )
             .unwrap(),
+            tokens_risk_factors: TestToken::all_tokens()
+                .into_iter()
+                .filter_map(|t| {
+                    let id = t.id;
+                    t.risk_factor.map(|risk| (id, risk))
+                })
+                .collect(),
+        },
         gas_cost_tx: GasOperationsCost::from_constants(TEST_FAST_WITHDRAW_COEFF),
-        tokens_risk_factors: TestToken::all_tokens()
-            .into_iter()
-            .filter_map(|t| {
-                let id = t.id;
-                t.risk_factor.map(|risk| (id, risk))
-            })
-            .collect(),
         not_subsidized_tokens: vec![
             Address::from_str("34083bbd70d394110487feaa087da875a54624ec").unwrap(),
         ]
@@ -141,7 +148,11 @@ impl FeeTickerAPI for MockApiProvider {
     }
 }
 
-struct MockTickerInfo;
+#[derive(Default)]
+struct MockTickerInfo {
+    fee_params: Option<FeeParamsVersion>,
+    prover_stats: ProverStats,
+}
 
 #[async_trait]
 impl FeeTickerInfo for MockTickerInfo {
@@ -149,6 +160,14 @@ impl FeeTickerInfo for MockTickerInfo {
         // Always false for simplicity.
         false
     }
+
+    async fn fee_params(&mut self) -> anyhow::Result<Option<FeeParamsVersion>> {
+        Ok(self.fee_params.clone())
+    }
+
+    async fn prover_stats(&mut self, _since: chrono::DateTime<Utc>) -> anyhow::Result<ProverStats> {
+        Ok(self.prover_stats.clone())
+    }
 }
 
 fn format_with_dot(num: &Ratio<BigUint>, precision: usize) -> String {
@@ -162,7 +181,7 @@ fn test_ticker_formula() {
     let config = get_test_ticker_config();
     let mut ticker = FeeTicker::new(
         MockApiProvider,
-        MockTickerInfo,
+        MockTickerInfo::default(),
         mpsc::channel(1).1,
         config,
         validator,
@@ -258,7 +277,7 @@ fn test_fee_for_unsubsidized_tokens() {
     let config = get_test_ticker_config();
     let mut ticker = FeeTicker::new(
         MockApiProvider,
-        MockTickerInfo,
+        MockTickerInfo::default(),
         mpsc::channel(1).1,
         config,
         validator,
@@ -313,3 +332,167 @@ fn test_fee_for_unsubsidized_tokens() {
         }
     }
 }
+
+#[test]
+fn test_fee_params_update() {
+    // Operator has doubled the ZKP cost and the risk factor of ETH.
+    let default_params = get_test_ticker_config().fee_params;
+    let mut params = default_params.clone();
+    params.zkp_cost_chunk_usd = default_params.zkp_cost_chunk_usd * BigUint::from(2u32);
+    params
+        .tokens_risk_factors
+        .insert(0, Ratio::from_integer(2u32.into()));
+    let info = MockTickerInfo {
+        fee_params: Some(FeeParamsVersion {
+            version: 1,
+            params: params.clone(),
+            created_at: Utc::now(),
+        }),
+        ..Default::default()
+    };
+
+    let mut default_ticker = FeeTicker::new(
+        MockApiProvider,
+        MockTickerInfo::default(),
+        mpsc::channel(1).1,
+        get_test_ticker_config(),
+        FeeTokenValidator::new(HashMap::new(), Default::default()),
+    );
+    let mut ticker = FeeTicker::new(
+        MockApiProvider,
+        info,
+        mpsc::channel(1).1,
+        get_test_ticker_config(),
+        FeeTokenValidator::new(HashMap::new(), Default::default()),
+    );
+
+    let default_fee = block_on(default_ticker.get_fee_from_ticker_in_wei(
+        TxFeeTypes::Transfer,
+        0.into(),
+        Address::default(),
+    ))
+    .unwrap();
+    let fee = block_on(ticker.get_fee_from_ticker_in_wei(
+        TxFeeTypes::Transfer,
+        0.into(),
+        Address::default(),
+    ))
+    .unwrap();
+
+    // Stored parameters are applied, defaults are kept if there are none.
+    assert_eq!(default_ticker.fee_params_version, None);
+    assert_eq!(default_ticker.config.fee_params, default_params);
+    assert_eq!(ticker.fee_params_version, Some(1));
+    assert_eq!(ticker.config.fee_params, params);
+
+    // ZKP part is affected by both parameters, while the gas part only by the risk factor.
+    // Fees are rounded up, so the difference of 1 wei is possible.
+    let four = BigUint::from(4u32);
+    let two = BigUint::from(2u32);
+    assert!(fee.zkp_fee <= &default_fee.zkp_fee * &four);
+    assert!(fee.zkp_fee + &four > &default_fee.zkp_fee * &four);
+    assert!(fee.gas_fee <= &default_fee.gas_fee * &two);
+    assert!(fee.gas_fee + &two > &default_fee.gas_fee * &two);
+}
+
+#[test]
+fn test_fee_quotes() {
+    let quote_signer = FeeQuoteSigner::new("secret", chrono::Duration::seconds(60));
+    let mut ticker = FeeTicker::new(
+        MockApiProvider,
+        MockTickerInfo::default(),
+        mpsc::channel(1).1,
+        get_test_ticker_config(),
+        FeeTokenValidator::new(HashMap::new(), Default::default()),
+    );
+    let recipient = Address::repeat_byte(1);
+
+    // Quotes are not issued unless the signer is set.
+    let fee =
+        block_on(ticker.get_fee_from_ticker_in_wei(TxFeeTypes::Withdraw, 0.into(), recipient))
+            .unwrap();
+    assert!(fee.quote.is_none());
+
+    let mut ticker = ticker.with_quote_signer(quote_signer.clone());
+    let fee =
+        block_on(ticker.get_fee_from_ticker_in_wei(TxFeeTypes::Withdraw, 0.into(), recipient))
+            .unwrap();
+    let quote = fee.quote.expect("Fee quote is not issued");
+    assert!(quote.valid_until > Utc::now());
+
+    let quoted_fee = quote_signer
+        .verify(&quote.id, TxFeeTypes::Withdraw, recipient, 0)
+        .unwrap();
+    assert_eq!(quoted_fee, fee.total_fee);
+}
+
+#[test]
+fn test_dynamic_zkp_cost() {
+    let ratio = |value: &str| {
+        UnsignedRatioSerializeAsDecimal::deserialize_from_str_with_dot(value).unwrap()
+    };
+    let options = DynamicZkpCostOptions {
+        prover_cost_per_second_usd: ratio("0.001"),
+        min_zkp_cost_chunk_usd: ratio("0.0001"),
+        max_zkp_cost_chunk_usd: ratio("0.1"),
+        stats_period: Duration::from_secs(3600),
+    };
+    let ticker = |info: MockTickerInfo| {
+        FeeTicker::new(
+            MockApiProvider,
+            info,
+            mpsc::channel(1).1,
+            get_test_ticker_config(),
+            FeeTokenValidator::new(HashMap::new(), Default::default()),
+        )
+        .with_dynamic_zkp_cost(Some(options.clone()))
+    };
+    let get_fee = |info: MockTickerInfo| {
+        block_on(ticker(info).get_fee_from_ticker_in_wei(
+            TxFeeTypes::Withdraw,
+            0.into(),
+            Address::default(),
+        ))
+        .unwrap()
+    };
+
+    // No blocks were proven, so the default cost is used.
+    let default_fee = get_fee(MockTickerInfo::default());
+    assert_eq!(
+        default_fee.zkp_cost_chunk_usd,
+        get_test_ticker_config().fee_params.zkp_cost_chunk_usd
+    );
+    let params = block_on(ticker(MockTickerInfo::default()).active_fee_params());
+    assert_eq!(
+        params.effective_zkp_cost_chunk_usd,
+        params.params.zkp_cost_chunk_usd
+    );
+    assert!(!params.is_zkp_cost_dynamic);
+
+    // Proving a chunk takes 2 seconds, and the provers are twice overloaded.
+    let info = MockTickerInfo {
+        prover_stats: ProverStats {
+            proving_time: vec![ProvingTimeStats {
+                block_size: 10,
+                jobs_count: 3,
+                avg_proving_time: Duration::from_secs(20),
+            }],
+            pending_jobs: 4,
+            provers_required: 4,
+            idle_provers: 2,
+        },
+        ..Default::default()
+    };
+    let fee = get_fee(info.clone());
+    assert_eq!(fee.zkp_cost_chunk_usd, ratio("0.004"));
+    let params = block_on(ticker(info).active_fee_params());
+    assert_eq!(params.effective_zkp_cost_chunk_usd, ratio("0.004"));
+    assert!(params.is_zkp_cost_dynamic);
+
+    // ZKP fee is increased accordingly, while the gas fee is not affected.
+    // Fees are rounded up, so the difference of 1 wei is possible.
+    let four = BigUint::from(4u32);
+    assert!(fee.zkp_fee <= &default_fee.zkp_fee * &four);
+    assert!(fee.zkp_fee + &four > &default_fee.zkp_fee * &four);
+    assert_eq!(fee.gas_fee, default_fee.gas_fee);
+}
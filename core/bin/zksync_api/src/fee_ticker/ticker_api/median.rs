@@ -0,0 +1,260 @@
+// Built-in deps
+use std::fmt;
+use std::time::Instant;
+// External deps
+use anyhow::format_err;
+use async_trait::async_trait;
+use futures::future::join_all;
+use num::{rational::Ratio, BigUint, ToPrimitive};
+// Workspace deps
+use super::TokenPriceAPI;
+use zksync_types::TokenPrice;
+
+/// Token price source which can be aggregated by the `MedianTokenPriceAPI`.
+pub type BoxedTokenPriceAPI = Box<dyn TokenPriceAPI + Send + Sync>;
+
+/// Token price API aggregating several other sources.
+///
+/// All the sources are queried concurrently and the median of their prices is taken.
+/// Prices deviating from the median by more than `max_deviation` are rejected as outliers.
+/// If less than `quorum` sources have provided an acceptable price, an error is returned,
+/// so the ticker falls back to the historical price of the token.
+pub struct MedianTokenPriceAPI {
+    sources: Vec<(&'static str, BoxedTokenPriceAPI)>,
+    max_deviation: f64,
+    quorum: usize,
+}
+
+impl fmt::Debug for MedianTokenPriceAPI {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sources: Vec<_> = self.sources.iter().map(|(name, _)| name).collect();
+        f.debug_struct("MedianTokenPriceAPI")
+            .field("sources", &sources)
+            .field("max_deviation", &self.max_deviation)
+            .field("quorum", &self.quorum)
+            .finish()
+    }
+}
+
+impl MedianTokenPriceAPI {
+    pub fn new(
+        sources: Vec<(&'static str, BoxedTokenPriceAPI)>,
+        max_deviation: f64,
+        quorum: usize,
+    ) -> Self {
+        assert!(
+            quorum > 0 && quorum <= sources.len(),
+            "Token price quorum must be within [1, {}], got {}",
+            sources.len(),
+            quorum
+        );
+        assert!(
+            max_deviation >= 0.0,
+            "Max token price deviation must be non-negative"
+        );
+
+        Self {
+            sources,
+            max_deviation,
+            quorum,
+        }
+    }
+
+    /// Takes the median of the prices reported by the sources, rejecting the outliers.
+    fn aggregate(
+        &self,
+        token_symbol: &str,
+        prices: Vec<(&'static str, TokenPrice)>,
+    ) -> Result<TokenPrice, anyhow::Error> {
+        self.check_quorum(token_symbol, prices.len())?;
+
+        let median = median(prices.iter().map(|(_, price)| &price.usd_price));
+        let median_f64 = ratio_to_f64(&median);
+
+        let accepted: Vec<_> = prices
+            .into_iter()
+            .filter(|(source, price)| {
+                let deviation = if median_f64 > 0.0 {
+                    (ratio_to_f64(&price.usd_price) - median_f64).abs() / median_f64
+                } else if price.usd_price == median {
+                    0.0
+                } else {
+                    f64::INFINITY
+                };
+                metrics::gauge!("ticker.median.source_deviation", deviation, "source" => *source);
+
+                if deviation > self.max_deviation {
+                    log::warn!(
+                        "Price of '{}' reported by {} deviates from the median by {:.2}%, rejecting it",
+                        token_symbol,
+                        source,
+                        deviation * 100.0
+                    );
+                    false
+                } else {
+                    true
+                }
+            })
+            .map(|(_, price)| price)
+            .collect();
+        self.check_quorum(token_symbol, accepted.len())?;
+
+        let last_updated = accepted
+            .iter()
+            .map(|price| price.last_updated)
+            .min()
+            .expect("Quorum is always positive");
+        Ok(TokenPrice {
+            usd_price: median(accepted.iter().map(|price| &price.usd_price)),
+            last_updated,
+        })
+    }
+
+    fn check_quorum(&self, token_symbol: &str, prices_count: usize) -> Result<(), anyhow::Error> {
+        if prices_count < self.quorum {
+            metrics::increment_counter!("ticker.median.quorum_lost");
+            return Err(format_err!(
+                "Price quorum for '{}' is lost: {} of {} sources agree, {} required",
+                token_symbol,
+                prices_count,
+                self.sources.len(),
+                self.quorum
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TokenPriceAPI for MedianTokenPriceAPI {
+    async fn get_price(&self, token_symbol: &str) -> Result<TokenPrice, anyhow::Error> {
+        let start = Instant::now();
+
+        let requests = self.sources.iter().map(|(source, api)| async move {
+            let start = Instant::now();
+            let price = api.get_price(token_symbol).await;
+            metrics::histogram!("ticker.median.source_latency", start.elapsed(), "source" => *source);
+
+            match price {
+                Ok(price) => Some((*source, price)),
+                Err(err) => {
+                    log::warn!("Failed to get price from {}: {}", source, err);
+                    None
+                }
+            }
+        });
+        let prices = join_all(requests).await.into_iter().flatten().collect();
+
+        let result = self.aggregate(token_symbol, prices);
+        metrics::histogram!("ticker.median.get_price", start.elapsed());
+        result
+    }
+}
+
+fn median<'a>(prices: impl Iterator<Item = &'a Ratio<BigUint>>) -> Ratio<BigUint> {
+    let mut prices: Vec<_> = prices.cloned().collect();
+    prices.sort();
+
+    let middle = prices.len() / 2;
+    if prices.len() % 2 == 0 {
+        (&prices[middle - 1] + &prices[middle]) / Ratio::from_integer(BigUint::from(2u32))
+    } else {
+        prices[middle].clone()
+    }
+}
+
+fn ratio_to_f64(ratio: &Ratio<BigUint>) -> f64 {
+    let numer = ratio.numer().to_f64().unwrap_or(f64::INFINITY);
+    let denom = ratio.denom().to_f64().unwrap_or(f64::INFINITY);
+    numer / denom
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use futures::executor::block_on;
+
+    /// Source returning a fixed price or an error if the price is not set.
+    struct TestSource(Option<u32>);
+
+    #[async_trait]
+    impl TokenPriceAPI for TestSource {
+        async fn get_price(&self, _token_symbol: &str) -> Result<TokenPrice, anyhow::Error> {
+            let price = self.0.ok_or_else(|| format_err!("Source is unavailable"))?;
+            Ok(TokenPrice {
+                usd_price: Ratio::from_integer(price.into()),
+                last_updated: Utc::now(),
+            })
+        }
+    }
+
+    fn median_api(prices: &[Option<u32>], quorum: usize) -> MedianTokenPriceAPI {
+        const NAMES: [&str; 4] = ["first", "second", "third", "fourth"];
+
+        let sources = prices
+            .iter()
+            .zip(NAMES.iter())
+            .map(|(price, name)| (*name, Box::new(TestSource(*price)) as BoxedTokenPriceAPI))
+            .collect();
+        MedianTokenPriceAPI::new(sources, 0.1, quorum)
+    }
+
+    fn get_price(api: &MedianTokenPriceAPI) -> Result<Ratio<BigUint>, anyhow::Error> {
+        block_on(api.get_price("ETH")).map(|price| price.usd_price)
+    }
+
+    #[test]
+    fn median_of_sources() {
+        let api = median_api(&[Some(100), Some(98), Some(103)], 2);
+        assert_eq!(get_price(&api).unwrap(), Ratio::from_integer(100u32.into()));
+
+        let api = median_api(&[Some(100), Some(98), Some(103), Some(101)], 2);
+        assert_eq!(
+            get_price(&api).unwrap(),
+            Ratio::new(201u32.into(), 2u32.into())
+        );
+    }
+
+    #[test]
+    fn outliers_are_rejected() {
+        // Median is 101.5, so the last price is rejected and the median of the rest is taken.
+        let api = median_api(&[Some(100), Some(101), Some(102), Some(1000)], 3);
+        assert_eq!(get_price(&api).unwrap(), Ratio::from_integer(101u32.into()));
+
+        // Both prices deviate from the median by 1/3, so there is no quorum.
+        let api = median_api(&[Some(100), Some(200)], 1);
+        assert!(get_price(&api).is_err());
+    }
+
+    #[test]
+    fn unavailable_sources() {
+        let api = median_api(&[Some(100), None, Some(102)], 2);
+        assert_eq!(get_price(&api).unwrap(), Ratio::from_integer(101u32.into()));
+
+        let api = median_api(&[Some(100), None, None], 2);
+        assert!(get_price(&api).is_err());
+    }
+
+    #[test]
+    fn oldest_update_time_is_reported() {
+        let now = Utc::now();
+        let price = |usd_price: u32, last_updated| TokenPrice {
+            usd_price: Ratio::from_integer(usd_price.into()),
+            last_updated,
+        };
+
+        let api = median_api(&[None, None, None], 2);
+        let aggregated = api
+            .aggregate(
+                "ETH",
+                vec![
+                    ("first", price(100, now)),
+                    ("second", price(100, now - Duration::seconds(30))),
+                    ("third", price(1000, now - Duration::seconds(60))),
+                ],
+            )
+            .unwrap();
+        assert_eq!(aggregated.last_updated, now - Duration::seconds(30));
+    }
+}
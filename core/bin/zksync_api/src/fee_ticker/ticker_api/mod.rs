@@ -12,6 +12,8 @@ use zksync_types::{Token, TokenId, TokenLike, TokenPrice};
 
 pub mod coingecko;
 pub mod coinmarkercap;
+pub mod median;
+pub mod uniswap;
 
 const API_PRICE_EXPIRATION_TIME_SECS: i64 = 300; // 5 mins
 const HISTORICAL_PRICE_EXPIRATION_TIME: Duration = Duration::from_secs(60);
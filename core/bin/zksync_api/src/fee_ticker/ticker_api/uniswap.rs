@@ -0,0 +1,489 @@
+//! Token price source deriving prices from the reserves of the Uniswap-v2-style DEX pairs.
+
+// Built-in deps
+use std::collections::{HashMap, VecDeque};
+// External deps
+use anyhow::format_err;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use ethabi::{ParamType, Token as AbiToken};
+use num::{rational::Ratio, traits::Pow, BigUint, Zero};
+use tokio::sync::Mutex;
+use web3::{
+    transports::Http,
+    types::{Bytes, CallRequest},
+    Web3,
+};
+// Workspace deps
+use super::{TokenPriceAPI, REQUEST_TIMEOUT};
+use crate::fee_ticker::fee_token_validator::TokenCacheWrapper;
+use zksync_config::UniswapV2Options;
+use zksync_types::{Address, Token, TokenLike, TokenPrice};
+
+/// Decimals of the wrapped ETH.
+const WETH_DECIMALS: u8 = 18;
+
+/// Price observations of the single token, ordered by time.
+type Observations = VecDeque<(DateTime<Utc>, Ratio<BigUint>)>;
+
+/// Token price API deriving the USD prices from the DEX pairs reserves.
+///
+/// Price of the token in ETH is taken from the token/WETH pair, and the price of ETH in USD
+/// is taken from the WETH/stablecoin pair. Spot prices are smoothed by the time-weighted
+/// average over the configured window to make price manipulations more expensive.
+#[derive(Debug)]
+pub struct UniswapV2API {
+    web3: Web3<Http>,
+    tokens: TokenCacheWrapper,
+    factory_address: Address,
+    weth_address: Address,
+    usd_token_address: Address,
+    usd_token_decimals: u8,
+    twap_window: Duration,
+    observations: Mutex<HashMap<Address, Observations>>,
+}
+
+impl UniswapV2API {
+    pub(crate) fn new(
+        options: UniswapV2Options,
+        tokens: impl Into<TokenCacheWrapper>,
+    ) -> Result<Self, anyhow::Error> {
+        let transport = Http::new(&options.web3_url)?;
+        let twap_window = Duration::from_std(options.twap_window)?;
+
+        Ok(Self {
+            web3: Web3::new(transport),
+            tokens: tokens.into(),
+            factory_address: options.factory_address,
+            weth_address: options.weth_address,
+            usd_token_address: options.usd_token_address,
+            usd_token_decimals: options.usd_token_decimals,
+            twap_window,
+            observations: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Returns the amount of wei in the token/WETH pair, or zero if there is no such pair.
+    pub async fn token_liquidity(&self, token: &Token) -> Result<BigUint, anyhow::Error> {
+        let token_address = self.erc20_address(token);
+        if token_address == self.weth_address {
+            anyhow::bail!("Liquidity of ETH can't be measured in ETH");
+        }
+
+        Ok(self
+            .reserves(token_address, self.weth_address)
+            .await?
+            .map(|(_, weth_reserve)| weth_reserve)
+            .unwrap_or_default())
+    }
+
+    /// Returns the current price of `token` in USD without smoothing.
+    async fn spot_price(&self, token: &Token) -> Result<Ratio<BigUint>, anyhow::Error> {
+        let token_address = self.erc20_address(token);
+        if token_address == self.usd_token_address {
+            return Ok(Ratio::from_integer(1u32.into()));
+        }
+
+        let eth_price = self
+            .pair_price(
+                (self.weth_address, WETH_DECIMALS),
+                (self.usd_token_address, self.usd_token_decimals),
+            )
+            .await?;
+        if token_address == self.weth_address {
+            return Ok(eth_price);
+        }
+
+        let token_eth_price = self
+            .pair_price(
+                (token_address, token.decimals),
+                (self.weth_address, WETH_DECIMALS),
+            )
+            .await?;
+        Ok(token_eth_price * eth_price)
+    }
+
+    /// Returns the price of the single `token` unit in the `base` units.
+    /// Both tokens are represented by their addresses and decimals.
+    async fn pair_price(
+        &self,
+        token: (Address, u8),
+        base: (Address, u8),
+    ) -> Result<Ratio<BigUint>, anyhow::Error> {
+        let (token_reserve, base_reserve) = self
+            .reserves(token.0, base.0)
+            .await?
+            .ok_or_else(|| format_err!("There is no pair for {:?} and {:?}", token.0, base.0))?;
+        if token_reserve.is_zero() || base_reserve.is_zero() {
+            anyhow::bail!("Pair of {:?} and {:?} has no liquidity", token.0, base.0);
+        }
+
+        let token_unit = BigUint::from(10u32).pow(u32::from(token.1));
+        let base_unit = BigUint::from(10u32).pow(u32::from(base.1));
+        Ok(Ratio::new(
+            base_reserve * token_unit,
+            token_reserve * base_unit,
+        ))
+    }
+
+    /// Returns the reserves of the `token` and `base` in their pair,
+    /// or `None` if the pair doesn't exist.
+    async fn reserves(
+        &self,
+        token: Address,
+        base: Address,
+    ) -> Result<Option<(BigUint, BigUint)>, anyhow::Error> {
+        let pair = self
+            .call(
+                self.factory_address,
+                "getPair(address,address)",
+                &[AbiToken::Address(token), AbiToken::Address(base)],
+                &[ParamType::Address],
+            )
+            .await?
+            .remove(0)
+            .to_address()
+            .ok_or_else(|| format_err!("Malformed getPair response"))?;
+        if pair == Address::zero() {
+            return Ok(None);
+        }
+
+        let mut reserves = self
+            .call(
+                pair,
+                "getReserves()",
+                &[],
+                &[
+                    ParamType::Uint(112),
+                    ParamType::Uint(112),
+                    ParamType::Uint(32),
+                ],
+            )
+            .await?
+            .into_iter()
+            .take(2)
+            .map(|reserve| {
+                let reserve = reserve
+                    .to_uint()
+                    .ok_or_else(|| format_err!("Malformed getReserves response"))?;
+                let mut bytes = [0u8; 32];
+                reserve.to_big_endian(&mut bytes);
+                Ok(BigUint::from_bytes_be(&bytes))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let (reserve_1, reserve_0) = (reserves.remove(1), reserves.remove(0));
+
+        // Pair tokens are sorted by their addresses.
+        if token < base {
+            Ok(Some((reserve_0, reserve_1)))
+        } else {
+            Ok(Some((reserve_1, reserve_0)))
+        }
+    }
+
+    /// Calls the contract `function` and decodes the returned values.
+    async fn call(
+        &self,
+        contract: Address,
+        function: &str,
+        args: &[AbiToken],
+        outputs: &[ParamType],
+    ) -> Result<Vec<AbiToken>, anyhow::Error> {
+        let mut data = tiny_keccak::keccak256(function.as_bytes())[..4].to_vec();
+        data.extend(ethabi::encode(args));
+
+        let request = CallRequest {
+            from: None,
+            to: Some(contract),
+            gas: None,
+            gas_price: None,
+            value: None,
+            data: Some(Bytes(data)),
+        };
+        let response = tokio::time::timeout(REQUEST_TIMEOUT, self.web3.eth().call(request, None))
+            .await
+            .map_err(|_| format_err!("DEX call '{}' timeout", function))?
+            .map_err(|err| format_err!("DEX call '{}' failed: {}", function, err))?;
+
+        Ok(ethabi::decode(outputs, &response.0)?)
+    }
+
+    /// Returns the address of the ERC20 token traded on the DEX.
+    /// ETH is traded as WETH.
+    fn erc20_address(&self, token: &Token) -> Address {
+        if token.address == Address::zero() {
+            self.weth_address
+        } else {
+            token.address
+        }
+    }
+
+    /// Stores the spot price observation and returns the time-weighted average price.
+    async fn twap(
+        &self,
+        token_address: Address,
+        spot_price: Ratio<BigUint>,
+        now: DateTime<Utc>,
+    ) -> Ratio<BigUint> {
+        let mut observations = self.observations.lock().await;
+        let observations = observations.entry(token_address).or_default();
+
+        observations.push_back((now, spot_price));
+
+        // The last observation made before the window start is kept,
+        // since its price was actual at the window start.
+        let window_start = now - self.twap_window;
+        while observations.len() > 1 && observations[1].0 <= window_start {
+            observations.pop_front();
+        }
+
+        time_weighted_average(observations, window_start, now)
+    }
+}
+
+#[async_trait]
+impl TokenPriceAPI for UniswapV2API {
+    async fn get_price(&self, token_symbol: &str) -> Result<TokenPrice, anyhow::Error> {
+        let token = self
+            .tokens
+            .get_token(TokenLike::Symbol(token_symbol.to_string()))
+            .await?
+            .ok_or_else(|| format_err!("Token '{}' is not supported by zkSync", token_symbol))?;
+
+        let spot_price = self.spot_price(&token).await?;
+        let now = Utc::now();
+        let usd_price = self.twap(self.erc20_address(&token), spot_price, now).await;
+
+        Ok(TokenPrice {
+            usd_price,
+            last_updated: now,
+        })
+    }
+}
+
+/// Calculates the average price over the window weighted by the time it was actual.
+/// Every observed price is considered actual from the moment it was observed
+/// (or from the window start for the earlier observations) until the next observation
+/// (or until `now` for the last one).
+fn time_weighted_average(
+    observations: &Observations,
+    window_start: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Ratio<BigUint> {
+    let mut weighted_sum = Ratio::from_integer(BigUint::zero());
+    let mut total_weight = BigUint::zero();
+
+    let next_times = observations.iter().skip(1).map(|(time, _)| *time);
+    for ((time, price), next_time) in observations.iter().zip(next_times.chain(Some(now))) {
+        let actual_since = std::cmp::max(*time, window_start);
+        let weight = BigUint::from((next_time - actual_since).num_milliseconds().max(0) as u64);
+        weighted_sum = weighted_sum + price * Ratio::from_integer(weight.clone());
+        total_weight += weight;
+    }
+
+    if total_weight.is_zero() {
+        observations
+            .back()
+            .map(|(_, price)| price.clone())
+            .expect("At least one observation is stored")
+    } else {
+        weighted_sum / Ratio::from_integer(total_weight)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use actix_web::{web, App, HttpResponse};
+    use serde_json::{json, Value};
+    use std::str::FromStr;
+
+    const FACTORY: &str = "5c69bee701ef814a2b6a3edd4b1652cb9cc5aa6f";
+    const WETH: &str = "c02aaa39b223fe8d0a0e5c4f27ead9083c756cc2";
+    const DAI: &str = "6b175474e89094c44da98b954eedeac495271d0f";
+    const LONG_TAIL: &str = "1111111111111111111111111111111111111111";
+    const NO_PAIR: &str = "2222222222222222222222222222222222222222";
+
+    /// Pair of the mock DEX: pair address and reserves of both tokens.
+    type Pair = (&'static str, (&'static str, u128), (&'static str, u128));
+
+    fn pairs() -> Vec<Pair> {
+        const ETH: u128 = 1_000_000_000_000_000_000;
+        vec![
+            // 1 ETH = 10 DAI.
+            (
+                "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+                (WETH, 100 * ETH),
+                (DAI, 1000 * ETH),
+            ),
+            // 1 LTT (6 decimals) = 0.01 ETH.
+            (
+                "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb",
+                (LONG_TAIL, 500_000_000),
+                (WETH, 5 * ETH),
+            ),
+        ]
+    }
+
+    fn selector(function: &str) -> String {
+        hex::encode(&tiny_keccak::keccak256(function.as_bytes())[..4])
+    }
+
+    /// Mock Ethereum node handling `eth_call` requests to the factory and pair contracts.
+    async fn eth_call(request: web::Json<Value>) -> HttpResponse {
+        let call = &request["params"][0];
+        let to = call["to"].as_str().unwrap().trim_start_matches("0x");
+        let data = hex::decode(call["data"].as_str().unwrap().trim_start_matches("0x")).unwrap();
+        let function = hex::encode(&data[..4]);
+
+        let result = if to == FACTORY && function == selector("getPair(address,address)") {
+            let tokens = ethabi::decode(&[ParamType::Address, ParamType::Address], &data[4..])
+                .unwrap()
+                .into_iter()
+                .map(|token| hex::encode(token.to_address().unwrap()))
+                .collect::<Vec<_>>();
+            let pair = pairs()
+                .into_iter()
+                .find(|(_, (token_0, _), (token_1, _))| {
+                    tokens.contains(&token_0.to_string()) && tokens.contains(&token_1.to_string())
+                })
+                .map(|(pair, _, _)| Address::from_str(pair).unwrap())
+                .unwrap_or_else(Address::zero);
+            ethabi::encode(&[AbiToken::Address(pair)])
+        } else if function == selector("getReserves()") {
+            let (_, mut reserve_0, mut reserve_1) = pairs()
+                .into_iter()
+                .find(|(pair, _, _)| *pair == to)
+                .unwrap();
+            // Pair tokens are sorted by their addresses.
+            if reserve_0.0 > reserve_1.0 {
+                std::mem::swap(&mut reserve_0, &mut reserve_1);
+            }
+            ethabi::encode(&[
+                AbiToken::Uint(reserve_0.1.into()),
+                AbiToken::Uint(reserve_1.1.into()),
+                AbiToken::Uint(0.into()),
+            ])
+        } else {
+            panic!("Unexpected call: {}", call);
+        };
+
+        HttpResponse::Ok().json(json!({
+            "jsonrpc": "2.0",
+            "id": request["id"],
+            "result": format!("0x{}", hex::encode(result)),
+        }))
+    }
+
+    pub(crate) fn tokens() -> HashMap<TokenLike, Token> {
+        vec![
+            Token::new(0, Address::zero(), "ETH", 18),
+            Token::new(1, Address::from_str(DAI).unwrap(), "DAI", 18),
+            Token::new(2, Address::from_str(LONG_TAIL).unwrap(), "LTT", 6),
+            Token::new(3, Address::from_str(NO_PAIR).unwrap(), "NOPE", 18),
+        ]
+        .into_iter()
+        .map(|token| (TokenLike::Symbol(token.symbol.clone()), token))
+        .collect()
+    }
+
+    pub(crate) fn uniswap_api(
+        server: &actix_web::test::TestServer,
+        twap_window: std::time::Duration,
+    ) -> UniswapV2API {
+        let options = UniswapV2Options {
+            web3_url: server.url(""),
+            factory_address: Address::from_str(FACTORY).unwrap(),
+            weth_address: Address::from_str(WETH).unwrap(),
+            usd_token_address: Address::from_str(DAI).unwrap(),
+            usd_token_decimals: 18,
+            twap_window,
+        };
+        UniswapV2API::new(options, tokens()).unwrap()
+    }
+
+    pub(crate) fn mock_node() -> actix_web::test::TestServer {
+        actix_web::test::start(|| App::new().route("/", web::post().to(eth_call)))
+    }
+
+    async fn usd_price(api: &UniswapV2API, symbol: &str) -> anyhow::Result<Ratio<BigUint>> {
+        api.get_price(symbol).await.map(|price| price.usd_price)
+    }
+
+    #[actix_rt::test]
+    async fn spot_prices() {
+        let server = mock_node();
+        let api = uniswap_api(&server, std::time::Duration::from_secs(0));
+
+        assert_eq!(
+            usd_price(&api, "ETH").await.unwrap(),
+            Ratio::from_integer(10u32.into())
+        );
+        assert_eq!(
+            usd_price(&api, "DAI").await.unwrap(),
+            Ratio::from_integer(1u32.into())
+        );
+        assert_eq!(
+            usd_price(&api, "LTT").await.unwrap(),
+            Ratio::new(1u32.into(), 10u32.into())
+        );
+        assert!(usd_price(&api, "NOPE").await.is_err());
+        assert!(usd_price(&api, "UNKNOWN").await.is_err());
+    }
+
+    #[actix_rt::test]
+    async fn token_liquidity() {
+        let server = mock_node();
+        let api = uniswap_api(&server, std::time::Duration::from_secs(0));
+        let tokens = tokens();
+        let token = |symbol: &str| tokens[&TokenLike::Symbol(symbol.to_string())].clone();
+
+        assert_eq!(
+            api.token_liquidity(&token("LTT")).await.unwrap(),
+            BigUint::from(5_000_000_000_000_000_000u128)
+        );
+        assert_eq!(
+            api.token_liquidity(&token("NOPE")).await.unwrap(),
+            BigUint::zero()
+        );
+        assert!(api.token_liquidity(&token("ETH")).await.is_err());
+    }
+
+    #[test]
+    fn twap_calculation() {
+        let start = Utc::now();
+        let price = |price: u32| Ratio::from_integer(BigUint::from(price));
+        let at = |secs: i64| start + Duration::seconds(secs);
+
+        // The only observation is taken as is.
+        let observations: Observations = vec![(at(0), price(10))].into_iter().collect();
+        assert_eq!(
+            time_weighted_average(&observations, at(0), at(0)),
+            price(10)
+        );
+
+        // Price 10 was actual for 30 seconds and price 40 for 10 seconds.
+        let observations: Observations = vec![(at(0), price(10)), (at(30), price(40))]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            time_weighted_average(&observations, at(0), at(40)),
+            Ratio::new(700u32.into(), 40u32.into())
+        );
+
+        // Price observed before the window is actual until the next observation,
+        // the price observed just now isn't accounted yet.
+        let observations: Observations = vec![
+            (at(-20), price(20)),
+            (at(10), price(10)),
+            (at(30), price(40)),
+            (at(40), price(1000)),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            time_weighted_average(&observations, at(0), at(40)),
+            price(20)
+        );
+    }
+}
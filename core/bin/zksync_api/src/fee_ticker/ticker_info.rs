@@ -3,10 +3,13 @@
 
 // External deps
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 // Workspace deps
 use zksync_storage::ConnectionPool;
-use zksync_types::Address;
+use zksync_types::{Address, FeeParamsVersion};
+use zksync_witness_generator::ScalerOracle;
 // Local deps
+use crate::fee_ticker::zkp_cost::ProverStats;
 
 /// Api responsible for querying for TokenPrices
 #[async_trait]
@@ -14,15 +17,27 @@ pub trait FeeTickerInfo {
     /// Check whether account exists in the zkSync network or not.
     /// Returns `true` if account does not yet exist in the zkSync network.
     async fn is_account_new(&mut self, address: Address) -> bool;
+
+    /// Returns the latest version of the fee parameters set by the server operator, if any.
+    async fn fee_params(&mut self) -> anyhow::Result<Option<FeeParamsVersion>>;
+
+    /// Returns the statistics of the provers, taking into account the blocks proven since the given moment.
+    async fn prover_stats(&mut self, since: DateTime<Utc>) -> anyhow::Result<ProverStats>;
 }
 
 pub struct TickerInfo {
     db: ConnectionPool,
+    scaler: ScalerOracle,
+    idle_provers: u32,
 }
 
 impl TickerInfo {
-    pub fn new(db: ConnectionPool) -> Self {
-        Self { db }
+    pub fn new(db: ConnectionPool, idle_provers: u32) -> Self {
+        Self {
+            scaler: ScalerOracle::new(db.clone(), idle_provers),
+            db,
+            idle_provers,
+        }
     }
 }
 
@@ -45,4 +60,25 @@ impl FeeTickerInfo for TickerInfo {
         // If account is `Some(_)` then it's not new.
         account_state.committed.is_none()
     }
+
+    async fn fee_params(&mut self) -> anyhow::Result<Option<FeeParamsVersion>> {
+        let mut storage = self.db.access_storage().await?;
+        storage.tokens_schema().load_fee_params().await
+    }
+    async fn prover_stats(&mut self, since: DateTime<Utc>) -> anyhow::Result<ProverStats> {
+        let (proving_time, pending_jobs) = {
+            let mut storage = self.db.access_storage().await?;
+            let proving_time = storage.prover_schema().proving_time_stats(since).await?;
+            let pending_jobs = storage.prover_schema().pending_jobs_count().await?;
+            (proving_time, pending_jobs)
+        };
+        let provers_required = self.scaler.provers_required().await?;
+
+        Ok(ProverStats {
+            proving_time,
+            pending_jobs,
+            provers_required,
+            idle_provers: self.idle_provers,
+        })
+    }
 }
@@ -0,0 +1,144 @@
+//! ZKP cost derived from the actual prover throughput.
+//!
+//! Cost of proving a chunk is calculated as follows:
+//! `prover cost per second * average proving time of a chunk * provers load`,
+//! where the provers load is the ratio of the provers required by the scaler to the idle ones.
+//! Thus, while the provers keep up with the blocks, only the compute time is paid, and once
+//! the queue of blocks grows and more provers have to be started, the cost grows proportionally.
+//! The result is clamped by the configured bounds.
+
+// Built-in deps
+use std::cmp;
+// External deps
+use num::{rational::Ratio, BigUint};
+// Workspace deps
+use zksync_config::DynamicZkpCostOptions;
+use zksync_storage::prover::records::ProvingTimeStats;
+// Local deps
+
+/// Prover statistics required to calculate the ZKP cost.
+#[derive(Debug, Clone, Default)]
+pub struct ProverStats {
+    /// Proving time of the recently proven blocks, grouped by the block size.
+    pub proving_time: Vec<ProvingTimeStats>,
+    /// Amount of the blocks awaiting for the proof.
+    pub pending_jobs: u32,
+    /// Amount of the provers required to process the pending blocks.
+    pub provers_required: u32,
+    /// Amount of the provers running even if there are no blocks to prove.
+    pub idle_provers: u32,
+}
+
+/// Calculates the cost of proving a chunk in USD.
+/// Returns `None` if there are no recently proven blocks to derive the cost from.
+pub fn zkp_cost_chunk_usd(
+    options: &DynamicZkpCostOptions,
+    stats: &ProverStats,
+) -> Option<Ratio<BigUint>> {
+    let (proving_time_ms, chunks) = stats.proving_time.iter().fold(
+        (BigUint::from(0u32), BigUint::from(0u32)),
+        |(time, chunks), stats| {
+            let jobs = BigUint::from(stats.jobs_count);
+            (
+                time + BigUint::from(stats.avg_proving_time.as_millis() as u64) * &jobs,
+                chunks + BigUint::from(stats.block_size) * jobs,
+            )
+        },
+    );
+    if chunks == BigUint::from(0u32) {
+        return None;
+    }
+
+    let proving_time_chunk_secs = Ratio::new(proving_time_ms, chunks * BigUint::from(1000u32));
+    let provers_load = Ratio::new(
+        BigUint::from(stats.provers_required),
+        BigUint::from(cmp::max(stats.idle_provers, 1)),
+    );
+    let cost = &options.prover_cost_per_second_usd * proving_time_chunk_secs * provers_load;
+
+    Some(
+        cost.max(options.min_zkp_cost_chunk_usd.clone())
+            .min(options.max_zkp_cost_chunk_usd.clone()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use zksync_utils::UnsignedRatioSerializeAsDecimal;
+
+    fn ratio(value: &str) -> Ratio<BigUint> {
+        UnsignedRatioSerializeAsDecimal::deserialize_from_str_with_dot(value).unwrap()
+    }
+
+    fn options() -> DynamicZkpCostOptions {
+        DynamicZkpCostOptions {
+            prover_cost_per_second_usd: ratio("0.001"),
+            min_zkp_cost_chunk_usd: ratio("0.0001"),
+            max_zkp_cost_chunk_usd: ratio("0.1"),
+            stats_period: Duration::from_secs(3600),
+        }
+    }
+
+    fn proving_time(block_size: usize, jobs_count: u64, secs: u64) -> ProvingTimeStats {
+        ProvingTimeStats {
+            block_size,
+            jobs_count,
+            avg_proving_time: Duration::from_secs(secs),
+        }
+    }
+
+    #[test]
+    fn no_proven_blocks() {
+        let stats = ProverStats {
+            provers_required: 1,
+            idle_provers: 1,
+            ..Default::default()
+        };
+        assert_eq!(zkp_cost_chunk_usd(&options(), &stats), None);
+    }
+
+    #[test]
+    fn cost_from_proving_time() {
+        // 2 blocks of 10 chunks proven in 40 seconds each and
+        // 1 block of 50 chunks proven in 60 seconds: 140 seconds for 70 chunks.
+        let mut stats = ProverStats {
+            proving_time: vec![proving_time(10, 2, 40), proving_time(50, 1, 60)],
+            pending_jobs: 0,
+            provers_required: 2,
+            idle_provers: 2,
+        };
+        assert_eq!(zkp_cost_chunk_usd(&options(), &stats), Some(ratio("0.002")));
+
+        // Provers are overloaded, so more of them are required.
+        stats.pending_jobs = 5;
+        stats.provers_required = 5;
+        assert_eq!(zkp_cost_chunk_usd(&options(), &stats), Some(ratio("0.005")));
+    }
+
+    #[test]
+    fn cost_is_bounded() {
+        let stats = ProverStats {
+            proving_time: vec![proving_time(100, 1, 1)],
+            pending_jobs: 0,
+            provers_required: 1,
+            idle_provers: 1,
+        };
+        assert_eq!(
+            zkp_cost_chunk_usd(&options(), &stats),
+            Some(options().min_zkp_cost_chunk_usd)
+        );
+
+        let stats = ProverStats {
+            proving_time: vec![proving_time(10, 1, 10_000)],
+            pending_jobs: 0,
+            provers_required: 1,
+            idle_provers: 1,
+        };
+        assert_eq!(
+            zkp_cost_chunk_usd(&options(), &stats),
+            Some(options().max_zkp_cost_chunk_usd)
+        );
+    }
+}
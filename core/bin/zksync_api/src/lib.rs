@@ -1,6 +1,9 @@
 #![recursion_limit = "256"]
 
-use crate::{api_server::start_api_server, fee_ticker::run_ticker_task};
+use crate::{
+    api_server::start_api_server,
+    fee_ticker::{run_ticker_task, FeeQuoteSigner},
+};
 use futures::channel::mpsc;
 use zksync_config::{AdminServerOptions, ApiServerOptions, ConfigurationOptions};
 use zksync_storage::ConnectionPool;
@@ -25,7 +28,11 @@ pub fn run_api(
     let api_server_options = ApiServerOptions::from_env();
     let admin_server_options = AdminServerOptions::from_env();
 
-    let ticker_task = run_ticker_task(connection_pool.clone(), ticker_request_receiver);
+    let ticker_task = run_ticker_task(
+        connection_pool.clone(),
+        ticker_request_receiver,
+        FeeQuoteSigner::from_options(&api_server_options),
+    );
 
     start_api_server(
         connection_pool,
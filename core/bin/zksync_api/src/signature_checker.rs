@@ -14,12 +14,15 @@ use futures::{
 };
 use tokio::runtime::{Builder, Handle};
 // Workspace uses
-use zksync_types::{tx::TxEthSignature, SignedZkSyncTx, ZkSyncTx};
+use zksync_types::{
+    tx::{Eip712Domain, TxEthSignature, TxsBatch},
+    SignedZkSyncTx, ZkSyncTx,
+};
 // Local uses
 use crate::{eth_checker::EthereumChecker, tx_error::TxAddError};
 use zksync_config::ConfigurationOptions;
 use zksync_types::tx::EthSignData;
-use zksync_utils::panic_notify::ThreadPanicNotify;
+use zksync_utils::{panic_notify::ThreadPanicNotify, unix_timestamp};
 
 /// `TxVariant` is used to form a verify request. It is possible to wrap
 /// either a single transaction, or the transaction batch.
@@ -44,8 +47,9 @@ impl VerifiedTx {
     pub async fn verify(
         request: &mut VerifyTxSignatureRequest,
         eth_checker: &EthereumChecker<web3::transports::Http>,
+        eip712_domain: &Eip712Domain,
     ) -> Result<Self, TxAddError> {
-        verify_eth_signature(request, eth_checker).await?;
+        verify_eth_signature(request, eth_checker, eip712_domain).await?;
         verify_tx_correctness(&mut request.tx)?;
 
         Ok(Self(request.tx.clone()))
@@ -75,20 +79,24 @@ impl VerifiedTx {
 }
 
 /// Verifies the Ethereum signature of the (batch of) transaction(s).
+///
+/// EIP-712 signatures are checked against the typed data built from the transactions
+/// themselves, so the message provided with such signatures is ignored.
 async fn verify_eth_signature(
     request: &VerifyTxSignatureRequest,
     eth_checker: &EthereumChecker<web3::transports::Http>,
+    eip712_domain: &Eip712Domain,
 ) -> Result<(), TxAddError> {
     match &request.tx {
         TxVariant::Tx(tx) => {
-            verify_eth_signature_single_tx(tx, eth_checker).await?;
+            verify_eth_signature_single_tx(tx, eth_checker, eip712_domain).await?;
         }
         TxVariant::Batch(txs, eth_sign_data) => {
-            verify_eth_signature_txs_batch(txs, eth_sign_data, eth_checker).await?;
+            verify_eth_signature_txs_batch(txs, eth_sign_data, eth_checker, eip712_domain).await?;
             // In case there're signatures provided for some of transactions
             // we still verify them.
             for tx in txs {
-                verify_eth_signature_single_tx(tx, eth_checker).await?;
+                verify_eth_signature_single_tx(tx, eth_checker, eip712_domain).await?;
             }
         }
     }
@@ -99,6 +107,7 @@ async fn verify_eth_signature(
 async fn verify_eth_signature_single_tx(
     tx: &SignedZkSyncTx,
     eth_checker: &EthereumChecker<web3::transports::Http>,
+    eip712_domain: &Eip712Domain,
 ) -> Result<(), TxAddError> {
     let start = Instant::now();
     // Check if the tx is a `ChangePubKey` operation without an Ethereum signature.
@@ -146,6 +155,19 @@ async fn verify_eth_signature_single_tx(
                     return Err(TxAddError::IncorrectTx);
                 }
             }
+            TxEthSignature::EIP712Signature(packed_signature) => {
+                let typed_data = tx
+                    .tx
+                    .eip712_struct()
+                    .ok_or(TxAddError::IncorrectEthSignature)?;
+                let signer_account = packed_signature
+                    .typed_data_recover_signer(eip712_domain, typed_data)
+                    .or(Err(TxAddError::IncorrectEthSignature))?;
+
+                if signer_account != tx.tx.account() {
+                    return Err(TxAddError::IncorrectEthSignature);
+                }
+            }
         };
     }
 
@@ -160,6 +182,7 @@ async fn verify_eth_signature_txs_batch(
     txs: &[SignedZkSyncTx],
     eth_sign_data: &EthSignData,
     eth_checker: &EthereumChecker<web3::transports::Http>,
+    eip712_domain: &Eip712Domain,
 ) -> Result<(), TxAddError> {
     let start = Instant::now();
     match &eth_sign_data.signature {
@@ -188,6 +211,17 @@ async fn verify_eth_signature_txs_batch(
                 }
             }
         }
+        TxEthSignature::EIP712Signature(packed_signature) => {
+            let batch = TxsBatch::new(txs.iter().map(|tx| &tx.tx))
+                .ok_or(TxAddError::IncorrectEthSignature)?;
+            let signer_account = packed_signature
+                .typed_data_recover_signer(eip712_domain, &batch)
+                .or(Err(TxAddError::IncorrectEthSignature))?;
+
+            if txs.iter().any(|tx| tx.tx.account() != signer_account) {
+                return Err(TxAddError::IncorrectEthSignature);
+            }
+        }
     };
 
     metrics::histogram!(
@@ -198,19 +232,25 @@ async fn verify_eth_signature_txs_batch(
 }
 
 /// Verifies the correctness of the ZKSync transaction(s) (including the
-/// signature check).
+/// signature check) and that their validity time range hasn't ended yet.
+///
+/// Transactions which become valid in the future are accepted: they are kept in the mempool
+/// until their validity time range starts.
 fn verify_tx_correctness(tx: &mut TxVariant) -> Result<(), TxAddError> {
-    match tx {
-        TxVariant::Tx(tx) => {
-            if !tx.tx.check_correctness() {
-                return Err(TxAddError::IncorrectTx);
-            }
-        }
-        TxVariant::Batch(batch, _) => {
-            if batch.iter_mut().any(|tx| !tx.tx.check_correctness()) {
-                return Err(TxAddError::IncorrectTx);
-            }
-        }
+    let txs = match tx {
+        TxVariant::Tx(tx) => std::slice::from_mut(tx),
+        TxVariant::Batch(batch, _) => batch.as_mut_slice(),
+    };
+    if txs.iter_mut().any(|tx| !tx.tx.check_correctness()) {
+        return Err(TxAddError::IncorrectTx);
+    }
+
+    let now = unix_timestamp();
+    if txs.iter().any(|tx| {
+        let time_range = tx.tx.time_range();
+        !time_range.check_correctness() || time_range.is_expired(now)
+    }) {
+        return Err(TxAddError::InvalidTimeRange);
     }
     Ok(())
 }
@@ -234,6 +274,10 @@ pub fn start_sign_checker_detached(
     let web3 = web3::Web3::new(transport);
 
     let eth_checker = EthereumChecker::new(web3, config_options.contract_eth_addr);
+    let eip712_domain = Eip712Domain::new(
+        config_options.chain_id.into(),
+        config_options.contract_eth_addr,
+    );
 
     /// Main signature check requests handler.
     /// Basically it receives the requests through the channel and verifies signatures,
@@ -242,11 +286,12 @@ pub fn start_sign_checker_detached(
         handle: Handle,
         mut input: mpsc::Receiver<VerifyTxSignatureRequest>,
         eth_checker: EthereumChecker<web3::transports::Http>,
+        eip712_domain: Eip712Domain,
     ) {
         while let Some(mut request) = input.next().await {
             let eth_checker = eth_checker.clone();
             handle.spawn(async move {
-                let resp = VerifiedTx::verify(&mut request, &eth_checker).await;
+                let resp = VerifiedTx::verify(&mut request, &eth_checker, &eip712_domain).await;
 
                 request.response.send(resp).unwrap_or_default();
             });
@@ -264,7 +309,7 @@ pub fn start_sign_checker_detached(
                 .build()
                 .expect("failed to build runtime for signature processor");
             let handle = runtime.handle().clone();
-            runtime.block_on(checker_routine(handle, input, eth_checker));
+            runtime.block_on(checker_routine(handle, input, eth_checker, eip712_domain));
         })
         .expect("failed to start signature checker thread");
 }
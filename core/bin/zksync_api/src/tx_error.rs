@@ -41,4 +41,40 @@ pub enum TxAddError {
 
     #[error("The number of withdrawals in the batch is too big")]
     BatchWithdrawalsOverload,
+
+    #[error("Mempool is full and tx fee is too low to replace any of pending txs")]
+    MempoolIsFull,
+
+    #[error("Too many pending txs for the account")]
+    TooManyPendingTxs,
+
+    #[error("Fee is too low to replace the pending tx with the same nonce")]
+    ReplacementFeeTooLow,
+
+    #[error("Pending tx with the same nonce is a part of batch and cannot be replaced")]
+    BatchTxReplacement,
+
+    #[error("Batch tx has the same nonce as a pending tx")]
+    BatchNonceConflict,
+
+    #[error("Tx nonce is too far ahead of the account nonce")]
+    NonceTooHigh,
+
+    #[error("Tx can't be executed at the current time")]
+    InvalidTimeRange,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Error)]
+pub enum TxCancelError {
+    #[error("Tx is not pending in the mempool")]
+    NotPending,
+
+    #[error("Tx is a part of batch and cannot be cancelled")]
+    BatchTx,
+
+    #[error("Cancel request signature is incorrect")]
+    IncorrectSignature,
+
+    #[error("Database unavailable")]
+    DbError,
 }
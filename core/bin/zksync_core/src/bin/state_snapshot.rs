@@ -0,0 +1,186 @@
+//! Exports and imports snapshots of the state keeper state.
+//!
+//! Exported snapshot contains the last committed state, so it can be used to check
+//! the state of another environment, to restore the account tree cache without
+//! rebuilding the tree, or to initialize the empty database with the snapshot state.
+
+use std::path::PathBuf;
+
+use anyhow::ensure;
+use structopt::StructOpt;
+use zksync_core::state_keeper::{snapshot::StateSnapshot, ZkSyncStateInitParams};
+use zksync_crypto::convert::FeConvert;
+use zksync_storage::{ConnectionPool, StorageProcessor};
+use zksync_types::{block::Block, AccountId, AccountUpdate};
+
+#[derive(StructOpt)]
+enum Command {
+    /// Writes the snapshot of the last committed state to the file
+    Export {
+        #[structopt(long, parse(from_os_str))]
+        output: PathBuf,
+    },
+    /// Verifies the snapshot and stores it to the database.
+    /// If the database already contains the snapshot block, its root hash must match the snapshot
+    /// and only the account tree cache is stored. Otherwise, the database must be empty
+    Import {
+        #[structopt(long, parse(from_os_str))]
+        input: PathBuf,
+    },
+}
+
+#[derive(StructOpt)]
+#[structopt(name = "zkSync state snapshot tool", author = "Matter Labs")]
+struct Opt {
+    #[structopt(subcommand)]
+    command: Command,
+}
+
+async fn export(connection_pool: &ConnectionPool, output: PathBuf) -> anyhow::Result<()> {
+    let mut storage = connection_pool.access_storage().await?;
+    let init_params = ZkSyncStateInitParams::restore_from_db(&mut storage).await?;
+
+    let snapshot = StateSnapshot::new(&init_params);
+    std::fs::write(&output, snapshot.encode()?)?;
+
+    log::info!(
+        "Exported snapshot of the block #{} ({} accounts, root hash {}) to {}",
+        snapshot.last_block_number,
+        snapshot.accounts.len(),
+        snapshot.root_hash.to_hex(),
+        output.display()
+    );
+    Ok(())
+}
+
+async fn import(connection_pool: &ConnectionPool, input: PathBuf) -> anyhow::Result<()> {
+    let snapshot = StateSnapshot::decode(&std::fs::read(&input)?)?;
+    snapshot.verify()?;
+    let account_updates = snapshot.account_updates();
+    let init_params = snapshot.restore()?;
+    let block_number = init_params.last_block_number;
+    let root_hash = init_params.tree.root_hash();
+
+    let mut storage = connection_pool.access_storage().await?;
+    let mut transaction = storage.start_transaction().await?;
+    let block = transaction
+        .chain()
+        .block_schema()
+        .get_block(block_number)
+        .await?;
+    match block {
+        Some(block) => {
+            ensure!(
+                block.new_root_hash == root_hash,
+                "Snapshot root hash {} doesn't match the stored root hash {} of the block #{}",
+                root_hash.to_hex(),
+                block.new_root_hash.to_hex(),
+                block_number
+            );
+            store_tree_cache(&mut transaction, &init_params).await?;
+        }
+        None => {
+            store_snapshot_state(&mut transaction, &init_params, &account_updates).await?;
+
+            // Check that the state keeper will observe exactly the snapshot state.
+            let restored = ZkSyncStateInitParams::restore_from_db(&mut transaction).await?;
+            ensure!(
+                restored.last_block_number == block_number
+                    && restored.tree.root_hash() == root_hash,
+                "Restored state of the block #{} (root hash {}) doesn't match the snapshot",
+                restored.last_block_number,
+                restored.tree.root_hash().to_hex()
+            );
+        }
+    }
+    transaction.commit().await?;
+
+    log::info!(
+        "Imported snapshot of the block #{} (root hash {})",
+        block_number,
+        root_hash.to_hex()
+    );
+    Ok(())
+}
+
+/// Stores the account tree cache of the snapshot block, unless it's already stored.
+async fn store_tree_cache(
+    storage: &mut StorageProcessor<'_>,
+    init_params: &ZkSyncStateInitParams,
+) -> anyhow::Result<()> {
+    let block_number = init_params.last_block_number;
+    let tree_cache_exists = storage
+        .chain()
+        .block_schema()
+        .get_account_tree_cache_block(block_number)
+        .await?
+        .is_some();
+    if !tree_cache_exists {
+        storage
+            .chain()
+            .block_schema()
+            .store_account_tree_cache(
+                block_number,
+                serde_json::to_value(init_params.tree.get_internals())?,
+            )
+            .await?;
+    }
+    Ok(())
+}
+
+/// Stores the snapshot state along with the snapshot block to the empty database.
+async fn store_snapshot_state(
+    storage: &mut StorageProcessor<'_>,
+    init_params: &ZkSyncStateInitParams,
+    account_updates: &[(AccountId, AccountUpdate)],
+) -> anyhow::Result<()> {
+    let (last_block_number, accounts) = storage
+        .chain()
+        .state_schema()
+        .load_committed_state(None)
+        .await?;
+    ensure!(
+        last_block_number == 0 && accounts.is_empty(),
+        "Snapshot block #{} is not found, so the database must be empty",
+        init_params.last_block_number
+    );
+
+    // Snapshot doesn't contain the block metadata, so the block is stored without operations.
+    // Fee account is the one created in the genesis block.
+    let block = Block::new(
+        init_params.last_block_number,
+        init_params.tree.root_hash(),
+        0,
+        Vec::new(),
+        (
+            init_params.unprocessed_priority_op,
+            init_params.unprocessed_priority_op,
+        ),
+        0,
+        0.into(),
+        0.into(),
+        0,
+    );
+    storage
+        .chain()
+        .block_schema()
+        .save_snapshot_block(
+            block,
+            account_updates,
+            serde_json::to_value(init_params.tree.get_internals())?,
+        )
+        .await?;
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    env_logger::init();
+    let opt = Opt::from_args();
+    let connection_pool = ConnectionPool::new(Some(1));
+
+    match opt.command {
+        Command::Export { output } => export(&connection_pool, output).await,
+        Command::Import { input } => import(&connection_pool, input).await,
+    }
+}
@@ -4,15 +4,19 @@
 //! It does it in small batches, called here `miniblocks`, which are smaller that full blocks.
 //!
 //! Right now logic of this actor is simple, but in future consensus will replace it using the same API.
+//!
+//! Block production can be paused (e.g. during incidents), in which case no new miniblocks are
+//! proposed until it's resumed. Mempool keeps accepting the transactions in the meantime.
 
 // External deps
 use futures::{
     channel::{mpsc, oneshot},
-    SinkExt,
+    SinkExt, StreamExt,
 };
 use tokio::{task::JoinHandle, time};
 // Workspace deps
 use zksync_config::ConfigurationOptions;
+use zksync_types::block::BlockProductionMode;
 // Local deps
 use crate::{
     mempool::{GetBlockRequest, MempoolRequest, ProposedBlock},
@@ -32,8 +36,17 @@ fn create_mempool_req(
     )
 }
 
+#[derive(Debug)]
+pub enum BlockProposerRequest {
+    /// Changes the mode of the block production.
+    SetMode(BlockProductionMode),
+    /// Returns the current mode of the block production.
+    GetMode(oneshot::Sender<BlockProductionMode>),
+}
+
 struct BlockProposer {
     current_priority_op_number: u64,
+    mode: BlockProductionMode,
 
     mempool_requests: mpsc::Sender<MempoolRequest>,
     statekeeper_requests: mpsc::Sender<StateKeeperRequest>,
@@ -59,6 +72,27 @@ impl BlockProposer {
             .await
             .expect("state keeper receiver dropped");
     }
+
+    /// Proposes a new miniblock, unless the block production is paused.
+    async fn on_timer_tick(&mut self) {
+        if self.mode == BlockProductionMode::Active {
+            self.commit_new_tx_mini_batch().await;
+        }
+    }
+
+    fn handle_request(&mut self, request: BlockProposerRequest) {
+        match request {
+            BlockProposerRequest::SetMode(mode) => {
+                if self.mode != mode {
+                    log::info!("Block production mode changed to {:?}", mode);
+                }
+                self.mode = mode;
+            }
+            BlockProposerRequest::GetMode(sender) => {
+                sender.send(self.mode).unwrap_or_default();
+            }
+        }
+    }
 }
 
 // driving engine of the application
@@ -67,6 +101,7 @@ pub fn run_block_proposer_task(
     config_options: &ConfigurationOptions,
     mempool_requests: mpsc::Sender<MempoolRequest>,
     mut statekeeper_requests: mpsc::Sender<StateKeeperRequest>,
+    mut requests: mpsc::Receiver<BlockProposerRequest>,
 ) -> JoinHandle<()> {
     let miniblock_interval = config_options
         .miniblock_timings
@@ -88,14 +123,89 @@ pub fn run_block_proposer_task(
 
         let mut block_proposer = BlockProposer {
             current_priority_op_number,
+            mode: BlockProductionMode::Active,
             mempool_requests,
             statekeeper_requests,
         };
 
         loop {
-            timer.tick().await;
-
-            block_proposer.commit_new_tx_mini_batch().await;
+            tokio::select! {
+                _ = timer.tick() => {
+                    block_proposer.on_timer_tick().await;
+                }
+                Some(request) = requests.next() => {
+                    block_proposer.handle_request(request);
+                }
+            }
         }
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block_proposer() -> (
+        BlockProposer,
+        mpsc::Receiver<MempoolRequest>,
+        mpsc::Receiver<StateKeeperRequest>,
+    ) {
+        let (mempool_requests, mempool_rx) = mpsc::channel(16);
+        let (statekeeper_requests, statekeeper_rx) = mpsc::channel(16);
+        let block_proposer = BlockProposer {
+            current_priority_op_number: 0,
+            mode: BlockProductionMode::Active,
+            mempool_requests,
+            statekeeper_requests,
+        };
+
+        (block_proposer, mempool_rx, statekeeper_rx)
+    }
+
+    /// Responds with an empty block to the single mempool request.
+    fn spawn_mempool(mut mempool_rx: mpsc::Receiver<MempoolRequest>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            if let Some(MempoolRequest::GetBlock(request)) = mempool_rx.next().await {
+                let block = ProposedBlock {
+                    priority_ops: Vec::new(),
+                    txs: Vec::new(),
+                };
+                request.response_sender.send(block).unwrap_or_default();
+            }
+        })
+    }
+
+    fn get_mode(block_proposer: &mut BlockProposer) -> BlockProductionMode {
+        let (sender, mut receiver) = oneshot::channel();
+        block_proposer.handle_request(BlockProposerRequest::GetMode(sender));
+        receiver
+            .try_recv()
+            .expect("response sender dropped")
+            .expect("mode is not sent")
+    }
+
+    /// Checks that the paused block proposer doesn't propose miniblocks until it's resumed.
+    #[tokio::test]
+    async fn pause_and_resume() {
+        let (mut block_proposer, mempool_rx, mut statekeeper_rx) = block_proposer();
+        assert_eq!(get_mode(&mut block_proposer), BlockProductionMode::Active);
+
+        block_proposer.handle_request(BlockProposerRequest::SetMode(BlockProductionMode::Paused));
+        assert_eq!(get_mode(&mut block_proposer), BlockProductionMode::Paused);
+
+        // Neither mempool nor state keeper is requested, otherwise the call would block forever.
+        block_proposer.on_timer_tick().await;
+        assert!(statekeeper_rx.try_next().is_err());
+
+        block_proposer.handle_request(BlockProposerRequest::SetMode(BlockProductionMode::Active));
+        assert_eq!(get_mode(&mut block_proposer), BlockProductionMode::Active);
+
+        let mempool = spawn_mempool(mempool_rx);
+        block_proposer.on_timer_tick().await;
+        mempool.await.unwrap();
+        match statekeeper_rx.try_next() {
+            Ok(Some(StateKeeperRequest::ExecuteMiniBlock(block))) => assert!(block.is_empty()),
+            _ => panic!("Miniblock is not proposed"),
+        }
+    }
+}
@@ -138,8 +138,12 @@ async fn commit_block(
         .await
         .expect("Failed initializing a DB transaction");
 
+    let mut failed_txs = Vec::new();
     for exec_op in block.block_transactions.clone() {
         if let Some(exec_tx) = exec_op.get_executed_tx() {
+            if !exec_tx.success {
+                failed_txs.push((exec_tx.signed_tx.account(), exec_tx.signed_tx.nonce()));
+            }
             if exec_tx.success && exec_tx.signed_tx.tx.is_withdraw() {
                 transaction
                     .chain()
@@ -183,7 +187,7 @@ async fn commit_block(
         .expect("committer must commit the op into db");
 
     mempool_req_sender
-        .send(MempoolRequest::UpdateNonces(accounts_updated))
+        .send(MempoolRequest::UpdateNonces(accounts_updated, failed_txs))
         .await
         .map_err(|e| log::warn!("Failed notify mempool about account updates: {}", e))
         .unwrap_or_default();
@@ -148,6 +148,8 @@ pub async fn run_core(
     let (eth_watch_req_sender, eth_watch_req_receiver) = mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
     let (mempool_request_sender, mempool_request_receiver) =
         mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
+    let (block_proposer_req_sender, block_proposer_req_receiver) =
+        mpsc::channel(DEFAULT_CHANNEL_CAPACITY);
 
     // Start Ethereum Watcher.
     let eth_watch_task = start_eth_watch(
@@ -176,6 +178,8 @@ pub async fn run_core(
         config_opts.miniblock_timings.max_miniblock_iterations,
         config_opts.miniblock_timings.fast_miniblock_iterations,
         config_opts.max_number_of_withdrawals_per_block,
+        config_opts.block_sealing.clone(),
+        config_opts.fee_routing.clone(),
     );
     let state_keeper_task = start_state_keeper(state_keeper, pending_block);
 
@@ -199,6 +203,7 @@ pub async fn run_core(
         &config_opts,
         mempool_request_sender.clone(),
         state_keeper_req_sender.clone(),
+        block_proposer_req_receiver,
     );
 
     // Start private API.
@@ -206,6 +211,8 @@ pub async fn run_core(
         panic_notify.clone(),
         mempool_request_sender,
         eth_watch_req_sender,
+        state_keeper_req_sender,
+        block_proposer_req_sender,
         api_server_options,
     );
 
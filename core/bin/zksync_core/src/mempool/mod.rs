@@ -0,0 +1,1165 @@
+//! Mempool is simple in memory buffer for transactions.
+//!
+//! Its role is to:
+//! 1) Accept transactions from api, check signatures and basic nonce correctness(nonce not too small).
+//! To do nonce correctness check mempool stores mapping `AccountAddress -> Nonce`, this mapping is updated
+//! when new block is committed.
+//! 2) When polled return vector of the transactions in the queue.
+//! The order in which transactions are proposed is defined by the configured block packing strategy
+//! (see `BlockPackingStrategy`), while the nonce order of transactions sent by the same account,
+//! atomicity of batches and the limit of withdrawals per block are always preserved.
+//!
+//! Only the transactions which nonces follow the account nonce without gaps are "ready" to be proposed.
+//! Transactions with a nonce gap are held in the "future" queue and are moved to the "ready" one
+//! once the missing transactions arrive or the account nonce is updated. Nonce of the transaction
+//! can't be ahead of the next expected account nonce by more than the configured limit.
+//! Transactions which validity time range hasn't started yet are accepted, but aren't proposed
+//! (as well as the next transactions of the same account) until they become valid.
+//!
+//! The number of transactions stored in the mempool is limited: when the mempool is full, new transactions
+//! replace the ones paying the lowest fee, and transactions that weren't included into a block for too long
+//! are evicted. Evicted transactions are removed from the database along with the reason of eviction.
+//! Pending transaction can be replaced by another transaction with the same nonce, if the latter pays
+//! a fee which is higher by at least the configured percentage. Batches can't replace pending transactions,
+//! so a batch containing a transaction with the same nonce as a pending one is rejected.
+//!
+//! Mempool is not persisted on disc, all transactions will be lost on node shutdown.
+//!
+//! Communication channel with other actors:
+//! Mempool does not push information to other actors, only accepts requests. (see `MempoolRequest`)
+//!
+//! Communication with db:
+//! on restart mempool restores nonces of the accounts that are stored in the account tree.
+
+// Built-in deps
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+// External uses
+use chrono::{DateTime, Utc};
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt, StreamExt,
+};
+use num::{rational::Ratio, traits::Pow, BigUint, Zero};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::task::JoinHandle;
+// Workspace uses
+use zksync_storage::{ConnectionPool, QueryResult, StorageProcessor};
+use zksync_types::{
+    mempool::{SignedTxVariant, SignedTxsBatch},
+    tx::{TxEthSignature, TxHash},
+    AccountId, AccountUpdate, AccountUpdates, Address, Nonce, PriorityOp, SignedZkSyncTx,
+    TransferOp, TransferToNewOp, ZkSyncTx,
+};
+// Local uses
+use self::{
+    packing::{packing_strategy, BlockPackingStrategy},
+    queue::{fee_per_chunk, BlockLimits, QueuedTx, TxQueue},
+};
+use crate::eth_watch::EthWatchRequest;
+use zksync_config::ConfigurationOptions;
+use zksync_utils::unix_timestamp;
+
+mod packing;
+mod queue;
+
+#[cfg(test)]
+mod tests;
+
+/// Reason stored for the transactions evicted in favor of the transactions paying higher fee.
+const CAPACITY_EXCEEDED_REASON: &str = "Mempool capacity exceeded";
+/// Reason stored for the transactions replaced by the transactions with the same nonce.
+const REPLACED_REASON: &str = "Replaced by transaction with higher fee";
+/// Reason stored for the transactions cancelled by their senders.
+const CANCELLED_REASON: &str = "Cancelled by user";
+/// Reason stored for the transactions that weren't included into a block in time.
+const TX_EXPIRED_REASON: &str = "Transaction expired";
+/// Reason stored for the transactions which validity time range has ended.
+const TX_OUTDATED_REASON: &str = "Transaction validity time range has ended";
+
+/// Converts the moment the transaction was stored in the database into the moment of its
+/// insertion into the queue, so the restored transactions don't get their TTL restarted.
+fn restored_inserted_at(created_at: DateTime<Utc>) -> Instant {
+    let now = Instant::now();
+    let age = (Utc::now() - created_at).to_std().unwrap_or_default();
+    now.checked_sub(age).unwrap_or(now)
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Error)]
+pub enum TxAddError {
+    #[error("Tx nonce is too low.")]
+    NonceMismatch,
+
+    #[error("Tx is incorrect")]
+    IncorrectTx,
+
+    #[error("Transaction fee is too low")]
+    TxFeeTooLow,
+
+    #[error("Transactions batch summary fee is too low")]
+    TxBatchFeeTooLow,
+
+    #[error("EIP1271 signature could not be verified")]
+    EIP1271SignatureVerificationFail,
+
+    #[error("MissingEthSignature")]
+    MissingEthSignature,
+
+    #[error("Eth signature is incorrect")]
+    IncorrectEthSignature,
+
+    #[error("Change pubkey tx is not authorized onchain")]
+    ChangePkNotAuthorized,
+
+    #[error("Internal error")]
+    Other,
+
+    #[error("Database unavailable")]
+    DbError,
+
+    #[error("Transaction batch is empty")]
+    EmptyBatch,
+
+    #[error("Batch will not fit in any of supported block sizes")]
+    BatchTooBig,
+
+    #[error("The number of withdrawals in the batch is too big")]
+    BatchWithdrawalsOverload,
+
+    #[error("Mempool is full and tx fee is too low to replace any of pending txs")]
+    MempoolIsFull,
+
+    #[error("Too many pending txs for the account")]
+    TooManyPendingTxs,
+
+    #[error("Fee is too low to replace the pending tx with the same nonce")]
+    ReplacementFeeTooLow,
+
+    #[error("Pending tx with the same nonce is a part of batch and cannot be replaced")]
+    BatchTxReplacement,
+
+    #[error("Batch tx has the same nonce as a pending tx")]
+    BatchNonceConflict,
+
+    #[error("Tx nonce is too far ahead of the account nonce")]
+    NonceTooHigh,
+
+    #[error("Tx can't be executed at the current time")]
+    InvalidTimeRange,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Error)]
+pub enum TxCancelError {
+    #[error("Tx is not pending in the mempool")]
+    NotPending,
+
+    #[error("Tx is a part of batch and cannot be cancelled")]
+    BatchTx,
+
+    #[error("Cancel request signature is incorrect")]
+    IncorrectSignature,
+
+    #[error("Database unavailable")]
+    DbError,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ProposedBlock {
+    pub priority_ops: Vec<PriorityOp>,
+    pub txs: Vec<SignedTxVariant>,
+}
+
+impl ProposedBlock {
+    pub fn is_empty(&self) -> bool {
+        self.priority_ops.is_empty() && self.txs.is_empty()
+    }
+}
+
+#[derive(Debug)]
+pub struct GetBlockRequest {
+    pub last_priority_op_number: u64,
+    pub response_sender: oneshot::Sender<ProposedBlock>,
+}
+
+#[derive(Debug)]
+pub enum MempoolRequest {
+    /// Add new transaction to mempool, transaction should be previously checked
+    /// for correctness (including its Ethereum and ZKSync signatures).
+    /// oneshot is used to receive tx add result.
+    NewTx(Box<SignedZkSyncTx>, oneshot::Sender<Result<(), TxAddError>>),
+    /// Add a new batch of transactions to the mempool. All transactions in batch must
+    /// be either executed successfully, or otherwise fail all together.
+    /// Invariants for each individual transaction in the batch are the same as in
+    /// `NewTx` variant of this enum.
+    NewTxsBatch(
+        Vec<SignedZkSyncTx>,
+        Option<TxEthSignature>,
+        oneshot::Sender<Result<(), TxAddError>>,
+    ),
+    /// Remove pending transaction from the mempool, request should be previously checked
+    /// for correctness (including the signature of the transaction sender).
+    /// Transactions already proposed for the block can't be cancelled.
+    CancelTx(TxHash, oneshot::Sender<Result<(), TxCancelError>>),
+    /// When block is committed, nonces of the account tree should be updated too.
+    /// Account and nonce of every transaction failed in the block are provided as well,
+    /// since these nonces were not used.
+    UpdateNonces(AccountUpdates, Vec<(Address, Nonce)>),
+    /// Get transactions from the mempool.
+    GetBlock(GetBlockRequest),
+}
+
+struct MempoolState {
+    // account and last committed nonce
+    account_nonces: HashMap<Address, Nonce>,
+    account_ids: HashMap<AccountId, Address>,
+    // account and nonce following the transactions proposed for the blocks that aren't committed yet
+    proposed_nonces: HashMap<Address, Nonce>,
+    /// Transactions that can be proposed for the block: nonces of the account transactions
+    /// follow the account nonce without gaps.
+    ready_txs: TxQueue,
+    /// Transactions waiting for the account transactions with lower nonces.
+    future_txs: TxQueue,
+}
+
+impl MempoolState {
+    fn chunks_for_tx(&self, tx: &ZkSyncTx) -> usize {
+        match tx {
+            ZkSyncTx::Transfer(tx) => {
+                if self.account_nonces.contains_key(&tx.to) {
+                    TransferOp::CHUNKS
+                } else {
+                    TransferToNewOp::CHUNKS
+                }
+            }
+            _ => tx.min_chunks(),
+        }
+    }
+
+    fn chunks_for_batch(&self, batch: &SignedTxsBatch) -> usize {
+        batch.txs.iter().map(|tx| self.chunks_for_tx(&tx.tx)).sum()
+    }
+
+    fn required_chunks(&self, element: &SignedTxVariant) -> usize {
+        match element {
+            SignedTxVariant::Tx(tx) => self.chunks_for_tx(&tx.tx),
+            SignedTxVariant::Batch(batch) => self.chunks_for_batch(batch),
+        }
+    }
+
+    async fn restore_from_db(db_pool: &ConnectionPool, tx_ttl: Duration) -> Self {
+        let mut storage = db_pool.access_storage().await.expect("mempool db restore");
+        let mut transaction = storage
+            .start_transaction()
+            .await
+            .expect("mempool db transaction");
+
+        let (_, accounts) = transaction
+            .chain()
+            .state_schema()
+            .load_committed_state(None)
+            .await
+            .expect("mempool account state load");
+
+        let mut account_ids = HashMap::new();
+        let mut account_nonces = HashMap::new();
+
+        for (id, account) in accounts {
+            account_ids.insert(id, account.address);
+            account_nonces.insert(account.address, account.nonce);
+        }
+
+        // Remove any possible duplicates of already executed transactions
+        // from the database.
+        transaction
+            .chain()
+            .mempool_schema()
+            .collect_garbage()
+            .await
+            .expect("Collecting garbage in the mempool schema failed");
+
+        // Evict transactions that have expired while the server was down.
+        let deadline =
+            Utc::now() - chrono::Duration::from_std(tx_ttl).expect("Incorrect mempool tx TTL");
+        let expired_txs = transaction
+            .chain()
+            .mempool_schema()
+            .evict_expired_txs(deadline, TX_EXPIRED_REASON)
+            .await
+            .expect("Evicting expired txs from the mempool schema failed");
+        if !expired_txs.is_empty() {
+            log::info!(
+                "{} expired transactions were evicted from the persistent mempool storage",
+                expired_txs.len()
+            );
+        }
+
+        // Load transactions that were not yet processed and are awaiting in the
+        // mempool. They are pushed in the order of arrival, so their age in the
+        // queues keeps counting from the moment they were received.
+        let mut stored_txs: Vec<_> = transaction
+            .chain()
+            .mempool_schema()
+            .load_txs_with_creation_time()
+            .await
+            .expect("Attempt to restore mempool txs from DB failed")
+            .into();
+        stored_txs.sort_by_key(|(_, created_at)| *created_at);
+
+        let mut state = Self {
+            account_nonces,
+            account_ids,
+            proposed_nonces: HashMap::new(),
+            ready_txs: TxQueue::new(),
+            future_txs: TxQueue::new(),
+        };
+        for (tx, created_at) in stored_txs {
+            let fee_value = fee_value(&mut transaction, tx.txs())
+                .await
+                .expect("Attempt to load token prices for mempool txs failed");
+            state.push_at(tx, fee_value, restored_inserted_at(created_at));
+        }
+
+        transaction
+            .commit()
+            .await
+            .expect("mempool db transaction commit");
+
+        log::info!(
+            "{} transactions were restored from the persistent mempool storage, {} of them are ready",
+            state.len(),
+            state.ready_txs.len()
+        );
+
+        state
+    }
+
+    /// Returns the number of elements in both ready and future queues.
+    fn len(&self) -> usize {
+        self.ready_txs.len() + self.future_txs.len()
+    }
+
+    fn nonce(&self, address: &Address) -> Nonce {
+        *self.account_nonces.get(address).unwrap_or(&0)
+    }
+
+    /// Returns the nonce of the account transaction that can be executed after the committed
+    /// and proposed account transactions, as well as the ready ones.
+    fn next_nonce(&self, address: &Address) -> Nonce {
+        let mut nonce = self.unused_nonce(address);
+        if let Some(ready_nonce) = self.ready_txs.next_nonce(address) {
+            nonce = nonce.max(ready_nonce);
+        }
+        nonce
+    }
+
+    /// Checks whether the transactions can be executed right after the ready ones.
+    fn is_ready(&self, txs: &[SignedZkSyncTx]) -> bool {
+        let mut next_nonces = HashMap::new();
+        txs.iter().all(|tx| {
+            let address = tx.account();
+            let next_nonce = next_nonces
+                .entry(address)
+                .or_insert_with(|| self.next_nonce(&address));
+            if tx.nonce() > *next_nonce {
+                return false;
+            }
+            *next_nonce = (*next_nonce).max(tx.nonce() + 1);
+            true
+        })
+    }
+
+    /// Returns the number of the account transactions stored in both queues.
+    fn account_txs_count(&self, address: &Address) -> usize {
+        self.ready_txs.account_txs_count(address) + self.future_txs.account_txs_count(address)
+    }
+
+    /// Returns the element containing the account transaction with the given nonce.
+    fn find(&self, address: &Address, nonce: Nonce) -> Option<&QueuedTx> {
+        self.ready_txs
+            .find(address, nonce)
+            .or_else(|| self.future_txs.find(address, nonce))
+    }
+
+    /// Returns the element containing the transaction with the given hash.
+    fn find_by_hash(&self, tx_hash: TxHash) -> Option<&QueuedTx> {
+        self.ready_txs
+            .find_by_hash(tx_hash)
+            .or_else(|| self.future_txs.find_by_hash(tx_hash))
+    }
+
+    /// Adds a new element to the ready queue, or to the future one if it has a nonce gap.
+    fn push(&mut self, variant: SignedTxVariant, fee_value: Ratio<BigUint>) {
+        self.push_at(variant, fee_value, Instant::now());
+    }
+
+    /// Same as `push`, but the element is considered inserted at the given moment.
+    fn push_at(
+        &mut self,
+        variant: SignedTxVariant,
+        fee_value: Ratio<BigUint>,
+        inserted_at: Instant,
+    ) {
+        if self.is_ready(variant.txs()) {
+            let accounts = variant.txs().iter().map(|tx| tx.account()).collect();
+            self.ready_txs.push_at(variant, fee_value, inserted_at);
+            self.promote(accounts);
+        } else {
+            self.future_txs.push_at(variant, fee_value, inserted_at);
+        }
+    }
+
+    /// Moves the elements that became ready from the future queue to the ready one.
+    /// Only the elements containing transactions of the given accounts are checked.
+    fn promote(&mut self, mut accounts: Vec<Address>) {
+        while let Some(address) = accounts.pop() {
+            let seq = match self.future_txs.first_account_tx(&address) {
+                Some(tx) if self.is_ready(tx.variant.txs()) => tx.seq(),
+                _ => continue,
+            };
+
+            let tx = self
+                .future_txs
+                .remove(seq)
+                .expect("Promoted element must be in the queue");
+            // Promoted element may fill the nonce gaps of all its accounts.
+            accounts.extend(tx.nonces().into_iter().map(|(address, _)| address));
+            self.ready_txs.insert(tx);
+            metrics::counter!("mempool.promoted_txs", 1);
+        }
+    }
+
+    /// Moves the ready elements that follow the given account transactions back to the future queue,
+    /// since the removal of these transactions creates nonce gaps.
+    fn demote(&mut self, mut removed: Vec<(Address, Nonce)>) {
+        while let Some((address, nonce)) = removed.pop() {
+            for seq in self.ready_txs.account_txs_after(&address, nonce) {
+                if let Some(tx) = self.ready_txs.remove(seq) {
+                    removed.extend(tx.nonces());
+                    self.future_txs.insert(tx);
+                }
+            }
+        }
+    }
+
+    /// Removes the element with the given sequential number from the queues.
+    fn remove(&mut self, seq: u64) -> Option<QueuedTx> {
+        if let Some(tx) = self.future_txs.remove(seq) {
+            return Some(tx);
+        }
+
+        let tx = self.ready_txs.remove(seq)?;
+        self.demote(tx.nonces());
+        Some(tx)
+    }
+
+    /// Removes from the queues all the elements that were added more than `ttl` ago.
+    fn remove_expired(&mut self, ttl: Duration) -> Vec<SignedTxVariant> {
+        let mut expired = self.future_txs.remove_expired(ttl);
+        let expired_ready = self.ready_txs.remove_expired(ttl);
+        self.demote(
+            expired_ready
+                .iter()
+                .flat_map(|tx| tx.txs())
+                .map(|tx| (tx.account(), tx.nonce()))
+                .collect(),
+        );
+        expired.extend(expired_ready);
+        expired
+    }
+
+    /// Removes from the queues all the elements which can't be executed at the `timestamp`
+    /// or later because of the time range of their transactions.
+    fn remove_outdated(&mut self, timestamp: u64) -> Vec<SignedTxVariant> {
+        let mut outdated = self.future_txs.remove_outdated(timestamp);
+        let outdated_ready = self.ready_txs.remove_outdated(timestamp);
+        self.demote(
+            outdated_ready
+                .iter()
+                .flat_map(|tx| tx.txs())
+                .map(|tx| (tx.account(), tx.nonce()))
+                .collect(),
+        );
+        outdated.extend(outdated_ready);
+        outdated
+    }
+
+    /// Updates the account nonces with the changes of the committed block and promotes
+    /// the transactions which nonce gaps were closed.
+    ///
+    /// Nonces of the transactions failed in the block were not used, so the proposed nonces
+    /// of their accounts are rolled back, and the ready transactions that relied on them
+    /// are moved back to the future queue.
+    fn update_nonces(&mut self, updates: AccountUpdates, failed_txs: Vec<(Address, Nonce)>) {
+        let mut updated_accounts = Vec::new();
+        for (id, update) in updates {
+            match update {
+                AccountUpdate::Create { address, nonce } => {
+                    self.account_ids.insert(id, address);
+                    self.account_nonces.insert(address, nonce);
+                    updated_accounts.push(address);
+                }
+                AccountUpdate::Delete { address, .. } => {
+                    self.account_ids.remove(&id);
+                    self.account_nonces.remove(&address);
+                    self.proposed_nonces.remove(&address);
+                }
+                AccountUpdate::UpdateBalance { new_nonce, .. }
+                | AccountUpdate::ChangePubKeyHash { new_nonce, .. } => {
+                    if let Some(address) = self.account_ids.get(&id) {
+                        if let Some(nonce) = self.account_nonces.get_mut(address) {
+                            *nonce = new_nonce;
+                            updated_accounts.push(*address);
+                        }
+                    }
+                }
+            }
+        }
+
+        for (address, nonce) in failed_txs {
+            if let Some(proposed_nonce) = self.proposed_nonces.get_mut(&address) {
+                if *proposed_nonce > nonce {
+                    *proposed_nonce = nonce;
+                    self.demote(vec![(address, nonce)]);
+                }
+                updated_accounts.push(address);
+            }
+        }
+
+        // Proposed transactions are committed, so there is no need to track their nonces anymore.
+        for address in &updated_accounts {
+            let committed_nonce = self.nonce(address);
+            if let Some(&proposed_nonce) = self.proposed_nonces.get(address) {
+                if proposed_nonce <= committed_nonce {
+                    self.proposed_nonces.remove(address);
+                }
+            }
+        }
+
+        self.promote(updated_accounts);
+    }
+
+    /// Removes the element chosen by `Mempool::select_evicted` from the queue.
+    /// Its transactions must be already evicted from the database.
+    fn remove_evicted(&mut self, evicted: Option<(u64, Vec<TxHash>)>) {
+        if let Some((seq, hashes)) = evicted {
+            self.remove(seq);
+            log::debug!("Txs evicted from the full mempool: {:?}", hashes);
+            metrics::counter!("mempool.evicted_txs", hashes.len() as u64);
+        }
+    }
+
+    /// Returns the lowest nonce which isn't used by the committed or proposed account transactions.
+    fn unused_nonce(&self, address: &Address) -> Nonce {
+        let nonce = self.nonce(address);
+        match self.proposed_nonces.get(address) {
+            Some(&proposed_nonce) => nonce.max(proposed_nonce),
+            None => nonce,
+        }
+    }
+
+    /// Checks that the nonces of the transactions aren't used by the committed transactions
+    /// or the ones proposed for the not yet committed block.
+    fn check_nonces(&self, txs: &[SignedZkSyncTx]) -> Result<(), TxAddError> {
+        for tx in txs {
+            if tx.nonce() < self.unused_nonce(&tx.account()) {
+                return Err(TxAddError::NonceMismatch);
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that none of the batch transactions has the same account and nonce as a pending one.
+    fn check_batch_conflicts(&self, txs: &[SignedZkSyncTx]) -> Result<(), TxAddError> {
+        if txs
+            .iter()
+            .any(|tx| self.find(&tx.account(), tx.nonce()).is_some())
+        {
+            return Err(TxAddError::BatchNonceConflict);
+        }
+        Ok(())
+    }
+
+    /// Checks that the nonces of the transactions are not too far ahead of the expected account nonces.
+    fn check_nonce_gap(
+        &self,
+        txs: &[SignedZkSyncTx],
+        max_nonce_gap: Nonce,
+    ) -> Result<(), TxAddError> {
+        for tx in txs {
+            let max_nonce = self.next_nonce(&tx.account()).saturating_add(max_nonce_gap);
+            if tx.nonce() > max_nonce {
+                return Err(TxAddError::NonceTooHigh);
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that the validity time range of the transactions hasn't ended by the `timestamp`.
+    fn check_time_range(txs: &[SignedZkSyncTx], timestamp: u64) -> Result<(), TxAddError> {
+        if txs.iter().any(|tx| tx.time_range().is_expired(timestamp)) {
+            return Err(TxAddError::InvalidTimeRange);
+        }
+        Ok(())
+    }
+
+    fn add_tx(&mut self, tx: SignedZkSyncTx, fee_value: Ratio<BigUint>) -> Result<(), TxAddError> {
+        // Correctness should be checked by `signature_checker`, thus
+        // `tx.check_correctness()` is not invoked here.
+
+        self.check_nonces(std::slice::from_ref(&tx))?;
+        self.push(tx.into(), fee_value);
+        Ok(())
+    }
+
+    fn add_batch(
+        &mut self,
+        batch: SignedTxsBatch,
+        fee_value: Ratio<BigUint>,
+    ) -> Result<(), TxAddError> {
+        assert_ne!(batch.batch_id, 0, "Batch ID was not set");
+
+        self.check_nonces(&batch.txs)?;
+
+        self.push(SignedTxVariant::Batch(batch), fee_value);
+
+        Ok(())
+    }
+}
+
+struct Mempool {
+    db_pool: ConnectionPool,
+    mempool_state: MempoolState,
+    requests: mpsc::Receiver<MempoolRequest>,
+    eth_watch_req: mpsc::Sender<EthWatchRequest>,
+    max_block_size_chunks: usize,
+    max_number_of_withdrawals_per_block: usize,
+    capacity: usize,
+    max_txs_per_account: usize,
+    tx_ttl: Duration,
+    replacement_fee_bump_percent: u32,
+    max_nonce_gap: Nonce,
+    packing_strategy: Box<dyn BlockPackingStrategy>,
+}
+
+impl Mempool {
+    /// Looks for the pending transaction with the same account and nonce as the new one.
+    /// Returns the sequential number of its queue element along with the transaction itself.
+    fn find_replaced(
+        &self,
+        tx: &SignedZkSyncTx,
+    ) -> Result<Option<(u64, SignedZkSyncTx, Ratio<BigUint>)>, TxAddError> {
+        let queued = match self.mempool_state.find(&tx.account(), tx.nonce()) {
+            Some(queued) => queued,
+            None => return Ok(None),
+        };
+
+        match &queued.variant {
+            SignedTxVariant::Tx(old_tx) => Ok(Some((
+                queued.seq(),
+                old_tx.clone(),
+                queued.fee_value.clone(),
+            ))),
+            SignedTxVariant::Batch(_) => Err(TxAddError::BatchTxReplacement),
+        }
+    }
+
+    /// Checks that the new transaction pays enough to replace the pending one.
+    /// Fees paid in the same token are compared directly, otherwise their USD values are compared.
+    fn check_replacement_fee(
+        &self,
+        old_tx: &SignedZkSyncTx,
+        old_fee_value: &Ratio<BigUint>,
+        new_tx: &SignedZkSyncTx,
+        new_fee_value: &Ratio<BigUint>,
+    ) -> Result<(), TxAddError> {
+        let (old_fee, new_fee) = match (old_tx.get_fee_info(), new_tx.get_fee_info()) {
+            (Some((_, old_token, _, old_fee)), Some((_, new_token, _, new_fee)))
+                if old_token == new_token =>
+            {
+                (Ratio::from_integer(old_fee), Ratio::from_integer(new_fee))
+            }
+            _ => (old_fee_value.clone(), new_fee_value.clone()),
+        };
+
+        let min_fee = old_fee.clone()
+            * Ratio::new(
+                BigUint::from(100 + self.replacement_fee_bump_percent),
+                BigUint::from(100u32),
+            );
+        if new_fee > old_fee && new_fee >= min_fee {
+            Ok(())
+        } else {
+            Err(TxAddError::ReplacementFeeTooLow)
+        }
+    }
+
+    /// Checks that adding the transactions won't exceed the limit of pending transactions per account.
+    fn check_pending_txs_limit(&self, txs: &[SignedZkSyncTx]) -> Result<(), TxAddError> {
+        let mut new_txs: HashMap<Address, usize> = HashMap::new();
+        for tx in txs {
+            *new_txs.entry(tx.account()).or_default() += 1;
+        }
+
+        for (address, count) in new_txs {
+            let pending_txs = self.mempool_state.account_txs_count(&address);
+            if pending_txs + count > self.max_txs_per_account {
+                return Err(TxAddError::TooManyPendingTxs);
+            }
+        }
+        Ok(())
+    }
+
+    /// If the mempool is full, chooses the element to be evicted in favor of the new one.
+    /// Returns the sequential number of the evicted element along with the hashes of its transactions,
+    /// or an error if all the pending elements pay more than the new one.
+    fn select_evicted(
+        &self,
+        txs: &[SignedZkSyncTx],
+        required_chunks: usize,
+        fee_value: &Ratio<BigUint>,
+    ) -> Result<Option<(u64, Vec<TxHash>)>, TxAddError> {
+        if self.mempool_state.len() < self.capacity {
+            return Ok(None);
+        }
+
+        let new_fee = fee_per_chunk(fee_value, required_chunks);
+        let new_accounts: Vec<_> = txs.iter().map(|tx| tx.account()).collect();
+        let is_ready = self.mempool_state.is_ready(txs);
+        let element_chunks = |tx: &SignedTxVariant| self.mempool_state.required_chunks(tx);
+
+        // Transactions with nonce gaps are evicted first, since they can't be executed anyway.
+        let future_candidate = self
+            .mempool_state
+            .future_txs
+            .eviction_candidate(&new_accounts, element_chunks)
+            .filter(|(_, fee)| is_ready || *fee < new_fee);
+        let candidate = future_candidate.or_else(|| {
+            self.mempool_state
+                .ready_txs
+                .eviction_candidate(&new_accounts, element_chunks)
+                .filter(|(_, fee)| *fee < new_fee)
+        });
+
+        match candidate {
+            Some((tx, _)) => Ok(Some((tx.seq(), tx.variant.hashes()))),
+            None => Err(TxAddError::MempoolIsFull),
+        }
+    }
+
+    /// Removes the transactions that weren't included into a block in time, and the ones
+    /// which validity time range has ended.
+    async fn evict_expired_txs(&mut self) {
+        let expired_txs = self.mempool_state.remove_expired(self.tx_ttl);
+        if !expired_txs.is_empty() {
+            let hashes: Vec<_> = expired_txs.iter().flat_map(|tx| tx.hashes()).collect();
+            log::debug!("Expired txs evicted from the mempool: {:?}", hashes);
+            metrics::counter!("mempool.expired_txs", hashes.len() as u64);
+            self.evict_from_storage(&hashes, TX_EXPIRED_REASON).await;
+        }
+
+        let outdated_txs = self.mempool_state.remove_outdated(unix_timestamp());
+        if !outdated_txs.is_empty() {
+            let hashes: Vec<_> = outdated_txs.iter().flat_map(|tx| tx.hashes()).collect();
+            log::debug!("Outdated txs evicted from the mempool: {:?}", hashes);
+            metrics::counter!("mempool.outdated_txs", hashes.len() as u64);
+            self.evict_from_storage(&hashes, TX_OUTDATED_REASON).await;
+        }
+    }
+
+    /// Removes the transactions evicted from the mempool queues from the database.
+    async fn evict_from_storage(&mut self, hashes: &[TxHash], reason: &str) {
+        // Failing to remove the transactions from the database is not critical,
+        // they will be evicted on the next restore of the mempool.
+        let result = match self.db_pool.access_storage().await {
+            Ok(mut storage) => {
+                storage
+                    .chain()
+                    .mempool_schema()
+                    .evict_txs(hashes, reason)
+                    .await
+            }
+            Err(err) => Err(err.into()),
+        };
+        if let Err(err) = result {
+            log::warn!("Failed to evict txs from the mempool storage: {}", err);
+        }
+    }
+
+    async fn add_tx(&mut self, tx: SignedZkSyncTx) -> Result<(), TxAddError> {
+        MempoolState::check_time_range(std::slice::from_ref(&tx), unix_timestamp())?;
+        self.mempool_state.check_nonces(std::slice::from_ref(&tx))?;
+        self.mempool_state
+            .check_nonce_gap(std::slice::from_ref(&tx), self.max_nonce_gap)?;
+        let replaced = self.find_replaced(&tx)?;
+        if replaced.is_none() {
+            self.check_pending_txs_limit(std::slice::from_ref(&tx))?;
+        }
+
+        let mut storage = self.db_pool.access_storage().await.map_err(|err| {
+            log::warn!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+
+        let mut transaction = storage.start_transaction().await.map_err(|err| {
+            log::warn!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+        let fee_value = fee_value(&mut transaction, std::slice::from_ref(&tx))
+            .await
+            .map_err(|err| {
+                log::warn!("Mempool storage access error: {}", err);
+                TxAddError::DbError
+            })?;
+        // Replacement doesn't increase the size of the mempool, so nothing has to be evicted.
+        let evicted = match &replaced {
+            Some((_, old_tx, old_fee_value)) => {
+                self.check_replacement_fee(old_tx, old_fee_value, &tx, &fee_value)?;
+                transaction
+                    .chain()
+                    .mempool_schema()
+                    .replace_tx(old_tx.hash(), tx.hash(), REPLACED_REASON)
+                    .await
+                    .map_err(|err| {
+                        log::warn!("Mempool storage access error: {}", err);
+                        TxAddError::DbError
+                    })?;
+                None
+            }
+            None => self.select_evicted(
+                std::slice::from_ref(&tx),
+                self.mempool_state.chunks_for_tx(&tx.tx),
+                &fee_value,
+            )?,
+        };
+        if let Some((_, hashes)) = &evicted {
+            transaction
+                .chain()
+                .mempool_schema()
+                .evict_txs(hashes, CAPACITY_EXCEEDED_REASON)
+                .await
+                .map_err(|err| {
+                    log::warn!("Mempool storage access error: {}", err);
+                    TxAddError::DbError
+                })?;
+        }
+        transaction
+            .chain()
+            .mempool_schema()
+            .insert_tx(&tx)
+            .await
+            .map_err(|err| {
+                log::warn!("Mempool storage access error: {}", err);
+                TxAddError::DbError
+            })?;
+
+        transaction.commit().await.map_err(|err| {
+            log::warn!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+
+        if let Some((seq, old_tx, _)) = replaced {
+            self.mempool_state.remove(seq);
+            log::debug!(
+                "Tx {} was replaced by {}",
+                old_tx.hash().to_string(),
+                tx.hash().to_string()
+            );
+            metrics::counter!("mempool.replaced_txs", 1);
+        }
+        self.mempool_state.remove_evicted(evicted);
+        self.mempool_state.add_tx(tx, fee_value)
+    }
+
+    async fn add_batch(
+        &mut self,
+        txs: Vec<SignedZkSyncTx>,
+        eth_signature: Option<TxEthSignature>,
+    ) -> Result<(), TxAddError> {
+        MempoolState::check_time_range(&txs, unix_timestamp())?;
+
+        let mut storage = self.db_pool.access_storage().await.map_err(|err| {
+            log::warn!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+
+        let mut batch: SignedTxsBatch = SignedTxsBatch {
+            txs: txs.clone(),
+            batch_id: 0, // Will be determined after inserting to the database
+            eth_signature: eth_signature.clone(),
+        };
+
+        if self.mempool_state.chunks_for_batch(&batch) > self.max_block_size_chunks {
+            return Err(TxAddError::BatchTooBig);
+        }
+
+        let mut number_of_withdrawals = 0;
+        for tx in txs {
+            if tx.tx.is_withdraw() {
+                number_of_withdrawals += 1;
+            }
+        }
+        if number_of_withdrawals > self.max_number_of_withdrawals_per_block {
+            return Err(TxAddError::BatchWithdrawalsOverload);
+        }
+
+        self.mempool_state.check_nonces(&batch.txs)?;
+        self.mempool_state.check_batch_conflicts(&batch.txs)?;
+        self.mempool_state
+            .check_nonce_gap(&batch.txs, self.max_nonce_gap)?;
+        self.check_pending_txs_limit(&batch.txs)?;
+
+        let mut transaction = storage.start_transaction().await.map_err(|err| {
+            log::warn!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+        let fee_value = fee_value(&mut transaction, &batch.txs)
+            .await
+            .map_err(|err| {
+                log::warn!("Mempool storage access error: {}", err);
+                TxAddError::DbError
+            })?;
+        let evicted = self.select_evicted(
+            &batch.txs,
+            self.mempool_state.chunks_for_batch(&batch),
+            &fee_value,
+        )?;
+        if let Some((_, hashes)) = &evicted {
+            transaction
+                .chain()
+                .mempool_schema()
+                .evict_txs(hashes, CAPACITY_EXCEEDED_REASON)
+                .await
+                .map_err(|err| {
+                    log::warn!("Mempool storage access error: {}", err);
+                    TxAddError::DbError
+                })?;
+        }
+        let batch_id = transaction
+            .chain()
+            .mempool_schema()
+            .insert_batch(&batch.txs, eth_signature)
+            .await
+            .map_err(|err| {
+                log::warn!("Mempool storage access error: {}", err);
+                TxAddError::DbError
+            })?;
+        transaction.commit().await.map_err(|err| {
+            log::warn!("Mempool storage access error: {}", err);
+            TxAddError::DbError
+        })?;
+
+        batch.batch_id = batch_id;
+
+        self.mempool_state.remove_evicted(evicted);
+        self.mempool_state.add_batch(batch, fee_value)
+    }
+
+    async fn cancel_tx(&mut self, tx_hash: TxHash) -> Result<(), TxCancelError> {
+        // Transactions proposed for the block are removed from the queue, so they can't be found.
+        let queued = self
+            .mempool_state
+            .find_by_hash(tx_hash)
+            .ok_or(TxCancelError::NotPending)?;
+        if let SignedTxVariant::Batch(_) = queued.variant {
+            return Err(TxCancelError::BatchTx);
+        }
+        let seq = queued.seq();
+
+        let mut storage = self.db_pool.access_storage().await.map_err(|err| {
+            log::warn!("Mempool storage access error: {}", err);
+            TxCancelError::DbError
+        })?;
+        storage
+            .chain()
+            .mempool_schema()
+            .evict_txs(&[tx_hash], CANCELLED_REASON)
+            .await
+            .map_err(|err| {
+                log::warn!("Mempool storage access error: {}", err);
+                TxCancelError::DbError
+            })?;
+
+        self.mempool_state.remove(seq);
+        metrics::counter!("mempool.cancelled_txs", 1);
+        Ok(())
+    }
+
+    async fn run(mut self) {
+        while let Some(request) = self.requests.next().await {
+            match request {
+                MempoolRequest::NewTx(tx, resp) => {
+                    let tx_add_result = self.add_tx(*tx).await;
+                    resp.send(tx_add_result).unwrap_or_default();
+                }
+                MempoolRequest::NewTxsBatch(txs, eth_signature, resp) => {
+                    let tx_add_result = self.add_batch(txs, eth_signature).await;
+                    resp.send(tx_add_result).unwrap_or_default();
+                }
+                MempoolRequest::CancelTx(tx_hash, resp) => {
+                    let tx_cancel_result = self.cancel_tx(tx_hash).await;
+                    resp.send(tx_cancel_result).unwrap_or_default();
+                }
+                MempoolRequest::GetBlock(block) => {
+                    // Generate proposed block.
+                    let proposed_block =
+                        self.propose_new_block(block.last_priority_op_number).await;
+
+                    // Send the proposed block to the request initiator.
+                    block
+                        .response_sender
+                        .send(proposed_block)
+                        .expect("mempool proposed block response send failed");
+                }
+                MempoolRequest::UpdateNonces(updates, failed_txs) => {
+                    self.mempool_state.update_nonces(updates, failed_txs);
+                }
+            }
+        }
+    }
+
+    async fn propose_new_block(&mut self, current_unprocessed_priority_op: u64) -> ProposedBlock {
+        let start = std::time::Instant::now();
+        self.evict_expired_txs().await;
+
+        let (chunks_left, priority_ops) = self
+            .select_priority_ops(current_unprocessed_priority_op)
+            .await;
+        let (_chunks_left, txs) = self.prepare_tx_for_block(chunks_left);
+
+        log::trace!("Proposed priority ops for block: {:#?}", priority_ops);
+        log::trace!("Proposed txs for block: {:#?}", txs);
+        metrics::histogram!("mempool.propose_new_block", start.elapsed());
+        ProposedBlock { priority_ops, txs }
+    }
+
+    /// Returns: chunks left from max amount of chunks, ops selected
+    async fn select_priority_ops(
+        &self,
+        current_unprocessed_priority_op: u64,
+    ) -> (usize, Vec<PriorityOp>) {
+        let eth_watch_resp = oneshot::channel();
+        self.eth_watch_req
+            .clone()
+            .send(EthWatchRequest::GetPriorityQueueOps {
+                op_start_id: current_unprocessed_priority_op,
+                max_chunks: self.max_block_size_chunks,
+                resp: eth_watch_resp.0,
+            })
+            .await
+            .expect("ETH watch req receiver dropped");
+
+        let priority_ops = eth_watch_resp.1.await.expect("Err response from eth watch");
+
+        (
+            self.max_block_size_chunks
+                - priority_ops
+                    .iter()
+                    .map(|op| op.data.chunks())
+                    .sum::<usize>(),
+            priority_ops,
+        )
+    }
+
+    /// Selects the set of transactions that fits into the block according to the packing strategy.
+    /// Returns: chunks left from `chunks_left`, txs selected
+    fn prepare_tx_for_block(&mut self, chunks_left: usize) -> (usize, Vec<SignedTxVariant>) {
+        if self.mempool_state.ready_txs.is_empty() {
+            return (chunks_left, Vec::new());
+        }
+
+        // Queue is taken out of the state, since the amount of chunks required for the transaction
+        // depends on the accounts known to the mempool.
+        let mut ready_txs = std::mem::take(&mut self.mempool_state.ready_txs);
+        let limits = BlockLimits {
+            chunks: chunks_left,
+            withdrawals: self.max_number_of_withdrawals_per_block,
+            timestamp: unix_timestamp(),
+        };
+        let (chunks_left, txs) = self.packing_strategy.select(&mut ready_txs, limits, &|tx| {
+            self.mempool_state.required_chunks(tx)
+        });
+        self.mempool_state.ready_txs = ready_txs;
+
+        // Nonces of the proposed transactions are tracked until the block is committed,
+        // so the next transactions of the same accounts are considered ready.
+        for tx in txs.iter().flat_map(|tx| tx.txs()) {
+            let proposed_nonce = self
+                .mempool_state
+                .proposed_nonces
+                .entry(tx.account())
+                .or_default();
+            *proposed_nonce = (*proposed_nonce).max(tx.nonce() + 1);
+        }
+
+        (chunks_left, txs)
+    }
+}
+
+/// Calculates the value of fees paid by the transaction (or by all the transactions of the batch) in USD.
+/// Token prices are taken from the latest ticker prices stored in the database, fees paid in tokens
+/// without known price are considered worthless.
+async fn fee_value(
+    storage: &mut StorageProcessor<'_>,
+    txs: &[SignedZkSyncTx],
+) -> QueryResult<Ratio<BigUint>> {
+    let mut total_value = Ratio::zero();
+    for tx in txs {
+        let (_, token, _, fee) = match tx.get_fee_info() {
+            Some(fee_info) => fee_info,
+            None => continue,
+        };
+
+        let token = match storage.tokens_schema().get_token(token).await? {
+            Some(token) => token,
+            None => continue,
+        };
+        let price = storage
+            .tokens_schema()
+            .get_historical_ticker_price(token.id)
+            .await?;
+
+        if let Some(price) = price {
+            let token_precision = BigUint::from(10u32).pow(u32::from(token.decimals));
+            total_value += Ratio::from_integer(fee) * price.usd_price / token_precision;
+        }
+    }
+
+    Ok(total_value)
+}
+
+#[must_use]
+pub fn run_mempool_task(
+    db_pool: ConnectionPool,
+    requests: mpsc::Receiver<MempoolRequest>,
+    eth_watch_req: mpsc::Sender<EthWatchRequest>,
+    config: &ConfigurationOptions,
+) -> JoinHandle<()> {
+    let config = config.clone();
+    tokio::spawn(async move {
+        let mempool_state = MempoolState::restore_from_db(&db_pool, config.mempool.tx_ttl).await;
+
+        let mempool = Mempool {
+            db_pool,
+            mempool_state,
+            requests,
+            eth_watch_req,
+            max_block_size_chunks: *config
+                .available_block_chunk_sizes
+                .iter()
+                .max()
+                .expect("failed to find max block chunks size"),
+            max_number_of_withdrawals_per_block: config.max_number_of_withdrawals_per_block,
+            capacity: config.mempool.capacity,
+            max_txs_per_account: config.mempool.max_txs_per_account,
+            tx_ttl: config.mempool.tx_ttl,
+            replacement_fee_bump_percent: config.mempool.replacement_fee_bump_percent,
+            max_nonce_gap: config.mempool.max_nonce_gap,
+            packing_strategy: packing_strategy(config.mempool.block_packing_strategy),
+        };
+
+        mempool.run().await
+    })
+}
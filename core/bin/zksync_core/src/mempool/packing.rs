@@ -0,0 +1,91 @@
+//! Strategies of choosing the mempool transactions to be included into the block.
+//!
+//! Regardless of the strategy, transactions of each account are proposed in the order
+//! of their nonces, batches are proposed atomically, and the number of withdrawal
+//! operations doesn't exceed the limit for a block.
+
+// Built-in deps
+use std::cmp::Reverse;
+// Workspace uses
+use zksync_config::BlockPackingStrategyType;
+use zksync_types::mempool::SignedTxVariant;
+// Local uses
+use super::queue::{fee_per_chunk, BlockLimits, Overflow, TxQueue};
+
+/// Strategy of filling the block with the transactions from the mempool queue.
+pub trait BlockPackingStrategy: std::fmt::Debug + Send + Sync {
+    /// Removes from the `queue` the elements to be included into the block and returns
+    /// them in the order of execution, along with the amount of chunks left.
+    fn select(
+        &self,
+        queue: &mut TxQueue,
+        limits: BlockLimits,
+        required_chunks: &dyn Fn(&SignedTxVariant) -> usize,
+    ) -> (usize, Vec<SignedTxVariant>);
+}
+
+/// Creates the packing strategy of the given type.
+pub fn packing_strategy(strategy: BlockPackingStrategyType) -> Box<dyn BlockPackingStrategy> {
+    match strategy {
+        BlockPackingStrategyType::Fifo => Box::new(FifoStrategy),
+        BlockPackingStrategyType::Knapsack => Box::new(KnapsackStrategy),
+        BlockPackingStrategyType::MaxFee => Box::new(MaxFeeStrategy),
+    }
+}
+
+/// Transactions are proposed in the order of arrival until the first one that
+/// doesn't fit into the block.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FifoStrategy;
+
+impl BlockPackingStrategy for FifoStrategy {
+    fn select(
+        &self,
+        queue: &mut TxQueue,
+        limits: BlockLimits,
+        required_chunks: &dyn Fn(&SignedTxVariant) -> usize,
+    ) -> (usize, Vec<SignedTxVariant>) {
+        queue.select_for_block(
+            limits,
+            required_chunks,
+            |tx, _| Reverse(tx.seq()),
+            Overflow::Stop,
+        )
+    }
+}
+
+/// Greedy approximation of the knapsack problem: the largest transactions are
+/// placed first, and the remaining space is filled with the smaller ones.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct KnapsackStrategy;
+
+impl BlockPackingStrategy for KnapsackStrategy {
+    fn select(
+        &self,
+        queue: &mut TxQueue,
+        limits: BlockLimits,
+        required_chunks: &dyn Fn(&SignedTxVariant) -> usize,
+    ) -> (usize, Vec<SignedTxVariant>) {
+        queue.select_for_block(limits, required_chunks, |_, chunks| chunks, Overflow::Skip)
+    }
+}
+
+/// Transactions paying the greatest fee per chunk are proposed first.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MaxFeeStrategy;
+
+impl BlockPackingStrategy for MaxFeeStrategy {
+    fn select(
+        &self,
+        queue: &mut TxQueue,
+        limits: BlockLimits,
+        required_chunks: &dyn Fn(&SignedTxVariant) -> usize,
+    ) -> (usize, Vec<SignedTxVariant>) {
+        queue.select_for_block(
+            limits,
+            required_chunks,
+            |tx, chunks| fee_per_chunk(&tx.fee_value, chunks),
+            Overflow::Skip,
+        )
+    }
+}
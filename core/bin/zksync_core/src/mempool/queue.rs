@@ -0,0 +1,368 @@
+//! Priority queue for the transactions awaiting to be included into the block.
+//!
+//! The order in which elements are proposed for the block is defined by the block
+//! packing strategy (see `packing` module), e.g. by the fee they pay for one block chunk.
+//! Since transactions of the same account must be executed in the order of their
+//! nonces, element can be proposed for the block only if there are no elements in the
+//! queue containing transactions of the same account with lower nonce, and the validity
+//! time range of all its transactions has started.
+//!
+//! Batches are stored as a single element, which is ranked by the summary fee of all
+//! the transactions in the batch, and are always proposed atomically.
+//!
+//! Sequential numbers of the elements are unique across all the queues, so an element
+//! can be moved from one queue to another keeping its position in the arrival order.
+
+// Built-in deps
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BinaryHeap, HashMap, HashSet},
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+// External uses
+use num::{rational::Ratio, BigUint, Zero};
+// Workspace uses
+use zksync_types::{mempool::SignedTxVariant, tx::TxHash, Address, Nonce};
+
+/// Sequential number to be assigned to the next element added to any of the queues.
+static NEXT_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Capacity of the block available for the transactions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlockLimits {
+    /// Number of block chunks.
+    pub chunks: usize,
+    /// Max number of withdrawal operations.
+    pub withdrawals: usize,
+    /// Timestamp of the block proposal. Elements with transactions which become valid
+    /// later are not proposed.
+    pub timestamp: u64,
+}
+
+/// Behavior of the selection when an element doesn't fit into the block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Overflow {
+    /// Finish the selection.
+    Stop,
+    /// Skip the element and continue with the next ones.
+    Skip,
+}
+
+/// Element of the mempool queue.
+#[derive(Debug, Clone)]
+pub struct QueuedTx {
+    /// Sequential number of the element, which is used to preserve the arrival
+    /// order of the elements with the same priority.
+    seq: u64,
+    /// Moment when the element was added to the queue.
+    inserted_at: Instant,
+    /// Transaction or batch of transactions.
+    pub variant: SignedTxVariant,
+    /// Fee paid by the element (converted to USD with the token price
+    /// known at the moment of insertion).
+    pub fee_value: Ratio<BigUint>,
+}
+
+impl QueuedTx {
+    pub fn seq(&self) -> u64 {
+        self.seq
+    }
+
+    /// Returns the number of withdrawal operations in the element.
+    fn withdrawals_count(&self) -> usize {
+        self.variant
+            .txs()
+            .iter()
+            .filter(|tx| tx.is_withdraw())
+            .count()
+    }
+
+    /// Checks whether the validity time range of all the transactions in the element has started
+    /// by the `timestamp`.
+    fn is_started(&self, timestamp: u64) -> bool {
+        self.variant
+            .txs()
+            .iter()
+            .all(|tx| tx.tx.time_range().valid_from <= timestamp)
+    }
+
+    /// Returns the `(account, nonce)` pairs of all the transactions in the element.
+    pub fn nonces(&self) -> Vec<(Address, Nonce)> {
+        self.variant
+            .txs()
+            .iter()
+            .map(|tx| (tx.account(), tx.nonce()))
+            .collect()
+    }
+}
+
+/// Queue of the transactions sorted by their fee-per-chunk value.
+#[derive(Debug, Default)]
+pub struct TxQueue {
+    txs: BTreeMap<u64, QueuedTx>,
+    /// For every account, elements containing its transactions ordered by the nonce.
+    account_txs: HashMap<Address, BTreeMap<(Nonce, u64), u64>>,
+}
+
+impl TxQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.txs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.txs.is_empty()
+    }
+
+    /// Returns the number of the account transactions stored in the queue.
+    pub fn account_txs_count(&self, address: &Address) -> usize {
+        self.account_txs.get(address).map_or(0, BTreeMap::len)
+    }
+
+    /// Returns the element containing the account transaction with the given nonce.
+    pub fn find(&self, address: &Address, nonce: Nonce) -> Option<&QueuedTx> {
+        let txs = self.account_txs.get(address)?;
+        let (_, seq) = txs.range((nonce, 0)..=(nonce, u64::MAX)).next()?;
+        self.txs.get(seq)
+    }
+
+    /// Returns the nonce following the greatest nonce of the account transactions stored in the queue.
+    pub fn next_nonce(&self, address: &Address) -> Option<Nonce> {
+        let txs = self.account_txs.get(address)?;
+        txs.keys().next_back().map(|(nonce, _)| nonce + 1)
+    }
+
+    /// Returns the element containing the account transaction with the lowest nonce.
+    pub fn first_account_tx(&self, address: &Address) -> Option<&QueuedTx> {
+        let txs = self.account_txs.get(address)?;
+        txs.values().next().and_then(|seq| self.txs.get(seq))
+    }
+
+    /// Returns the sequential numbers of the elements containing the account transactions
+    /// with the nonce greater than the given one.
+    pub fn account_txs_after(&self, address: &Address, nonce: Nonce) -> Vec<u64> {
+        self.account_txs.get(address).map_or_else(Vec::new, |txs| {
+            txs.range((nonce, u64::MAX)..)
+                .map(|(_, &seq)| seq)
+                .collect()
+        })
+    }
+
+    /// Returns the element containing the transaction with the given hash.
+    pub fn find_by_hash(&self, tx_hash: TxHash) -> Option<&QueuedTx> {
+        self.txs
+            .values()
+            .find(|tx| tx.variant.hashes().contains(&tx_hash))
+    }
+
+    /// Adds a new element to the queue.
+    pub fn push(&mut self, variant: SignedTxVariant, fee_value: Ratio<BigUint>) {
+        self.push_at(variant, fee_value, Instant::now());
+    }
+
+    /// Adds a new element to the queue, considering it inserted at the given moment.
+    /// Elements must be added in the order of their insertion moments.
+    pub fn push_at(
+        &mut self,
+        variant: SignedTxVariant,
+        fee_value: Ratio<BigUint>,
+        inserted_at: Instant,
+    ) {
+        self.insert(QueuedTx {
+            seq: NEXT_SEQ.fetch_add(1, Ordering::Relaxed),
+            inserted_at,
+            variant,
+            fee_value,
+        });
+    }
+
+    /// Adds the element taken from another queue, keeping its sequential number and insertion time.
+    pub fn insert(&mut self, tx: QueuedTx) {
+        let seq = tx.seq;
+        for (address, nonce) in tx.nonces() {
+            self.account_txs
+                .entry(address)
+                .or_default()
+                .insert((nonce, seq), seq);
+        }
+        self.txs.insert(seq, tx);
+    }
+
+    /// Removes the element with the given sequential number from the queue.
+    pub fn remove(&mut self, seq: u64) -> Option<QueuedTx> {
+        let tx = self.txs.remove(&seq)?;
+        for (address, nonce) in tx.nonces() {
+            if let Some(txs) = self.account_txs.get_mut(&address) {
+                txs.remove(&(nonce, seq));
+                if txs.is_empty() {
+                    self.account_txs.remove(&address);
+                }
+            }
+        }
+        Some(tx)
+    }
+
+    /// Removes from the queue all the elements that were added more than `ttl` ago.
+    pub fn remove_expired(&mut self, ttl: Duration) -> Vec<SignedTxVariant> {
+        // Elements are stored in the insertion order, so the expired ones form a prefix of the queue.
+        let expired: Vec<_> = self
+            .txs
+            .values()
+            .take_while(|tx| tx.inserted_at.elapsed() >= ttl)
+            .map(|tx| tx.seq)
+            .collect();
+
+        expired
+            .into_iter()
+            .filter_map(|seq| self.remove(seq))
+            .map(|tx| tx.variant)
+            .collect()
+    }
+
+    /// Removes from the queue all the elements containing transactions which validity
+    /// time range has ended before the `timestamp`.
+    pub fn remove_outdated(&mut self, timestamp: u64) -> Vec<SignedTxVariant> {
+        let outdated: Vec<_> = self
+            .txs
+            .values()
+            .filter(|tx| {
+                tx.variant
+                    .txs()
+                    .iter()
+                    .any(|tx| tx.tx.time_range().is_expired(timestamp))
+            })
+            .map(|tx| tx.seq)
+            .collect();
+
+        outdated
+            .into_iter()
+            .filter_map(|seq| self.remove(seq))
+            .map(|tx| tx.variant)
+            .collect()
+    }
+
+    /// Returns the element which is the first to be evicted if the queue is full, i.e. the
+    /// element with the lowest fee per chunk among the ones whose eviction doesn't create
+    /// nonce gaps (for each account, it contains the greatest nonce in the queue).
+    /// Elements containing transactions of the `new_accounts` are never chosen, since the
+    /// new element may depend on them.
+    pub fn eviction_candidate(
+        &self,
+        new_accounts: &[Address],
+        required_chunks: impl Fn(&SignedTxVariant) -> usize,
+    ) -> Option<(&QueuedTx, Ratio<BigUint>)> {
+        self.txs
+            .values()
+            .filter(|tx| {
+                tx.nonces().into_iter().all(|(address, _)| {
+                    !new_accounts.contains(&address)
+                        && self
+                            .account_txs
+                            .get(&address)
+                            .and_then(|txs| txs.values().next_back())
+                            == Some(&tx.seq)
+                })
+            })
+            .map(|tx| {
+                (
+                    tx,
+                    fee_per_chunk(&tx.fee_value, required_chunks(&tx.variant)),
+                )
+            })
+            // Among the elements with the same fee, the latest one is evicted.
+            .min_by(|(lhs, lhs_fee), (rhs, rhs_fee)| {
+                lhs_fee.cmp(rhs_fee).then(rhs.seq.cmp(&lhs.seq))
+            })
+    }
+
+    /// Removes from the queue the set of elements that fits into the block `limits` and returns
+    /// it in the order of execution, along with the amount of chunks left.
+    ///
+    /// Elements are taken greedily in the order of `priority` (the greatest first). Element can be
+    /// taken only if there are no elements containing transactions of the same accounts with lower
+    /// nonces, and its transactions are valid at `limits.timestamp`. If an element doesn't fit into the remaining space, the selection either stops, or
+    /// the element is skipped together with all the transactions of the same accounts with greater
+    /// nonces, so other elements can fill the block.
+    pub fn select_for_block<P: Ord>(
+        &mut self,
+        limits: BlockLimits,
+        required_chunks: impl Fn(&SignedTxVariant) -> usize,
+        priority: impl Fn(&QueuedTx, usize) -> P,
+        overflow: Overflow,
+    ) -> (usize, Vec<SignedTxVariant>) {
+        let mut chunks_left = limits.chunks;
+        let mut withdrawals_left = limits.withdrawals;
+
+        let mut candidates = BinaryHeap::new();
+        let mut enqueued = HashSet::new();
+        for tx in self.txs.values() {
+            if self.is_ready(tx, limits.timestamp) {
+                let chunks = required_chunks(&tx.variant);
+                candidates.push((priority(tx, chunks), Reverse(tx.seq), chunks));
+                enqueued.insert(tx.seq);
+            }
+        }
+
+        let mut selected = Vec::new();
+        while let Some((_, Reverse(seq), chunks)) = candidates.pop() {
+            let withdrawals = self.txs[&seq].withdrawals_count();
+            if chunks > chunks_left || withdrawals > withdrawals_left {
+                match overflow {
+                    Overflow::Stop => break,
+                    // Element doesn't fit, transactions of its accounts remain blocked until the next block.
+                    Overflow::Skip => continue,
+                }
+            }
+            chunks_left -= chunks;
+            withdrawals_left -= withdrawals;
+
+            let tx = self.remove(seq).expect("Candidate must be in the queue");
+
+            // Taking an element may unblock the next elements of the same accounts.
+            for (address, _) in tx.nonces() {
+                let next_seq = self
+                    .account_txs
+                    .get(&address)
+                    .and_then(|txs| txs.values().next())
+                    .copied();
+
+                if let Some(next_seq) = next_seq {
+                    let next_tx = &self.txs[&next_seq];
+                    if !enqueued.contains(&next_seq) && self.is_ready(next_tx, limits.timestamp) {
+                        let chunks = required_chunks(&next_tx.variant);
+                        candidates.push((priority(next_tx, chunks), Reverse(next_seq), chunks));
+                        enqueued.insert(next_seq);
+                    }
+                }
+            }
+
+            selected.push(tx.variant);
+        }
+
+        (chunks_left, selected)
+    }
+
+    /// Element is ready to be proposed if for each account it contains the lowest nonce in the queue
+    /// and its transactions can be executed at the `timestamp`.
+    fn is_ready(&self, tx: &QueuedTx, timestamp: u64) -> bool {
+        tx.is_started(timestamp)
+            && tx.nonces().into_iter().all(|(address, _)| {
+                self.account_txs
+                    .get(&address)
+                    .and_then(|txs| txs.values().next())
+                    == Some(&tx.seq)
+            })
+    }
+}
+
+/// Calculates the fee paid by an element for one block chunk.
+pub fn fee_per_chunk(fee_value: &Ratio<BigUint>, chunks: usize) -> Ratio<BigUint> {
+    if chunks == 0 {
+        return Ratio::zero();
+    }
+    fee_value.clone() / Ratio::from_integer(BigUint::from(chunks))
+}
@@ -0,0 +1,732 @@
+use super::{
+    packing::{BlockPackingStrategy, FifoStrategy, KnapsackStrategy, MaxFeeStrategy},
+    queue::{BlockLimits, TxQueue},
+    restored_inserted_at, MempoolState, TxAddError,
+};
+use chrono::Utc;
+use num::{rational::Ratio, BigUint};
+use std::{collections::HashMap, time::Duration};
+use zksync_types::{
+    mempool::{SignedTxVariant, SignedTxsBatch},
+    tx::{TimeRange, Withdraw},
+    AccountUpdate, Address, Nonce, SignedZkSyncTx, Transfer, ZkSyncTx,
+};
+
+fn transfer(from: Address, nonce: Nonce) -> SignedZkSyncTx {
+    transfer_with_time_range(from, nonce, Default::default())
+}
+
+fn transfer_with_time_range(from: Address, nonce: Nonce, time_range: TimeRange) -> SignedZkSyncTx {
+    let transfer = Transfer::new(
+        0,
+        from,
+        Address::random(),
+        0,
+        100u32.into(),
+        1u32.into(),
+        nonce,
+        time_range,
+        None,
+    );
+    ZkSyncTx::Transfer(Box::new(transfer)).into()
+}
+
+fn withdraw(from: Address, nonce: Nonce) -> SignedZkSyncTx {
+    let withdraw = Withdraw::new(
+        0,
+        from,
+        Address::random(),
+        0,
+        100u32.into(),
+        1u32.into(),
+        nonce,
+        Default::default(),
+        None,
+    );
+    ZkSyncTx::Withdraw(Box::new(withdraw)).into()
+}
+
+fn usd(value: u32) -> Ratio<BigUint> {
+    Ratio::from_integer(BigUint::from(value))
+}
+
+fn required_chunks(tx: &SignedTxVariant) -> usize {
+    match tx {
+        SignedTxVariant::Tx(tx) => tx.min_chunks(),
+        SignedTxVariant::Batch(batch) => batch.txs.iter().map(|tx| tx.min_chunks()).sum(),
+    }
+}
+
+/// Selects transactions for the block with the default packing strategy and
+/// without the limit of withdrawals.
+fn select_for_block(queue: &mut TxQueue, chunks: usize) -> (usize, Vec<SignedTxVariant>) {
+    let limits = BlockLimits {
+        chunks,
+        withdrawals: usize::MAX,
+        timestamp: u64::MAX,
+    };
+    MaxFeeStrategy.select(queue, limits, &required_chunks)
+}
+
+fn nonces(txs: &[SignedTxVariant]) -> Vec<(Address, Nonce)> {
+    txs.iter()
+        .flat_map(|tx| match tx {
+            SignedTxVariant::Tx(tx) => vec![(tx.account(), tx.nonce())],
+            SignedTxVariant::Batch(batch) => batch
+                .txs
+                .iter()
+                .map(|tx| (tx.account(), tx.nonce()))
+                .collect(),
+        })
+        .collect()
+}
+
+/// Creates a mempool state with the given committed account nonces.
+fn mempool_state(accounts: &[(Address, Nonce)]) -> MempoolState {
+    MempoolState {
+        account_nonces: accounts.iter().copied().collect(),
+        account_ids: accounts
+            .iter()
+            .enumerate()
+            .map(|(id, (address, _))| (id as u32, *address))
+            .collect(),
+        proposed_nonces: HashMap::new(),
+        ready_txs: TxQueue::new(),
+        future_txs: TxQueue::new(),
+    }
+}
+
+/// Checks that transactions paying more are proposed first.
+#[test]
+fn higher_fee_goes_first() {
+    let (alice, bob, carol) = (Address::random(), Address::random(), Address::random());
+
+    let mut queue = TxQueue::new();
+    queue.push(transfer(alice, 0).into(), usd(1));
+    queue.push(transfer(bob, 0).into(), usd(10));
+    queue.push(transfer(carol, 0).into(), usd(5));
+
+    let (chunks_left, txs) = select_for_block(&mut queue, 100);
+
+    assert_eq!(chunks_left, 94);
+    assert_eq!(nonces(&txs), vec![(bob, 0), (carol, 0), (alice, 0)]);
+    assert!(queue.is_empty());
+}
+
+/// Checks that transactions of the same account are proposed in the nonce order,
+/// even if the transaction with greater nonce pays more.
+#[test]
+fn nonce_order_is_preserved() {
+    let (alice, bob) = (Address::random(), Address::random());
+
+    let mut queue = TxQueue::new();
+    queue.push(transfer(alice, 1).into(), usd(100));
+    queue.push(transfer(alice, 0).into(), usd(1));
+    queue.push(transfer(bob, 0).into(), usd(10));
+
+    let (_, txs) = select_for_block(&mut queue, 100);
+
+    assert_eq!(nonces(&txs), vec![(bob, 0), (alice, 0), (alice, 1)]);
+}
+
+/// Checks that elements that don't fit into the block are skipped, and the remaining
+/// space is filled with smaller elements.
+#[test]
+fn smaller_txs_fill_the_block() {
+    let (alice, bob, carol) = (Address::random(), Address::random(), Address::random());
+
+    let mut queue = TxQueue::new();
+    // Withdraw requires 6 chunks and has the best fee per chunk.
+    queue.push(withdraw(alice, 0).into(), usd(60));
+    // This one doesn't fit after the withdraw.
+    queue.push(withdraw(bob, 0).into(), usd(30));
+    // And this one takes the remaining space.
+    queue.push(transfer(carol, 0).into(), usd(2));
+
+    let (chunks_left, txs) = select_for_block(&mut queue, 8);
+
+    assert_eq!(chunks_left, 0);
+    assert_eq!(nonces(&txs), vec![(alice, 0), (carol, 0)]);
+    assert_eq!(queue.len(), 1);
+
+    // Skipped transaction is proposed in the next block.
+    let (_, txs) = select_for_block(&mut queue, 8);
+    assert_eq!(nonces(&txs), vec![(bob, 0)]);
+}
+
+/// Checks that account transactions with greater nonces are not proposed if the
+/// previous transaction didn't fit into the block.
+#[test]
+fn skipped_tx_blocks_account() {
+    let alice = Address::random();
+
+    let mut queue = TxQueue::new();
+    queue.push(withdraw(alice, 0).into(), usd(60));
+    queue.push(transfer(alice, 1).into(), usd(60));
+
+    let (chunks_left, txs) = select_for_block(&mut queue, 4);
+
+    assert_eq!(chunks_left, 4);
+    assert!(txs.is_empty());
+    assert_eq!(queue.len(), 2);
+}
+
+/// Checks that batches are ranked by the summary fee and are proposed atomically.
+#[test]
+fn batch_is_ranked_by_summary_fee() {
+    let (alice, bob, carol) = (Address::random(), Address::random(), Address::random());
+
+    let mut queue = TxQueue::new();
+    queue.push(transfer(alice, 0).into(), usd(5));
+    queue.push(
+        SignedTxVariant::batch(vec![transfer(bob, 0), transfer(carol, 0)], 1, None),
+        usd(12),
+    );
+
+    // Batch requires 4 chunks and pays 3 per chunk, while the single transfer pays 2.5 per chunk.
+    let (_, txs) = select_for_block(&mut queue, 4);
+
+    assert_eq!(nonces(&txs), vec![(bob, 0), (carol, 0)]);
+    assert_eq!(queue.len(), 1);
+
+    // Batch is never split between blocks.
+    let mut queue = TxQueue::new();
+    queue.push(
+        SignedTxVariant::batch(vec![transfer(bob, 0), transfer(carol, 0)], 1, None),
+        usd(12),
+    );
+    let (chunks_left, txs) = select_for_block(&mut queue, 2);
+
+    assert_eq!(chunks_left, 2);
+    assert!(txs.is_empty());
+}
+
+/// Checks that FIFO strategy proposes transactions in the order of arrival and stops
+/// at the first transaction that doesn't fit into the block.
+#[test]
+fn fifo_stops_at_first_overflow() {
+    let (alice, bob, carol) = (Address::random(), Address::random(), Address::random());
+
+    let mut queue = TxQueue::new();
+    queue.push(withdraw(alice, 0).into(), usd(1));
+    queue.push(withdraw(bob, 0).into(), usd(100));
+    queue.push(transfer(carol, 0).into(), usd(100));
+
+    let limits = BlockLimits {
+        chunks: 8,
+        withdrawals: usize::MAX,
+        timestamp: u64::MAX,
+    };
+    let (chunks_left, txs) = FifoStrategy.select(&mut queue, limits, &required_chunks);
+
+    assert_eq!(chunks_left, 2);
+    assert_eq!(nonces(&txs), vec![(alice, 0)]);
+    assert_eq!(queue.len(), 2);
+}
+
+/// Checks that knapsack strategy places the largest transactions first and fills
+/// the remaining space with the smaller ones.
+#[test]
+fn knapsack_fills_the_block() {
+    let (alice, bob, carol) = (Address::random(), Address::random(), Address::random());
+
+    let mut queue = TxQueue::new();
+    queue.push(transfer(alice, 0).into(), usd(100));
+    queue.push(transfer(bob, 0).into(), usd(100));
+    queue.push(withdraw(carol, 0).into(), usd(1));
+
+    let limits = BlockLimits {
+        chunks: 10,
+        withdrawals: usize::MAX,
+        timestamp: u64::MAX,
+    };
+    let (chunks_left, txs) = KnapsackStrategy.select(&mut queue, limits, &required_chunks);
+
+    assert_eq!(chunks_left, 0);
+    assert_eq!(nonces(&txs), vec![(carol, 0), (alice, 0), (bob, 0)]);
+    assert!(queue.is_empty());
+}
+
+/// Checks that the number of withdrawals in the block is limited regardless of the
+/// strategy, and batches exceeding the limit are not split.
+#[test]
+fn withdrawals_limit_is_respected() {
+    let (alice, bob, carol, dave, eve) = (
+        Address::random(),
+        Address::random(),
+        Address::random(),
+        Address::random(),
+        Address::random(),
+    );
+
+    let queue = || {
+        let mut queue = TxQueue::new();
+        queue.push(
+            SignedTxVariant::batch(vec![withdraw(alice, 0), withdraw(bob, 0)], 1, None),
+            usd(1000),
+        );
+        queue.push(withdraw(carol, 0).into(), usd(100));
+        queue.push(withdraw(dave, 0).into(), usd(10));
+        queue.push(transfer(eve, 0).into(), usd(1));
+        queue
+    };
+    let limits = BlockLimits {
+        chunks: 100,
+        withdrawals: 1,
+        timestamp: u64::MAX,
+    };
+
+    let strategies: Vec<Box<dyn BlockPackingStrategy>> = vec![
+        Box::new(FifoStrategy),
+        Box::new(KnapsackStrategy),
+        Box::new(MaxFeeStrategy),
+    ];
+    for strategy in strategies {
+        let (_, txs) = strategy.select(&mut queue(), limits, &required_chunks);
+
+        let withdrawals = txs
+            .iter()
+            .flat_map(|tx| tx.txs())
+            .filter(|tx| tx.is_withdraw())
+            .count();
+        assert!(withdrawals <= 1, "{:?}: {:?}", strategy, nonces(&txs));
+        assert!(
+            !nonces(&txs).contains(&(alice, 0)) && !nonces(&txs).contains(&(bob, 0)),
+            "{:?}: batch must not be included",
+            strategy
+        );
+    }
+
+    // Fee-maximising strategy skips the withdrawals exceeding the limit.
+    let mut queue = queue();
+    let (_, txs) = MaxFeeStrategy.select(&mut queue, limits, &required_chunks);
+
+    assert_eq!(nonces(&txs), vec![(carol, 0), (eve, 0)]);
+    assert_eq!(queue.len(), 2);
+}
+
+/// Checks that pending transactions are counted per account, including the batch ones.
+#[test]
+fn account_txs_are_counted() {
+    let (alice, bob) = (Address::random(), Address::random());
+
+    let mut queue = TxQueue::new();
+    queue.push(transfer(alice, 0).into(), usd(1));
+    queue.push(
+        SignedTxVariant::batch(vec![transfer(alice, 1), transfer(bob, 0)], 1, None),
+        usd(1),
+    );
+
+    assert_eq!(queue.account_txs_count(&alice), 2);
+    assert_eq!(queue.account_txs_count(&bob), 1);
+    assert_eq!(queue.account_txs_count(&Address::random()), 0);
+
+    select_for_block(&mut queue, 100);
+    assert_eq!(queue.account_txs_count(&alice), 0);
+    assert_eq!(queue.account_txs_count(&bob), 0);
+}
+
+/// Checks that only the elements older than TTL are removed as expired.
+#[test]
+fn expired_txs_are_removed() {
+    let alice = Address::random();
+
+    let mut queue = TxQueue::new();
+    queue.push(transfer(alice, 0).into(), usd(1));
+    queue.push(transfer(alice, 1).into(), usd(1));
+
+    assert!(queue.remove_expired(Duration::from_secs(3600)).is_empty());
+    assert_eq!(queue.len(), 2);
+
+    let expired = queue.remove_expired(Duration::from_secs(0));
+    assert_eq!(nonces(&expired), vec![(alice, 0), (alice, 1)]);
+    assert!(queue.is_empty());
+    assert_eq!(queue.account_txs_count(&alice), 0);
+}
+
+/// Checks that the elements restored from the database keep the age they had before the restart,
+/// so they expire once the TTL passes since they were received.
+#[test]
+fn restored_txs_keep_their_age() {
+    let (alice, bob) = (Address::random(), Address::random());
+    let ttl = Duration::from_secs(3600);
+
+    let mut queue = TxQueue::new();
+    let received_at = Utc::now() - chrono::Duration::minutes(90);
+    queue.push_at(
+        transfer(alice, 0).into(),
+        usd(1),
+        restored_inserted_at(received_at),
+    );
+    let received_at = Utc::now() - chrono::Duration::minutes(30);
+    queue.push_at(
+        transfer(bob, 0).into(),
+        usd(1),
+        restored_inserted_at(received_at),
+    );
+    queue.push(transfer(bob, 1).into(), usd(1));
+
+    let expired = queue.remove_expired(ttl);
+    assert_eq!(nonces(&expired), vec![(alice, 0)]);
+    assert_eq!(queue.len(), 2);
+
+    // Transactions stored with a timestamp from the future aren't considered expired.
+    let mut queue = TxQueue::new();
+    let received_at = Utc::now() + chrono::Duration::minutes(10);
+    queue.push_at(
+        transfer(alice, 0).into(),
+        usd(1),
+        restored_inserted_at(received_at),
+    );
+    assert!(queue.remove_expired(ttl).is_empty());
+}
+
+/// Checks that the elements are removed as outdated once the time range of any of their
+/// transactions has ended.
+#[test]
+fn outdated_txs_are_removed() {
+    let (alice, bob) = (Address::random(), Address::random());
+
+    let mut queue = TxQueue::new();
+    queue.push(
+        transfer_with_time_range(alice, 0, TimeRange::new(0, 100)).into(),
+        usd(1),
+    );
+    queue.push(
+        SignedTxVariant::batch(
+            vec![
+                transfer(bob, 0),
+                transfer_with_time_range(bob, 1, TimeRange::new(0, 200)),
+            ],
+            1,
+            None,
+        ),
+        usd(1),
+    );
+    queue.push(transfer(alice, 1).into(), usd(1));
+
+    assert!(queue.remove_outdated(100).is_empty());
+    assert_eq!(queue.len(), 3);
+
+    let outdated = queue.remove_outdated(101);
+    assert_eq!(nonces(&outdated), vec![(alice, 0)]);
+    let outdated = queue.remove_outdated(201);
+    assert_eq!(nonces(&outdated), vec![(bob, 0), (bob, 1)]);
+    assert_eq!(queue.len(), 1);
+}
+
+/// Checks that the transactions which time range has ended are not accepted.
+#[test]
+fn outdated_txs_are_rejected() {
+    let alice = Address::random();
+    let txs = [
+        transfer(alice, 0),
+        transfer_with_time_range(alice, 1, TimeRange::new(0, 100)),
+    ];
+
+    assert!(MempoolState::check_time_range(&txs, 100).is_ok());
+    assert!(matches!(
+        MempoolState::check_time_range(&txs, 101),
+        Err(TxAddError::InvalidTimeRange)
+    ));
+}
+
+/// Checks that the transactions which time range hasn't started yet are accepted, but not proposed
+/// (along with the next transactions of the same account) until they become valid.
+#[test]
+fn future_txs_are_held_back() {
+    let (alice, bob) = (Address::random(), Address::random());
+    let txs = [transfer_with_time_range(alice, 0, TimeRange::new(100, 200))];
+    assert!(MempoolState::check_time_range(&txs, 50).is_ok());
+
+    let mut queue = TxQueue::new();
+    queue.push(txs[0].clone().into(), usd(100));
+    queue.push(transfer(alice, 1).into(), usd(100));
+    queue.push(transfer(bob, 0).into(), usd(1));
+
+    let limits = |timestamp| BlockLimits {
+        chunks: 100,
+        withdrawals: usize::MAX,
+        timestamp,
+    };
+    let (_, txs) = MaxFeeStrategy.select(&mut queue, limits(99), &required_chunks);
+    assert_eq!(nonces(&txs), vec![(bob, 0)]);
+
+    let (_, txs) = MaxFeeStrategy.select(&mut queue, limits(100), &required_chunks);
+    assert_eq!(nonces(&txs), vec![(alice, 0), (alice, 1)]);
+    assert!(queue.is_empty());
+}
+
+/// Checks that the cheapest element is chosen for eviction, unless its eviction creates
+/// a nonce gap or the new element belongs to the same account.
+#[test]
+fn cheapest_tx_is_evicted() {
+    let (alice, bob, carol) = (Address::random(), Address::random(), Address::random());
+
+    let mut queue = TxQueue::new();
+    // Alice's first transaction is the cheapest one, but evicting it leaves a nonce gap.
+    queue.push(transfer(alice, 0).into(), usd(1));
+    queue.push(transfer(alice, 1).into(), usd(5));
+    queue.push(transfer(bob, 0).into(), usd(3));
+
+    let (tx, fee) = queue
+        .eviction_candidate(&[carol], required_chunks)
+        .expect("Candidate must be found");
+    assert_eq!(nonces(&[tx.variant.clone()]), vec![(bob, 0)]);
+    assert_eq!(fee, Ratio::new(BigUint::from(3u32), BigUint::from(2u32)));
+
+    // Transactions of the same account are never evicted in favor of the new one.
+    let (tx, _) = queue
+        .eviction_candidate(&[bob], required_chunks)
+        .expect("Candidate must be found");
+    assert_eq!(nonces(&[tx.variant.clone()]), vec![(alice, 1)]);
+
+    let seq = tx.seq();
+    queue.remove(seq);
+    assert_eq!(queue.len(), 2);
+    assert_eq!(queue.account_txs_count(&alice), 1);
+}
+
+/// Checks that queued transactions can be found by the account and nonce.
+#[test]
+fn find_by_nonce() {
+    let (alice, bob) = (Address::random(), Address::random());
+
+    let mut queue = TxQueue::new();
+    queue.push(transfer(alice, 0).into(), usd(1));
+    queue.push(
+        SignedTxVariant::batch(vec![transfer(alice, 1), transfer(bob, 0)], 1, None),
+        usd(1),
+    );
+
+    let found = queue.find(&alice, 0).expect("Tx must be found");
+    assert_eq!(nonces(&[found.variant.clone()]), vec![(alice, 0)]);
+
+    let found = queue.find(&bob, 0).expect("Batch must be found");
+    assert!(matches!(found.variant, SignedTxVariant::Batch(_)));
+
+    assert!(queue.find(&alice, 2).is_none());
+    assert!(queue.find(&bob, 1).is_none());
+}
+
+/// Checks that transactions with nonce gaps are held in the future queue until
+/// the missing transactions arrive.
+#[test]
+fn future_txs_are_promoted() {
+    let alice = Address::random();
+    let mut state = mempool_state(&[(alice, 5)]);
+
+    state.add_tx(transfer(alice, 7), usd(1)).unwrap();
+    state.add_tx(transfer(alice, 6), usd(1)).unwrap();
+    assert_eq!(state.ready_txs.len(), 0);
+    assert_eq!(state.future_txs.len(), 2);
+    assert_eq!(state.account_txs_count(&alice), 2);
+
+    state.add_tx(transfer(alice, 5), usd(1)).unwrap();
+    assert_eq!(state.ready_txs.len(), 3);
+    assert_eq!(state.future_txs.len(), 0);
+
+    let (_, txs) = select_for_block(&mut state.ready_txs, 100);
+    assert_eq!(nonces(&txs), vec![(alice, 5), (alice, 6), (alice, 7)]);
+}
+
+/// Checks that batch is ready only if the nonces of all its transactions follow
+/// the nonces of the corresponding accounts.
+#[test]
+fn batch_with_gap_is_not_ready() {
+    let (alice, bob) = (Address::random(), Address::random());
+    let mut state = mempool_state(&[(alice, 0), (bob, 0)]);
+
+    let batch = SignedTxVariant::batch(vec![transfer(alice, 0), transfer(bob, 1)], 1, None);
+    state.push(batch, usd(1));
+    assert_eq!(state.future_txs.len(), 1);
+
+    state.add_tx(transfer(bob, 0), usd(1)).unwrap();
+    assert_eq!(state.ready_txs.len(), 2);
+    assert_eq!(state.future_txs.len(), 0);
+}
+
+/// Checks that the future transactions are promoted once the account nonce is updated,
+/// and the proposed transactions are taken into account.
+#[test]
+fn future_txs_are_promoted_on_nonce_update() {
+    let (alice, bob) = (Address::random(), Address::random());
+    let mut state = mempool_state(&[(alice, 0), (bob, 0)]);
+
+    state.add_tx(transfer(alice, 2), usd(1)).unwrap();
+    state.add_tx(transfer(bob, 1), usd(1)).unwrap();
+    assert_eq!(state.future_txs.len(), 2);
+
+    // Bob's transaction with nonce 0 was proposed for the block.
+    state.proposed_nonces.insert(bob, 1);
+    state.promote(vec![bob]);
+    assert_eq!(state.ready_txs.len(), 1);
+
+    // Alice's transactions were executed without the participation of the mempool.
+    state.update_nonces(
+        vec![(
+            0,
+            AccountUpdate::UpdateBalance {
+                old_nonce: 0,
+                new_nonce: 2,
+                balance_update: (0, 0u32.into(), 0u32.into()),
+            },
+        )],
+        Vec::new(),
+    );
+    assert_eq!(state.ready_txs.len(), 2);
+    assert_eq!(state.future_txs.len(), 0);
+
+    // Once the block is committed, proposed nonces are not tracked anymore.
+    state.update_nonces(
+        vec![(
+            1,
+            AccountUpdate::UpdateBalance {
+                old_nonce: 0,
+                new_nonce: 1,
+                balance_update: (0, 0u32.into(), 0u32.into()),
+            },
+        )],
+        Vec::new(),
+    );
+    assert!(state.proposed_nonces.is_empty());
+}
+
+/// Checks that the removal of the ready transaction moves the next transactions
+/// of the same account back to the future queue.
+#[test]
+fn removed_tx_demotes_next_txs() {
+    let (alice, bob) = (Address::random(), Address::random());
+    let mut state = mempool_state(&[(alice, 0), (bob, 0)]);
+
+    state.add_tx(transfer(alice, 0), usd(1)).unwrap();
+    state.add_tx(transfer(alice, 1), usd(1)).unwrap();
+    state.push(
+        SignedTxVariant::batch(vec![transfer(alice, 2), transfer(bob, 0)], 1, None),
+        usd(1),
+    );
+    state.add_tx(transfer(bob, 1), usd(1)).unwrap();
+    assert_eq!(state.ready_txs.len(), 4);
+
+    let seq = state.find(&alice, 1).unwrap().seq();
+    state.remove(seq);
+    assert_eq!(state.ready_txs.len(), 1);
+    assert_eq!(state.future_txs.len(), 2);
+
+    // Replacement fills the gap.
+    state.add_tx(transfer(alice, 1), usd(2)).unwrap();
+    assert_eq!(state.ready_txs.len(), 4);
+    assert_eq!(state.future_txs.len(), 0);
+}
+
+/// Checks that the nonce can't be too far ahead of the next expected account nonce.
+#[test]
+fn nonce_gap_is_limited() {
+    let alice = Address::random();
+    let mut state = mempool_state(&[(alice, 3)]);
+
+    state.check_nonce_gap(&[transfer(alice, 5)], 2).unwrap();
+    assert!(matches!(
+        state.check_nonce_gap(&[transfer(alice, 6)], 2),
+        Err(TxAddError::NonceTooHigh)
+    ));
+
+    // Ready transactions move the expected nonce forward.
+    state.add_tx(transfer(alice, 3), usd(1)).unwrap();
+    state.check_nonce_gap(&[transfer(alice, 6)], 2).unwrap();
+}
+
+/// Checks that the batch can't be added along with the pending transaction with the same nonce.
+#[test]
+fn batch_conflicting_with_pending_tx_is_rejected() {
+    let (alice, bob) = (Address::random(), Address::random());
+    let mut state = mempool_state(&[(alice, 0), (bob, 0)]);
+
+    state.add_tx(transfer(alice, 0), usd(1)).unwrap();
+    state.add_tx(transfer(bob, 2), usd(1)).unwrap();
+
+    assert!(matches!(
+        state.check_batch_conflicts(&[transfer(bob, 0), transfer(alice, 0)]),
+        Err(TxAddError::BatchNonceConflict)
+    ));
+    // Conflicting transaction may be held in the future queue as well.
+    assert!(matches!(
+        state.check_batch_conflicts(&[transfer(alice, 1), transfer(bob, 2)]),
+        Err(TxAddError::BatchNonceConflict)
+    ));
+    state
+        .check_batch_conflicts(&[transfer(alice, 1), transfer(bob, 0)])
+        .unwrap();
+}
+
+/// Checks that the transactions reusing the nonces of the proposed but not yet committed
+/// transactions are rejected.
+#[test]
+fn proposed_nonces_are_not_reused() {
+    let alice = Address::random();
+    let mut state = mempool_state(&[(alice, 1)]);
+
+    // Alice's transactions with nonces 1 and 2 were proposed for the block.
+    state.proposed_nonces.insert(alice, 3);
+    for nonce in 0..3 {
+        assert!(matches!(
+            state.add_tx(transfer(alice, nonce), usd(1)),
+            Err(TxAddError::NonceMismatch)
+        ));
+    }
+    assert!(matches!(
+        state.add_batch(
+            SignedTxsBatch {
+                txs: vec![transfer(alice, 3), transfer(alice, 2)],
+                batch_id: 1,
+                eth_signature: None,
+            },
+            usd(1)
+        ),
+        Err(TxAddError::NonceMismatch)
+    ));
+    assert_eq!(state.len(), 0);
+
+    state.add_tx(transfer(alice, 3), usd(1)).unwrap();
+    assert_eq!(state.ready_txs.len(), 1);
+}
+
+/// Checks that the proposed nonces are rolled back once the proposed transaction fails,
+/// so the next account transactions are not considered ready anymore.
+#[test]
+fn proposed_nonce_is_rolled_back_on_failure() {
+    let (alice, bob) = (Address::random(), Address::random());
+    let mut state = mempool_state(&[(alice, 0), (bob, 0)]);
+
+    // Alice's transactions with nonces 0 and 1 were proposed for the block.
+    state.proposed_nonces.insert(alice, 2);
+    state.add_tx(transfer(alice, 2), usd(1)).unwrap();
+    state.add_tx(transfer(alice, 3), usd(1)).unwrap();
+    assert_eq!(state.ready_txs.len(), 2);
+
+    // Alice's first transaction was executed, but the second one has failed.
+    state.update_nonces(
+        vec![(
+            0,
+            AccountUpdate::UpdateBalance {
+                old_nonce: 0,
+                new_nonce: 1,
+                balance_update: (0, 0u32.into(), 0u32.into()),
+            },
+        )],
+        vec![(alice, 1)],
+    );
+    assert!(state.proposed_nonces.is_empty());
+    assert_eq!(state.next_nonce(&alice), 1);
+    assert_eq!(state.ready_txs.len(), 0);
+    assert_eq!(state.future_txs.len(), 2);
+
+    // Failure of the transaction which wasn't proposed by the mempool changes nothing.
+    state.update_nonces(Vec::new(), vec![(bob, 0)]);
+    assert!(state.proposed_nonces.is_empty());
+
+    // Once the failed nonce is filled, the held transactions become ready again.
+    state.add_tx(transfer(alice, 1), usd(1)).unwrap();
+    assert_eq!(state.ready_txs.len(), 3);
+    assert_eq!(state.future_txs.len(), 0);
+}
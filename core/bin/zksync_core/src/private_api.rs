@@ -7,31 +7,60 @@
 //! All the incoming data is assumed to be correct and not double-checked
 //! for correctness.
 
-use crate::{eth_watch::EthWatchRequest, mempool::MempoolRequest};
-use actix_web::{web, App, HttpResponse, HttpServer};
+use crate::{
+    block_proposer::BlockProposerRequest, eth_watch::EthWatchRequest, mempool::MempoolRequest,
+    state_keeper::StateKeeperRequest,
+};
+use actix_web::{http::header, web, App, HttpRequest, HttpResponse, HttpServer};
 use futures::{
     channel::{mpsc, oneshot},
     sink::SinkExt,
 };
 use std::thread;
 use zksync_config::ApiServerOptions;
-use zksync_types::{tx::TxEthSignature, Address, SignedZkSyncTx, H256};
+use zksync_types::{
+    block::BlockProductionMode,
+    codec,
+    mempool::{SignedTxVariant, SignedTxsBatch},
+    tx::{TxEthSignature, TxHash},
+    Address, SignedZkSyncTx, H256,
+};
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
 #[derive(Debug, Clone)]
 struct AppState {
     mempool_tx_sender: mpsc::Sender<MempoolRequest>,
     eth_watch_req_sender: mpsc::Sender<EthWatchRequest>,
+    state_keeper_req_sender: mpsc::Sender<StateKeeperRequest>,
+    block_proposer_req_sender: mpsc::Sender<BlockProposerRequest>,
+}
+
+/// Checks whether the request body is encoded with the zkSync binary codec
+/// rather than JSON.
+fn is_binary_request(req: &HttpRequest) -> bool {
+    req.headers()
+        .get(header::CONTENT_TYPE)
+        .map(|content_type| content_type == codec::CONTENT_TYPE)
+        .unwrap_or(false)
 }
 
 /// Adds a new transaction into the mempool.
 /// Returns a JSON representation of `Result<(), TxAddError>`.
 /// Expects transaction to be checked on the API side.
+/// Transaction can be sent either as JSON or in the binary encoding.
 #[actix_web::post("/new_tx")]
 async fn new_tx(
+    req: HttpRequest,
     data: web::Data<AppState>,
-    web::Json(tx): web::Json<SignedZkSyncTx>,
+    body: web::Bytes,
 ) -> actix_web::Result<HttpResponse> {
+    let tx: SignedZkSyncTx = if is_binary_request(&req) {
+        codec::decode(&body).map_err(|err| HttpResponse::BadRequest().body(err.to_string()))?
+    } else {
+        serde_json::from_slice(&body)
+            .map_err(|err| HttpResponse::BadRequest().body(err.to_string()))?
+    };
+
     let (sender, receiver) = oneshot::channel();
     let item = MempoolRequest::NewTx(Box::new(tx), sender);
     let mut mempool_sender = data.mempool_tx_sender.clone();
@@ -50,11 +79,23 @@ async fn new_tx(
 /// Adds a new transactions batch into the mempool.
 /// Returns a JSON representation of `Result<(), TxAddError>`.
 /// Expects transaction to be checked on the API side.
+/// Batch can be sent either as JSON or in the binary encoding of `SignedTxsBatch`,
+/// in which case the batch ID is ignored.
 #[actix_web::post("/new_txs_batch")]
 async fn new_txs_batch(
+    req: HttpRequest,
     data: web::Data<AppState>,
-    web::Json((txs, eth_signature)): web::Json<(Vec<SignedZkSyncTx>, Option<TxEthSignature>)>,
+    body: web::Bytes,
 ) -> actix_web::Result<HttpResponse> {
+    let (txs, eth_signature) = if is_binary_request(&req) {
+        let batch: SignedTxsBatch =
+            codec::decode(&body).map_err(|err| HttpResponse::BadRequest().body(err.to_string()))?;
+        (batch.txs, batch.eth_signature)
+    } else {
+        serde_json::from_slice::<(Vec<SignedZkSyncTx>, Option<TxEthSignature>)>(&body)
+            .map_err(|err| HttpResponse::BadRequest().body(err.to_string()))?
+    };
+
     let (sender, receiver) = oneshot::channel();
     let item = MempoolRequest::NewTxsBatch(txs, eth_signature, sender);
     let mut mempool_sender = data.mempool_tx_sender.clone();
@@ -70,6 +111,58 @@ async fn new_txs_batch(
     Ok(HttpResponse::Ok().json(response))
 }
 
+/// Removes a pending transaction from the mempool.
+/// Returns a JSON representation of `Result<(), TxCancelError>`.
+/// Expects cancel request to be checked on the API side.
+#[actix_web::post("/cancel_tx")]
+async fn cancel_tx(
+    data: web::Data<AppState>,
+    web::Json(tx_hash): web::Json<TxHash>,
+) -> actix_web::Result<HttpResponse> {
+    let (sender, receiver) = oneshot::channel();
+    let item = MempoolRequest::CancelTx(tx_hash, sender);
+    let mut mempool_sender = data.mempool_tx_sender.clone();
+    mempool_sender
+        .send(item)
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    let response = receiver
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Executes transactions against the current state without applying their changes.
+/// Several transactions are executed as a batch.
+/// Returns a JSON representation of `Result<Vec<TxSimulationResult>, TxAddError>`.
+#[actix_web::post("/simulate_tx")]
+async fn simulate_tx(
+    data: web::Data<AppState>,
+    web::Json(mut txs): web::Json<Vec<SignedZkSyncTx>>,
+) -> actix_web::Result<HttpResponse> {
+    let tx = if txs.len() == 1 {
+        SignedTxVariant::Tx(txs.remove(0))
+    } else {
+        SignedTxVariant::batch(txs, 0, None)
+    };
+
+    let (sender, receiver) = oneshot::channel();
+    let item = StateKeeperRequest::SimulateTx(tx, sender);
+    let mut state_keeper_sender = data.state_keeper_req_sender.clone();
+    state_keeper_sender
+        .send(item)
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    let response = receiver
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
 /// Obtains information about unconfirmed deposits known for a certain address.
 #[actix_web::get("/unconfirmed_deposits/{address}")]
 async fn unconfirmed_deposits(
@@ -118,11 +211,77 @@ async fn unconfirmed_op(
     Ok(HttpResponse::Ok().json(response))
 }
 
+/// Changes the mode of the block production and returns the resulting mode.
+async fn set_block_production_mode(
+    data: web::Data<AppState>,
+    mode: BlockProductionMode,
+) -> actix_web::Result<HttpResponse> {
+    let mut block_proposer_sender = data.block_proposer_req_sender.clone();
+    block_proposer_sender
+        .send(BlockProposerRequest::SetMode(mode))
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    Ok(HttpResponse::Ok().json(mode))
+}
+
+/// Stops proposing new blocks until the block production is resumed.
+/// Returns a JSON representation of the resulting `BlockProductionMode`.
+#[actix_web::post("/block_proposer/pause")]
+async fn pause_block_proposer(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    set_block_production_mode(data, BlockProductionMode::Paused).await
+}
+
+/// Resumes the block production.
+/// Returns a JSON representation of the resulting `BlockProductionMode`.
+#[actix_web::post("/block_proposer/resume")]
+async fn resume_block_proposer(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    set_block_production_mode(data, BlockProductionMode::Active).await
+}
+
+/// Obtains the current mode of the block production.
+#[actix_web::get("/block_proposer/mode")]
+async fn block_production_mode(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let (sender, receiver) = oneshot::channel();
+    let item = BlockProposerRequest::GetMode(sender);
+    let mut block_proposer_sender = data.block_proposer_req_sender.clone();
+    block_proposer_sender
+        .send(item)
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    let response = receiver
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// Seals the pending block without waiting for the sealing criteria.
+/// Responds with `false` if the pending block was empty, so no block was sealed.
+#[actix_web::post("/seal_block")]
+async fn seal_block(data: web::Data<AppState>) -> actix_web::Result<HttpResponse> {
+    let (sender, receiver) = oneshot::channel();
+    let mut state_keeper_sender = data.state_keeper_req_sender.clone();
+    state_keeper_sender
+        .send(StateKeeperRequest::SealBlock(sender))
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    let sealed = receiver
+        .await
+        .map_err(|_err| HttpResponse::InternalServerError().finish())?;
+
+    Ok(HttpResponse::Ok().json(sealed))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn start_private_core_api(
     panic_notify: mpsc::Sender<bool>,
     mempool_tx_sender: mpsc::Sender<MempoolRequest>,
     eth_watch_req_sender: mpsc::Sender<EthWatchRequest>,
+    state_keeper_req_sender: mpsc::Sender<StateKeeperRequest>,
+    block_proposer_req_sender: mpsc::Sender<BlockProposerRequest>,
     api_server_options: ApiServerOptions,
 ) {
     thread::Builder::new()
@@ -137,6 +296,8 @@ pub fn start_private_core_api(
                     let app_state = AppState {
                         mempool_tx_sender: mempool_tx_sender.clone(),
                         eth_watch_req_sender: eth_watch_req_sender.clone(),
+                        state_keeper_req_sender: state_keeper_req_sender.clone(),
+                        block_proposer_req_sender: block_proposer_req_sender.clone(),
                     };
 
                     // By calling `register_data` instead of `data` we're avoiding double
@@ -146,8 +307,14 @@ pub fn start_private_core_api(
                         .app_data(web::Data::new(app_state))
                         .service(new_tx)
                         .service(new_txs_batch)
+                        .service(cancel_tx)
+                        .service(simulate_tx)
                         .service(unconfirmed_op)
                         .service(unconfirmed_deposits)
+                        .service(pause_block_proposer)
+                        .service(resume_block_proposer)
+                        .service(block_production_mode)
+                        .service(seal_block)
                 })
                 .bind(&api_server_options.core_server_address)
                 .expect("failed to bind")
@@ -0,0 +1,91 @@
+//! Routing of the collected fees.
+//!
+//! The circuit allows only one fee account per block, so instead of splitting fees
+//! of the separate operations, the state keeper chooses the fee account of the whole
+//! block when it's sealed. The choice depends only on the operations of the block,
+//! so the restored pending block gets the same fee account.
+
+// Workspace uses
+use zksync_config::{FeeRoute, FeeRouteCondition, FeeRoutingOptions, FeeRoutingPolicy};
+use zksync_state::state::ZkSyncState;
+use zksync_types::{block::ExecutedOperations, AccountId, TokenLike, ZkSyncTx};
+
+/// Chooses the fee account of the block according to the configured routes.
+#[derive(Debug, Clone)]
+pub(super) struct FeeRouter {
+    policy: FeeRoutingPolicy,
+    routes: Vec<FeeRoute>,
+    /// Account receiving fees of the blocks not matched by any route.
+    default_fee_account: AccountId,
+}
+
+impl FeeRouter {
+    pub fn new(options: FeeRoutingOptions, default_fee_account: AccountId) -> Self {
+        Self {
+            policy: options.policy,
+            routes: options.routes,
+            default_fee_account,
+        }
+    }
+
+    /// Returns the fee account for the block consisting of the given operations.
+    ///
+    /// Routes to the accounts which don't exist in the `state` are ignored.
+    pub fn fee_account(&self, state: &ZkSyncState, operations: &[ExecutedOperations]) -> AccountId {
+        let mut candidates = self.routes.iter().filter_map(|route| {
+            let matched = operations
+                .iter()
+                .filter(|op| Self::matches(&route.condition, op))
+                .count();
+            if matched == 0 {
+                return None;
+            }
+
+            state
+                .get_account_by_address(&route.fee_account)
+                .map(|(account_id, _)| (account_id, matched))
+        });
+
+        let chosen = match self.policy {
+            FeeRoutingPolicy::FirstMatch => candidates.next(),
+            // `max_by_key` returns the last maximum element, so the routes are reversed
+            // in order to prefer the earlier one.
+            FeeRoutingPolicy::MostOperations => {
+                candidates.rev().max_by_key(|(_, matched)| *matched)
+            }
+        };
+
+        chosen
+            .map(|(account_id, _)| account_id)
+            .unwrap_or(self.default_fee_account)
+    }
+
+    /// Checks whether the fee-paying operation is matched by the route condition.
+    fn matches(condition: &FeeRouteCondition, operation: &ExecutedOperations) -> bool {
+        let tx = match operation {
+            ExecutedOperations::Tx(tx) if tx.success => &tx.signed_tx.tx,
+            _ => return false,
+        };
+        let fee_token = match tx.get_fee_info() {
+            Some((_, TokenLike::Id(token), _, _)) => token,
+            _ => return false,
+        };
+
+        match condition {
+            FeeRouteCondition::Token(token) => fee_token == *token,
+            FeeRouteCondition::TxType(tx_type) => tx_type == Self::tx_type(tx),
+        }
+    }
+
+    /// Name of the transaction type, as used in its JSON representation.
+    fn tx_type(tx: &ZkSyncTx) -> &'static str {
+        match tx {
+            ZkSyncTx::Transfer(_) => "Transfer",
+            ZkSyncTx::Withdraw(_) => "Withdraw",
+            ZkSyncTx::Close(_) => "Close",
+            ZkSyncTx::ChangePubKey(_) => "ChangePubKey",
+            ZkSyncTx::ForcedExit(_) => "ForcedExit",
+            ZkSyncTx::Swap(_) => "Swap",
+        }
+    }
+}
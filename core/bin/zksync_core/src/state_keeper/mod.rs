@@ -9,39 +9,66 @@ use futures::{
 use itertools::Itertools;
 use tokio::task::JoinHandle;
 // Workspace uses
+use zksync_config::{BlockSealingOptions, FeeRoutingOptions};
 use zksync_crypto::ff;
 use zksync_state::state::{CollectedFee, OpSuccess, ZkSyncState};
 use zksync_storage::ConnectionPool;
 use zksync_types::{
     block::{
-        Block, ExecutedOperations, ExecutedPriorityOp, ExecutedTx,
+        Block, BlockSealReason, ExecutedOperations, ExecutedPriorityOp, ExecutedTx,
         PendingBlock as SendablePendingBlock,
     },
     gas_counter::GasCounter,
     mempool::SignedTxVariant,
-    tx::{TxHash, ZkSyncTx},
+    tx::{
+        BatchExecutionError, SimulatedFee, TracedFee, TxExecutionError, TxExecutionTrace, TxHash,
+        TxSimulationResult, ZkSyncTx,
+    },
     Account, AccountId, AccountTree, AccountUpdate, AccountUpdates, ActionType, Address,
     BlockNumber, PriorityOp, SignedZkSyncTx,
 };
+use zksync_utils::unix_timestamp;
 // Local uses
 use crate::{
     committer::{AppliedUpdatesRequest, BlockCommitRequest, CommitRequest},
-    mempool::ProposedBlock,
+    mempool::{ProposedBlock, TxAddError},
+};
+
+use self::{
+    fee_routing::FeeRouter,
+    sealing::{OpRequirements, SealingCriteria},
 };
 
+mod fee_routing;
+mod sealing;
+pub mod snapshot;
 #[cfg(test)]
 mod tests;
 
+/// Maximum age (in seconds) of the block timestamp accepted by the contract on commit,
+/// see `COMMIT_TIMESTAMP_NOT_OLDER` in the contract config.
+const COMMIT_TIMESTAMP_NOT_OLDER: u64 = 8 * 60 * 60;
+
 pub enum ExecutedOpId {
     Transaction(TxHash),
     PriorityOp(u64),
 }
 
+#[derive(Debug)]
 pub enum StateKeeperRequest {
     GetAccount(Address, oneshot::Sender<Option<(AccountId, Account)>>),
     GetLastUnprocessedPriorityOp(oneshot::Sender<u64>),
     ExecuteMiniBlock(ProposedBlock),
-    SealBlock,
+    /// Seals the pending block regardless of the sealing criteria.
+    /// Responds with `false` if the pending block is empty, so there was nothing to seal.
+    SealBlock(oneshot::Sender<bool>),
+    /// Executes the transaction (or the batch) without applying its changes to the state.
+    /// Result is returned for each transaction of the batch.
+    /// Batches are subject to the same limits as the ones added to the mempool.
+    SimulateTx(
+        SignedTxVariant,
+        oneshot::Sender<Result<Vec<TxSimulationResult>, TxAddError>>,
+    ),
 }
 
 #[derive(Debug, Clone)]
@@ -61,6 +88,10 @@ struct PendingBlock {
     collected_fees: Vec<CollectedFee>,
     /// Number of stored account updates in the db (from `account_updates` field)
     stored_account_updates: usize,
+    /// Time when the first operation was included into the block.
+    first_op_at: Option<Instant>,
+    /// Unix timestamp of the block, set when the first operation is included into the block.
+    timestamp: Option<u64>,
 }
 
 impl PendingBlock {
@@ -78,8 +109,17 @@ impl PendingBlock {
             fast_processing_required: false,
             collected_fees: Vec::new(),
             stored_account_updates: 0,
+            first_op_at: None,
+            timestamp: None,
         }
     }
+
+    /// Returns `true` if the block timestamp is too old for the block to be committed.
+    fn is_commit_expired(&self, now: u64) -> bool {
+        self.timestamp
+            .map(|timestamp| timestamp + COMMIT_TIMESTAMP_NOT_OLDER < now)
+            .unwrap_or(false)
+    }
 }
 
 /// Responsible for tx processing and block forming.
@@ -87,7 +127,7 @@ pub struct ZkSyncStateKeeper {
     /// Current plasma state
     state: ZkSyncState,
 
-    fee_account_id: AccountId,
+    fee_router: FeeRouter,
     current_unprocessed_priority_op: u64,
 
     pending_block: PendingBlock,
@@ -96,9 +136,9 @@ pub struct ZkSyncStateKeeper {
     tx_for_commitments: mpsc::Sender<CommitRequest>,
 
     available_block_chunk_sizes: Vec<usize>,
-    max_miniblock_iterations: usize,
-    fast_miniblock_iterations: usize,
     max_number_of_withdrawals_per_block: usize,
+    /// Criteria which cause the pending block to be sealed.
+    sealing_criteria: SealingCriteria,
 
     // Two fields below are for optimization: we don't want to overwrite all the block contents over and over.
     // With these fields we'll be able save the diff between two pending block states only.
@@ -346,6 +386,8 @@ impl ZkSyncStateKeeper {
         max_miniblock_iterations: usize,
         fast_miniblock_iterations: usize,
         max_number_of_withdrawals_per_block: usize,
+        sealing_options: BlockSealingOptions,
+        fee_routing_options: FeeRoutingOptions,
     ) -> Self {
         assert!(!available_block_chunk_sizes.is_empty());
 
@@ -366,17 +408,23 @@ impl ZkSyncStateKeeper {
             .expect("Fee account should be present in the account tree");
         // Keeper starts with the NEXT block
         let max_block_size = *available_block_chunk_sizes.iter().max().unwrap();
+        let sealing_criteria = SealingCriteria::new(
+            &sealing_options,
+            max_block_size,
+            max_number_of_withdrawals_per_block,
+            max_miniblock_iterations,
+            fast_miniblock_iterations,
+        );
         let keeper = ZkSyncStateKeeper {
             state,
-            fee_account_id,
+            fee_router: FeeRouter::new(fee_routing_options, fee_account_id),
             current_unprocessed_priority_op: initial_state.unprocessed_priority_op,
             rx_for_blocks,
             tx_for_commitments,
             pending_block: PendingBlock::new(initial_state.unprocessed_priority_op, max_block_size),
             available_block_chunk_sizes,
-            max_miniblock_iterations,
-            fast_miniblock_iterations,
             max_number_of_withdrawals_per_block,
+            sealing_criteria,
 
             success_txs_pending_len: 0,
             failed_txs_pending_len: 0,
@@ -399,6 +447,23 @@ impl ZkSyncStateKeeper {
             // `apply_txs_batch` to preserve the original execution order. Otherwise there may
             // be a state corruption, if e.g. `Deposit` will be executed before `TransferToNew`
             // and account IDs will change.
+            // Operations must be executed at the same block timestamp. Block without executed
+            // operations gets the new timestamp, so it doesn't get too old to be committed.
+            if !pending_block.success_operations.is_empty() {
+                self.pending_block.timestamp = pending_block.timestamp;
+            }
+            if self.pending_block.is_commit_expired(unix_timestamp()) {
+                // Timestamp can't be changed without re-executing the operations, which may
+                // give another result. Such a block has to be handled by the server operator.
+                log::error!(
+                    "Restored pending block #{} has the timestamp {:?} which is too old \
+                     for the block to be committed",
+                    self.state.block_number,
+                    self.pending_block.timestamp
+                );
+                metrics::counter!("state_keeper.restored_block_commit_expired", 1);
+            }
+
             let mut txs_count = 0;
             let mut priority_op_count = 0;
             for operation in pending_block.success_operations {
@@ -500,8 +565,15 @@ impl ZkSyncStateKeeper {
                 StateKeeperRequest::ExecuteMiniBlock(proposed_block) => {
                     self.execute_proposed_block(proposed_block).await;
                 }
-                StateKeeperRequest::SealBlock => {
-                    self.seal_pending_block().await;
+                StateKeeperRequest::SealBlock(sender) => {
+                    let sealed = !self.pending_block_is_empty();
+                    if sealed {
+                        self.seal_pending_block(BlockSealReason::Forced).await;
+                    }
+                    sender.send(sealed).unwrap_or_default();
+                }
+                StateKeeperRequest::SimulateTx(tx, sender) => {
+                    sender.send(self.simulate_tx(&tx)).unwrap_or_default();
                 }
             }
         }
@@ -523,8 +595,8 @@ impl ZkSyncStateKeeper {
                 Ok(exec_op) => {
                     executed_ops.push(exec_op);
                 }
-                Err(priority_op) => {
-                    self.seal_pending_block().await;
+                Err((priority_op, reason)) => {
+                    self.seal_pending_block(reason).await;
 
                     priority_op_queue.push_front(priority_op);
                 }
@@ -539,11 +611,11 @@ impl ZkSyncStateKeeper {
                         Ok(exec_op) => {
                             executed_ops.push(exec_op);
                         }
-                        Err(_) => {
-                            // We could not execute the tx due to either of block size limit
-                            // or the withdraw operations limit, so we seal this block and
+                        Err(reason) => {
+                            // We could not execute the tx due to one of the sealing criteria
+                            // (e.g. the block size limit), so we seal this block and
                             // the last transaction will go to the next block instead.
-                            self.seal_pending_block().await;
+                            self.seal_pending_block(reason).await;
 
                             tx_queue.push_front(variant);
                         }
@@ -554,11 +626,11 @@ impl ZkSyncStateKeeper {
                         Ok(mut ops) => {
                             executed_ops.append(&mut ops);
                         }
-                        Err(_) => {
-                            // We could not execute the batch tx due to either of block size limit
-                            // or the withdraw operations limit, so we seal this block and
+                        Err(reason) => {
+                            // We could not execute the batch tx due to one of the sealing criteria
+                            // (e.g. the block size limit), so we seal this block and
                             // the last transaction will go to the next block instead.
-                            self.seal_pending_block().await;
+                            self.seal_pending_block(reason).await;
 
                             tx_queue.push_front(variant);
                         }
@@ -571,16 +643,11 @@ impl ZkSyncStateKeeper {
             self.pending_block.pending_block_iteration += 1;
         }
 
-        // If pending block contains withdrawals we seal it faster
-        let max_miniblock_iterations = if self.pending_block.fast_processing_required {
-            self.fast_miniblock_iterations
-        } else {
-            self.max_miniblock_iterations
-        };
-        if self.pending_block.chunks_left == 0
-            || self.pending_block.pending_block_iteration > max_miniblock_iterations
-        {
-            self.seal_pending_block().await;
+        let seal_reason = self
+            .sealing_criteria
+            .triggered(&self.pending_block, Instant::now());
+        if let Some(reason) = seal_reason {
+            self.seal_pending_block(reason).await;
         } else {
             // We've already incremented the pending block iteration, so this iteration will count towards
             // reaching the block commitment timeout.
@@ -593,33 +660,44 @@ impl ZkSyncStateKeeper {
         metrics::histogram!("state_keeper.execute_proposed_block", start.elapsed());
     }
 
-    // Err if there is no space in current block
+    /// Returns the operation back along with the reason to seal the block,
+    /// if the operation doesn't fit into the current block.
     fn apply_priority_op(
         &mut self,
         priority_op: PriorityOp,
-    ) -> Result<ExecutedOperations, PriorityOp> {
+    ) -> Result<ExecutedOperations, (PriorityOp, BlockSealReason)> {
         let start = Instant::now();
         let chunks_needed = priority_op.data.chunks();
-        if self.pending_block.chunks_left < chunks_needed {
-            return Err(priority_op);
-        }
 
         // Check if adding this transaction to the block won't make the contract operations
         // too expensive.
+        let mut gas_counter = self.pending_block.gas_counter.clone();
         let non_executed_op = self
             .state
             .priority_op_to_zksync_op(priority_op.data.clone());
-        if self
-            .pending_block
-            .gas_counter
-            .add_op(&non_executed_op)
-            .is_err()
-        {
+        if gas_counter.add_op(&non_executed_op).is_err() {
             // We've reached the gas limit, seal the block.
             // This transaction will go into the next one.
-            return Err(priority_op);
+            return Err((priority_op, BlockSealReason::GasLimit));
         }
 
+        let requirements = OpRequirements {
+            chunks: chunks_needed,
+            withdrawals: 0,
+            gas_counter,
+        };
+        if let Some(reason) = self
+            .sealing_criteria
+            .rejecting(&self.pending_block, &requirements)
+        {
+            return Err((priority_op, reason));
+        }
+        self.pending_block.gas_counter = requirements.gas_counter;
+        self.pending_block.first_op_at.get_or_insert(start);
+        self.pending_block
+            .timestamp
+            .get_or_insert_with(unix_timestamp);
+
         let OpSuccess {
             fee,
             mut updates,
@@ -653,17 +731,13 @@ impl ZkSyncStateKeeper {
         &mut self,
         txs: &[SignedZkSyncTx],
         batch_id: i64,
-    ) -> Result<Vec<ExecutedOperations>, ()> {
+    ) -> Result<Vec<ExecutedOperations>, BlockSealReason> {
         metrics::gauge!("tx_batch_size", txs.len() as f64);
         let start = Instant::now();
         let chunks_needed = self.state.chunks_for_batch(txs);
 
-        // If we can't add the tx to the block due to the size limit, we return this tx,
-        // seal the block and execute it again.
-        if self.pending_block.chunks_left < chunks_needed {
-            return Err(());
-        }
-
+        let mut gas_counter = self.pending_block.gas_counter.clone();
+        let mut withdrawals = 0;
         for tx in txs {
             // Check if adding this transaction to the block won't make the contract operations
             // too expensive.
@@ -671,48 +745,72 @@ impl ZkSyncStateKeeper {
             if let Ok(non_executed_op) = non_executed_op {
                 // We only care about successful conversions, since if conversion failed,
                 // then transaction will fail as well (as it shares the same code base).
-                if self
-                    .pending_block
-                    .gas_counter
-                    .add_op(&non_executed_op)
-                    .is_err()
-                {
+                if gas_counter.add_op(&non_executed_op).is_err() {
                     // We've reached the gas limit, seal the block.
                     // This transaction will go into the next one.
-                    return Err(());
+                    return Err(BlockSealReason::GasLimit);
                 }
             }
 
             if matches!(&tx.tx, &ZkSyncTx::Withdraw(_)) {
-                // Increase amount of the withdraw operations in this block.
-                self.pending_block.withdrawals_amount += 1;
+                withdrawals += 1;
             }
+        }
 
-            // Check if we've reached the withdraw operations amount limit.
-            // If so, this block will be sealed and this tx will go to the next block.
-            if self.pending_block.withdrawals_amount
-                > self.max_number_of_withdrawals_per_block as u32
-            {
-                return Err(());
-            }
+        // If the batch doesn't fit into the block (e.g. due to the size limit or the withdraw
+        // operations limit), we return it, seal the block and execute the batch again.
+        let requirements = OpRequirements {
+            chunks: chunks_needed,
+            withdrawals,
+            gas_counter,
+        };
+        if let Some(reason) = self
+            .sealing_criteria
+            .rejecting(&self.pending_block, &requirements)
+        {
+            return Err(reason);
         }
+        self.pending_block.gas_counter = requirements.gas_counter;
+        // Increase amount of the withdraw operations in this block.
+        self.pending_block.withdrawals_amount += withdrawals;
+        self.pending_block.first_op_at.get_or_insert(start);
+        let block_timestamp = *self
+            .pending_block
+            .timestamp
+            .get_or_insert_with(unix_timestamp);
 
-        let all_updates = self.state.execute_txs_batch(txs);
+        let invalid_tx = txs
+            .iter()
+            .position(|tx| !tx.tx.time_range().is_valid(block_timestamp));
+        let all_updates = match invalid_tx {
+            None => self.state.execute_txs_batch(txs),
+            Some(index) => {
+                // Batch is executed atomically, so if any of its transactions can't be executed
+                // at the block timestamp, the whole batch fails.
+                let error = BatchExecutionError {
+                    index,
+                    error: TxExecutionError::InvalidTimeRange,
+                };
+                txs.iter().map(|_| Err(error)).collect()
+            }
+        };
         let mut executed_operations = Vec::new();
 
-        for (tx, tx_updates) in txs.iter().zip(all_updates) {
+        for (tx_index, (tx, tx_updates)) in txs.iter().zip(all_updates).enumerate() {
             match tx_updates {
                 Ok(OpSuccess {
                     fee,
                     mut updates,
                     executed_op,
                 }) => {
+                    let block_index = self.pending_block.pending_op_block_index;
+                    let trace = execution_trace(block_index, executed_op.chunks(), &fee, &updates);
+
                     self.pending_block.chunks_left -= executed_op.chunks();
                     self.pending_block.account_updates.append(&mut updates);
                     if let Some(fee) = fee {
                         self.pending_block.collected_fees.push(fee);
                     }
-                    let block_index = self.pending_block.pending_op_block_index;
                     self.pending_block.pending_op_block_index += 1;
 
                     let exec_result = ExecutedOperations::Tx(Box::new(ExecutedTx {
@@ -720,9 +818,11 @@ impl ZkSyncStateKeeper {
                         success: true,
                         op: Some(executed_op),
                         fail_reason: None,
+                        fail_code: None,
                         block_index: Some(block_index),
                         created_at: chrono::Utc::now(),
                         batch_id: Some(batch_id),
+                        trace: Some(trace),
                     }));
                     self.pending_block
                         .success_operations
@@ -736,9 +836,11 @@ impl ZkSyncStateKeeper {
                         success: false,
                         op: None,
                         fail_reason: Some(e.to_string()),
+                        fail_code: Some(e.tx_error(tx_index)),
                         block_index: None,
                         created_at: chrono::Utc::now(),
                         batch_id: Some(batch_id),
+                        trace: None,
                     };
                     self.pending_block.failed_txs.push(failed_tx.clone());
                     let exec_result = ExecutedOperations::Tx(Box::new(failed_tx));
@@ -751,34 +853,44 @@ impl ZkSyncStateKeeper {
         Ok(executed_operations)
     }
 
-    fn apply_tx(&mut self, tx: &SignedZkSyncTx) -> Result<ExecutedOperations, ()> {
+    fn apply_tx(&mut self, tx: &SignedZkSyncTx) -> Result<ExecutedOperations, BlockSealReason> {
         let start = Instant::now();
         let chunks_needed = self.state.chunks_for_tx(&tx);
 
-        // If we can't add the tx to the block due to the size limit, we return this tx,
-        // seal the block and execute it again.
-        if self.pending_block.chunks_left < chunks_needed {
-            return Err(());
-        }
-
         // Check if adding this transaction to the block won't make the contract operations
         // too expensive.
+        let mut gas_counter = self.pending_block.gas_counter.clone();
         let non_executed_op = self.state.zksync_tx_to_zksync_op(tx.tx.clone());
         if let Ok(non_executed_op) = non_executed_op {
             // We only care about successful conversions, since if conversion failed,
             // then transaction will fail as well (as it shares the same code base).
-            if self
-                .pending_block
-                .gas_counter
-                .add_op(&non_executed_op)
-                .is_err()
-            {
+            if gas_counter.add_op(&non_executed_op).is_err() {
                 // We've reached the gas limit, seal the block.
                 // This transaction will go into the next one.
-                return Err(());
+                return Err(BlockSealReason::GasLimit);
             }
         }
 
+        // If the tx doesn't fit into the block (e.g. due to the size limit or the withdraw
+        // operations limit), we return it, seal the block and execute the tx again.
+        let requirements = OpRequirements {
+            chunks: chunks_needed,
+            withdrawals: matches!(&tx.tx, ZkSyncTx::Withdraw(_)) as u32,
+            gas_counter,
+        };
+        if let Some(reason) = self
+            .sealing_criteria
+            .rejecting(&self.pending_block, &requirements)
+        {
+            return Err(reason);
+        }
+        self.pending_block.gas_counter = requirements.gas_counter;
+        self.pending_block.first_op_at.get_or_insert(start);
+        let block_timestamp = *self
+            .pending_block
+            .timestamp
+            .get_or_insert_with(unix_timestamp);
+
         if let ZkSyncTx::Withdraw(tx) = &tx.tx {
             // Increase amount of the withdraw operations in this block.
             self.pending_block.withdrawals_amount += 1;
@@ -789,13 +901,11 @@ impl ZkSyncStateKeeper {
             }
         }
 
-        // Check if we've reached the withdraw operations amount limit.
-        // If so, this block will be sealed and this tx will go to the next block.
-        if self.pending_block.withdrawals_amount > self.max_number_of_withdrawals_per_block as u32 {
-            return Err(());
-        }
-
-        let tx_updates = self.state.execute_tx(tx.tx.clone());
+        let tx_updates = if tx.tx.time_range().is_valid(block_timestamp) {
+            self.state.execute_tx(tx.tx.clone())
+        } else {
+            Err(TxExecutionError::InvalidTimeRange)
+        };
 
         let exec_result = match tx_updates {
             Ok(OpSuccess {
@@ -803,12 +913,14 @@ impl ZkSyncStateKeeper {
                 mut updates,
                 executed_op,
             }) => {
+                let block_index = self.pending_block.pending_op_block_index;
+                let trace = execution_trace(block_index, chunks_needed, &fee, &updates);
+
                 self.pending_block.chunks_left -= chunks_needed;
                 self.pending_block.account_updates.append(&mut updates);
                 if let Some(fee) = fee {
                     self.pending_block.collected_fees.push(fee);
                 }
-                let block_index = self.pending_block.pending_op_block_index;
                 self.pending_block.pending_op_block_index += 1;
 
                 let exec_result = ExecutedOperations::Tx(Box::new(ExecutedTx {
@@ -816,9 +928,11 @@ impl ZkSyncStateKeeper {
                     success: true,
                     op: Some(executed_op),
                     fail_reason: None,
+                    fail_code: None,
                     block_index: Some(block_index),
                     created_at: chrono::Utc::now(),
                     batch_id: None,
+                    trace: Some(trace),
                 }));
                 self.pending_block
                     .success_operations
@@ -832,9 +946,11 @@ impl ZkSyncStateKeeper {
                     success: false,
                     op: None,
                     fail_reason: Some(e.to_string()),
+                    fail_code: Some(e),
                     block_index: None,
                     created_at: chrono::Utc::now(),
                     batch_id: None,
+                    trace: None,
                 };
                 self.pending_block.failed_txs.push(failed_tx.clone());
                 ExecutedOperations::Tx(Box::new(failed_tx))
@@ -846,7 +962,16 @@ impl ZkSyncStateKeeper {
     }
 
     /// Finalizes the pending block, transforming it into a full block.
-    async fn seal_pending_block(&mut self) {
+    /// Checks whether the pending block contains no operations, so sealing it would
+    /// produce an empty block.
+    fn pending_block_is_empty(&self) -> bool {
+        self.pending_block.success_operations.is_empty()
+            && self.pending_block.failed_txs.is_empty()
+            && self.pending_block.unprocessed_priority_op_before
+                == self.current_unprocessed_priority_op
+    }
+
+    async fn seal_pending_block(&mut self, reason: BlockSealReason) {
         let start = Instant::now();
         let mut pending_block = std::mem::replace(
             &mut self.pending_block,
@@ -864,9 +989,12 @@ impl ZkSyncStateKeeper {
         self.failed_txs_pending_len = 0;
 
         // Apply fees of pending block
+        let fee_account_id = self
+            .fee_router
+            .fee_account(&self.state, &pending_block.success_operations);
         let fee_updates = self
             .state
-            .collect_fee(&pending_block.collected_fees, self.fee_account_id);
+            .collect_fee(&pending_block.collected_fees, fee_account_id);
         pending_block
             .account_updates
             .extend(fee_updates.into_iter());
@@ -881,21 +1009,26 @@ impl ZkSyncStateKeeper {
 
         let commit_gas_limit = pending_block.gas_counter.commit_gas_limit();
         let verify_gas_limit = pending_block.gas_counter.verify_gas_limit();
+        let timestamp = pending_block.timestamp.unwrap_or_else(unix_timestamp);
 
-        let block_commit_request = BlockCommitRequest {
-            block: Block::new_from_available_block_sizes(
-                self.state.block_number,
-                self.state.root_hash(),
-                self.fee_account_id,
-                block_transactions,
-                (
-                    pending_block.unprocessed_priority_op_before,
-                    self.current_unprocessed_priority_op,
-                ),
-                &self.available_block_chunk_sizes,
-                commit_gas_limit,
-                verify_gas_limit,
+        let mut block = Block::new_from_available_block_sizes(
+            self.state.block_number,
+            self.state.root_hash(),
+            fee_account_id,
+            block_transactions,
+            (
+                pending_block.unprocessed_priority_op_before,
+                self.current_unprocessed_priority_op,
             ),
+            &self.available_block_chunk_sizes,
+            commit_gas_limit,
+            verify_gas_limit,
+            timestamp,
+        );
+        block.seal_reason = Some(reason);
+
+        let block_commit_request = BlockCommitRequest {
+            block,
             accounts_updated: pending_block.account_updates.clone(),
         };
         let first_update_order_id = pending_block.stored_account_updates;
@@ -908,12 +1041,14 @@ impl ZkSyncStateKeeper {
         self.state.block_number += 1;
 
         log::info!(
-            "Creating full block: {}, operations: {}, chunks_left: {}, miniblock iterations: {}",
+            "Creating full block: {}, operations: {}, chunks_left: {}, miniblock iterations: {}, seal reason: {}",
             block_commit_request.block.block_number,
             block_commit_request.block.block_transactions.len(),
             pending_block.chunks_left,
-            pending_block.pending_block_iteration
+            pending_block.pending_block_iteration,
+            reason
         );
+        metrics::counter!("state_keeper.sealed_blocks", 1, "reason" => reason.as_str());
 
         let commit_request = CommitRequest::Block((block_commit_request, applied_updates_request));
         self.tx_for_commitments
@@ -952,6 +1087,7 @@ impl ZkSyncStateKeeper {
             pending_block_iteration: self.pending_block.pending_block_iteration,
             success_operations: new_success_operations,
             failed_txs: new_failed_operations,
+            timestamp: self.pending_block.timestamp,
         };
         let first_update_order_id = self.pending_block.stored_account_updates;
         let account_updates = self.pending_block.account_updates[first_update_order_id..].to_vec();
@@ -981,6 +1117,92 @@ impl ZkSyncStateKeeper {
     fn account(&self, address: &Address) -> Option<(AccountId, Account)> {
         self.state.get_account_by_address(address)
     }
+
+    /// Executes the transaction (or the batch) against the copy of the current state,
+    /// so the changes made by it are discarded.
+    fn simulate_tx(&self, tx: &SignedTxVariant) -> Result<Vec<TxSimulationResult>, TxAddError> {
+        let start = Instant::now();
+
+        let mut state = self.state.clone();
+        let results = match tx {
+            SignedTxVariant::Tx(tx) => vec![state
+                .execute_tx(tx.tx.clone())
+                .map_err(|error| (error.to_string(), error))],
+            SignedTxVariant::Batch(batch) => {
+                self.check_batch_limits(&batch.txs)?;
+                state
+                    .execute_txs_batch(&batch.txs)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(tx_index, result)| {
+                        result.map_err(|error| (error.to_string(), error.tx_error(tx_index)))
+                    })
+                    .collect()
+            }
+        };
+
+        let results = results
+            .into_iter()
+            .map(|result| match result {
+                Ok(OpSuccess {
+                    fee,
+                    updates,
+                    executed_op,
+                }) => {
+                    let fee = fee.map(|fee| SimulatedFee {
+                        token: fee.token,
+                        amount: fee.amount,
+                    });
+                    TxSimulationResult::success(updates, executed_op.chunks(), fee)
+                }
+                Err((fail_reason, fail_code)) => {
+                    TxSimulationResult::failure(fail_reason, fail_code)
+                }
+            })
+            .collect();
+
+        metrics::histogram!("state_keeper.simulate_tx", start.elapsed());
+        Ok(results)
+    }
+
+    /// Checks that the batch fits into the block, as it's done by the mempool.
+    fn check_batch_limits(&self, txs: &[SignedZkSyncTx]) -> Result<(), TxAddError> {
+        if txs.is_empty() {
+            return Err(TxAddError::EmptyBatch);
+        }
+
+        let max_block_size = *self
+            .available_block_chunk_sizes
+            .last()
+            .expect("failed to get max block size");
+        if self.state.chunks_for_batch(txs) > max_block_size {
+            return Err(TxAddError::BatchTooBig);
+        }
+
+        let withdrawals = txs.iter().filter(|tx| tx.tx.is_withdraw()).count();
+        if withdrawals > self.max_number_of_withdrawals_per_block {
+            return Err(TxAddError::BatchWithdrawalsOverload);
+        }
+        Ok(())
+    }
+}
+
+/// Creates the trace of the successfully executed transaction.
+fn execution_trace(
+    block_index: u32,
+    chunks: usize,
+    fee: &Option<CollectedFee>,
+    updates: &[(AccountId, AccountUpdate)],
+) -> TxExecutionTrace {
+    TxExecutionTrace {
+        block_index,
+        chunks,
+        fee: fee.as_ref().map(|fee| TracedFee {
+            token: fee.token,
+            amount: fee.amount.clone(),
+        }),
+        updates: updates.to_vec(),
+    }
 }
 
 #[must_use]
@@ -0,0 +1,220 @@
+//! Criteria of sealing the pending block.
+//!
+//! Before an operation is included into the pending block, it's checked against every criterion:
+//! if the operation doesn't fit, the block is sealed and the operation goes to the next one.
+//! After each executed miniblock criteria are checked again to decide whether the block
+//! should be sealed without waiting for more operations.
+
+// Built-in deps
+use std::time::{Duration, Instant};
+// Workspace uses
+use zksync_config::BlockSealingOptions;
+use zksync_types::{
+    block::BlockSealReason,
+    gas_counter::{GasCounter, TX_GAS_LIMIT},
+    U256,
+};
+// Local uses
+use super::PendingBlock;
+
+/// Resources of the pending block required by the operation (or the batch of operations).
+#[derive(Debug, Clone)]
+pub(super) struct OpRequirements {
+    /// Number of chunks required for the operation.
+    pub chunks: usize,
+    /// Number of withdrawal operations.
+    pub withdrawals: u32,
+    /// Gas counter of the pending block with the cost of the operation added.
+    pub gas_counter: GasCounter,
+}
+
+/// Condition under which the pending block has to be sealed.
+pub(super) trait SealingCriterion: std::fmt::Debug + Send + Sync {
+    /// Reason of the sealing reported when the criterion fires.
+    fn reason(&self) -> BlockSealReason;
+
+    /// Returns `false` if the operation can't be included into the pending block,
+    /// so the block has to be sealed first.
+    fn fits(&self, _block: &PendingBlock, _op: &OpRequirements) -> bool {
+        true
+    }
+
+    /// Returns `true` if the pending block has to be sealed after the executed miniblock.
+    fn should_seal(&self, _block: &PendingBlock, _now: Instant) -> bool {
+        false
+    }
+}
+
+/// Set of the criteria checked by the state keeper.
+#[derive(Debug)]
+pub(super) struct SealingCriteria {
+    criteria: Vec<Box<dyn SealingCriterion>>,
+}
+
+impl SealingCriteria {
+    pub fn new(
+        options: &BlockSealingOptions,
+        max_block_size: usize,
+        max_withdrawals: usize,
+        max_miniblock_iterations: usize,
+        fast_miniblock_iterations: usize,
+    ) -> Self {
+        Self {
+            criteria: vec![
+                Box::new(ChunksFill::new(max_block_size, options.chunks_fill_percent)),
+                Box::new(GasLimit::new(options.gas_limit_percent)),
+                Box::new(WithdrawalsLimit {
+                    max_withdrawals: max_withdrawals as u32,
+                }),
+                Box::new(BlockAge {
+                    max_age: options.max_block_age,
+                }),
+                Box::new(MiniblockIterations {
+                    max_iterations: max_miniblock_iterations,
+                    fast_iterations: fast_miniblock_iterations,
+                }),
+            ],
+        }
+    }
+
+    /// Returns the reason to seal the pending block, if the operation doesn't fit into it.
+    pub fn rejecting(&self, block: &PendingBlock, op: &OpRequirements) -> Option<BlockSealReason> {
+        self.criteria
+            .iter()
+            .find(|criterion| !criterion.fits(block, op))
+            .map(|criterion| criterion.reason())
+    }
+
+    /// Returns the reason to seal the pending block after the executed miniblock, if any.
+    pub fn triggered(&self, block: &PendingBlock, now: Instant) -> Option<BlockSealReason> {
+        self.criteria
+            .iter()
+            .find(|criterion| criterion.should_seal(block, now))
+            .map(|criterion| criterion.reason())
+    }
+}
+
+/// Block is sealed once the configured part of its chunks is filled.
+/// Operation that requires more chunks than left in the block goes to the next one.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct ChunksFill {
+    block_size: usize,
+    /// Number of filled chunks at which the block is sealed.
+    fill_threshold: usize,
+}
+
+impl ChunksFill {
+    pub fn new(block_size: usize, fill_percent: u32) -> Self {
+        let fill_percent = fill_percent.min(100) as usize;
+        // Round up, so the empty block is never considered filled.
+        let fill_threshold = ((block_size * fill_percent + 99) / 100).max(1);
+
+        Self {
+            block_size,
+            fill_threshold,
+        }
+    }
+}
+
+impl SealingCriterion for ChunksFill {
+    fn reason(&self) -> BlockSealReason {
+        BlockSealReason::ChunksFill
+    }
+
+    fn fits(&self, block: &PendingBlock, op: &OpRequirements) -> bool {
+        op.chunks <= block.chunks_left
+    }
+
+    fn should_seal(&self, block: &PendingBlock, _now: Instant) -> bool {
+        self.block_size - block.chunks_left >= self.fill_threshold
+    }
+}
+
+/// Block is sealed before the commit or verify Ethereum transaction requires
+/// more than the configured part of the transaction gas limit.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct GasLimit {
+    gas_limit: U256,
+}
+
+impl GasLimit {
+    pub fn new(gas_limit_percent: u32) -> Self {
+        let gas_limit_percent = gas_limit_percent.min(100);
+
+        Self {
+            gas_limit: U256::from(TX_GAS_LIMIT) * U256::from(gas_limit_percent) / U256::from(100),
+        }
+    }
+}
+
+impl SealingCriterion for GasLimit {
+    fn reason(&self) -> BlockSealReason {
+        BlockSealReason::GasLimit
+    }
+
+    fn fits(&self, block: &PendingBlock, op: &OpRequirements) -> bool {
+        // The first operation is always accepted, otherwise it would be postponed forever
+        // if the limit is set lower than the cost of a single operation.
+        block.pending_op_block_index == 0
+            || (op.gas_counter.commit_gas_limit() <= self.gas_limit
+                && op.gas_counter.verify_gas_limit() <= self.gas_limit)
+    }
+}
+
+/// Block is sealed once there is no room for the next withdrawal operation.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct WithdrawalsLimit {
+    max_withdrawals: u32,
+}
+
+impl SealingCriterion for WithdrawalsLimit {
+    fn reason(&self) -> BlockSealReason {
+        BlockSealReason::WithdrawalsLimit
+    }
+
+    fn fits(&self, block: &PendingBlock, op: &OpRequirements) -> bool {
+        block.withdrawals_amount + op.withdrawals <= self.max_withdrawals
+    }
+}
+
+/// Block is sealed once its oldest operation has been waiting for the configured time.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct BlockAge {
+    max_age: Duration,
+}
+
+impl SealingCriterion for BlockAge {
+    fn reason(&self) -> BlockSealReason {
+        BlockSealReason::BlockAge
+    }
+
+    fn should_seal(&self, block: &PendingBlock, now: Instant) -> bool {
+        block
+            .first_op_at
+            .map(|first_op_at| now.saturating_duration_since(first_op_at) >= self.max_age)
+            .unwrap_or(false)
+    }
+}
+
+/// Block is sealed after the configured number of miniblock iterations.
+/// Blocks with fast withdrawals use a separate (usually smaller) limit.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct MiniblockIterations {
+    max_iterations: usize,
+    fast_iterations: usize,
+}
+
+impl SealingCriterion for MiniblockIterations {
+    fn reason(&self) -> BlockSealReason {
+        BlockSealReason::MiniblockIterations
+    }
+
+    fn should_seal(&self, block: &PendingBlock, _now: Instant) -> bool {
+        let max_iterations = if block.fast_processing_required {
+            self.fast_iterations
+        } else {
+            self.max_iterations
+        };
+        block.pending_block_iteration > max_iterations
+    }
+}
@@ -0,0 +1,203 @@
+//! Snapshots of the state keeper state.
+//!
+//! Snapshot contains everything required to initialize the state keeper without
+//! rebuilding the account tree: accounts, the account tree cache, the number of the
+//! last block and the id of the first unprocessed priority operation.
+//!
+//! Encoded snapshot consists of a header followed by the JSON payload:
+//!
+//! | Field    | Size     | Description                          |
+//! |----------|----------|--------------------------------------|
+//! | magic    | 8 bytes  | `SNAPSHOT_MAGIC`                     |
+//! | version  | 4 bytes  | big-endian format version            |
+//! | checksum | 32 bytes | keccak256 hash of the payload        |
+//! | payload  | the rest | JSON-encoded `StateSnapshot`         |
+
+// Built-in deps
+use std::convert::TryInto;
+// External uses
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+// Workspace uses
+use zksync_crypto::{
+    convert::FeConvert, merkle_tree::parallel_smt::SparseMerkleTreeSerializableCacheBN256,
+    params::account_tree_depth, serialization::FrSerde, Fr,
+};
+use zksync_types::{
+    Account, AccountId, AccountTree, AccountUpdate, AccountUpdates, BlockNumber, PubKeyHash,
+};
+// Local uses
+use super::ZkSyncStateInitParams;
+
+/// Magic bytes opening every encoded snapshot.
+pub const SNAPSHOT_MAGIC: &[u8; 8] = b"ZKSNAPST";
+/// Version of the snapshot format produced by `StateSnapshot::encode`.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+const HEADER_SIZE: usize = 8 + 4 + 32;
+
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error("Snapshot header is malformed")]
+    InvalidHeader,
+
+    #[error("Snapshot format version {0} is not supported")]
+    UnsupportedVersion(u32),
+
+    #[error("Snapshot checksum doesn't match its contents")]
+    ChecksumMismatch,
+
+    #[error("Snapshot payload is malformed: {0}")]
+    InvalidPayload(#[from] serde_json::Error),
+
+    #[error("Restored root hash {actual} doesn't match the snapshot root hash {expected}")]
+    RootHashMismatch { expected: String, actual: String },
+}
+
+/// State of the state keeper at the end of the certain block.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateSnapshot {
+    pub last_block_number: BlockNumber,
+    pub unprocessed_priority_op: u64,
+    #[serde(with = "FrSerde")]
+    pub root_hash: Fr,
+    /// Accounts sorted by their ids.
+    pub accounts: Vec<(AccountId, Account)>,
+    pub tree_cache: SparseMerkleTreeSerializableCacheBN256,
+}
+
+impl StateSnapshot {
+    /// Takes a snapshot of the state keeper initial state.
+    pub fn new(init_params: &ZkSyncStateInitParams) -> Self {
+        let mut accounts = init_params
+            .tree
+            .items
+            .iter()
+            .map(|(id, account)| (*id, account.clone()))
+            .collect::<Vec<_>>();
+        accounts.sort_unstable_by_key(|(id, _)| *id);
+
+        Self {
+            last_block_number: init_params.last_block_number,
+            unprocessed_priority_op: init_params.unprocessed_priority_op,
+            root_hash: init_params.tree.root_hash(),
+            accounts,
+            tree_cache: init_params.tree.get_internals(),
+        }
+    }
+
+    /// Restores the state keeper initial state using the account tree cache and checks
+    /// that its root hash matches the recorded one.
+    ///
+    /// Hashes of the tree nodes are taken from the cache, so the accounts are not checked
+    /// against the root hash: use `StateSnapshot::verify` for that.
+    pub fn restore(self) -> Result<ZkSyncStateInitParams, SnapshotError> {
+        let mut init_params = ZkSyncStateInitParams::new();
+        for (id, account) in self.accounts {
+            init_params.insert_account(id, account);
+        }
+        init_params.tree.set_internals(self.tree_cache);
+        init_params.last_block_number = self.last_block_number;
+        init_params.unprocessed_priority_op = self.unprocessed_priority_op;
+
+        check_root_hash(self.root_hash, init_params.tree.root_hash())?;
+        Ok(init_params)
+    }
+
+    /// Rebuilds the account tree from scratch and checks that its root hash matches
+    /// the recorded one. This operation is expensive for the large trees.
+    pub fn verify(&self) -> Result<(), SnapshotError> {
+        let mut tree = AccountTree::new(account_tree_depth());
+        for (id, account) in &self.accounts {
+            tree.insert(*id, account.clone());
+        }
+
+        check_root_hash(self.root_hash, tree.root_hash())
+    }
+
+    /// Returns the account updates which create the snapshot accounts from scratch.
+    pub fn account_updates(&self) -> AccountUpdates {
+        let mut updates = Vec::new();
+        for (id, account) in &self.accounts {
+            updates.push((
+                *id,
+                AccountUpdate::Create {
+                    address: account.address,
+                    nonce: account.nonce,
+                },
+            ));
+            if account.pub_key_hash != PubKeyHash::default() {
+                updates.push((
+                    *id,
+                    AccountUpdate::ChangePubKeyHash {
+                        old_pub_key_hash: PubKeyHash::default(),
+                        new_pub_key_hash: account.pub_key_hash.clone(),
+                        old_nonce: account.nonce,
+                        new_nonce: account.nonce,
+                    },
+                ));
+            }
+
+            let mut balances = account
+                .get_nonzero_balances()
+                .into_iter()
+                .collect::<Vec<_>>();
+            balances.sort_unstable_by_key(|(token, _)| *token);
+            for (token, balance) in balances {
+                updates.push((
+                    *id,
+                    AccountUpdate::UpdateBalance {
+                        old_nonce: account.nonce,
+                        new_nonce: account.nonce,
+                        balance_update: (token, Default::default(), balance.0),
+                    },
+                ));
+            }
+        }
+        updates
+    }
+
+    /// Encodes the snapshot with the current format version.
+    pub fn encode(&self) -> Result<Vec<u8>, SnapshotError> {
+        let payload = serde_json::to_vec(self)?;
+
+        let mut bytes = Vec::with_capacity(HEADER_SIZE + payload.len());
+        bytes.extend_from_slice(SNAPSHOT_MAGIC);
+        bytes.extend_from_slice(&SNAPSHOT_VERSION.to_be_bytes());
+        bytes.extend_from_slice(&tiny_keccak::keccak256(&payload));
+        bytes.extend(payload);
+        Ok(bytes)
+    }
+
+    /// Decodes the snapshot, verifying its version and checksum.
+    ///
+    /// Note that the root hash is not checked: see `StateSnapshot::restore` and `StateSnapshot::verify`.
+    pub fn decode(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        if bytes.len() < HEADER_SIZE || bytes[..8] != SNAPSHOT_MAGIC[..] {
+            return Err(SnapshotError::InvalidHeader);
+        }
+
+        let version = u32::from_be_bytes(bytes[8..12].try_into().unwrap());
+        if version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+
+        let (checksum, payload) = bytes[12..].split_at(32);
+        if tiny_keccak::keccak256(payload) != checksum {
+            return Err(SnapshotError::ChecksumMismatch);
+        }
+
+        Ok(serde_json::from_slice(payload)?)
+    }
+}
+
+fn check_root_hash(expected: Fr, actual: Fr) -> Result<(), SnapshotError> {
+    if expected != actual {
+        return Err(SnapshotError::RootHashMismatch {
+            expected: expected.to_hex(),
+            actual: actual.to_hex(),
+        });
+    }
+    Ok(())
+}
@@ -1,15 +1,22 @@
-use super::{CommitRequest, ZkSyncStateInitParams, ZkSyncStateKeeper};
-use crate::mempool::ProposedBlock;
+use super::{CommitRequest, ZkSyncStateInitParams, ZkSyncStateKeeper, COMMIT_TIMESTAMP_NOT_OLDER};
+use crate::mempool::{ProposedBlock, TxAddError};
 use futures::{channel::mpsc, stream::StreamExt};
 use num::BigUint;
+use std::time::Duration;
+use zksync_config::{BlockSealingOptions, FeeRoutingOptions, FeeRoutingPolicy};
 use zksync_crypto::{
     priv_key_from_fs,
     rand::{Rng, SeedableRng, XorShiftRng},
     PrivateKey,
 };
 use zksync_types::{
-    mempool::SignedTxVariant, mempool::SignedTxsBatch, tx::PackedEthSignature, AccountId, H160, *,
+    block::{BlockSealReason, PendingBlock as SendablePendingBlock},
+    mempool::SignedTxVariant,
+    mempool::SignedTxsBatch,
+    tx::{PackedEthSignature, TimeRange, TxExecutionError},
+    AccountId, H160, *,
 };
+use zksync_utils::unix_timestamp;
 
 struct StateKeeperTester {
     state_keeper: ZkSyncStateKeeper,
@@ -17,12 +24,46 @@ struct StateKeeperTester {
     fee_collector: AccountId,
 }
 
+/// Sealing options under which blocks are sealed only by the chunks and
+/// the miniblock iterations limits.
+fn default_sealing_options() -> BlockSealingOptions {
+    BlockSealingOptions {
+        chunks_fill_percent: 100,
+        gas_limit_percent: 100,
+        max_block_age: Duration::from_secs(u64::MAX),
+    }
+}
+
+/// Fee routing options under which all the fees go to the fee collector.
+fn default_fee_routing_options() -> FeeRoutingOptions {
+    FeeRoutingOptions {
+        policy: FeeRoutingPolicy::FirstMatch,
+        routes: Vec::new(),
+    }
+}
+
 impl StateKeeperTester {
     fn new(
         available_chunk_size: usize,
         max_iterations: usize,
         fast_iterations: usize,
         number_of_withdrawals: usize,
+    ) -> Self {
+        Self::with_sealing_options(
+            available_chunk_size,
+            max_iterations,
+            fast_iterations,
+            number_of_withdrawals,
+            default_sealing_options(),
+        )
+    }
+
+    fn with_sealing_options(
+        available_chunk_size: usize,
+        max_iterations: usize,
+        fast_iterations: usize,
+        number_of_withdrawals: usize,
+        sealing_options: BlockSealingOptions,
     ) -> Self {
         const CHANNEL_SIZE: usize = 32768;
         let (_request_tx, request_rx) = mpsc::channel(CHANNEL_SIZE);
@@ -43,6 +84,8 @@ impl StateKeeperTester {
             max_iterations,
             fast_iterations,
             number_of_withdrawals,
+            sealing_options,
+            default_fee_routing_options(),
         );
 
         Self {
@@ -104,6 +147,7 @@ fn create_account_and_transfer<B: Into<BigUint>>(
         transfer_amount.into(),
         BigUint::from(1u32),
         account.nonce,
+        Default::default(),
         &sk,
     )
     .unwrap();
@@ -159,6 +203,7 @@ fn create_account_and_withdrawal_impl<B: Into<BigUint>>(
         withdraw_amount.into(),
         BigUint::from(1u32),
         account.nonce,
+        Default::default(),
         &sk,
     )
     .unwrap();
@@ -245,6 +290,8 @@ fn test_create_incorrect_state_keeper() {
         MAX_ITERATIONS,
         FAST_ITERATIONS,
         NUMBER_OF_WITHDRAWALS,
+        default_sealing_options(),
+        default_fee_routing_options(),
     );
 }
 
@@ -278,7 +325,7 @@ mod apply_priority_op {
         let mut tester = StateKeeperTester::new(1, 1, 1, 0);
         let deposit = create_deposit(0, 1u32);
         let result = tester.state_keeper.apply_priority_op(deposit);
-        assert!(result.is_err());
+        assert!(matches!(result, Err((_, BlockSealReason::ChunksFill))));
     }
 }
 
@@ -306,6 +353,31 @@ mod apply_tx {
         assert_eq!(pending_block.withdrawals_amount, 1);
     }
 
+    /// Checks that the trace of the executed transaction describes its changes.
+    #[test]
+    fn execution_trace() {
+        let mut tester = StateKeeperTester::new(12, 1, 1, 2);
+        let withdraw = create_account_and_withdrawal(&mut tester, 0, 1, 200u32, 145u32);
+        let trace = match tester.state_keeper.apply_tx(&withdraw) {
+            Ok(ExecutedOperations::Tx(tx)) => tx.trace.expect("Trace is not recorded"),
+            result => panic!("Unexpected result: {:?}", result),
+        };
+        let pending_block = &tester.state_keeper.pending_block;
+
+        assert_eq!(trace.block_index, 0);
+        assert_eq!(trace.chunks, 12 - pending_block.chunks_left);
+        assert_eq!(trace.updates, pending_block.account_updates);
+        let fee = trace.fee.expect("Fee is not recorded");
+        assert_eq!(fee.token, pending_block.collected_fees[0].token);
+        assert_eq!(fee.amount, pending_block.collected_fees[0].amount);
+
+        let failed_withdraw = create_account_and_withdrawal(&mut tester, 0, 2, 100u32, 145u32);
+        match tester.state_keeper.apply_tx(&failed_withdraw) {
+            Ok(ExecutedOperations::Tx(tx)) => assert!(tx.trace.is_none()),
+            result => panic!("Unexpected result: {:?}", result),
+        }
+    }
+
     /// Checks if fast withdrawal makes fast processing required
     #[test]
     fn fast_withdrawal() {
@@ -341,6 +413,41 @@ mod apply_tx {
         assert_eq!(pending_block.withdrawals_amount, 1);
     }
 
+    /// Checks that the transaction which can't be executed at the block timestamp fails.
+    #[test]
+    fn invalid_time_range() {
+        let mut tester = StateKeeperTester::new(6, 1, 1, 1);
+        let (account, sk) = tester.add_account(1);
+        tester.set_balance(1, 0, 200u32);
+        let withdraw = Withdraw::new_signed(
+            1,
+            account.address,
+            account.address,
+            0,
+            145u32.into(),
+            1u32.into(),
+            account.nonce,
+            TimeRange::new(0, 1),
+            &sk,
+        )
+        .unwrap();
+        let withdraw = SignedZkSyncTx {
+            tx: ZkSyncTx::Withdraw(Box::new(withdraw)),
+            eth_sign_data: None,
+        };
+
+        match tester.state_keeper.apply_tx(&withdraw) {
+            Ok(ExecutedOperations::Tx(tx)) => {
+                assert!(!tx.success);
+                assert_eq!(tx.fail_code, Some(TxExecutionError::InvalidTimeRange));
+            }
+            result => panic!("Unexpected result: {:?}", result),
+        }
+        let pending_block = tester.state_keeper.pending_block;
+        assert!(pending_block.timestamp.is_some());
+        assert!(pending_block.account_updates.is_empty());
+    }
+
     /// Checks if processing withdrawal fails because of
     /// small number of chunks left in the block
     #[test]
@@ -348,7 +455,7 @@ mod apply_tx {
         let mut tester = StateKeeperTester::new(1, 1, 1, 1);
         let withdraw = create_account_and_withdrawal(&mut tester, 0, 1, 200u32, 145u32);
         let result = tester.state_keeper.apply_tx(&withdraw);
-        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), BlockSealReason::ChunksFill);
     }
 
     /// Checks if processing withdrawal fails because of
@@ -358,7 +465,7 @@ mod apply_tx {
         let mut tester = StateKeeperTester::new(6, 1, 1, 0);
         let withdraw = create_account_and_withdrawal(&mut tester, 0, 1, 200u32, 145u32);
         let result = tester.state_keeper.apply_tx(&withdraw);
-        assert!(result.is_err());
+        assert_eq!(result.unwrap_err(), BlockSealReason::WithdrawalsLimit);
     }
 
     /// Checks if processing withdrawal fails because the gas limit is reached.
@@ -375,7 +482,7 @@ mod apply_tx {
             if i < withdrawals_number {
                 assert!(result.is_ok())
             } else {
-                assert!(result.is_err())
+                assert_eq!(result.unwrap_err(), BlockSealReason::GasLimit)
             }
         }
     }
@@ -395,7 +502,10 @@ async fn seal_pending_block() {
     assert!(tester.state_keeper.apply_priority_op(deposit).is_ok());
 
     let old_updates_len = tester.state_keeper.pending_block.account_updates.len();
-    tester.state_keeper.seal_pending_block().await;
+    tester
+        .state_keeper
+        .seal_pending_block(BlockSealReason::Forced)
+        .await;
 
     assert!(tester.state_keeper.pending_block.failed_txs.is_empty());
     assert!(tester
@@ -417,6 +527,8 @@ async fn seal_pending_block() {
         assert_eq!(block.block.block_transactions.len(), 3);
         assert_eq!(collected_fees, BigUint::from(1u32));
         assert_eq!(block.block.processed_priority_ops, (0, 1));
+        assert_eq!(block.block.seal_reason, Some(BlockSealReason::Forced));
+        assert!(block.block.timestamp > 0);
         assert_eq!(
             tester.state_keeper.state.block_number,
             block.block.block_number + 1
@@ -431,6 +543,159 @@ async fn seal_pending_block() {
     }
 }
 
+/// Checks that the forced sealing is not applied to the empty pending block.
+#[test]
+fn empty_pending_block() {
+    let mut tester = StateKeeperTester::new(20, 3, 3, 2);
+    assert!(tester.state_keeper.pending_block_is_empty());
+
+    let bad_withdraw = create_account_and_withdrawal(&mut tester, 2, 2, 100u32, 145u32);
+    assert!(tester.state_keeper.apply_tx(&bad_withdraw).is_ok());
+    assert!(!tester.state_keeper.pending_block_is_empty());
+
+    let mut tester = StateKeeperTester::new(20, 3, 3, 2);
+    let deposit = create_deposit(0, 12u32);
+    assert!(tester.state_keeper.apply_priority_op(deposit).is_ok());
+    assert!(!tester.state_keeper.pending_block_is_empty());
+}
+
+mod simulate_tx {
+    use super::*;
+
+    /// Checks that simulated transaction doesn't change the state.
+    #[test]
+    fn success() {
+        let mut tester = StateKeeperTester::new(6, 1, 1, 1);
+        let withdraw = create_account_and_withdrawal(&mut tester, 0, 1, 200u32, 145u32);
+        let old_root_hash = tester.state_keeper.state.root_hash();
+
+        let results = tester.state_keeper.simulate_tx(&withdraw.into()).unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].success);
+        assert!(!results[0].updates.is_empty());
+        assert_eq!(results[0].chunks, 6);
+        assert_eq!(
+            results[0].fee.as_ref().map(|fee| fee.amount.clone()),
+            Some(BigUint::from(1u32))
+        );
+        assert_eq!(tester.state_keeper.state.root_hash(), old_root_hash);
+        assert!(tester.state_keeper.pending_block.account_updates.is_empty());
+    }
+
+    /// Checks that the reason of the failure is reported.
+    #[test]
+    fn failure() {
+        let mut tester = StateKeeperTester::new(6, 1, 1, 1);
+        let withdraw = create_account_and_withdrawal(&mut tester, 0, 1, 100u32, 145u32);
+
+        let results = tester.state_keeper.simulate_tx(&withdraw.into()).unwrap();
+
+        assert_eq!(results.len(), 1);
+        assert!(!results[0].success);
+        assert!(results[0].updates.is_empty());
+        assert!(results[0].fail_reason.is_some());
+        assert_eq!(
+            results[0].fail_code,
+            Some(TxExecutionError::InsufficientBalance)
+        );
+    }
+
+    /// Checks that the result is returned for each transaction of the batch,
+    /// and the whole batch fails if one of its transactions fails.
+    #[test]
+    fn batch() {
+        let mut tester = StateKeeperTester::new(10, 1, 1, 1);
+        let transfer = create_account_and_transfer(&mut tester, 0, 1, 200u32, 145u32);
+        let withdraw = create_account_and_withdrawal(&mut tester, 0, 2, 100u32, 145u32);
+        let old_root_hash = tester.state_keeper.state.root_hash();
+
+        let batch = SignedTxVariant::batch(vec![transfer.clone()], 1, None);
+        let results = tester.state_keeper.simulate_tx(&batch).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].success);
+
+        let batch = SignedTxVariant::batch(vec![transfer, withdraw], 1, None);
+        let results = tester.state_keeper.simulate_tx(&batch).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|result| !result.success));
+
+        assert_eq!(tester.state_keeper.state.root_hash(), old_root_hash);
+    }
+
+    /// Checks that the simulated transfer to the new account doesn't affect
+    /// the ID assigned to the account by the actually executed transfer.
+    #[test]
+    fn transfer_to_new() {
+        let mut tester = StateKeeperTester::new(10, 1, 1, 1);
+        let (account, sk) = tester.add_account(1);
+        tester.set_balance(1, 0, 200u32);
+        let transfer = Transfer::new_signed(
+            1,
+            account.address,
+            Address::repeat_byte(0x77),
+            0,
+            BigUint::from(100u32),
+            BigUint::from(1u32),
+            account.nonce,
+            Default::default(),
+            &sk,
+        )
+        .unwrap();
+        let transfer = SignedZkSyncTx {
+            tx: ZkSyncTx::Transfer(Box::new(transfer)),
+            eth_sign_data: None,
+        };
+
+        let results = tester
+            .state_keeper
+            .simulate_tx(&transfer.clone().into())
+            .unwrap();
+        let new_account_id = results[0]
+            .updates
+            .iter()
+            .find_map(|(id, update)| match update {
+                AccountUpdate::Create { .. } => Some(*id),
+                _ => None,
+            })
+            .expect("New account is not created");
+
+        assert!(tester.state_keeper.apply_tx(&transfer).is_ok());
+        let (account_id, _) = tester
+            .state_keeper
+            .state
+            .get_account_by_address(&Address::repeat_byte(0x77))
+            .expect("New account is not created");
+        assert_eq!(account_id, new_account_id);
+    }
+
+    /// Checks that the batch which can't be added to the mempool is not simulated.
+    #[test]
+    fn batch_limits() {
+        let mut tester = StateKeeperTester::new(10, 1, 1, 1);
+        let first_withdraw = create_account_and_withdrawal(&mut tester, 0, 1, 200u32, 145u32);
+        let second_withdraw = create_account_and_withdrawal(&mut tester, 0, 2, 200u32, 145u32);
+
+        let batch = SignedTxVariant::batch(vec![first_withdraw, second_withdraw], 1, None);
+        assert!(matches!(
+            tester.state_keeper.simulate_tx(&batch),
+            Err(TxAddError::BatchTooBig)
+        ));
+
+        tester.state_keeper.available_block_chunk_sizes = vec![20];
+        assert!(matches!(
+            tester.state_keeper.simulate_tx(&batch),
+            Err(TxAddError::BatchWithdrawalsOverload)
+        ));
+
+        let batch = SignedTxVariant::batch(Vec::new(), 1, None);
+        assert!(matches!(
+            tester.state_keeper.simulate_tx(&batch),
+            Err(TxAddError::EmptyBatch)
+        ));
+    }
+}
+
 /// Checks if block storing is done correctly by storing a block
 /// with 1 priority_op, 1 succeeded tx, 1 failed tx
 #[tokio::test]
@@ -476,6 +741,56 @@ async fn store_pending_block() {
     }
 }
 
+/// Checks that the pending block restored after the commit window has passed gets
+/// the new timestamp if there are no executed operations, and is detected otherwise.
+#[tokio::test]
+async fn restore_outdated_pending_block() {
+    let outdated_timestamp = unix_timestamp() - COMMIT_TIMESTAMP_NOT_OLDER - 60;
+    let pending_block = |success_operations| SendablePendingBlock {
+        number: 1,
+        chunks_left: 10,
+        unprocessed_priority_op_before: 0,
+        pending_block_iteration: 1,
+        success_operations,
+        failed_txs: Vec::new(),
+        timestamp: Some(outdated_timestamp),
+    };
+
+    // Block without operations is re-stamped once the first operation is included.
+    let mut tester = StateKeeperTester::new(10, 1, 1, 1);
+    tester
+        .state_keeper
+        .initialize(Some(pending_block(Vec::new())))
+        .await;
+    assert_eq!(tester.state_keeper.pending_block.timestamp, None);
+    let transfer = create_account_and_transfer(&mut tester, 0, 1, 200u32, 100u32);
+    assert!(tester.state_keeper.apply_tx(&transfer).is_ok());
+    assert!(tester.state_keeper.pending_block.timestamp > Some(outdated_timestamp));
+    assert!(!tester
+        .state_keeper
+        .pending_block
+        .is_commit_expired(unix_timestamp()));
+
+    // Operations must be executed at the original timestamp, so the block can't be committed.
+    let deposit = StateKeeperTester::new(10, 1, 1, 1)
+        .state_keeper
+        .apply_priority_op(create_deposit(0, 1u32))
+        .unwrap();
+    let mut tester = StateKeeperTester::new(10, 1, 1, 1);
+    tester
+        .state_keeper
+        .initialize(Some(pending_block(vec![deposit])))
+        .await;
+    assert_eq!(
+        tester.state_keeper.pending_block.timestamp,
+        Some(outdated_timestamp)
+    );
+    assert!(tester
+        .state_keeper
+        .pending_block
+        .is_commit_expired(unix_timestamp()));
+}
+
 mod execute_proposed_block {
     use super::*;
 
@@ -923,3 +1238,353 @@ mod execute_proposed_block {
         }
     }
 }
+
+mod sealing_criteria {
+    use super::*;
+
+    /// Executes the proposed block with a single withdrawal and returns
+    /// the reason of the block sealing, if the block was sealed.
+    async fn apply_withdrawal(
+        tester: &mut StateKeeperTester,
+        account_id: AccountId,
+    ) -> Option<BlockSealReason> {
+        let withdraw = create_account_and_withdrawal(tester, 0, account_id, 200u32, 145u32);
+        let proposed_block = ProposedBlock {
+            txs: vec![SignedTxVariant::Tx(withdraw)],
+            priority_ops: Vec::new(),
+        };
+        tester
+            .state_keeper
+            .execute_proposed_block(proposed_block)
+            .await;
+
+        match tester.response_rx.next().await {
+            Some(CommitRequest::Block((block, _))) => block.block.seal_reason,
+            Some(CommitRequest::PendingBlock(_)) => None,
+            _ => panic!("Block is not received!"),
+        }
+    }
+
+    /// Checks that the block is sealed once the configured part of its chunks is filled.
+    #[tokio::test]
+    async fn chunks_fill() {
+        let mut tester = StateKeeperTester::with_sealing_options(
+            20,
+            10,
+            10,
+            10,
+            BlockSealingOptions {
+                chunks_fill_percent: 50,
+                ..default_sealing_options()
+            },
+        );
+
+        assert_eq!(apply_withdrawal(&mut tester, 1).await, None);
+        assert_eq!(
+            apply_withdrawal(&mut tester, 2).await,
+            Some(BlockSealReason::ChunksFill)
+        );
+    }
+
+    /// Checks that the operation which makes the block too expensive to commit
+    /// goes to the next block, but the first operation of the block is always accepted.
+    #[test]
+    fn gas_limit() {
+        let mut tester = StateKeeperTester::with_sealing_options(
+            20,
+            10,
+            10,
+            10,
+            BlockSealingOptions {
+                gas_limit_percent: 1,
+                ..default_sealing_options()
+            },
+        );
+        let first_withdraw = create_account_and_withdrawal(&mut tester, 0, 1, 200u32, 145u32);
+        let second_withdraw = create_account_and_withdrawal(&mut tester, 0, 2, 200u32, 145u32);
+
+        assert!(tester.state_keeper.apply_tx(&first_withdraw).is_ok());
+        let gas_counter = tester.state_keeper.pending_block.gas_counter.clone();
+        assert_eq!(
+            tester.state_keeper.apply_tx(&second_withdraw).unwrap_err(),
+            BlockSealReason::GasLimit
+        );
+        // Rejected operation doesn't affect the gas counter.
+        assert_eq!(
+            tester
+                .state_keeper
+                .pending_block
+                .gas_counter
+                .commit_gas_limit(),
+            gas_counter.commit_gas_limit()
+        );
+    }
+
+    /// Checks that the block is sealed once its oldest operation is old enough,
+    /// and the empty block is never sealed by its age.
+    #[tokio::test]
+    async fn block_age() {
+        let mut tester = StateKeeperTester::with_sealing_options(
+            20,
+            10,
+            10,
+            10,
+            BlockSealingOptions {
+                max_block_age: Duration::from_secs(0),
+                ..default_sealing_options()
+            },
+        );
+
+        tester
+            .state_keeper
+            .execute_proposed_block(ProposedBlock {
+                txs: Vec::new(),
+                priority_ops: Vec::new(),
+            })
+            .await;
+        assert!(tester.state_keeper.pending_block.first_op_at.is_none());
+
+        assert_eq!(
+            apply_withdrawal(&mut tester, 1).await,
+            Some(BlockSealReason::BlockAge)
+        );
+    }
+
+    /// Checks that the block is sealed after the configured number of miniblock iterations.
+    #[tokio::test]
+    async fn miniblock_iterations() {
+        let mut tester = StateKeeperTester::new(20, 1, 1, 10);
+
+        assert_eq!(apply_withdrawal(&mut tester, 1).await, None);
+        assert_eq!(
+            apply_withdrawal(&mut tester, 2).await,
+            Some(BlockSealReason::MiniblockIterations)
+        );
+    }
+}
+
+mod snapshot {
+    use super::*;
+    use crate::state_keeper::snapshot::{SnapshotError, StateSnapshot, SNAPSHOT_VERSION};
+    use zksync_crypto::params::account_tree_depth;
+    use zksync_types::helpers::apply_updates;
+
+    fn init_params() -> ZkSyncStateInitParams {
+        let mut init_params = ZkSyncStateInitParams::new();
+        for id in 0..3 {
+            let mut account = Account::default_with_address(&H160::random());
+            account.set_balance(0, BigUint::from(100u32 * (id + 1)));
+            account.nonce = id;
+            init_params.insert_account(id, account);
+        }
+        init_params.last_block_number = 5;
+        init_params.unprocessed_priority_op = 7;
+        init_params
+    }
+
+    /// Checks that the decoded snapshot restores the same state.
+    #[test]
+    fn encode_decode_roundtrip() {
+        let init_params = init_params();
+        let snapshot = StateSnapshot::new(&init_params);
+
+        let bytes = snapshot.encode().unwrap();
+        let restored = StateSnapshot::decode(&bytes).unwrap().restore().unwrap();
+
+        assert_eq!(restored.tree.root_hash(), init_params.tree.root_hash());
+        assert_eq!(restored.acc_id_by_addr, init_params.acc_id_by_addr);
+        assert_eq!(restored.last_block_number, 5);
+        assert_eq!(restored.unprocessed_priority_op, 7);
+    }
+
+    /// Checks that the corrupted or unsupported snapshots are rejected.
+    #[test]
+    fn decode_corrupted() {
+        let bytes = StateSnapshot::new(&init_params()).encode().unwrap();
+
+        let mut corrupted = bytes.clone();
+        *corrupted.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            StateSnapshot::decode(&corrupted),
+            Err(SnapshotError::ChecksumMismatch)
+        ));
+
+        let mut future_version = bytes.clone();
+        future_version[8..12].copy_from_slice(&(SNAPSHOT_VERSION + 1).to_be_bytes());
+        assert!(matches!(
+            StateSnapshot::decode(&future_version),
+            Err(SnapshotError::UnsupportedVersion(_))
+        ));
+
+        assert!(matches!(
+            StateSnapshot::decode(&bytes[..20]),
+            Err(SnapshotError::InvalidHeader)
+        ));
+    }
+
+    /// Checks that the snapshot is rejected if the accounts don't match the root hash.
+    #[test]
+    fn verify_root_hash_mismatch() {
+        let mut snapshot = StateSnapshot::new(&init_params());
+        snapshot.verify().unwrap();
+
+        snapshot.accounts[1].1.nonce += 1;
+        assert!(matches!(
+            snapshot.verify(),
+            Err(SnapshotError::RootHashMismatch { .. })
+        ));
+    }
+
+    /// Checks that the snapshot is not restored if the tree cache doesn't match the root hash.
+    #[test]
+    fn restore_root_hash_mismatch() {
+        let mut snapshot = StateSnapshot::new(&init_params());
+        snapshot.root_hash = StateSnapshot::new(&ZkSyncStateInitParams::new()).root_hash;
+
+        assert!(matches!(
+            snapshot.restore(),
+            Err(SnapshotError::RootHashMismatch { .. })
+        ));
+    }
+
+    /// Checks that the account updates of the snapshot create exactly the snapshot accounts.
+    #[test]
+    fn account_updates() {
+        let mut init_params = init_params();
+        let mut account = init_params.tree.get(1).unwrap().clone();
+        let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+        account.pub_key_hash = PubKeyHash::from_privkey(&priv_key_from_fs(rng.gen()));
+        account.set_balance(1, BigUint::from(5u32));
+        init_params.insert_account(1, account);
+        let snapshot = StateSnapshot::new(&init_params);
+
+        let mut accounts = AccountMap::default();
+        apply_updates(&mut accounts, snapshot.account_updates());
+
+        let mut tree = AccountTree::new(account_tree_depth());
+        for (id, account) in accounts {
+            tree.insert(id, account);
+        }
+        assert_eq!(tree.root_hash(), snapshot.root_hash);
+    }
+}
+
+mod fee_routing {
+    use super::*;
+    use crate::state_keeper::fee_routing::FeeRouter;
+    use zksync_config::{FeeRoute, FeeRouteCondition};
+
+    const TREASURY_ID: AccountId = 10;
+
+    /// Creates a tester with the treasury account and returns the addresses
+    /// of the fee collector and the treasury.
+    fn tester_with_treasury() -> (StateKeeperTester, Address, Address) {
+        let mut tester = StateKeeperTester::new(20, 3, 3, 2);
+        let (treasury, _) = tester.add_account(TREASURY_ID);
+        let fee_collector = tester
+            .state_keeper
+            .state
+            .get_account(tester.fee_collector)
+            .unwrap();
+
+        (tester, fee_collector.address, treasury.address)
+    }
+
+    fn set_routes(
+        tester: &mut StateKeeperTester,
+        policy: FeeRoutingPolicy,
+        routes: Vec<(FeeRouteCondition, Address)>,
+    ) {
+        let routes = routes
+            .into_iter()
+            .map(|(condition, fee_account)| FeeRoute {
+                condition,
+                fee_account,
+            })
+            .collect();
+        tester.state_keeper.fee_router =
+            FeeRouter::new(FeeRoutingOptions { policy, routes }, tester.fee_collector);
+    }
+
+    /// Seals the pending block and returns its fee account.
+    async fn seal_block(tester: &mut StateKeeperTester) -> AccountId {
+        tester
+            .state_keeper
+            .seal_pending_block(BlockSealReason::Forced)
+            .await;
+
+        match tester.response_rx.next().await {
+            Some(CommitRequest::Block((block, _))) => block.block.fee_account,
+            _ => panic!("Block is not received!"),
+        }
+    }
+
+    /// Checks that the fees go to the routed account only if the block contains matching operations.
+    #[tokio::test]
+    async fn route_by_token() {
+        let (mut tester, _, treasury) = tester_with_treasury();
+        set_routes(
+            &mut tester,
+            FeeRoutingPolicy::FirstMatch,
+            vec![(FeeRouteCondition::Token(1), treasury)],
+        );
+
+        let transfer = create_account_and_transfer(&mut tester, 0, 1, 200u32, 100u32);
+        assert!(tester.state_keeper.apply_tx(&transfer).is_ok());
+        assert_eq!(seal_block(&mut tester).await, tester.fee_collector);
+
+        let transfer = create_account_and_transfer(&mut tester, 1, 2, 200u32, 100u32);
+        assert!(tester.state_keeper.apply_tx(&transfer).is_ok());
+        assert_eq!(seal_block(&mut tester).await, TREASURY_ID);
+
+        let treasury_fees = tester
+            .state_keeper
+            .state
+            .get_account(TREASURY_ID)
+            .unwrap()
+            .get_balance(1);
+        assert_eq!(treasury_fees, BigUint::from(1u32));
+    }
+
+    /// Checks that the route matching the most operations is chosen by the `MostOperations` policy.
+    #[tokio::test]
+    async fn route_by_most_operations() {
+        let (mut tester, fee_collector, treasury) = tester_with_treasury();
+        // Route to the fee collector goes first, but matches fewer operations.
+        set_routes(
+            &mut tester,
+            FeeRoutingPolicy::MostOperations,
+            vec![
+                (
+                    FeeRouteCondition::TxType("Transfer".to_string()),
+                    fee_collector,
+                ),
+                (FeeRouteCondition::TxType("Withdraw".to_string()), treasury),
+            ],
+        );
+
+        let transfer = create_account_and_transfer(&mut tester, 0, 1, 200u32, 100u32);
+        let first_withdraw = create_account_and_withdrawal(&mut tester, 0, 2, 200u32, 100u32);
+        let second_withdraw = create_account_and_withdrawal(&mut tester, 0, 3, 200u32, 100u32);
+        for tx in &[transfer, first_withdraw, second_withdraw] {
+            assert!(tester.state_keeper.apply_tx(tx).is_ok());
+        }
+
+        assert_eq!(seal_block(&mut tester).await, TREASURY_ID);
+    }
+
+    /// Checks that the route to the account missing in the state is ignored.
+    #[tokio::test]
+    async fn route_to_missing_account() {
+        let (mut tester, _, _) = tester_with_treasury();
+        set_routes(
+            &mut tester,
+            FeeRoutingPolicy::FirstMatch,
+            vec![(FeeRouteCondition::Token(0), H160::random())],
+        );
+
+        let transfer = create_account_and_transfer(&mut tester, 0, 1, 200u32, 100u32);
+        assert!(tester.state_keeper.apply_tx(&transfer).is_ok());
+        assert_eq!(seal_block(&mut tester).await, tester.fee_collector);
+    }
+}
@@ -720,7 +720,7 @@ impl<ETH: EthereumInterface, DB: DatabaseInterface> ETHSender<ETH, DB> {
     fn operation_to_raw_tx(&self, op: &Operation) -> Vec<u8> {
         match &op.action {
             Action::Commit => {
-                let root = op.block.get_eth_encoded_root();
+                let new_block_info = op.block.get_eth_new_block_info();
 
                 let public_data = op.block.get_eth_public_data();
                 log::debug!(
@@ -742,7 +742,7 @@ impl<ETH: EthereumInterface, DB: DatabaseInterface> ETHSender<ETH, DB> {
                     (
                         u64::from(op.block.block_number),
                         u64::from(op.block.fee_account),
-                        vec![root],
+                        new_block_info,
                         public_data,
                         witness_data.0,
                         witness_data.1,
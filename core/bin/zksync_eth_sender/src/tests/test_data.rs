@@ -48,6 +48,7 @@ fn get_operation(id: i64, block_number: u32, action: Action) -> Operation {
             50,
             1_000_000.into(),
             1_500_000.into(),
+            0,
         ),
     }
 }
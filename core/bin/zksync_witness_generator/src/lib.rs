@@ -20,9 +20,10 @@ use zksync_prover_utils::api::{BlockToProveRes, ProverReq, PublishReq, WorkingOn
 use zksync_storage::ConnectionPool;
 use zksync_types::BlockNumber;
 // Local deps
-use self::scaler::ScalerOracle;
 use zksync_utils::panic_notify::ThreadPanicNotify;
 
+pub use self::scaler::ScalerOracle;
+
 mod scaler;
 mod witness_generator;
 
@@ -294,6 +294,7 @@ mod tests {
             0,
             U256::default(),
             U256::default(),
+            0,
         );
         assert_eq!(
             WitnessGenerator::next_witness_block(3, 4, &BlockInfo::NoWitness(empty_block)),
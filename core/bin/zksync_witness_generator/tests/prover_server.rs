@@ -293,6 +293,7 @@ pub async fn test_operation_and_wanted_prover_data(
         &ConfigurationOptions::from_env().available_block_chunk_sizes,
         1_000_000.into(),
         1_500_000.into(),
+        0,
     );
 
     let mut pub_data = vec![];
@@ -347,6 +348,7 @@ pub async fn test_operation_and_wanted_prover_data(
             Some(root_after_fee),
             Some(zksync_crypto::Fr::from_str(&block.fee_account.to_string()).unwrap()),
             Some(zksync_crypto::Fr::from_str(&(block.block_number).to_string()).unwrap()),
+            Some(zksync_crypto::Fr::from_str(&block.timestamp.to_string()).unwrap()),
         );
 
     (
@@ -361,6 +363,7 @@ pub async fn test_operation_and_wanted_prover_data(
             initial_used_subtree_root,
             new_root: block.new_root_hash,
             validator_address: zksync_crypto::Fr::from_str(&block.fee_account.to_string()).unwrap(),
+            block_timestamp: zksync_crypto::Fr::from_str(&block.timestamp.to_string()).unwrap(),
             operations,
             validator_balances,
             validator_audit_path,
@@ -19,6 +19,17 @@ use crate::{
     utils,
 };
 
+/// Number of Ethereum addresses used by the `Swap` operation (recipients of both orders).
+pub const SPECIAL_ETH_ADDRESSES_NUMBER: usize = 2;
+/// Number of tokens used by the `Swap` operation (tokens sold by both orders and the fee token).
+pub const SPECIAL_TOKENS_NUMBER: usize = 3;
+/// Number of accounts used by the `Swap` operation (owners and recipients of both orders and the submitter).
+pub const SPECIAL_ACCOUNTS_NUMBER: usize = 5;
+/// Number of price components used by the `Swap` operation (two per order).
+pub const SPECIAL_PRICES_NUMBER: usize = 4;
+/// Number of packed amounts used by the `Swap` operation (minimal and maximal amounts of both orders).
+pub const SPECIAL_AMOUNTS_NUMBER: usize = 4;
+
 pub struct AllocatedOperationBranch<E: RescueEngine> {
     pub account: AccountContent<E>,
     pub account_audit_path: Vec<AllocatedNum<E>>, //we do not need their bit representations
@@ -106,8 +117,18 @@ pub struct AllocatedOperationData<E: Engine> {
     pub new_pubkey_hash: CircuitElement<E>,
     pub eth_address: CircuitElement<E>,
     pub pub_nonce: CircuitElement<E>,
+    pub valid_from: CircuitElement<E>,
+    pub valid_until: CircuitElement<E>,
     pub a: CircuitElement<E>,
     pub b: CircuitElement<E>,
+    pub second_amount_packed: CircuitElement<E>,
+    pub second_amount_unpacked: CircuitElement<E>,
+    pub special_eth_addresses: Vec<CircuitElement<E>>,
+    pub special_tokens: Vec<CircuitElement<E>>,
+    pub special_accounts: Vec<CircuitElement<E>>,
+    pub special_prices: Vec<CircuitElement<E>>,
+    pub special_amounts_packed: Vec<CircuitElement<E>>,
+    pub special_amounts_unpacked: Vec<CircuitElement<E>>,
 }
 
 impl<E: RescueEngine> AllocatedOperationData<E> {
@@ -168,16 +189,86 @@ impl<E: RescueEngine> AllocatedOperationData<E> {
             franklin_constants::NONCE_BIT_WIDTH,
         );
 
+        let valid_from = CircuitElement::unsafe_empty_of_some_length(
+            zero_element.clone(),
+            franklin_constants::TIMESTAMP_BIT_WIDTH,
+        );
+
+        let valid_until = CircuitElement::unsafe_empty_of_some_length(
+            zero_element.clone(),
+            franklin_constants::TIMESTAMP_BIT_WIDTH,
+        );
+
         let a = CircuitElement::unsafe_empty_of_some_length(
             zero_element.clone(),
             franklin_constants::BALANCE_BIT_WIDTH,
         );
 
         let b = CircuitElement::unsafe_empty_of_some_length(
-            zero_element,
+            zero_element.clone(),
             franklin_constants::BALANCE_BIT_WIDTH,
         );
 
+        let second_amount_packed = CircuitElement::unsafe_empty_of_some_length(
+            zero_element.clone(),
+            franklin_constants::AMOUNT_EXPONENT_BIT_WIDTH
+                + franklin_constants::AMOUNT_MANTISSA_BIT_WIDTH,
+        );
+
+        let second_amount_unpacked = CircuitElement::unsafe_empty_of_some_length(
+            zero_element.clone(),
+            franklin_constants::BALANCE_BIT_WIDTH,
+        );
+
+        let special_eth_addresses = vec![
+            CircuitElement::unsafe_empty_of_some_length(
+                zero_element.clone(),
+                franklin_constants::ETH_ADDRESS_BIT_WIDTH,
+            );
+            SPECIAL_ETH_ADDRESSES_NUMBER
+        ];
+
+        let special_tokens = vec![
+            CircuitElement::unsafe_empty_of_some_length(
+                zero_element.clone(),
+                franklin_constants::TOKEN_BIT_WIDTH,
+            );
+            SPECIAL_TOKENS_NUMBER
+        ];
+
+        let special_accounts = vec![
+            CircuitElement::unsafe_empty_of_some_length(
+                zero_element.clone(),
+                franklin_constants::ACCOUNT_ID_BIT_WIDTH,
+            );
+            SPECIAL_ACCOUNTS_NUMBER
+        ];
+
+        let special_prices = vec![
+            CircuitElement::unsafe_empty_of_some_length(
+                zero_element.clone(),
+                franklin_constants::PRICE_BIT_WIDTH,
+            );
+            SPECIAL_PRICES_NUMBER
+        ];
+
+        let special_amounts_packed = vec![
+            CircuitElement::unsafe_empty_of_some_length(
+                zero_element.clone(),
+                franklin_constants::AMOUNT_EXPONENT_BIT_WIDTH
+                    + franklin_constants::AMOUNT_MANTISSA_BIT_WIDTH,
+            );
+            SPECIAL_AMOUNTS_NUMBER
+        ];
+
+        let special_amounts_unpacked = vec![
+            CircuitElement::unsafe_empty_of_some_length(
+                zero_element,
+                franklin_constants::BALANCE_BIT_WIDTH,
+            );
+            SPECIAL_AMOUNTS_NUMBER
+        ];
+
         Ok(AllocatedOperationData {
             eth_address,
             pub_nonce,
@@ -190,8 +281,18 @@ impl<E: RescueEngine> AllocatedOperationData<E> {
             second_sig_msg,
             third_sig_msg,
             new_pubkey_hash,
+            valid_from,
+            valid_until,
             a,
             b,
+            second_amount_packed,
+            second_amount_unpacked,
+            special_eth_addresses,
+            special_tokens,
+            special_accounts,
+            special_prices,
+            special_amounts_packed,
+            special_amounts_unpacked,
         })
     }
 
@@ -277,6 +378,16 @@ impl<E: RescueEngine> AllocatedOperationData<E> {
             || op.args.pub_nonce.grab(),
             franklin_constants::NONCE_BIT_WIDTH,
         )?;
+        let valid_from = CircuitElement::from_fe_with_known_length(
+            cs.namespace(|| "valid_from"),
+            || op.args.valid_from.grab(),
+            franklin_constants::TIMESTAMP_BIT_WIDTH,
+        )?;
+        let valid_until = CircuitElement::from_fe_with_known_length(
+            cs.namespace(|| "valid_until"),
+            || op.args.valid_until.grab(),
+            franklin_constants::TIMESTAMP_BIT_WIDTH,
+        )?;
         let a = CircuitElement::from_fe_with_known_length(
             cs.namespace(|| "a"),
             || op.args.a.grab(),
@@ -288,6 +399,57 @@ impl<E: RescueEngine> AllocatedOperationData<E> {
             franklin_constants::BALANCE_BIT_WIDTH,
         )?;
 
+        let second_amount_packed = CircuitElement::from_fe_with_known_length(
+            cs.namespace(|| "second_amount_packed"),
+            || op.args.second_amount_packed.grab(),
+            franklin_constants::AMOUNT_EXPONENT_BIT_WIDTH
+                + franklin_constants::AMOUNT_MANTISSA_BIT_WIDTH,
+        )?;
+        let second_amount_unpacked =
+            unpack_amount(cs.namespace(|| "second_amount"), &second_amount_packed)?;
+
+        let special_eth_addresses = allocate_special_elements(
+            cs.namespace(|| "special_eth_addresses"),
+            &op.args.special_eth_addresses,
+            SPECIAL_ETH_ADDRESSES_NUMBER,
+            franklin_constants::ETH_ADDRESS_BIT_WIDTH,
+        )?;
+        let special_tokens = allocate_special_elements(
+            cs.namespace(|| "special_tokens"),
+            &op.args.special_tokens,
+            SPECIAL_TOKENS_NUMBER,
+            franklin_constants::TOKEN_BIT_WIDTH,
+        )?;
+        let special_accounts = allocate_special_elements(
+            cs.namespace(|| "special_accounts"),
+            &op.args.special_accounts,
+            SPECIAL_ACCOUNTS_NUMBER,
+            franklin_constants::ACCOUNT_ID_BIT_WIDTH,
+        )?;
+        let special_prices = allocate_special_elements(
+            cs.namespace(|| "special_prices"),
+            &op.args.special_prices,
+            SPECIAL_PRICES_NUMBER,
+            franklin_constants::PRICE_BIT_WIDTH,
+        )?;
+        let special_amounts_packed = allocate_special_elements(
+            cs.namespace(|| "special_amounts_packed"),
+            &op.args.special_amounts,
+            SPECIAL_AMOUNTS_NUMBER,
+            franklin_constants::AMOUNT_EXPONENT_BIT_WIDTH
+                + franklin_constants::AMOUNT_MANTISSA_BIT_WIDTH,
+        )?;
+        let special_amounts_unpacked = special_amounts_packed
+            .iter()
+            .enumerate()
+            .map(|(i, amount_packed)| {
+                unpack_amount(
+                    cs.namespace(|| format!("special_amount_{}", i)),
+                    amount_packed,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
         Ok(AllocatedOperationData {
             eth_address,
             pub_nonce,
@@ -300,8 +462,56 @@ impl<E: RescueEngine> AllocatedOperationData<E> {
             second_sig_msg,
             third_sig_msg,
             new_pubkey_hash,
+            valid_from,
+            valid_until,
             a,
             b,
+            second_amount_packed,
+            second_amount_unpacked,
+            special_eth_addresses,
+            special_tokens,
+            special_accounts,
+            special_prices,
+            special_amounts_packed,
+            special_amounts_unpacked,
         })
     }
 }
+
+fn unpack_amount<E: RescueEngine, CS: ConstraintSystem<E>>(
+    mut cs: CS,
+    amount_packed: &CircuitElement<E>,
+) -> Result<CircuitElement<E>, SynthesisError> {
+    let amount_parsed = parse_with_exponent_le(
+        cs.namespace(|| "parse amount"),
+        &amount_packed.get_bits_le(),
+        franklin_constants::AMOUNT_EXPONENT_BIT_WIDTH,
+        franklin_constants::AMOUNT_MANTISSA_BIT_WIDTH,
+        10,
+    )?;
+    CircuitElement::from_number_with_known_length(
+        cs.namespace(|| "amount"),
+        amount_parsed,
+        franklin_constants::BALANCE_BIT_WIDTH,
+    )
+}
+
+fn allocate_special_elements<E: RescueEngine, CS: ConstraintSystem<E>>(
+    mut cs: CS,
+    witness: &[Option<E::Fr>],
+    number: usize,
+    length: usize,
+) -> Result<Vec<CircuitElement<E>>, SynthesisError> {
+    assert_eq!(witness.len(), number, "wrong number of special elements");
+    witness
+        .iter()
+        .enumerate()
+        .map(|(i, element)| {
+            CircuitElement::from_fe_with_known_length(
+                cs.namespace(|| format!("element {}", i)),
+                || element.grab(),
+                length,
+            )
+        })
+        .collect()
+}
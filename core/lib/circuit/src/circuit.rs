@@ -18,11 +18,13 @@ use zksync_crypto::franklin_crypto::{
 };
 // Workspace deps
 use zksync_crypto::params::{
-    self, FR_BIT_WIDTH_PADDED, SIGNED_FORCED_EXIT_BIT_WIDTH, SIGNED_TRANSFER_BIT_WIDTH,
+    self, FR_BIT_WIDTH_PADDED, SIGNED_FORCED_EXIT_BIT_WIDTH, SIGNED_ORDER_BIT_WIDTH,
+    SIGNED_SWAP_BIT_WIDTH, SIGNED_TRANSFER_BIT_WIDTH,
 };
 use zksync_types::{
     operations::{ChangePubKeyOp, NoopOp},
-    CloseOp, DepositOp, ForcedExitOp, FullExitOp, TransferOp, TransferToNewOp, WithdrawOp,
+    CloseOp, DepositOp, ForcedExitOp, FullExitOp, Order, SwapOp, TransferOp, TransferToNewOp,
+    WithdrawOp,
 };
 // Local deps
 use crate::{
@@ -41,7 +43,7 @@ use crate::{
     },
 };
 
-const DIFFERENT_TRANSACTIONS_TYPE_NUMBER: usize = 9;
+const DIFFERENT_TRANSACTIONS_TYPE_NUMBER: usize = 10;
 pub struct ZkSyncCircuit<'a, E: RescueEngine + JubjubEngine> {
     pub rescue_params: &'a <E as RescueEngine>::Params,
     pub jubjub_params: &'a <E as JubjubEngine>::Params,
@@ -51,6 +53,8 @@ pub struct ZkSyncCircuit<'a, E: RescueEngine + JubjubEngine> {
 
     pub block_number: Option<E::Fr>,
     pub validator_address: Option<E::Fr>,
+    /// Timestamp of the block, transactions must be valid at this moment
+    pub block_timestamp: Option<E::Fr>,
 
     pub pub_data_commitment: Option<E::Fr>,
     pub operations: Vec<Operation<E>>,
@@ -69,6 +73,7 @@ impl<'a, E: RescueEngine + JubjubEngine> std::clone::Clone for ZkSyncCircuit<'a,
             initial_used_subtree_root: self.initial_used_subtree_root,
             block_number: self.block_number,
             validator_address: self.validator_address,
+            block_timestamp: self.block_timestamp,
             pub_data_commitment: self.pub_data_commitment,
             operations: self.operations.clone(),
 
@@ -169,6 +174,7 @@ impl<'a, E: RescueEngine + JubjubEngine> Circuit<E> for ZkSyncCircuit<'a, E> {
             data[FullExitOp::OP_CODE as usize] = vec![zero.clone(); 2];
             data[ChangePubKeyOp::OP_CODE as usize] = vec![zero.clone(); 2];
             data[ForcedExitOp::OP_CODE as usize] = vec![zero.clone(); 2];
+            data[SwapOp::OP_CODE as usize] = vec![zero.clone(); 2];
 
             // this operation is disabled for now
             // data[CloseOp::OP_CODE as usize] = vec![];
@@ -178,6 +184,12 @@ impl<'a, E: RescueEngine + JubjubEngine> Circuit<E> for ZkSyncCircuit<'a, E> {
 
         assert_eq!(pubdata_holder.len(), DIFFERENT_TRANSACTIONS_TYPE_NUMBER);
 
+        let block_timestamp = CircuitElement::from_fe_with_known_length(
+            cs.namespace(|| "block_timestamp"),
+            || self.block_timestamp.grab(),
+            params::TIMESTAMP_BIT_WIDTH,
+        )?;
+
         // Main cycle that processes operations:
         for (i, operation) in self.operations.iter().enumerate() {
             let cs = &mut cs.namespace(|| format!("chunk number {}", i));
@@ -234,6 +246,7 @@ impl<'a, E: RescueEngine + JubjubEngine> Circuit<E> for ZkSyncCircuit<'a, E> {
                 &allocated_chunk_data,
                 &is_account_empty,
                 &operation_pub_data_chunk.get_number(),
+                &block_timestamp,
                 // &subtree_root, // Close disable
                 &mut last_token_id,
                 &mut fees,
@@ -459,6 +472,12 @@ impl<'a, E: RescueEngine + JubjubEngine> Circuit<E> for ZkSyncCircuit<'a, E> {
 
             hash_block = sha256::sha256(cs.namespace(|| "hash with new_root"), &pack_bits)?;
 
+            let mut pack_bits = vec![];
+            pack_bits.extend(hash_block);
+            pack_bits.extend(block_timestamp.into_padded_be_bits(256));
+
+            hash_block = sha256::sha256(cs.namespace(|| "hash with timestamp"), &pack_bits)?;
+
             let mut pack_bits = vec![];
             pack_bits.extend(hash_block);
             pack_bits.extend(block_pub_data_bits.into_iter());
@@ -648,6 +667,7 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
         chunk_data: &AllocatedChunkData<E>,
         is_account_empty: &Boolean,
         ext_pubdata_chunk: &AllocatedNum<E>,
+        block_timestamp: &CircuitElement<E>,
         // subtree_root: &CircuitElement<E>, // Close disable
         last_token_id: &mut AllocatedNum<E>,
         fees: &mut [AllocatedNum<E>],
@@ -731,6 +751,59 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
                 &op_data.full_amount,
                 &prev.op_data.full_amount,
             )?);
+            is_op_data_correct_flags.push(CircuitElement::equals(
+                cs.namespace(|| "is valid_from equal to previous"),
+                &op_data.valid_from,
+                &prev.op_data.valid_from,
+            )?);
+            is_op_data_correct_flags.push(CircuitElement::equals(
+                cs.namespace(|| "is valid_until equal to previous"),
+                &op_data.valid_until,
+                &prev.op_data.valid_until,
+            )?);
+            is_op_data_correct_flags.push(CircuitElement::equals(
+                cs.namespace(|| "is second_amount_packed equal to previous"),
+                &op_data.second_amount_packed,
+                &prev.op_data.second_amount_packed,
+            )?);
+            let special_elements = [
+                (
+                    "special_eth_addresses",
+                    &op_data.special_eth_addresses,
+                    &prev.op_data.special_eth_addresses,
+                ),
+                (
+                    "special_tokens",
+                    &op_data.special_tokens,
+                    &prev.op_data.special_tokens,
+                ),
+                (
+                    "special_accounts",
+                    &op_data.special_accounts,
+                    &prev.op_data.special_accounts,
+                ),
+                (
+                    "special_prices",
+                    &op_data.special_prices,
+                    &prev.op_data.special_prices,
+                ),
+                (
+                    "special_amounts_packed",
+                    &op_data.special_amounts_packed,
+                    &prev.op_data.special_amounts_packed,
+                ),
+            ];
+            for (name, elements, prev_elements) in special_elements.iter() {
+                for (i, (element, prev_element)) in
+                    elements.iter().zip(prev_elements.iter()).enumerate()
+                {
+                    is_op_data_correct_flags.push(CircuitElement::equals(
+                        cs.namespace(|| format!("is {} {} equal to previous", name, i)),
+                        element,
+                        prev_element,
+                    )?);
+                }
+            }
 
             let is_op_data_equal_to_previous = multi_and(
                 cs.namespace(|| "is_op_data_equal_to_previous"),
@@ -784,6 +857,13 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
             diff_a_b_bits_repacked,
         )?);
 
+        let is_valid_timestamp = is_time_range_valid(
+            cs.namespace(|| "is_valid_timestamp"),
+            &op_data.valid_from,
+            &op_data.valid_until,
+            block_timestamp,
+        )?;
+
         let mut op_flags = vec![];
         op_flags.push(self.deposit(
             cs.namespace(|| "deposit"),
@@ -807,6 +887,7 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
             &signer_key,
             &ext_pubdata_chunk,
             &signature_data.is_verified,
+            &is_valid_timestamp,
             &mut previous_pubdatas[TransferOp::OP_CODE as usize],
         )?);
         op_flags.push(self.transfer_to_new(
@@ -821,6 +902,7 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
             &signer_key,
             &ext_pubdata_chunk,
             &signature_data.is_verified,
+            &is_valid_timestamp,
             &mut previous_pubdatas[TransferToNewOp::OP_CODE as usize],
         )?);
         op_flags.push(self.withdraw(
@@ -832,6 +914,7 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
             &signer_key,
             &ext_pubdata_chunk,
             &signature_data.is_verified,
+            &is_valid_timestamp,
             &mut previous_pubdatas[WithdrawOp::OP_CODE as usize],
         )?);
         // Close disable.
@@ -864,6 +947,7 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
             &mut previous_pubdatas[ChangePubKeyOp::OP_CODE as usize],
             &is_a_geq_b,
             &signature_data.is_verified,
+            &is_valid_timestamp,
             &signer_key,
         )?);
         op_flags.push(self.noop(
@@ -886,8 +970,22 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
             &signer_key,
             &ext_pubdata_chunk,
             &signature_data.is_verified,
+            &is_valid_timestamp,
             &mut previous_pubdatas[ForcedExitOp::OP_CODE as usize],
         )?);
+        op_flags.push(self.swap(
+            cs.namespace(|| "swap"),
+            &mut cur,
+            &chunk_data,
+            &is_a_geq_b,
+            &is_account_empty,
+            &op_data,
+            &signer_key,
+            &ext_pubdata_chunk,
+            &signature_data.is_verified,
+            &is_valid_timestamp,
+            &mut previous_pubdatas[SwapOp::OP_CODE as usize],
+        )?);
 
         assert_eq!(DIFFERENT_TRANSACTIONS_TYPE_NUMBER - 1, op_flags.len());
 
@@ -959,6 +1057,7 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
         signer_key: &AllocatedSignerPubkey<E>,
         ext_pubdata_chunk: &AllocatedNum<E>,
         is_sig_verified: &Boolean,
+        is_valid_timestamp: &Boolean,
         pubdata_holder: &mut Vec<AllocatedNum<E>>,
     ) -> Result<Boolean, SynthesisError> {
         let mut base_valid_flags = vec![];
@@ -999,6 +1098,8 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
         serialized_tx_bits.extend(op_data.full_amount.get_bits_be());
         serialized_tx_bits.extend(op_data.fee_packed.get_bits_be());
         serialized_tx_bits.extend(cur.account.nonce.get_bits_be());
+        serialized_tx_bits.extend(op_data.valid_from.get_bits_be());
+        serialized_tx_bits.extend(op_data.valid_until.get_bits_be());
         assert_eq!(serialized_tx_bits.len(), params::SIGNED_WITHDRAW_BIT_WIDTH);
 
         let pubdata_chunk = select_pubdata_chunk(
@@ -1084,6 +1185,7 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
         )?);
         lhs_valid_flags.push(is_b_correct);
         lhs_valid_flags.push(is_a_geq_b.clone());
+        lhs_valid_flags.push(is_valid_timestamp.clone());
 
         lhs_valid_flags.push(no_nonce_overflow(
             cs.namespace(|| "no nonce overflow"),
@@ -1441,6 +1543,7 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
         pubdata_holder: &mut Vec<AllocatedNum<E>>,
         is_a_geq_b: &Boolean,
         is_sig_verified: &Boolean,
+        is_valid_timestamp: &Boolean,
         signer_key: &AllocatedSignerPubkey<E>,
     ) -> Result<Boolean, SynthesisError> {
         assert!(
@@ -1475,6 +1578,8 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
         serialized_tx_bits.extend(cur.token.get_bits_be());
         serialized_tx_bits.extend(op_data.fee_packed.get_bits_be());
         serialized_tx_bits.extend(cur.account.nonce.get_bits_be());
+        serialized_tx_bits.extend(op_data.valid_from.get_bits_be());
+        serialized_tx_bits.extend(op_data.valid_until.get_bits_be());
 
         assert_eq!(
             serialized_tx_bits.len(),
@@ -1557,6 +1662,7 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
         )?;
 
         is_valid_flags.push(is_address_correct);
+        is_valid_flags.push(is_valid_timestamp.clone());
 
         let is_signer_valid = CircuitElement::equals(
             cs.namespace(|| "signer_key_correect"),
@@ -1709,6 +1815,7 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
         signer_key: &AllocatedSignerPubkey<E>,
         ext_pubdata_chunk: &AllocatedNum<E>,
         is_sig_verified: &Boolean,
+        is_valid_timestamp: &Boolean,
         pubdata_holder: &mut Vec<AllocatedNum<E>>,
     ) -> Result<Boolean, SynthesisError> {
         assert!(
@@ -1758,6 +1865,8 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
         serialized_tx_bits.extend(op_data.amount_packed.get_bits_be());
         serialized_tx_bits.extend(op_data.fee_packed.get_bits_be());
         serialized_tx_bits.extend(cur.account.nonce.get_bits_be());
+        serialized_tx_bits.extend(op_data.valid_from.get_bits_be());
+        serialized_tx_bits.extend(op_data.valid_until.get_bits_be());
         assert_eq!(serialized_tx_bits.len(), SIGNED_TRANSFER_BIT_WIDTH);
 
         let pubdata_chunk = select_pubdata_chunk(
@@ -1811,6 +1920,7 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
 
         lhs_valid_flags.push(is_b_correct);
         lhs_valid_flags.push(is_a_geq_b.clone());
+        lhs_valid_flags.push(is_valid_timestamp.clone());
 
         lhs_valid_flags.push(no_nonce_overflow(
             cs.namespace(|| "no nonce overflow"),
@@ -1949,6 +2059,7 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
         signer_key: &AllocatedSignerPubkey<E>,
         ext_pubdata_chunk: &AllocatedNum<E>,
         is_sig_verified: &Boolean,
+        is_valid_timestamp: &Boolean,
         pubdata_holder: &mut Vec<AllocatedNum<E>>,
     ) -> Result<Boolean, SynthesisError> {
         assert!(
@@ -1991,6 +2102,8 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
         serialized_tx_bits.extend(op_data.amount_packed.get_bits_be());
         serialized_tx_bits.extend(op_data.fee_packed.get_bits_be());
         serialized_tx_bits.extend(cur.account.nonce.get_bits_be());
+        serialized_tx_bits.extend(op_data.valid_from.get_bits_be());
+        serialized_tx_bits.extend(op_data.valid_until.get_bits_be());
         assert_eq!(serialized_tx_bits.len(), SIGNED_TRANSFER_BIT_WIDTH);
 
         let pubdata_chunk = select_pubdata_chunk(
@@ -2051,6 +2164,7 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
 
         lhs_valid_flags.push(is_b_correct);
         lhs_valid_flags.push(is_a_geq_b.clone());
+        lhs_valid_flags.push(is_valid_timestamp.clone());
         lhs_valid_flags.push(is_sig_verified.clone());
         lhs_valid_flags.push(no_nonce_overflow(
             cs.namespace(|| "no nonce overflow"),
@@ -2149,6 +2263,7 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
         signer_key: &AllocatedSignerPubkey<E>,
         ext_pubdata_chunk: &AllocatedNum<E>,
         is_sig_verified: &Boolean,
+        is_valid_timestamp: &Boolean,
         pubdata_holder: &mut Vec<AllocatedNum<E>>,
     ) -> Result<Boolean, SynthesisError> {
         assert!(
@@ -2190,6 +2305,8 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
         serialized_tx_bits.extend(cur.token.get_bits_be());
         serialized_tx_bits.extend(op_data.fee_packed.get_bits_be());
         serialized_tx_bits.extend(lhs.account.nonce.get_bits_be());
+        serialized_tx_bits.extend(op_data.valid_from.get_bits_be());
+        serialized_tx_bits.extend(op_data.valid_until.get_bits_be());
         assert_eq!(serialized_tx_bits.len(), SIGNED_FORCED_EXIT_BIT_WIDTH);
 
         let pubdata_chunk = select_pubdata_chunk(
@@ -2249,6 +2366,7 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
 
         lhs_valid_flags.push(is_b_correct);
         lhs_valid_flags.push(is_a_geq_b.clone());
+        lhs_valid_flags.push(is_valid_timestamp.clone());
         lhs_valid_flags.push(is_sig_verified.clone());
         lhs_valid_flags.push(no_nonce_overflow(
             cs.namespace(|| "no nonce overflow"),
@@ -2364,6 +2482,359 @@ impl<'a, E: RescueEngine + JubjubEngine> ZkSyncCircuit<'a, E> {
         )?;
         Ok(is_op_valid)
     }
+
+    #[allow(clippy::too_many_arguments)]
+    fn swap<CS: ConstraintSystem<E>>(
+        &self,
+        mut cs: CS,
+        cur: &mut AllocatedOperationBranch<E>,
+        chunk_data: &AllocatedChunkData<E>,
+        is_a_geq_b: &Boolean,
+        is_account_empty: &Boolean,
+        op_data: &AllocatedOperationData<E>,
+        signer_key: &AllocatedSignerPubkey<E>,
+        ext_pubdata_chunk: &AllocatedNum<E>,
+        is_sig_verified: &Boolean,
+        is_valid_timestamp: &Boolean,
+        pubdata_holder: &mut Vec<AllocatedNum<E>>,
+    ) -> Result<Boolean, SynthesisError> {
+        assert!(
+            !pubdata_holder.is_empty(),
+            "pubdata holder has to be preallocated"
+        );
+
+        // Every chunk of the swap processes its own account:
+        // chunk 0 - submitter pays the fee (it's the lhs, so the fee token is taken from it),
+        // chunk 1 - owner of the first order sells `amount`,
+        // chunk 2 - recipient of the first order receives `second_amount`,
+        // chunk 3 - owner of the second order sells `second_amount`,
+        // chunk 4 - recipient of the second order receives `amount`.
+        // Account ids, tokens and orders data are the same for all the chunks,
+        // so they are taken from the `op_data` rather than from the current branch.
+        let accounts = &op_data.special_accounts;
+        let tokens = &op_data.special_tokens;
+        let amounts = [&op_data.amount_unpacked, &op_data.second_amount_unpacked];
+
+        // construct pubdata
+        let mut pubdata_bits = vec![];
+        pubdata_bits.extend(chunk_data.tx_type.get_bits_be());
+        for account in accounts {
+            pubdata_bits.extend(account.get_bits_be());
+        }
+        for token in tokens {
+            pubdata_bits.extend(token.get_bits_be());
+        }
+        pubdata_bits.extend(op_data.amount_packed.get_bits_be());
+        pubdata_bits.extend(op_data.second_amount_packed.get_bits_be());
+        pubdata_bits.extend(op_data.fee_packed.get_bits_be());
+
+        resize_grow_only(
+            &mut pubdata_bits,
+            SwapOp::CHUNKS * params::CHUNK_BIT_WIDTH,
+            Boolean::constant(false),
+        );
+
+        let (is_equal_pubdata, packed_pubdata) = vectorized_compare(
+            cs.namespace(|| "compare pubdata"),
+            &*pubdata_holder,
+            &pubdata_bits,
+        )?;
+
+        *pubdata_holder = packed_pubdata;
+
+        let pubdata_chunk = select_pubdata_chunk(
+            cs.namespace(|| "select_pubdata_chunk"),
+            &pubdata_bits,
+            &chunk_data.chunk_number,
+            SwapOp::CHUNKS,
+        )?;
+        let is_pubdata_chunk_correct = Boolean::from(Expression::equals(
+            cs.namespace(|| "is_pubdata_correct"),
+            &pubdata_chunk,
+            ext_pubdata_chunk,
+        )?);
+
+        // verify correct tx_code
+
+        let is_swap = Boolean::from(Expression::equals(
+            cs.namespace(|| "is_swap"),
+            &chunk_data.tx_type.get_number(),
+            Expression::u64::<CS>(u64::from(SwapOp::OP_CODE)),
+        )?);
+
+        let mut is_chunk = vec![];
+        for i in 0..SwapOp::CHUNKS {
+            is_chunk.push(Boolean::from(Expression::equals(
+                cs.namespace(|| format!("is_chunk_{}", i)),
+                &chunk_data.chunk_number,
+                Expression::u64::<CS>(i as u64),
+            )?));
+        }
+
+        let pubdata_properly_copied = boolean_or(
+            cs.namespace(|| "first chunk or pubdata is copied properly"),
+            &is_chunk[0],
+            &is_equal_pubdata,
+        )?;
+
+        let common_valid_flags = vec![is_pubdata_chunk_correct, is_swap, pubdata_properly_copied];
+
+        // submitter
+        let mut submitter_valid_flags = common_valid_flags.clone();
+        submitter_valid_flags.push(is_chunk[0].clone());
+
+        let mut serialized_tx_bits = vec![];
+
+        serialized_tx_bits.extend(chunk_data.tx_type.get_bits_be());
+        serialized_tx_bits.extend(cur.account_id.get_bits_be());
+        serialized_tx_bits.extend(cur.account.address.get_bits_be());
+        serialized_tx_bits.extend(cur.account.nonce.get_bits_be());
+        serialized_tx_bits.extend(accounts[0].get_bits_be());
+        serialized_tx_bits.extend(accounts[2].get_bits_be());
+        serialized_tx_bits.extend(tokens[0].get_bits_be());
+        serialized_tx_bits.extend(tokens[1].get_bits_be());
+        serialized_tx_bits.extend(op_data.amount_packed.get_bits_be());
+        serialized_tx_bits.extend(op_data.second_amount_packed.get_bits_be());
+        serialized_tx_bits.extend(tokens[2].get_bits_be());
+        serialized_tx_bits.extend(op_data.fee_packed.get_bits_be());
+        serialized_tx_bits.extend(op_data.valid_from.get_bits_be());
+        serialized_tx_bits.extend(op_data.valid_until.get_bits_be());
+        assert_eq!(serialized_tx_bits.len(), SIGNED_SWAP_BIT_WIDTH);
+
+        submitter_valid_flags.push(CircuitElement::equals(
+            cs.namespace(|| "is_submitter_correct"),
+            &cur.account_id,
+            &accounts[4],
+        )?);
+        submitter_valid_flags.push(CircuitElement::equals(
+            cs.namespace(|| "is_fee_token_correct"),
+            &cur.token,
+            &tokens[2],
+        )?);
+
+        // check operation arguments
+        let is_a_correct =
+            CircuitElement::equals(cs.namespace(|| "is_a_correct"), &op_data.a, &cur.balance)?;
+        submitter_valid_flags.push(is_a_correct);
+
+        let fee_expr = Expression::from(&op_data.fee.get_number());
+
+        let is_b_correct = Boolean::from(Expression::equals(
+            cs.namespace(|| "is_b_correct"),
+            &op_data.b.get_number(),
+            fee_expr.clone(),
+        )?);
+        submitter_valid_flags.push(is_b_correct);
+        submitter_valid_flags.push(is_a_geq_b.clone());
+        submitter_valid_flags.push(is_valid_timestamp.clone());
+        submitter_valid_flags.push(is_sig_verified.clone());
+        submitter_valid_flags.push(no_nonce_overflow(
+            cs.namespace(|| "submitter no nonce overflow"),
+            &cur.account.nonce.get_number(),
+        )?);
+
+        let is_serialized_tx_correct = verify_signature_message_construction(
+            cs.namespace(|| "is_serialized_tx_correct"),
+            serialized_tx_bits,
+            &op_data,
+        )?;
+        submitter_valid_flags.push(is_serialized_tx_correct);
+
+        let is_signer_valid = CircuitElement::equals(
+            cs.namespace(|| "submitter_signer_key_correct"),
+            &signer_key.pubkey.get_hash(),
+            &cur.account.pub_key_hash,
+        )?;
+        submitter_valid_flags.push(is_signer_valid);
+
+        let is_submitter_valid = multi_and(
+            cs.namespace(|| "is_submitter_valid"),
+            &submitter_valid_flags,
+        )?;
+
+        let updated_balance = Expression::from(&cur.balance.get_number()) - fee_expr;
+
+        let updated_nonce =
+            Expression::from(&cur.account.nonce.get_number()) + Expression::u64::<CS>(1);
+
+        cur.account.nonce = CircuitElement::conditionally_select_with_number_strict(
+            cs.namespace(|| "update submitter nonce"),
+            updated_nonce,
+            &cur.account.nonce,
+            &is_submitter_valid,
+        )?;
+
+        cur.balance = CircuitElement::conditionally_select_with_number_strict(
+            cs.namespace(|| "update submitter balance"),
+            updated_balance,
+            &cur.balance,
+            &is_submitter_valid,
+        )?;
+
+        let mut is_op_valid_flags = vec![is_submitter_valid];
+
+        for order in 0..2 {
+            let mut cs = cs.namespace(|| format!("order {}", order));
+            let other = 1 - order;
+            let amount_sell = amounts[order];
+            let amount_buy = amounts[other];
+
+            // order owner
+            let mut owner_valid_flags = common_valid_flags.clone();
+            owner_valid_flags.push(is_chunk[2 * order + 1].clone());
+
+            let mut serialized_order_bits = vec![];
+
+            serialized_order_bits.extend(
+                (0..8)
+                    .rev()
+                    .map(|bit| Boolean::constant((Order::MSG_TYPE >> bit) & 1 == 1)),
+            );
+            serialized_order_bits.extend(cur.account_id.get_bits_be());
+            serialized_order_bits.extend(op_data.special_eth_addresses[order].get_bits_be());
+            serialized_order_bits.extend(cur.account.nonce.get_bits_be());
+            serialized_order_bits.extend(tokens[order].get_bits_be());
+            serialized_order_bits.extend(tokens[other].get_bits_be());
+            serialized_order_bits.extend(op_data.special_prices[2 * order].get_bits_be());
+            serialized_order_bits.extend(op_data.special_prices[2 * order + 1].get_bits_be());
+            serialized_order_bits.extend(op_data.special_amounts_packed[2 * order].get_bits_be());
+            serialized_order_bits
+                .extend(op_data.special_amounts_packed[2 * order + 1].get_bits_be());
+            assert_eq!(serialized_order_bits.len(), SIGNED_ORDER_BIT_WIDTH);
+
+            owner_valid_flags.push(CircuitElement::equals(
+                cs.namespace(|| "is_owner_correct"),
+                &cur.account_id,
+                &accounts[2 * order],
+            )?);
+            owner_valid_flags.push(CircuitElement::equals(
+                cs.namespace(|| "is_token_sell_correct"),
+                &cur.token,
+                &tokens[order],
+            )?);
+
+            // check that owner has enough funds and the swap satisfies the order
+            owner_valid_flags.push(is_geq(
+                cs.namespace(|| "is_balance_sufficient"),
+                &cur.balance.get_number(),
+                &amount_sell.get_number(),
+                params::BALANCE_BIT_WIDTH,
+            )?);
+            owner_valid_flags.push(is_geq(
+                cs.namespace(|| "is_amount_geq_min"),
+                &amount_sell.get_number(),
+                &op_data.special_amounts_unpacked[2 * order].get_number(),
+                params::BALANCE_BIT_WIDTH,
+            )?);
+            owner_valid_flags.push(is_geq(
+                cs.namespace(|| "is_amount_leq_max"),
+                &op_data.special_amounts_unpacked[2 * order + 1].get_number(),
+                &amount_sell.get_number(),
+                params::BALANCE_BIT_WIDTH,
+            )?);
+
+            // amount_buy * price_sell >= amount_sell * price_buy
+            let bought_value = amount_buy.get_number().mul(
+                cs.namespace(|| "amount_buy * price_sell"),
+                &op_data.special_prices[2 * order].get_number(),
+            )?;
+            let sold_value = amount_sell.get_number().mul(
+                cs.namespace(|| "amount_sell * price_buy"),
+                &op_data.special_prices[2 * order + 1].get_number(),
+            )?;
+            owner_valid_flags.push(is_geq(
+                cs.namespace(|| "is_price_acceptable"),
+                &bought_value,
+                &sold_value,
+                params::BALANCE_BIT_WIDTH + params::PRICE_BIT_WIDTH,
+            )?);
+
+            owner_valid_flags.push(is_sig_verified.clone());
+            owner_valid_flags.push(no_nonce_overflow(
+                cs.namespace(|| "owner no nonce overflow"),
+                &cur.account.nonce.get_number(),
+            )?);
+
+            let is_serialized_order_correct = verify_signature_message_construction(
+                cs.namespace(|| "is_serialized_order_correct"),
+                serialized_order_bits,
+                &op_data,
+            )?;
+            owner_valid_flags.push(is_serialized_order_correct);
+
+            let is_signer_valid = CircuitElement::equals(
+                cs.namespace(|| "owner_signer_key_correct"),
+                &signer_key.pubkey.get_hash(),
+                &cur.account.pub_key_hash,
+            )?;
+            owner_valid_flags.push(is_signer_valid);
+
+            let is_owner_valid = multi_and(cs.namespace(|| "is_owner_valid"), &owner_valid_flags)?;
+
+            let updated_balance = Expression::from(&cur.balance.get_number())
+                - Expression::from(&amount_sell.get_number());
+
+            let updated_nonce =
+                Expression::from(&cur.account.nonce.get_number()) + Expression::u64::<CS>(1);
+
+            cur.account.nonce = CircuitElement::conditionally_select_with_number_strict(
+                cs.namespace(|| "update owner nonce"),
+                updated_nonce,
+                &cur.account.nonce,
+                &is_owner_valid,
+            )?;
+
+            cur.balance = CircuitElement::conditionally_select_with_number_strict(
+                cs.namespace(|| "update owner balance"),
+                updated_balance,
+                &cur.balance,
+                &is_owner_valid,
+            )?;
+
+            // order recipient
+            let mut recipient_valid_flags = common_valid_flags.clone();
+            recipient_valid_flags.push(is_chunk[2 * order + 2].clone());
+            recipient_valid_flags.push(is_account_empty.not());
+
+            recipient_valid_flags.push(CircuitElement::equals(
+                cs.namespace(|| "is_recipient_correct"),
+                &cur.account_id,
+                &accounts[2 * order + 1],
+            )?);
+            recipient_valid_flags.push(CircuitElement::equals(
+                cs.namespace(|| "is_token_buy_correct"),
+                &cur.token,
+                &tokens[other],
+            )?);
+            // Check that the recipient account is the one specified in the order.
+            recipient_valid_flags.push(CircuitElement::equals(
+                cs.namespace(|| "is_recipient_address_correct"),
+                &cur.account.address,
+                &op_data.special_eth_addresses[order],
+            )?);
+
+            let is_recipient_valid = multi_and(
+                cs.namespace(|| "is_recipient_valid"),
+                &recipient_valid_flags,
+            )?;
+
+            let updated_balance = Expression::from(&cur.balance.get_number())
+                + Expression::from(&amount_buy.get_number());
+
+            cur.balance = CircuitElement::conditionally_select_with_number_strict(
+                cs.namespace(|| "update recipient balance"),
+                updated_balance,
+                &cur.balance,
+                &is_recipient_valid,
+            )?;
+
+            is_op_valid_flags.push(is_owner_valid);
+            is_op_valid_flags.push(is_recipient_valid);
+        }
+
+        let is_op_valid = multi_or(cs.namespace(|| "is_op_valid"), &is_op_valid_flags)?;
+        Ok(is_op_valid)
+    }
 }
 
 pub fn check_account_data<E: RescueEngine, CS: ConstraintSystem<E>>(
@@ -2779,6 +3250,7 @@ fn generate_maxchunk_polynomial<E: JubjubEngine>() -> Vec<E::Fr> {
     points.push(get_xy(FullExitOp::OP_CODE, FullExitOp::CHUNKS));
     points.push(get_xy(ChangePubKeyOp::OP_CODE, ChangePubKeyOp::CHUNKS));
     points.push(get_xy(ForcedExitOp::OP_CODE, ForcedExitOp::CHUNKS));
+    points.push(get_xy(SwapOp::OP_CODE, SwapOp::CHUNKS));
 
     let interpolation = interpolate::<E>(&points[..]).expect("must interpolate");
     assert_eq!(interpolation.len(), DIFFERENT_TRANSACTIONS_TYPE_NUMBER);
@@ -2786,6 +3258,69 @@ fn generate_maxchunk_polynomial<E: JubjubEngine>() -> Vec<E::Fr> {
     interpolation
 }
 
+/// Checks that the block timestamp is within the time range of the transaction
+/// (both bounds are inclusive).
+fn is_time_range_valid<E: JubjubEngine, CS: ConstraintSystem<E>>(
+    mut cs: CS,
+    valid_from: &CircuitElement<E>,
+    valid_until: &CircuitElement<E>,
+    timestamp: &CircuitElement<E>,
+) -> Result<Boolean, SynthesisError> {
+    let is_not_too_early = is_timestamp_geq(
+        cs.namespace(|| "timestamp is not less than valid_from"),
+        timestamp,
+        valid_from,
+    )?;
+    let is_not_too_late = is_timestamp_geq(
+        cs.namespace(|| "valid_until is not less than timestamp"),
+        valid_until,
+        timestamp,
+    )?;
+
+    Boolean::and(
+        cs.namespace(|| "is timestamp within time range"),
+        &is_not_too_early,
+        &is_not_too_late,
+    )
+}
+
+fn is_timestamp_geq<E: JubjubEngine, CS: ConstraintSystem<E>>(
+    mut cs: CS,
+    a: &CircuitElement<E>,
+    b: &CircuitElement<E>,
+) -> Result<Boolean, SynthesisError> {
+    let diff = Expression::from(&a.get_number()) - Expression::from(&b.get_number());
+    let diff_bits = diff.into_bits_le_fixed(
+        cs.namespace(|| "timestamp diff bits"),
+        params::TIMESTAMP_BIT_WIDTH,
+    )?;
+    let diff_repacked = Expression::from_le_bits::<CS>(&diff_bits);
+
+    Ok(Boolean::from(Expression::equals(
+        cs.namespace(|| "timestamp diff equal to repacked"),
+        diff,
+        diff_repacked,
+    )?))
+}
+
+/// Checks that `a` is greater than or equal to `b`, provided that both of them fit into `bit_width` bits.
+fn is_geq<E: JubjubEngine, CS: ConstraintSystem<E>>(
+    mut cs: CS,
+    a: &AllocatedNum<E>,
+    b: &AllocatedNum<E>,
+    bit_width: usize,
+) -> Result<Boolean, SynthesisError> {
+    let diff = Expression::from(a) - Expression::from(b);
+    let diff_bits = diff.into_bits_le_fixed(cs.namespace(|| "diff bits"), bit_width)?;
+    let diff_repacked = Expression::from_le_bits::<CS>(&diff_bits);
+
+    Ok(Boolean::from(Expression::equals(
+        cs.namespace(|| "diff equal to repacked"),
+        diff,
+        diff_repacked,
+    )?))
+}
+
 fn no_nonce_overflow<E: JubjubEngine, CS: ConstraintSystem<E>>(
     mut cs: CS,
     nonce: &AllocatedNum<E>,
@@ -51,6 +51,14 @@ pub struct OperationArguments<E: RescueEngine> {
     pub new_pub_key_hash: Option<E::Fr>,
     pub eth_address: Option<E::Fr>,
     pub pub_nonce: Option<E::Fr>,
+    pub valid_from: Option<E::Fr>,
+    pub valid_until: Option<E::Fr>,
+    pub second_amount_packed: Option<E::Fr>,
+    pub special_eth_addresses: Vec<Option<E::Fr>>,
+    pub special_tokens: Vec<Option<E::Fr>>,
+    pub special_accounts: Vec<Option<E::Fr>>,
+    pub special_prices: Vec<Option<E::Fr>>,
+    pub special_amounts: Vec<Option<E::Fr>>,
 }
 
 #[derive(Clone)]
@@ -21,7 +21,7 @@ fn test_transpile_deposit_franklin_existing_account() {
         ZkSyncStateGenerator::generate(&vec![account]);
 
     let fee_account_id = 0;
-    let mut witness_accum = WitnessBuilder::new(&mut circuit_account_tree, fee_account_id, 1);
+    let mut witness_accum = WitnessBuilder::new(&mut circuit_account_tree, fee_account_id, 1, 0);
 
     let deposit_op = DepositOp {
         priority_op: Deposit {
@@ -102,7 +102,7 @@ fn test_new_transpile_deposit_franklin_existing_account_validate_only() {
     let deposit_to_account_id = account.id;
     let deposit_to_account_address = account.account.address;
     let (mut plasma_state, mut circuit_tree) = ZkSyncStateGenerator::generate(&vec![account]);
-    let mut witness_accum = WitnessBuilder::new(&mut circuit_tree, 0, 1);
+    let mut witness_accum = WitnessBuilder::new(&mut circuit_tree, 0, 1, 0);
 
     let deposit_op = DepositOp {
         priority_op: Deposit {
@@ -211,7 +211,7 @@ fn test_new_transpile_deposit_franklin_existing_account() {
     let (mut plasma_state, mut circuit_account_tree) =
         ZkSyncStateGenerator::generate(&vec![account]);
     let fee_account_id = 0;
-    let mut witness_accum = WitnessBuilder::new(&mut circuit_account_tree, fee_account_id, 1);
+    let mut witness_accum = WitnessBuilder::new(&mut circuit_account_tree, fee_account_id, 1, 0);
 
     let deposit_op = DepositOp {
         priority_op: Deposit {
@@ -386,7 +386,7 @@ fn test_fma_transpile_deposit_franklin_existing_account() {
     let deposit_to_account_id = account.id;
     let deposit_to_account_address = account.account.address;
     let (mut plasma_state, mut circuit_tree) = ZkSyncStateGenerator::generate(&vec![account]);
-    let mut witness_accum = WitnessBuilder::new(&mut circuit_tree, 0, 1);
+    let mut witness_accum = WitnessBuilder::new(&mut circuit_tree, 0, 1, 0);
 
     let deposit_op = DepositOp {
         priority_op: Deposit {
@@ -479,7 +479,7 @@ fn print_available_setup_powers() {
     let calculate_setup_power = |chunks: usize| -> (usize, u32) {
         let circuit = {
             let (_, mut circuit_account_tree) = ZkSyncStateGenerator::generate(&[]);
-            let mut witness_accum = WitnessBuilder::new(&mut circuit_account_tree, 0, 1);
+            let mut witness_accum = WitnessBuilder::new(&mut circuit_account_tree, 0, 1, 0);
             witness_accum.extend_pubdata_with_noops(chunks);
             witness_accum.collect_fees(&[]);
             witness_accum.calculate_pubdata_commitment();
@@ -38,6 +38,8 @@ pub struct ChangePubkeyOffChainData {
     pub fee_token: u32,
     pub fee: u128,
     pub nonce: Fr,
+    pub valid_from: u64,
+    pub valid_until: u64,
 }
 
 pub struct ChangePubkeyOffChainWitness<E: RescueEngine> {
@@ -61,6 +63,8 @@ impl Witness for ChangePubkeyOffChainWitness<Bn256> {
             fee_token: u32::from(change_pubkey_offchain.tx.fee_token),
             fee: change_pubkey_offchain.tx.fee.to_u128().unwrap(),
             nonce: Fr::from_str(&change_pubkey_offchain.tx.nonce.to_string()).unwrap(),
+            valid_from: change_pubkey_offchain.tx.time_range.valid_from,
+            valid_until: change_pubkey_offchain.tx.time_range.valid_until,
         };
 
         Self::apply_data(tree, change_pubkey_data)
@@ -216,12 +220,24 @@ impl ChangePubkeyOffChainWitness<Bn256> {
             args: OperationArguments {
                 eth_address: Some(change_pubkey_offcahin.address),
                 amount_packed: Some(Fr::zero()),
+                second_amount_packed: Some(Fr::zero()),
+                special_eth_addresses: vec![Some(Fr::zero()); 2],
+                special_tokens: vec![Some(Fr::zero()); 3],
+                special_accounts: vec![Some(Fr::zero()); 5],
+                special_prices: vec![Some(Fr::zero()); 4],
+                special_amounts: vec![Some(Fr::zero()); 4],
                 full_amount: Some(Fr::zero()),
                 fee: Some(fee_encoded),
                 a: Some(a),
                 b: Some(b),
                 pub_nonce: Some(change_pubkey_offcahin.nonce),
                 new_pub_key_hash: Some(change_pubkey_offcahin.new_pubkey_hash),
+                valid_from: Some(
+                    Fr::from_str(&change_pubkey_offcahin.valid_from.to_string()).unwrap(),
+                ),
+                valid_until: Some(
+                    Fr::from_str(&change_pubkey_offcahin.valid_until.to_string()).unwrap(),
+                ),
             },
             before_root: Some(before_root),
             after_root: Some(after_root),
@@ -172,8 +172,16 @@ impl CloseAccountWitness<Bn256> {
             args: OperationArguments {
                 eth_address: Some(Fr::zero()),
                 amount_packed: Some(Fr::zero()),
+                second_amount_packed: Some(Fr::zero()),
+                special_eth_addresses: vec![Some(Fr::zero()); 2],
+                special_tokens: vec![Some(Fr::zero()); 3],
+                special_accounts: vec![Some(Fr::zero()); 5],
+                special_prices: vec![Some(Fr::zero()); 4],
+                special_amounts: vec![Some(Fr::zero()); 4],
                 full_amount: Some(Fr::zero()),
                 pub_nonce: Some(Fr::zero()),
+                valid_from: Some(Fr::zero()),
+                valid_until: Some(Fr::zero()),
                 fee: Some(Fr::zero()),
                 a: Some(a),
                 b: Some(b),
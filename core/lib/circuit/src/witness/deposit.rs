@@ -235,11 +235,19 @@ impl DepositWitness<Bn256> {
             args: OperationArguments {
                 eth_address: Some(deposit.address),
                 amount_packed: Some(Fr::zero()),
+                second_amount_packed: Some(Fr::zero()),
+                special_eth_addresses: vec![Some(Fr::zero()); 2],
+                special_tokens: vec![Some(Fr::zero()); 3],
+                special_accounts: vec![Some(Fr::zero()); 5],
+                special_prices: vec![Some(Fr::zero()); 4],
+                special_amounts: vec![Some(Fr::zero()); 4],
                 full_amount: Some(amount_as_field_element),
                 fee: Some(Fr::zero()),
                 a: Some(a),
                 b: Some(b),
                 pub_nonce: Some(Fr::zero()),
+                valid_from: Some(Fr::zero()),
+                valid_until: Some(Fr::zero()),
                 new_pub_key_hash: Some(Fr::zero()),
             },
             before_root: Some(before_root),
@@ -17,7 +17,7 @@ use zksync_crypto::{
         account_tree_depth, ACCOUNT_ID_BIT_WIDTH, AMOUNT_EXPONENT_BIT_WIDTH,
         AMOUNT_MANTISSA_BIT_WIDTH, BALANCE_BIT_WIDTH, CHUNK_BIT_WIDTH, ETH_ADDRESS_BIT_WIDTH,
         FEE_EXPONENT_BIT_WIDTH, FEE_MANTISSA_BIT_WIDTH, NEW_PUBKEY_HASH_WIDTH, NONCE_BIT_WIDTH,
-        TOKEN_BIT_WIDTH, TX_TYPE_BIT_WIDTH,
+        TIMESTAMP_BIT_WIDTH, TOKEN_BIT_WIDTH, TX_TYPE_BIT_WIDTH,
     },
     primitives::FloatConversions,
 };
@@ -39,6 +39,8 @@ pub struct ForcedExitData {
     pub initiator_account_address: u32,
     pub target_account_address: u32,
     pub target_account_eth_address: Fr,
+    pub valid_from: u64,
+    pub valid_until: u64,
 }
 
 pub struct ForcedExitWitness<E: RescueEngine> {
@@ -73,6 +75,8 @@ impl Witness for ForcedExitWitness<Bn256> {
             initiator_account_address: forced_exit.tx.initiator_account_id,
             target_account_address: forced_exit.target_account_id,
             target_account_eth_address: eth_address_to_fr(&forced_exit.tx.target),
+            valid_from: forced_exit.tx.time_range.valid_from,
+            valid_until: forced_exit.tx.time_range.valid_until,
         };
         Self::apply_data(tree, &forced_exit_data)
     }
@@ -226,6 +230,16 @@ impl<E: RescueEngine> ForcedExitWitness<E> {
             &self.initiator_before.witness.account_witness.nonce.unwrap(),
             NONCE_BIT_WIDTH,
         );
+        append_be_fixed_width(
+            &mut sig_bits,
+            &self.args.valid_from.unwrap(),
+            TIMESTAMP_BIT_WIDTH,
+        );
+        append_be_fixed_width(
+            &mut sig_bits,
+            &self.args.valid_until.unwrap(),
+            TIMESTAMP_BIT_WIDTH,
+        );
         sig_bits
     }
 }
@@ -394,12 +408,20 @@ impl ForcedExitWitness<Bn256> {
             args: OperationArguments {
                 eth_address: Some(forced_exit.target_account_eth_address),
                 amount_packed: Some(amount_encoded),
+                second_amount_packed: Some(Fr::zero()),
+                special_eth_addresses: vec![Some(Fr::zero()); 2],
+                special_tokens: vec![Some(Fr::zero()); 3],
+                special_accounts: vec![Some(Fr::zero()); 5],
+                special_prices: vec![Some(Fr::zero()); 4],
+                special_amounts: vec![Some(Fr::zero()); 4],
                 full_amount: Some(amount_as_field_element),
                 fee: Some(fee_encoded),
                 pub_nonce: Some(Fr::zero()),
                 a: Some(a),
                 b: Some(b),
                 new_pub_key_hash: Some(Fr::zero()),
+                valid_from: Some(Fr::from_str(&forced_exit.valid_from.to_string()).unwrap()),
+                valid_until: Some(Fr::from_str(&forced_exit.valid_until.to_string()).unwrap()),
             },
             before_root: Some(before_root),
             intermediate_root: Some(intermediate_root),
@@ -11,6 +11,7 @@ pub use self::{
     deposit::DepositWitness,
     forced_exit::ForcedExitWitness,
     full_exit::FullExitWitness,
+    swap::SwapWitness,
     transfer::TransferWitness,
     transfer_to_new::TransferToNewWitness,
     utils::{SigDataInput, WitnessBuilder},
@@ -23,6 +24,7 @@ pub mod deposit;
 pub mod forced_exit;
 pub mod full_exit;
 pub mod noop;
+pub mod swap;
 pub mod transfer;
 pub mod transfer_to_new;
 pub mod withdraw;
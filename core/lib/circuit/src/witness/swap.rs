@@ -0,0 +1,318 @@
+// External deps
+use num::ToPrimitive;
+use zksync_crypto::franklin_crypto::{
+    bellman::pairing::{
+        bn256::{Bn256, Fr},
+        ff::{Field, PrimeField},
+    },
+    rescue::RescueEngine,
+};
+// Workspace deps
+use zksync_crypto::{
+    circuit::{
+        account::CircuitAccountTree,
+        utils::{append_be_fixed_width, eth_address_to_fr, le_bit_vector_into_field_element},
+    },
+    params::{
+        ACCOUNT_ID_BIT_WIDTH, AMOUNT_EXPONENT_BIT_WIDTH, AMOUNT_MANTISSA_BIT_WIDTH,
+        CHUNK_BIT_WIDTH, FEE_EXPONENT_BIT_WIDTH, FEE_MANTISSA_BIT_WIDTH, TOKEN_BIT_WIDTH,
+        TX_TYPE_BIT_WIDTH,
+    },
+    primitives::FloatConversions,
+};
+use zksync_types::operations::SwapOp;
+// Local deps
+use crate::{
+    operation::{Operation, OperationArguments, OperationBranch, OperationBranchWitness},
+    utils::resize_grow_only,
+    witness::{
+        utils::{apply_leaf_operation, get_audits, SigDataInput},
+        Witness,
+    },
+};
+
+pub struct SwapData {
+    pub amounts: (u128, u128),
+    pub fee: u128,
+    pub tokens: (u32, u32),
+    pub fee_token: u32,
+    pub accounts: (u32, u32),
+    pub recipients: (u32, u32),
+    pub submitter: u32,
+    pub recipient_addresses: (Fr, Fr),
+    pub prices: ((u128, u128), (u128, u128)),
+    pub amount_bounds: ((u128, u128), (u128, u128)),
+    pub valid_from: u64,
+    pub valid_until: u64,
+}
+
+/// Witness of the `Swap` operation.
+///
+/// Every chunk of the operation updates a single account (in this order):
+/// submitter, owner of the first order, recipient of the first order,
+/// owner of the second order and recipient of the second order.
+pub struct SwapWitness<E: RescueEngine> {
+    /// Branches of the updated accounts, taken right before the corresponding chunk is applied.
+    pub branches: Vec<OperationBranch<E>>,
+    /// Tree roots after every chunk is applied.
+    pub roots: Vec<Option<E::Fr>>,
+    pub args: OperationArguments<E>,
+    pub before_root: Option<E::Fr>,
+    pub tx_type: Option<E::Fr>,
+}
+
+impl Witness for SwapWitness<Bn256> {
+    type OperationType = SwapOp;
+    /// Signature data of the first order, the second order and the swap transaction itself.
+    type CalculateOpsInput = (SigDataInput, SigDataInput, SigDataInput);
+
+    fn apply_tx(tree: &mut CircuitAccountTree, swap: &SwapOp) -> Self {
+        let (order_0, order_1) = &swap.tx.orders;
+        let swap_data = SwapData {
+            amounts: (
+                swap.tx.amounts.0.to_u128().unwrap(),
+                swap.tx.amounts.1.to_u128().unwrap(),
+            ),
+            fee: swap.tx.fee.to_u128().unwrap(),
+            tokens: (u32::from(order_0.token_sell), u32::from(order_1.token_sell)),
+            fee_token: u32::from(swap.tx.fee_token),
+            accounts: swap.accounts,
+            recipients: swap.recipients,
+            submitter: swap.submitter,
+            recipient_addresses: (
+                eth_address_to_fr(&order_0.recipient_address),
+                eth_address_to_fr(&order_1.recipient_address),
+            ),
+            prices: (
+                (
+                    order_0.price.0.to_u128().unwrap(),
+                    order_0.price.1.to_u128().unwrap(),
+                ),
+                (
+                    order_1.price.0.to_u128().unwrap(),
+                    order_1.price.1.to_u128().unwrap(),
+                ),
+            ),
+            amount_bounds: (
+                (
+                    order_0.min_amount.to_u128().unwrap(),
+                    order_0.max_amount.to_u128().unwrap(),
+                ),
+                (
+                    order_1.min_amount.to_u128().unwrap(),
+                    order_1.max_amount.to_u128().unwrap(),
+                ),
+            ),
+            valid_from: swap.tx.time_range.valid_from,
+            valid_until: swap.tx.time_range.valid_until,
+        };
+        Self::apply_data(tree, &swap_data)
+    }
+
+    fn get_pubdata(&self) -> Vec<bool> {
+        let mut pubdata_bits = vec![];
+        append_be_fixed_width(&mut pubdata_bits, &self.tx_type.unwrap(), TX_TYPE_BIT_WIDTH);
+
+        for account in &self.args.special_accounts {
+            append_be_fixed_width(&mut pubdata_bits, &account.unwrap(), ACCOUNT_ID_BIT_WIDTH);
+        }
+        for token in &self.args.special_tokens {
+            append_be_fixed_width(&mut pubdata_bits, &token.unwrap(), TOKEN_BIT_WIDTH);
+        }
+        append_be_fixed_width(
+            &mut pubdata_bits,
+            &self.args.amount_packed.unwrap(),
+            AMOUNT_EXPONENT_BIT_WIDTH + AMOUNT_MANTISSA_BIT_WIDTH,
+        );
+        append_be_fixed_width(
+            &mut pubdata_bits,
+            &self.args.second_amount_packed.unwrap(),
+            AMOUNT_EXPONENT_BIT_WIDTH + AMOUNT_MANTISSA_BIT_WIDTH,
+        );
+        append_be_fixed_width(
+            &mut pubdata_bits,
+            &self.args.fee.unwrap(),
+            FEE_EXPONENT_BIT_WIDTH + FEE_MANTISSA_BIT_WIDTH,
+        );
+        resize_grow_only(&mut pubdata_bits, SwapOp::CHUNKS * CHUNK_BIT_WIDTH, false);
+        pubdata_bits
+    }
+
+    fn calculate_operations(
+        &self,
+        input: (SigDataInput, SigDataInput, SigDataInput),
+    ) -> Vec<Operation<Bn256>> {
+        let pubdata_chunks: Vec<_> = self
+            .get_pubdata()
+            .chunks(CHUNK_BIT_WIDTH)
+            .map(|x| le_bit_vector_into_field_element(&x.to_vec()))
+            .collect();
+
+        let (order_0_input, order_1_input, swap_input) = input;
+        // Chunks of the orders owners are signed by the owners, the rest ones by the submitter.
+        let inputs = [
+            &swap_input,
+            &order_0_input,
+            &swap_input,
+            &order_1_input,
+            &swap_input,
+        ];
+
+        (0..SwapOp::CHUNKS)
+            .map(|chunk| Operation {
+                new_root: self.roots[chunk],
+                tx_type: self.tx_type,
+                chunk: Some(Fr::from_str(&chunk.to_string()).unwrap()),
+                pubdata_chunk: Some(pubdata_chunks[chunk]),
+                first_sig_msg: Some(inputs[chunk].first_sig_msg),
+                second_sig_msg: Some(inputs[chunk].second_sig_msg),
+                third_sig_msg: Some(inputs[chunk].third_sig_msg),
+                signature_data: inputs[chunk].signature.clone(),
+                signer_pub_key_packed: inputs[chunk].signer_pub_key_packed.to_vec(),
+                args: self.args.clone(),
+                lhs: self.branches[chunk].clone(),
+                rhs: self.branches[chunk].clone(),
+            })
+            .collect()
+    }
+}
+
+impl SwapWitness<Bn256> {
+    fn apply_data(tree: &mut CircuitAccountTree, swap: &SwapData) -> Self {
+        //preparing data and base witness
+        let before_root = tree.root_hash();
+        log::debug!("Initial root = {}", before_root);
+
+        let fr_from_u128 = |value: u128| Fr::from_str(&value.to_string()).unwrap();
+        let fr_from_u32 = |value: u32| Fr::from_str(&value.to_string()).unwrap();
+        let amount_encoded = |amount: u128| -> Fr {
+            let amount_bits = FloatConversions::to_float(
+                amount,
+                AMOUNT_EXPONENT_BIT_WIDTH,
+                AMOUNT_MANTISSA_BIT_WIDTH,
+                10,
+            )
+            .unwrap();
+            le_bit_vector_into_field_element(&amount_bits)
+        };
+
+        let amounts_fe = (fr_from_u128(swap.amounts.0), fr_from_u128(swap.amounts.1));
+        let fee_as_field_element = fr_from_u128(swap.fee);
+
+        let fee_bits = FloatConversions::to_float(
+            swap.fee,
+            FEE_EXPONENT_BIT_WIDTH,
+            FEE_MANTISSA_BIT_WIDTH,
+            10,
+        )
+        .unwrap();
+
+        let fee_encoded: Fr = le_bit_vector_into_field_element(&fee_bits);
+
+        // Steps of the operation in the same order as in the circuit:
+        // (account, token, balance change, whether the nonce is incremented).
+        let steps = [
+            (swap.submitter, swap.fee_token, fee_as_field_element, true),
+            (swap.accounts.0, swap.tokens.0, amounts_fe.0, true),
+            (swap.recipients.0, swap.tokens.1, amounts_fe.1, false),
+            (swap.accounts.1, swap.tokens.1, amounts_fe.1, true),
+            (swap.recipients.1, swap.tokens.0, amounts_fe.0, false),
+        ];
+
+        let mut branches = Vec::with_capacity(steps.len());
+        let mut roots = Vec::with_capacity(steps.len());
+        let mut submitter_balance_before = Fr::zero();
+        for (i, &(account, token, amount, is_debit)) in steps.iter().enumerate() {
+            let (audit_path, audit_balance_path) = get_audits(tree, account, token);
+
+            let (account_witness_before, _, balance_before, _) = apply_leaf_operation(
+                tree,
+                account,
+                token,
+                |acc| {
+                    if is_debit {
+                        acc.nonce.add_assign(&Fr::from_str("1").unwrap());
+                    }
+                },
+                |bal| {
+                    if is_debit {
+                        bal.value.sub_assign(&amount)
+                    } else {
+                        bal.value.add_assign(&amount)
+                    }
+                },
+            );
+
+            if i == 0 {
+                submitter_balance_before = balance_before;
+            }
+
+            let root = tree.root_hash();
+            log::debug!("Root after chunk {} = {}", i, root);
+
+            branches.push(OperationBranch {
+                address: Some(fr_from_u32(account)),
+                token: Some(fr_from_u32(token)),
+                witness: OperationBranchWitness {
+                    account_witness: account_witness_before,
+                    account_path: audit_path,
+                    balance_value: Some(balance_before),
+                    balance_subtree_path: audit_balance_path,
+                },
+            });
+            roots.push(Some(root));
+        }
+
+        //calculate a and b
+        let a = submitter_balance_before;
+        let b = fee_as_field_element;
+
+        SwapWitness {
+            branches,
+            roots,
+            args: OperationArguments {
+                eth_address: Some(Fr::zero()),
+                amount_packed: Some(amount_encoded(swap.amounts.0)),
+                second_amount_packed: Some(amount_encoded(swap.amounts.1)),
+                special_eth_addresses: vec![
+                    Some(swap.recipient_addresses.0),
+                    Some(swap.recipient_addresses.1),
+                ],
+                special_tokens: vec![
+                    Some(fr_from_u32(swap.tokens.0)),
+                    Some(fr_from_u32(swap.tokens.1)),
+                    Some(fr_from_u32(swap.fee_token)),
+                ],
+                special_accounts: vec![
+                    Some(fr_from_u32(swap.accounts.0)),
+                    Some(fr_from_u32(swap.recipients.0)),
+                    Some(fr_from_u32(swap.accounts.1)),
+                    Some(fr_from_u32(swap.recipients.1)),
+                    Some(fr_from_u32(swap.submitter)),
+                ],
+                special_prices: vec![
+                    Some(fr_from_u128((swap.prices.0).0)),
+                    Some(fr_from_u128((swap.prices.0).1)),
+                    Some(fr_from_u128((swap.prices.1).0)),
+                    Some(fr_from_u128((swap.prices.1).1)),
+                ],
+                special_amounts: vec![
+                    Some(amount_encoded((swap.amount_bounds.0).0)),
+                    Some(amount_encoded((swap.amount_bounds.0).1)),
+                    Some(amount_encoded((swap.amount_bounds.1).0)),
+                    Some(amount_encoded((swap.amount_bounds.1).1)),
+                ],
+                full_amount: Some(Fr::zero()),
+                fee: Some(fee_encoded),
+                pub_nonce: Some(Fr::zero()),
+                a: Some(a),
+                b: Some(b),
+                new_pub_key_hash: Some(Fr::zero()),
+                valid_from: Some(Fr::from_str(&swap.valid_from.to_string()).unwrap()),
+                valid_until: Some(Fr::from_str(&swap.valid_until.to_string()).unwrap()),
+            },
+            before_root: Some(before_root),
+            tx_type: Some(Fr::from_str(&SwapOp::OP_CODE.to_string()).unwrap()),
+        }
+    }
+}
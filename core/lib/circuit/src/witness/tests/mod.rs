@@ -37,6 +37,7 @@ mod deposit;
 mod forced_exit;
 mod full_exit;
 mod noop;
+mod swap;
 pub(crate) mod test_utils;
 mod transfer;
 mod transfer_to_new;
@@ -155,7 +156,7 @@ fn apply_many_ops() -> ZkSyncCircuit<'static, Bn256> {
 
     // Initialize Plasma and WitnessBuilder.
     let (mut plasma_state, mut circuit_account_tree) = ZkSyncStateGenerator::generate(&accounts);
-    let mut witness_accum = WitnessBuilder::new(&mut circuit_account_tree, FEE_ACCOUNT_ID, 1);
+    let mut witness_accum = WitnessBuilder::new(&mut circuit_account_tree, FEE_ACCOUNT_ID, 1, 0);
 
     // Fees to be collected.
     let mut fees = vec![];
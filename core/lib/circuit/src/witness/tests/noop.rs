@@ -122,7 +122,7 @@ fn test_noop() {
     let mut circuit_account_tree = CircuitAccountTree::new(account_tree_depth());
     circuit_account_tree.insert(0, CircuitAccount::default());
 
-    let mut witness_accum = WitnessBuilder::new(&mut circuit_account_tree, 0, 1);
+    let mut witness_accum = WitnessBuilder::new(&mut circuit_account_tree, 0, 1, 0);
     witness_accum.extend_pubdata_with_noops(1);
     witness_accum.collect_fees(&[]);
     witness_accum.calculate_pubdata_commitment();
@@ -141,7 +141,8 @@ fn test_noop() {
 /// - Incorrect old root hash in `ZkSyncCircuit`,
 /// - Incorrect old root hash in both `pub_data_commitment` and `ZkSyncCircuit` (same value),
 /// - Incorrect validator address in pubdata,
-/// - Incorrect block number in pubdata.
+/// - Incorrect block number in pubdata,
+/// - Incorrect block timestamp in pubdata.
 ///
 /// All these checks are implemented within one test to reduce the overhead of the
 /// circuit initialization.
@@ -164,6 +165,7 @@ fn incorrect_circuit_pubdata() {
 
     // We'll create one block with number 1
     let block_number = Fr::from_str("1").unwrap();
+    let block_timestamp = Fr::from_str("1600000000").unwrap();
 
     // Validator account credentials
     let (validator_address_number, validator_address, validator_balances) =
@@ -221,6 +223,7 @@ fn incorrect_circuit_pubdata() {
             Some(pubdata_new_hash),
             Some(validator_address),
             Some(block_number),
+            Some(block_timestamp),
         );
 
         let circuit_instance = ZkSyncCircuit {
@@ -231,6 +234,7 @@ fn incorrect_circuit_pubdata() {
             operations: vec![operation.clone()],
             pub_data_commitment: Some(public_data_commitment),
             block_number: Some(block_number),
+            block_timestamp: Some(block_timestamp),
             validator_account: validator_account_witness.clone(),
             validator_address: Some(validator_address),
             validator_balances: validator_balances.clone(),
@@ -258,6 +262,7 @@ fn incorrect_circuit_pubdata() {
         Some(tree.root_hash()),
         Some(Default::default()),
         Some(block_number),
+        Some(block_timestamp),
     );
 
     let circuit_instance = ZkSyncCircuit {
@@ -268,6 +273,7 @@ fn incorrect_circuit_pubdata() {
         operations: vec![operation.clone()],
         pub_data_commitment: Some(pub_data_commitment),
         block_number: Some(block_number),
+        block_timestamp: Some(block_timestamp),
         validator_account: validator_account_witness.clone(),
         validator_address: Some(validator_address),
         validator_balances: validator_balances.clone(),
@@ -300,6 +306,7 @@ fn incorrect_circuit_pubdata() {
         Some(tree.root_hash()),
         Some(validator_address),
         Some(incorrect_block_number),
+        Some(block_timestamp),
     );
 
     let circuit_instance = ZkSyncCircuit {
@@ -307,13 +314,14 @@ fn incorrect_circuit_pubdata() {
         jubjub_params,
         old_root: Some(tree.root_hash()),
         initial_used_subtree_root: Some(get_used_subtree_root_hash(&tree)),
-        operations: vec![operation],
+        operations: vec![operation.clone()],
         pub_data_commitment: Some(pub_data_commitment),
         block_number: Some(block_number),
-        validator_account: validator_account_witness,
+        block_timestamp: Some(block_timestamp),
+        validator_account: validator_account_witness.clone(),
         validator_address: Some(validator_address),
-        validator_balances,
-        validator_audit_path,
+        validator_balances: validator_balances.clone(),
+        validator_audit_path: validator_audit_path.clone(),
     };
 
     // Block number is a part of pubdata, which is used to calculate the new root hash,
@@ -330,4 +338,46 @@ fn incorrect_circuit_pubdata() {
         error,
         expected_msg
     );
+
+    // -------------------------
+    // Incorrect block timestamp
+    // -------------------------
+
+    let incorrect_block_timestamp = Fr::from_str("1700000000").unwrap();
+    let pub_data_commitment = public_data_commitment::<Bn256>(
+        &[false; 64],
+        Some(tree.root_hash()),
+        Some(tree.root_hash()),
+        Some(validator_address),
+        Some(block_number),
+        Some(incorrect_block_timestamp),
+    );
+
+    let circuit_instance = ZkSyncCircuit {
+        rescue_params,
+        jubjub_params,
+        old_root: Some(tree.root_hash()),
+        initial_used_subtree_root: Some(get_used_subtree_root_hash(&tree)),
+        operations: vec![operation],
+        pub_data_commitment: Some(pub_data_commitment),
+        block_number: Some(block_number),
+        block_timestamp: Some(block_timestamp),
+        validator_account: validator_account_witness,
+        validator_address: Some(validator_address),
+        validator_balances,
+        validator_audit_path,
+    };
+
+    // Block timestamp is a part of pubdata, so the hash value will not match expected one.
+    let expected_msg = "enforce external data hash equality";
+
+    let error = check_circuit_non_panicking(circuit_instance)
+        .expect_err("Block timestamp: Incorrect pubdata values should lead to an error");
+
+    assert!(
+        error.contains(expected_msg),
+        "Block timestamp: Got error message '{}', but expected '{}'",
+        error,
+        expected_msg
+    );
 }
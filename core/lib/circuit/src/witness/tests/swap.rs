@@ -0,0 +1,180 @@
+// External deps
+use num::BigUint;
+// Workspace deps
+use zksync_crypto::franklin_crypto::bellman::pairing::bn256::Bn256;
+use zksync_state::{
+    handler::TxHandler,
+    state::{CollectedFee, ZkSyncState},
+};
+use zksync_types::{Order, Swap, SwapOp, TokenId};
+// Local deps
+use crate::witness::{
+    swap::SwapWitness,
+    tests::test_utils::{
+        corrupted_input_test_scenario, generic_test_scenario, incorrect_op_test_scenario,
+        WitnessTestAccount,
+    },
+    utils::SigDataInput,
+};
+
+const TOKEN_A: TokenId = 0;
+const TOKEN_B: TokenId = 1;
+
+/// Creates an order of the account selling `token_sell` with bought tokens sent to the same account.
+/// Token A is valued as two tokens B.
+fn sign_order(account: &WitnessTestAccount, token_sell: TokenId, token_buy: TokenId) -> Order {
+    let price = if token_sell == TOKEN_A {
+        (BigUint::from(1u32), BigUint::from(2u32))
+    } else {
+        (BigUint::from(2u32), BigUint::from(1u32))
+    };
+
+    account.zksync_account.sign_order(
+        token_sell,
+        token_buy,
+        price,
+        BigUint::from(1u32),
+        BigUint::from(1000u32),
+        &account.account.address,
+        None,
+        true,
+    )
+}
+
+/// Creates accounts for the swap: two orders owners and the submitter.
+fn swap_accounts(amounts: (u64, u64), fee_amount: u64) -> Vec<WitnessTestAccount> {
+    let mut accounts = vec![
+        WitnessTestAccount::new(1, amounts.0),
+        WitnessTestAccount::new_empty(2),
+        WitnessTestAccount::new(3, fee_amount),
+    ];
+    accounts[1]
+        .account
+        .add_balance(TOKEN_B, &BigUint::from(amounts.1));
+    accounts
+}
+
+fn create_swap_op(accounts: &[WitnessTestAccount], amounts: (u64, u64), fee_amount: u64) -> SwapOp {
+    let (owner_0, owner_1, submitter) = (&accounts[0], &accounts[1], &accounts[2]);
+    let orders = (
+        sign_order(owner_0, TOKEN_A, TOKEN_B),
+        sign_order(owner_1, TOKEN_B, TOKEN_A),
+    );
+
+    SwapOp {
+        tx: submitter.zksync_account.sign_swap(
+            orders,
+            (BigUint::from(amounts.0), BigUint::from(amounts.1)),
+            TOKEN_A,
+            BigUint::from(fee_amount),
+            None,
+            true,
+        ),
+        submitter: submitter.id,
+        accounts: (owner_0.id, owner_1.id),
+        recipients: (owner_0.id, owner_1.id),
+    }
+}
+
+/// Basic check for execution of `Swap` operation in circuit.
+/// Here we create two accounts exchanging tokens and the third one submitting the swap.
+#[test]
+#[ignore]
+fn test_swap_success() {
+    // Test vector of ((amount_sell, amount_buy), fee_amount).
+    let test_vector = vec![((50u64, 100u64), 3u64), ((1, 2), 1), ((100, 200), 0)];
+
+    for (amounts, fee_amount) in test_vector {
+        let accounts = swap_accounts(amounts, fee_amount);
+        let swap_op = create_swap_op(&accounts, amounts, fee_amount);
+
+        // Additional data required for performing the operation.
+        let input = SigDataInput::from_swap_op(&swap_op).expect("SigDataInput creation failed");
+
+        generic_test_scenario::<SwapWitness<Bn256>, _>(
+            &accounts,
+            swap_op,
+            input,
+            |plasma_state, op| {
+                let fee = <ZkSyncState as TxHandler<Swap>>::apply_op(plasma_state, &op)
+                    .expect("Swap failed")
+                    .0
+                    .unwrap();
+
+                vec![fee]
+            },
+        );
+    }
+}
+
+/// Checks that corrupted signature data leads to unsatisfied constraints in circuit.
+#[test]
+#[ignore]
+fn corrupted_ops_input() {
+    // Incorrect signature data will lead to `op_valid` constraint failure.
+    // See `circuit.rs` for details.
+    const EXPECTED_PANIC_MSG: &str = "op_valid is true";
+
+    // Legit input data.
+    let amounts = (50, 100);
+    let fee_amount = 3;
+    let accounts = swap_accounts(amounts, fee_amount);
+    let swap_op = create_swap_op(&accounts, amounts, fee_amount);
+
+    // Additional data required for performing the operation.
+    let (order_0_input, order_1_input, swap_input) =
+        SigDataInput::from_swap_op(&swap_op).expect("SigDataInput creation failed");
+
+    // Test vector with values corrupted one by one in every signature.
+    let mut test_vector = vec![];
+    for input in order_0_input.corrupted_variations() {
+        test_vector.push((input, order_1_input.clone(), swap_input.clone()));
+    }
+    for input in order_1_input.corrupted_variations() {
+        test_vector.push((order_0_input.clone(), input, swap_input.clone()));
+    }
+    for input in swap_input.corrupted_variations() {
+        test_vector.push((order_0_input.clone(), order_1_input.clone(), input));
+    }
+
+    for input in test_vector {
+        corrupted_input_test_scenario::<SwapWitness<Bn256>, _>(
+            &accounts,
+            swap_op.clone(),
+            input,
+            EXPECTED_PANIC_MSG,
+            |plasma_state, op| {
+                let fee = <ZkSyncState as TxHandler<Swap>>::apply_op(plasma_state, &op)
+                    .expect("Operation failed")
+                    .0
+                    .unwrap();
+                vec![fee]
+            },
+        );
+    }
+}
+
+/// Checks that executing a swap operation with amounts not
+/// satisfying the order price results in an error.
+#[test]
+#[ignore]
+fn test_incorrect_price() {
+    const FEE_AMOUNT: u64 = 3;
+
+    // Operation is not valid, since the first order requires at least 100 B for 50 A.
+    const ERR_MSG: &str = "op_valid is true/enforce equal to one";
+
+    let amounts = (50, 99);
+    let accounts = swap_accounts(amounts, FEE_AMOUNT);
+    let swap_op = create_swap_op(&accounts, amounts, FEE_AMOUNT);
+
+    // Additional data required for performing the operation.
+    let input = SigDataInput::from_swap_op(&swap_op).expect("SigDataInput creation failed");
+
+    incorrect_op_test_scenario::<SwapWitness<Bn256>, _>(&accounts, swap_op, input, ERR_MSG, || {
+        vec![CollectedFee {
+            token: TOKEN_A,
+            amount: FEE_AMOUNT.into(),
+        }]
+    });
+}
@@ -6,7 +6,10 @@ use zksync_state::{
     handler::TxHandler,
     state::{CollectedFee, TransferOutcome, ZkSyncState},
 };
-use zksync_types::{operations::TransferOp, tx::Transfer};
+use zksync_types::{
+    operations::TransferOp,
+    tx::{TimeRange, Transfer},
+};
 // Local deps
 use crate::witness::{
     tests::test_utils::{
@@ -398,3 +401,55 @@ fn test_transfer_replay() {
         },
     );
 }
+
+/// Checks that the transfer can't be executed if the block timestamp is out
+/// of the transaction time range.
+#[test]
+#[ignore]
+fn test_transfer_outside_of_time_range() {
+    const TOKEN_ID: u16 = 0;
+    const INITIAL_BALANCE: u64 = 10;
+    const TOKEN_AMOUNT: u64 = 7;
+    const FEE_AMOUNT: u64 = 3;
+
+    // Operation is not valid, since the block timestamp (zero in the test blocks)
+    // is less than `valid_from`.
+    const ERR_MSG: &str = "op_valid is true/enforce equal to one";
+
+    let accounts = vec![
+        WitnessTestAccount::new(1, INITIAL_BALANCE),
+        WitnessTestAccount::new_empty(2),
+    ];
+    let (account_from, account_to) = (&accounts[0], &accounts[1]);
+    let transfer_op = TransferOp {
+        tx: Transfer::new_signed(
+            account_from.id,
+            account_from.account.address,
+            account_to.account.address,
+            TOKEN_ID,
+            BigUint::from(TOKEN_AMOUNT),
+            BigUint::from(FEE_AMOUNT),
+            account_from.account.nonce,
+            TimeRange::new(1, u64::max_value()),
+            &account_from.zksync_account.private_key,
+        )
+        .expect("Failed to sign transfer"),
+        from: account_from.id,
+        to: account_to.id,
+    };
+
+    let input = SigDataInput::from_transfer_op(&transfer_op).expect("SigDataInput creation failed");
+
+    incorrect_op_test_scenario::<TransferWitness<Bn256>, _>(
+        &accounts,
+        transfer_op,
+        input,
+        ERR_MSG,
+        || {
+            vec![CollectedFee {
+                token: TOKEN_ID,
+                amount: FEE_AMOUNT.into(),
+            }]
+        },
+    );
+}
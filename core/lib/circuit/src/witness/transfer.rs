@@ -16,7 +16,8 @@ use zksync_crypto::{
     params::{
         account_tree_depth, ACCOUNT_ID_BIT_WIDTH, AMOUNT_EXPONENT_BIT_WIDTH,
         AMOUNT_MANTISSA_BIT_WIDTH, CHUNK_BIT_WIDTH, FEE_EXPONENT_BIT_WIDTH, FEE_MANTISSA_BIT_WIDTH,
-        NEW_PUBKEY_HASH_WIDTH, NONCE_BIT_WIDTH, TOKEN_BIT_WIDTH, TX_TYPE_BIT_WIDTH,
+        NEW_PUBKEY_HASH_WIDTH, NONCE_BIT_WIDTH, TIMESTAMP_BIT_WIDTH, TOKEN_BIT_WIDTH,
+        TX_TYPE_BIT_WIDTH,
     },
     primitives::FloatConversions,
 };
@@ -38,6 +39,8 @@ pub struct TransferData {
     pub token: u32,
     pub from_account_address: u32,
     pub to_account_address: u32,
+    pub valid_from: u64,
+    pub valid_until: u64,
 }
 
 pub struct TransferWitness<E: RescueEngine> {
@@ -65,6 +68,8 @@ impl Witness for TransferWitness<Bn256> {
             token: u32::from(transfer.tx.token),
             from_account_address: transfer.from,
             to_account_address: transfer.to,
+            valid_from: transfer.tx.time_range.valid_from,
+            valid_until: transfer.tx.time_range.valid_until,
         };
         // le_bit_vector_into_field_element()
         Self::apply_data(tree, &transfer_data)
@@ -193,6 +198,16 @@ impl<E: RescueEngine> TransferWitness<E> {
             &self.from_before.witness.account_witness.nonce.unwrap(),
             NONCE_BIT_WIDTH,
         );
+        append_be_fixed_width(
+            &mut sig_bits,
+            &self.args.valid_from.unwrap(),
+            TIMESTAMP_BIT_WIDTH,
+        );
+        append_be_fixed_width(
+            &mut sig_bits,
+            &self.args.valid_until.unwrap(),
+            TIMESTAMP_BIT_WIDTH,
+        );
         sig_bits
     }
 }
@@ -354,12 +369,20 @@ impl TransferWitness<Bn256> {
             args: OperationArguments {
                 eth_address: Some(Fr::zero()),
                 amount_packed: Some(amount_encoded),
+                second_amount_packed: Some(Fr::zero()),
+                special_eth_addresses: vec![Some(Fr::zero()); 2],
+                special_tokens: vec![Some(Fr::zero()); 3],
+                special_accounts: vec![Some(Fr::zero()); 5],
+                special_prices: vec![Some(Fr::zero()); 4],
+                special_amounts: vec![Some(Fr::zero()); 4],
                 full_amount: Some(amount_as_field_element),
                 fee: Some(fee_encoded),
                 pub_nonce: Some(Fr::zero()),
                 a: Some(a),
                 b: Some(b),
                 new_pub_key_hash: Some(Fr::zero()),
+                valid_from: Some(Fr::from_str(&transfer.valid_from.to_string()).unwrap()),
+                valid_until: Some(Fr::from_str(&transfer.valid_until.to_string()).unwrap()),
             },
             before_root: Some(before_root),
             intermediate_root: Some(intermediate_root),
@@ -14,8 +14,8 @@ use zksync_crypto::{
     params::{
         account_tree_depth, ACCOUNT_ID_BIT_WIDTH, AMOUNT_EXPONENT_BIT_WIDTH,
         AMOUNT_MANTISSA_BIT_WIDTH, CHUNK_BIT_WIDTH, ETH_ADDRESS_BIT_WIDTH, FEE_EXPONENT_BIT_WIDTH,
-        FEE_MANTISSA_BIT_WIDTH, NEW_PUBKEY_HASH_WIDTH, NONCE_BIT_WIDTH, TOKEN_BIT_WIDTH,
-        TX_TYPE_BIT_WIDTH,
+        FEE_MANTISSA_BIT_WIDTH, NEW_PUBKEY_HASH_WIDTH, NONCE_BIT_WIDTH, TIMESTAMP_BIT_WIDTH,
+        TOKEN_BIT_WIDTH, TX_TYPE_BIT_WIDTH,
     },
     primitives::FloatConversions,
 };
@@ -38,6 +38,8 @@ pub struct TransferToNewData {
     pub from_account_address: u32,
     pub to_account_address: u32,
     pub new_address: Fr,
+    pub valid_from: u64,
+    pub valid_until: u64,
 }
 
 pub struct TransferToNewWitness<E: RescueEngine> {
@@ -66,6 +68,8 @@ impl Witness for TransferToNewWitness<Bn256> {
             from_account_address: transfer_to_new.from,
             to_account_address: transfer_to_new.to,
             new_address: eth_address_to_fr(&transfer_to_new.tx.to),
+            valid_from: transfer_to_new.tx.time_range.valid_from,
+            valid_until: transfer_to_new.tx.time_range.valid_until,
         };
         // le_bit_vector_into_field_element()
         Self::apply_data(tree, &transfer_data)
@@ -218,6 +222,16 @@ impl<E: RescueEngine> TransferToNewWitness<E> {
             &self.from_before.witness.account_witness.nonce.unwrap(),
             NONCE_BIT_WIDTH,
         );
+        append_be_fixed_width(
+            &mut sig_bits,
+            &self.args.valid_from.unwrap(),
+            TIMESTAMP_BIT_WIDTH,
+        );
+        append_be_fixed_width(
+            &mut sig_bits,
+            &self.args.valid_until.unwrap(),
+            TIMESTAMP_BIT_WIDTH,
+        );
         sig_bits
     }
 }
@@ -404,12 +418,20 @@ impl TransferToNewWitness<Bn256> {
             args: OperationArguments {
                 eth_address: Some(transfer_to_new.new_address),
                 amount_packed: Some(amount_encoded),
+                second_amount_packed: Some(Fr::zero()),
+                special_eth_addresses: vec![Some(Fr::zero()); 2],
+                special_tokens: vec![Some(Fr::zero()); 3],
+                special_accounts: vec![Some(Fr::zero()); 5],
+                special_prices: vec![Some(Fr::zero()); 4],
+                special_amounts: vec![Some(Fr::zero()); 4],
                 full_amount: Some(amount_as_field_element),
                 fee: Some(fee_encoded),
                 a: Some(a),
                 b: Some(b),
                 pub_nonce: Some(Fr::zero()),
                 new_pub_key_hash: Some(Fr::zero()),
+                valid_from: Some(Fr::from_str(&transfer_to_new.valid_from.to_string()).unwrap()),
+                valid_until: Some(Fr::from_str(&transfer_to_new.valid_until.to_string()).unwrap()),
             },
             before_root: Some(before_root),
             intermediate_root: Some(intermediate_root),
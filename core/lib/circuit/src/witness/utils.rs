@@ -28,14 +28,16 @@ use zksync_crypto::{
 use zksync_state::state::CollectedFee;
 use zksync_types::{
     block::Block,
-    operations::{ChangePubKeyOp, CloseOp, ForcedExitOp, TransferOp, TransferToNewOp, WithdrawOp},
-    tx::PackedPublicKey,
+    operations::{
+        ChangePubKeyOp, CloseOp, ForcedExitOp, SwapOp, TransferOp, TransferToNewOp, WithdrawOp,
+    },
+    tx::{Order, PackedPublicKey},
     AccountId, BlockNumber, ZkSyncOp,
 };
 // Local deps
 use crate::witness::{
     ChangePubkeyOffChainWitness, CloseAccountWitness, DepositWitness, ForcedExitWitness,
-    FullExitWitness, TransferToNewWitness, TransferWitness, WithdrawWitness, Witness,
+    FullExitWitness, SwapWitness, TransferToNewWitness, TransferWitness, WithdrawWitness, Witness,
 };
 use crate::{
     account::AccountWitness,
@@ -51,6 +53,7 @@ pub struct WitnessBuilder<'a> {
     pub account_tree: &'a mut CircuitAccountTree,
     pub fee_account_id: AccountId,
     pub block_number: BlockNumber,
+    pub block_timestamp: u64,
     pub initial_root_hash: Fr,
     pub initial_used_subtree_root_hash: Fr,
     pub operations: Vec<Operation<Engine>>,
@@ -68,6 +71,7 @@ impl<'a> WitnessBuilder<'a> {
         account_tree: &'a mut CircuitAccountTree,
         fee_account_id: AccountId,
         block_number: BlockNumber,
+        block_timestamp: u64,
     ) -> WitnessBuilder {
         let initial_root_hash = account_tree.root_hash();
         let initial_used_subtree_root_hash = get_used_subtree_root_hash(account_tree);
@@ -75,6 +79,7 @@ impl<'a> WitnessBuilder<'a> {
             account_tree,
             fee_account_id,
             block_number,
+            block_timestamp,
             initial_root_hash,
             initial_used_subtree_root_hash,
             operations: Vec::new(),
@@ -160,6 +165,7 @@ impl<'a> WitnessBuilder<'a> {
             ),
             Some(Fr::from_str(&self.fee_account_id.to_string()).expect("failed to parse")),
             Some(Fr::from_str(&self.block_number.to_string()).unwrap()),
+            Some(Fr::from_str(&self.block_timestamp.to_string()).unwrap()),
         );
         self.pubdata_commitment = Some(public_data_commitment);
     }
@@ -177,6 +183,7 @@ impl<'a> WitnessBuilder<'a> {
                     .expect("pubdata commitment not present"),
             ),
             block_number: Some(Fr::from_str(&self.block_number.to_string()).unwrap()),
+            block_timestamp: Some(Fr::from_str(&self.block_timestamp.to_string()).unwrap()),
             validator_account: self
                 .fee_account_witness
                 .expect("fee account witness not present"),
@@ -248,6 +255,7 @@ pub fn public_data_commitment<E: JubjubEngine>(
     new_root: Option<E::Fr>,
     validator_address: Option<E::Fr>,
     block_number: Option<E::Fr>,
+    block_timestamp: Option<E::Fr>,
 ) -> E::Fr {
     let mut public_data_initial_bits = vec![];
 
@@ -306,6 +314,22 @@ pub fn public_data_commitment<E: JubjubEngine>(
     hash_result = [0u8; 32];
     h.result(&mut hash_result[..]);
 
+    let timestamp_bits: Vec<bool> =
+        BitIterator::new(block_timestamp.unwrap().into_repr()).collect();
+    let mut packed_timestamp_bits = vec![false; 256 - timestamp_bits.len()];
+    packed_timestamp_bits.extend(timestamp_bits);
+
+    let packed_timestamp_bytes = be_bit_vector_into_bytes(&packed_timestamp_bits);
+
+    let mut packed_with_timestamp = vec![];
+    packed_with_timestamp.extend(hash_result.iter());
+    packed_with_timestamp.extend(packed_timestamp_bytes);
+
+    h = Sha256::new();
+    h.input(&packed_with_timestamp);
+    hash_result = [0u8; 32];
+    h.result(&mut hash_result[..]);
+
     let mut final_bytes = vec![];
     let pubdata_bytes = be_bit_vector_into_bytes(&pubdata_bits.to_vec());
     final_bytes.extend(hash_result.iter());
@@ -544,6 +568,34 @@ impl SigDataInput {
         )
     }
 
+    pub fn from_order(order: &Order) -> Result<Self, anyhow::Error> {
+        let sign_packed = order
+            .signature
+            .signature
+            .serialize_packed()
+            .expect("signature serialize");
+        SigDataInput::new(&sign_packed, &order.get_bytes(), &order.signature.pub_key)
+    }
+
+    /// Returns the signature data of the first order, the second order and the swap itself.
+    pub fn from_swap_op(swap_op: &SwapOp) -> Result<(Self, Self, Self), anyhow::Error> {
+        let sign_packed = swap_op
+            .tx
+            .signature
+            .signature
+            .serialize_packed()
+            .expect("signature serialize");
+        Ok((
+            SigDataInput::from_order(&swap_op.tx.orders.0)?,
+            SigDataInput::from_order(&swap_op.tx.orders.1)?,
+            SigDataInput::new(
+                &sign_packed,
+                &swap_op.tx.get_bytes(),
+                &swap_op.tx.signature.pub_key,
+            )?,
+        ))
+    }
+
     /// Provides a vector of copies of this `SigDataInput` object, all with one field
     /// set to incorrect value.
     /// Used for circuit tests.
@@ -605,7 +657,12 @@ pub fn build_block_witness<'a>(
 
     log::info!("building prover data for block {}", &block_number);
 
-    let mut witness_accum = WitnessBuilder::new(account_tree, block.fee_account, block_number);
+    let mut witness_accum = WitnessBuilder::new(
+        account_tree,
+        block.fee_account,
+        block_number,
+        block.timestamp,
+    );
 
     let ops = block
         .block_transactions
@@ -723,6 +780,19 @@ pub fn build_block_witness<'a>(
                 });
                 pub_data.extend(forced_exit_witness.get_pubdata());
             }
+            ZkSyncOp::Swap(swap) => {
+                let swap_witness = SwapWitness::apply_tx(&mut witness_accum.account_tree, &swap);
+
+                let input = SigDataInput::from_swap_op(&swap)?;
+                let swap_operations = swap_witness.calculate_operations(input);
+
+                operations.extend(swap_operations);
+                fees.push(CollectedFee {
+                    token: swap.tx.fee_token,
+                    amount: swap.tx.fee,
+                });
+                pub_data.extend(swap_witness.get_pubdata());
+            }
             ZkSyncOp::Noop(_) => {} // Noops are handled below
         }
     }
@@ -17,7 +17,7 @@ use zksync_crypto::{
         account_tree_depth, ACCOUNT_ID_BIT_WIDTH, AMOUNT_EXPONENT_BIT_WIDTH,
         AMOUNT_MANTISSA_BIT_WIDTH, BALANCE_BIT_WIDTH, CHUNK_BIT_WIDTH, ETH_ADDRESS_BIT_WIDTH,
         FEE_EXPONENT_BIT_WIDTH, FEE_MANTISSA_BIT_WIDTH, NEW_PUBKEY_HASH_WIDTH, NONCE_BIT_WIDTH,
-        TOKEN_BIT_WIDTH, TX_TYPE_BIT_WIDTH,
+        TIMESTAMP_BIT_WIDTH, TOKEN_BIT_WIDTH, TX_TYPE_BIT_WIDTH,
     },
     primitives::FloatConversions,
 };
@@ -38,6 +38,8 @@ pub struct WithdrawData {
     pub token: u32,
     pub account_address: u32,
     pub eth_address: Fr,
+    pub valid_from: u64,
+    pub valid_until: u64,
 }
 
 pub struct WithdrawWitness<E: RescueEngine> {
@@ -60,6 +62,8 @@ impl Witness for WithdrawWitness<Bn256> {
             token: u32::from(withdraw.tx.token),
             account_address: withdraw.account_id,
             eth_address: eth_address_to_fr(&withdraw.tx.to),
+            valid_from: withdraw.tx.time_range.valid_from,
+            valid_until: withdraw.tx.time_range.valid_until,
         };
         // le_bit_vector_into_field_element()
         Self::apply_data(tree, &withdraw_data)
@@ -180,6 +184,16 @@ impl<E: RescueEngine> WithdrawWitness<E> {
             &self.before.witness.account_witness.nonce.unwrap(),
             NONCE_BIT_WIDTH,
         );
+        append_be_fixed_width(
+            &mut sig_bits,
+            &self.args.valid_from.unwrap(),
+            TIMESTAMP_BIT_WIDTH,
+        );
+        append_be_fixed_width(
+            &mut sig_bits,
+            &self.args.valid_until.unwrap(),
+            TIMESTAMP_BIT_WIDTH,
+        );
         sig_bits
     }
 }
@@ -271,12 +285,20 @@ impl WithdrawWitness<Bn256> {
             args: OperationArguments {
                 eth_address: Some(withdraw.eth_address),
                 amount_packed: Some(amount_encoded),
+                second_amount_packed: Some(Fr::zero()),
+                special_eth_addresses: vec![Some(Fr::zero()); 2],
+                special_tokens: vec![Some(Fr::zero()); 3],
+                special_accounts: vec![Some(Fr::zero()); 5],
+                special_prices: vec![Some(Fr::zero()); 4],
+                special_amounts: vec![Some(Fr::zero()); 4],
                 full_amount: Some(amount_as_field_element),
                 fee: Some(fee_encoded),
                 pub_nonce: Some(Fr::zero()),
                 a: Some(a),
                 b: Some(b),
                 new_pub_key_hash: Some(Fr::zero()),
+                valid_from: Some(Fr::from_str(&withdraw.valid_from.to_string()).unwrap()),
+                valid_until: Some(Fr::from_str(&withdraw.valid_until.to_string()).unwrap()),
             },
             before_root: Some(before_root),
             after_root: Some(after_root),
@@ -1,10 +1,13 @@
 // Built-in deps
 use std::{collections::HashSet, env, net::SocketAddr, str::FromStr, time::Duration};
 // External uses
+use num::{rational::Ratio, BigUint};
 use url::Url;
 // Workspace uses
-use zksync_types::{Address, H256};
-use zksync_utils::{get_env, parse_env, parse_env_if_exists, parse_env_with};
+use zksync_types::{Address, FeeParams, TokenId, H256};
+use zksync_utils::{
+    get_env, parse_env, parse_env_if_exists, parse_env_with, UnsignedRatioSerializeAsDecimal,
+};
 // Local uses
 
 pub mod test_config;
@@ -133,24 +136,86 @@ impl AdminServerOptions {
 
 #[derive(Clone, Debug)]
 pub enum TokenPriceSource {
-    CoinMarketCap { base_url: Url },
-    CoinGecko { base_url: Url },
+    CoinMarketCap {
+        base_url: Url,
+    },
+    CoinGecko {
+        base_url: Url,
+    },
+    /// Median of the prices reported by all the listed sources.
+    Median {
+        sources: Vec<TokenPriceSource>,
+        /// Max allowed relative deviation of the source price from the median (e.g. `0.1` for 10%).
+        /// Prices deviating more are considered outliers and rejected.
+        max_deviation: f64,
+        /// Min number of the sources which must agree on the price for it to be accepted.
+        quorum: usize,
+    },
+    /// Uniswap-v2-style DEX pairs reserves.
+    UniswapV2(UniswapV2Options),
 }
 
 impl TokenPriceSource {
     fn from_env() -> Self {
         match get_env("TOKEN_PRICE_SOURCE").to_lowercase().as_str() {
+            "median" => Self::Median {
+                sources: get_env("TOKEN_PRICE_MEDIAN_SOURCES")
+                    .split(',')
+                    .map(|source| Self::single_source_from_env(source.trim()))
+                    .collect(),
+                max_deviation: parse_env("TOKEN_PRICE_MEDIAN_MAX_DEVIATION"),
+                quorum: parse_env("TOKEN_PRICE_MEDIAN_QUORUM"),
+            },
+            source => Self::single_source_from_env(source),
+        }
+    }
+
+    fn single_source_from_env(source: &str) -> Self {
+        match source.to_lowercase().as_str() {
             "coinmarketcap" => Self::CoinMarketCap {
                 base_url: parse_env("COINMARKETCAP_BASE_URL"),
             },
             "coingecko" => Self::CoinGecko {
                 base_url: parse_env("COINGECKO_BASE_URL"),
             },
+            "uniswapv2" => Self::UniswapV2(UniswapV2Options::from_env()),
             source => panic!("Unknown token price source: {}", source),
         }
     }
 }
 
+/// Options of the Uniswap-v2-style DEX used to derive the token prices and liquidity.
+#[derive(Clone, Debug)]
+pub struct UniswapV2Options {
+    /// Ethereum node to query the DEX contracts from.
+    pub web3_url: String,
+    /// Address of the pairs factory contract.
+    pub factory_address: Address,
+    /// Address of the wrapped ETH, token prices are derived from the token/WETH pairs.
+    pub weth_address: Address,
+    /// Address of the USD stablecoin, ETH price is derived from the WETH/stablecoin pair.
+    pub usd_token_address: Address,
+    /// Number of decimals of the USD stablecoin.
+    pub usd_token_decimals: u8,
+    /// Window over which the time-weighted average price is calculated.
+    pub twap_window: Duration,
+}
+
+impl UniswapV2Options {
+    pub fn from_env() -> Self {
+        let parse_address = |name| parse_env_with(name, |s| s.trim_start_matches("0x"));
+
+        Self {
+            web3_url: get_env("WEB3_URL"),
+            factory_address: parse_address("UNISWAP_FACTORY_ADDRESS"),
+            weth_address: parse_address("UNISWAP_WETH_ADDRESS"),
+            usd_token_address: parse_address("UNISWAP_USD_TOKEN_ADDRESS"),
+            usd_token_decimals: parse_env("UNISWAP_USD_TOKEN_DECIMALS"),
+            twap_window: Duration::from_secs(parse_env("UNISWAP_TWAP_WINDOW_SECS")),
+        }
+    }
+}
+
 /// Configuration options related to generating blocks by state keeper.
 /// Each block is generated after a certain amount of miniblock iterations.
 /// Miniblock iteration is a routine of processing transactions received so far.
@@ -182,6 +247,176 @@ impl MiniblockTimings {
     }
 }
 
+/// Configuration options of the criteria which cause the state keeper to seal the pending block.
+/// Regardless of these options, block is sealed once it has no space for the next operation.
+#[derive(Debug, Clone)]
+pub struct BlockSealingOptions {
+    /// Block is sealed once this percent of its chunks is filled.
+    pub chunks_fill_percent: u32,
+    /// Max percent of the transaction gas limit that the commit and verify Ethereum
+    /// transactions of the block may require.
+    pub gas_limit_percent: u32,
+    /// Max time the oldest operation of the pending block waits for the block to be sealed.
+    pub max_block_age: Duration,
+}
+
+impl BlockSealingOptions {
+    pub fn from_env() -> Self {
+        Self {
+            chunks_fill_percent: parse_env("BLOCK_SEAL_CHUNKS_FILL_PERCENT"),
+            gas_limit_percent: parse_env("BLOCK_SEAL_GAS_LIMIT_PERCENT"),
+            max_block_age: Duration::from_secs(parse_env::<u64>("BLOCK_SEAL_MAX_AGE_SECS")),
+        }
+    }
+}
+
+/// Operations of the block matched by the fee route.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeeRouteCondition {
+    /// Transactions paying fee in the token with the given id.
+    Token(TokenId),
+    /// Transactions of the given type (e.g. `Transfer` or `Withdraw`).
+    TxType(String),
+}
+
+/// Rule directing the fees of the matching operations to the certain account.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeRoute {
+    pub condition: FeeRouteCondition,
+    /// Address of the account receiving the fees. If there is no such account in the
+    /// network yet, the route is ignored.
+    pub fee_account: Address,
+}
+
+impl FeeRoute {
+    /// Parses the route in the `token:<token_id>=<address>` or `tx:<tx_type>=<address>` form.
+    fn parse(route: &str) -> Self {
+        let (condition, fee_account) = match route.splitn(2, '=').collect::<Vec<_>>().as_slice() {
+            [condition, fee_account] => (*condition, *fee_account),
+            _ => panic!(
+                "Fee route should have the `<condition>=<address>` form: {}",
+                route
+            ),
+        };
+
+        let condition = match condition.splitn(2, ':').collect::<Vec<_>>().as_slice() {
+            ["token", token_id] => FeeRouteCondition::Token(
+                token_id
+                    .parse()
+                    .unwrap_or_else(|_| panic!("Invalid token id in the fee route: {}", route)),
+            ),
+            ["tx", tx_type] => FeeRouteCondition::TxType(tx_type.to_string()),
+            _ => panic!("Unknown fee route condition: {}", route),
+        };
+        let fee_account = fee_account
+            .trim_start_matches("0x")
+            .parse()
+            .unwrap_or_else(|_| panic!("Invalid fee account address in the fee route: {}", route));
+
+        Self {
+            condition,
+            fee_account,
+        }
+    }
+}
+
+/// Policy of choosing the single fee account of the block among the matching fee routes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FeeRoutingPolicy {
+    /// The first configured route matching any operation of the block is chosen.
+    FirstMatch,
+    /// The route matching the most operations of the block is chosen.
+    /// Ties are resolved in the order of configuration.
+    MostOperations,
+}
+
+/// Configuration options related to the routing of the collected fees.
+///
+/// The circuit allows only one fee account per block, so the routes are used to choose
+/// the fee account of the whole block. Fees of the blocks not matched by any route go
+/// to the operator fee account.
+#[derive(Debug, Clone)]
+pub struct FeeRoutingOptions {
+    pub policy: FeeRoutingPolicy,
+    pub routes: Vec<FeeRoute>,
+}
+
+impl FeeRoutingOptions {
+    pub fn from_env() -> Self {
+        let policy = match get_env("FEE_ROUTING_POLICY").to_lowercase().as_str() {
+            "first_match" => FeeRoutingPolicy::FirstMatch,
+            "most_operations" => FeeRoutingPolicy::MostOperations,
+            policy => panic!("Unknown fee routing policy: {}", policy),
+        };
+        let routes = get_env("FEE_ROUTING_RULES")
+            .split(',')
+            .map(str::trim)
+            .filter(|route| !route.is_empty())
+            .map(FeeRoute::parse)
+            .collect();
+
+        Self { policy, routes }
+    }
+}
+
+/// Strategy of choosing the mempool transactions to be included into the block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BlockPackingStrategyType {
+    /// Transactions are included in the order of arrival.
+    Fifo,
+    /// The largest transactions are included first, the remaining space is filled with smaller ones.
+    Knapsack,
+    /// Transactions paying the greatest fee per chunk are included first.
+    MaxFee,
+}
+
+impl BlockPackingStrategyType {
+    fn from_env() -> Self {
+        match get_env("MEMPOOL_BLOCK_PACKING_STRATEGY")
+            .to_lowercase()
+            .as_str()
+        {
+            "fifo" => Self::Fifo,
+            "knapsack" => Self::Knapsack,
+            "max_fee" => Self::MaxFee,
+            strategy => panic!("Unknown block packing strategy: {}", strategy),
+        }
+    }
+}
+
+/// Configuration options related to the limits of the memory pool.
+#[derive(Debug, Clone)]
+pub struct MempoolOptions {
+    /// Max number of transactions stored in the mempool. When the limit is reached,
+    /// transactions paying the lowest fee are evicted in favor of the new ones.
+    pub capacity: usize,
+    /// Max number of pending transactions of a single account.
+    pub max_txs_per_account: usize,
+    /// Time after which transaction that wasn't included into a block is evicted from the mempool.
+    pub tx_ttl: Duration,
+    /// Minimal fee increase (in percents) required to replace a pending transaction
+    /// with another transaction with the same nonce.
+    pub replacement_fee_bump_percent: u32,
+    /// Max difference between the nonce of a transaction and the next nonce expected from the account.
+    /// Transactions with a nonce gap are held until the missing transactions arrive.
+    pub max_nonce_gap: u32,
+    /// Strategy of choosing the transactions to be included into the block.
+    pub block_packing_strategy: BlockPackingStrategyType,
+}
+
+impl MempoolOptions {
+    pub fn from_env() -> Self {
+        Self {
+            capacity: parse_env("MEMPOOL_CAPACITY"),
+            max_txs_per_account: parse_env("MEMPOOL_MAX_TXS_PER_ACCOUNT"),
+            tx_ttl: Duration::from_secs(parse_env::<u64>("MEMPOOL_TX_TTL_SECS")),
+            replacement_fee_bump_percent: parse_env("MEMPOOL_REPLACEMENT_FEE_BUMP_PERCENT"),
+            max_nonce_gap: parse_env("MEMPOOL_MAX_NONCE_GAP"),
+            block_packing_strategy: BlockPackingStrategyType::from_env(),
+        }
+    }
+}
+
 /// Configuration options related to fee ticker.
 #[derive(Debug)]
 pub struct FeeTickerOptions {
@@ -193,6 +428,62 @@ pub struct FeeTickerOptions {
     pub disabled_tokens: HashSet<Address>,
     /// Tokens for which subsidies are disabled.
     pub not_subsidized_tokens: HashSet<Address>,
+    /// Min amount of wei in the token/WETH DEX pair for the token to be acceptable for paying fee in.
+    /// If not set, the liquidity is not checked.
+    pub min_fee_token_liquidity_wei: Option<u128>,
+    /// Default fee parameters, used until the server operator sets them via the admin server.
+    pub fee_params: FeeParams,
+    /// Parameters of the ZKP cost derived from the prover statistics.
+    /// If not set, the ZKP cost from the fee parameters is used.
+    pub dynamic_zkp_cost: Option<DynamicZkpCostOptions>,
+}
+
+/// Configuration of the ZKP cost derived from the actual prover throughput.
+#[derive(Debug, Clone)]
+pub struct DynamicZkpCostOptions {
+    /// Cost of running a single prover for a second, in USD.
+    pub prover_cost_per_second_usd: Ratio<BigUint>,
+    /// Lower bound of the ZKP cost of a chunk, in USD.
+    pub min_zkp_cost_chunk_usd: Ratio<BigUint>,
+    /// Upper bound of the ZKP cost of a chunk, in USD.
+    pub max_zkp_cost_chunk_usd: Ratio<BigUint>,
+    /// Period during which the proven blocks are taken into account.
+    pub stats_period: Duration,
+}
+
+impl DynamicZkpCostOptions {
+    /// Parses the options from the environment variables.
+    /// Returns `None` if the prover cost is not set.
+    pub fn from_env() -> Option<Self> {
+        let parse_ratio = |name: &str, value: &str| {
+            UnsignedRatioSerializeAsDecimal::deserialize_from_str_with_dot(value)
+                .unwrap_or_else(|e| panic!("Failed to parse environment variable {}: {}", name, e))
+        };
+
+        let prover_cost_per_second_usd =
+            parse_env_if_exists::<String>("TICKER_PROVER_COST_PER_SECOND_USD")?;
+        let options = Self {
+            prover_cost_per_second_usd: parse_ratio(
+                "TICKER_PROVER_COST_PER_SECOND_USD",
+                &prover_cost_per_second_usd,
+            ),
+            min_zkp_cost_chunk_usd: parse_ratio(
+                "TICKER_ZKP_COST_CHUNK_MIN_USD",
+                &get_env("TICKER_ZKP_COST_CHUNK_MIN_USD"),
+            ),
+            max_zkp_cost_chunk_usd: parse_ratio(
+                "TICKER_ZKP_COST_CHUNK_MAX_USD",
+                &get_env("TICKER_ZKP_COST_CHUNK_MAX_USD"),
+            ),
+            stats_period: Duration::from_secs(parse_env("TICKER_PROVER_STATS_PERIOD_SECS")),
+        };
+        assert!(
+            options.min_zkp_cost_chunk_usd <= options.max_zkp_cost_chunk_usd,
+            "Min ZKP cost of a chunk must not exceed the max one"
+        );
+
+        Some(options)
+    }
 }
 
 impl FeeTickerOptions {
@@ -203,12 +494,49 @@ impl FeeTickerOptions {
             .collect()
     }
 
+    fn fee_params() -> FeeParams {
+        let parse_ratio = |name: &str, value: &str| {
+            UnsignedRatioSerializeAsDecimal::deserialize_from_str_with_dot(value)
+                .unwrap_or_else(|e| panic!("Failed to parse environment variable {}: {}", name, e))
+        };
+
+        let tokens_risk_factors = get_env("TICKER_TOKENS_RISK_FACTORS")
+            .split(',')
+            .filter(|p| !p.is_empty())
+            .map(|p| {
+                let mut parts = p.splitn(2, '=');
+                let token_id = parts
+                    .next()
+                    .and_then(|id| id.parse::<TokenId>().ok())
+                    .unwrap_or_else(|| panic!("Incorrect token risk factor: {}", p));
+                let risk_factor = parts
+                    .next()
+                    .unwrap_or_else(|| panic!("Incorrect token risk factor: {}", p));
+                (
+                    token_id,
+                    parse_ratio("TICKER_TOKENS_RISK_FACTORS", risk_factor),
+                )
+            })
+            .collect();
+
+        FeeParams {
+            zkp_cost_chunk_usd: parse_ratio(
+                "TICKER_ZKP_COST_CHUNK_USD",
+                &get_env("TICKER_ZKP_COST_CHUNK_USD"),
+            ),
+            tokens_risk_factors,
+        }
+    }
+
     pub fn from_env() -> Self {
         Self {
             token_price_source: TokenPriceSource::from_env(),
             fast_processing_coeff: parse_env("TICKER_FAST_PROCESSING_COEFF"),
             disabled_tokens: Self::comma_separated_addresses("TICKER_DISABLED_TOKENS"),
             not_subsidized_tokens: Self::comma_separated_addresses("NOT_SUBSIDIZED_TOKENS"),
+            min_fee_token_liquidity_wei: parse_env_if_exists("TICKER_MIN_FEE_TOKEN_LIQUIDITY_WEI"),
+            fee_params: Self::fee_params(),
+            dynamic_zkp_cost: DynamicZkpCostOptions::from_env(),
         }
     }
 }
@@ -224,6 +552,10 @@ pub struct ApiServerOptions {
     /// Fee increase coefficient for fast processing of withdrawal.
     pub forced_exit_minimum_account_age: Duration,
     pub enforce_pubkey_change_fee: bool,
+    /// Secret used to sign the fee quotes issued by the fee ticker.
+    pub fee_quote_secret: String,
+    /// Period during which the issued fee quote is honoured by the server.
+    pub fee_quote_validity: Duration,
 }
 
 impl ApiServerOptions {
@@ -246,6 +578,8 @@ impl ApiServerOptions {
             forced_exit_minimum_account_age,
             enforce_pubkey_change_fee: parse_env_if_exists("ENFORCE_PUBKEY_CHANGE_FEE")
                 .unwrap_or(true),
+            fee_quote_secret: parse_env("FEE_QUOTE_SECRET"),
+            fee_quote_validity: Duration::from_secs(parse_env("FEE_QUOTE_VALIDITY_SECS")),
         }
     }
 }
@@ -254,6 +588,7 @@ impl ApiServerOptions {
 pub struct ConfigurationOptions {
     pub web3_url: String,
     pub genesis_tx_hash: H256,
+    pub chain_id: u8,
     pub contract_eth_addr: Address,
     pub governance_eth_addr: Address,
     pub operator_fee_eth_addr: Address,
@@ -263,6 +598,9 @@ pub struct ConfigurationOptions {
     pub eth_watch_poll_interval: Duration,
     pub eth_network: String,
     pub miniblock_timings: MiniblockTimings,
+    pub block_sealing: BlockSealingOptions,
+    pub fee_routing: FeeRoutingOptions,
+    pub mempool: MempoolOptions,
     pub prometheus_export_port: u16,
 }
 
@@ -281,6 +619,7 @@ impl ConfigurationOptions {
         Self {
             web3_url: get_env("WEB3_URL"),
             genesis_tx_hash: parse_env_with("GENESIS_TX_HASH", |s| &s[2..]),
+            chain_id: parse_env("CHAIN_ID"),
             contract_eth_addr: parse_env_with("CONTRACT_ADDR", |s| &s[2..]),
             governance_eth_addr: parse_env_with("GOVERNANCE_ADDR", |s| &s[2..]),
             operator_fee_eth_addr: parse_env_with("OPERATOR_FEE_ETH_ADDRESS", |s| &s[2..]),
@@ -292,6 +631,9 @@ impl ConfigurationOptions {
             )),
             eth_network: parse_env("ETH_NETWORK"),
             miniblock_timings: MiniblockTimings::from_env(),
+            block_sealing: BlockSealingOptions::from_env(),
+            fee_routing: FeeRoutingOptions::from_env(),
+            mempool: MempoolOptions::from_env(),
             prometheus_export_port: parse_env("PROMETHEUS_EXPORT_PORT"),
         }
     }
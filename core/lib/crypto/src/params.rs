@@ -96,6 +96,8 @@ pub const MAX_CIRCUIT_MSG_HASH_BITS: usize = 736;
 pub const ETH_ADDRESS_BIT_WIDTH: usize = 160;
 /// Block number bit width
 pub const BLOCK_NUMBER_BIT_WIDTH: usize = 32;
+/// Timestamp bit width
+pub const TIMESTAMP_BIT_WIDTH: usize = 64;
 
 /// Amount bit widths
 pub const AMOUNT_EXPONENT_BIT_WIDTH: usize = 5;
@@ -105,6 +107,9 @@ pub const AMOUNT_MANTISSA_BIT_WIDTH: usize = 35;
 pub const FEE_EXPONENT_BIT_WIDTH: usize = 5;
 pub const FEE_MANTISSA_BIT_WIDTH: usize = 11;
 
+/// Bit width of the order price components
+pub const PRICE_BIT_WIDTH: usize = 120;
+
 // Signature data
 pub const SIGNATURE_S_BIT_WIDTH: usize = 254;
 pub const SIGNATURE_S_BIT_WIDTH_PADDED: usize = 256;
@@ -133,7 +138,8 @@ pub const SIGNED_WITHDRAW_BIT_WIDTH: usize = TX_TYPE_BIT_WIDTH
     + BALANCE_BIT_WIDTH
     + FEE_EXPONENT_BIT_WIDTH
     + FEE_MANTISSA_BIT_WIDTH
-    + NONCE_BIT_WIDTH;
+    + NONCE_BIT_WIDTH
+    + 2 * TIMESTAMP_BIT_WIDTH;
 
 /// Size of the data that is signed for transfer tx
 pub const SIGNED_TRANSFER_BIT_WIDTH: usize = TX_TYPE_BIT_WIDTH
@@ -144,7 +150,8 @@ pub const SIGNED_TRANSFER_BIT_WIDTH: usize = TX_TYPE_BIT_WIDTH
     + AMOUNT_MANTISSA_BIT_WIDTH
     + FEE_EXPONENT_BIT_WIDTH
     + FEE_MANTISSA_BIT_WIDTH
-    + NONCE_BIT_WIDTH;
+    + NONCE_BIT_WIDTH
+    + 2 * TIMESTAMP_BIT_WIDTH;
 
 /// Size of the data that is signed for forced exit tx
 pub const SIGNED_FORCED_EXIT_BIT_WIDTH: usize = TX_TYPE_BIT_WIDTH
@@ -153,7 +160,8 @@ pub const SIGNED_FORCED_EXIT_BIT_WIDTH: usize = TX_TYPE_BIT_WIDTH
     + TOKEN_BIT_WIDTH
     + FEE_EXPONENT_BIT_WIDTH
     + FEE_MANTISSA_BIT_WIDTH
-    + NONCE_BIT_WIDTH;
+    + NONCE_BIT_WIDTH
+    + 2 * TIMESTAMP_BIT_WIDTH;
 
 /// Size of the data that is signed for change pubkey tx
 pub const SIGNED_CHANGE_PUBKEY_BIT_WIDTH: usize = TX_TYPE_BIT_WIDTH
@@ -163,7 +171,30 @@ pub const SIGNED_CHANGE_PUBKEY_BIT_WIDTH: usize = TX_TYPE_BIT_WIDTH
     + TOKEN_BIT_WIDTH
     + FEE_EXPONENT_BIT_WIDTH
     + FEE_MANTISSA_BIT_WIDTH
-    + NONCE_BIT_WIDTH;
+    + NONCE_BIT_WIDTH
+    + 2 * TIMESTAMP_BIT_WIDTH;
+
+/// Size of the data that is signed for order
+pub const SIGNED_ORDER_BIT_WIDTH: usize = TX_TYPE_BIT_WIDTH
+    + ACCOUNT_ID_BIT_WIDTH
+    + ADDRESS_WIDTH
+    + NONCE_BIT_WIDTH
+    + 2 * TOKEN_BIT_WIDTH
+    + 2 * PRICE_BIT_WIDTH
+    + 2 * (AMOUNT_EXPONENT_BIT_WIDTH + AMOUNT_MANTISSA_BIT_WIDTH);
+
+/// Size of the data that is signed for swap tx
+pub const SIGNED_SWAP_BIT_WIDTH: usize = TX_TYPE_BIT_WIDTH
+    + ACCOUNT_ID_BIT_WIDTH
+    + ADDRESS_WIDTH
+    + NONCE_BIT_WIDTH
+    + 2 * ACCOUNT_ID_BIT_WIDTH
+    + 2 * TOKEN_BIT_WIDTH
+    + 2 * (AMOUNT_EXPONENT_BIT_WIDTH + AMOUNT_MANTISSA_BIT_WIDTH)
+    + TOKEN_BIT_WIDTH
+    + FEE_EXPONENT_BIT_WIDTH
+    + FEE_MANTISSA_BIT_WIDTH
+    + 2 * TIMESTAMP_BIT_WIDTH;
 
 lazy_static! {
     pub static ref JUBJUB_PARAMS: AltJubjubBn256 = AltJubjubBn256::new();
@@ -4,7 +4,7 @@ use crate::EthereumSigner;
 use crate::RawTransaction;
 
 use jsonrpc_core::types::response::Output;
-use zksync_types::tx::{PackedEthSignature, TxEthSignature};
+use zksync_types::tx::{Eip712Domain, Eip712Struct, PackedEthSignature, TxEthSignature};
 use zksync_types::Address;
 
 use serde_json::Value;
@@ -84,6 +84,32 @@ impl EthereumSigner for JsonRpcSigner {
         }
     }
 
+    /// Signs the EIP-712 typed data via the `eth_signTypedData_v4` method.
+    async fn sign_typed_data(
+        &self,
+        domain: &Eip712Domain,
+        data: &(dyn Eip712Struct + Sync),
+    ) -> Result<TxEthSignature, SignerError> {
+        let message = JsonRpcRequest::sign_typed_data(self.address()?, domain, data);
+        let ret = self
+            .post(&message)
+            .await
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        let signature: PackedEthSignature = serde_json::from_value(ret)
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+
+        let signer_address = signature
+            .typed_data_recover_signer(domain, data)
+            .map_err(|err| SignerError::RecoverAddress(err.to_string()))?;
+        if signer_address == self.address()? {
+            Ok(TxEthSignature::EIP712Signature(signature))
+        } else {
+            Err(SignerError::SigningFailed(
+                "Invalid signature from JsonRpcSigner".to_string(),
+            ))
+        }
+    }
+
     /// Signs and returns the RLP-encoded transaction.
     async fn sign_transaction(&self, raw_tx: RawTransaction) -> Result<Vec<u8>, SignerError> {
         let msg = JsonRpcRequest::sign_transaction(self.address()?, raw_tx);
@@ -290,6 +316,7 @@ impl JsonRpcSigner {
 mod messages {
     use crate::RawTransaction;
     use hex::encode;
+    use zksync_types::tx::{Eip712Domain, Eip712Struct};
     use zksync_types::Address;
 
     #[derive(Debug, Serialize, Deserialize)]
@@ -336,6 +363,22 @@ mod messages {
             Self::create("eth_sign", params)
         }
 
+        /// Signs the EIP-712 typed data.
+        /// The address to sign with must be unlocked.
+        pub fn sign_typed_data(
+            address: Address,
+            domain: &Eip712Domain,
+            data: &dyn Eip712Struct,
+        ) -> Self {
+            let mut params = Vec::new();
+            params.push(serde_json::to_value(address).expect("serialization fail"));
+            params.push(
+                serde_json::to_value(domain.typed_data_json(data).to_string())
+                    .expect("serialization fail"),
+            );
+            Self::create("eth_signTypedData_v4", params)
+        }
+
         /// Signs a transaction that can be submitted to the network.
         /// The address to sign with must be unlocked.
         pub fn sign_transaction(from: Address, tx_data: RawTransaction) -> Self {
@@ -3,7 +3,7 @@ extern crate serde_derive;
 
 use async_trait::async_trait;
 use error::SignerError;
-use zksync_types::tx::TxEthSignature;
+use zksync_types::tx::{Eip712Domain, Eip712Struct, TxEthSignature};
 use zksync_types::Address;
 
 pub use json_rpc_signer::JsonRpcSigner;
@@ -18,6 +18,11 @@ pub mod raw_ethereum_tx;
 #[async_trait]
 pub trait EthereumSigner {
     async fn sign_message(&self, message: &[u8]) -> Result<TxEthSignature, SignerError>;
+    async fn sign_typed_data(
+        &self,
+        domain: &Eip712Domain,
+        data: &(dyn Eip712Struct + Sync),
+    ) -> Result<TxEthSignature, SignerError>;
     async fn sign_transaction(&self, raw_tx: RawTransaction) -> Result<Vec<u8>, SignerError>;
     async fn get_address(&self) -> Result<Address, SignerError>;
 }
@@ -3,7 +3,7 @@ use crate::{EthereumSigner, SignerError};
 
 use parity_crypto::publickey::sign;
 
-use zksync_types::tx::{PackedEthSignature, TxEthSignature};
+use zksync_types::tx::{Eip712Domain, Eip712Struct, PackedEthSignature, TxEthSignature};
 use zksync_types::{Address, H256};
 
 #[derive(Clone)]
@@ -39,6 +39,18 @@ impl EthereumSigner for PrivateKeySigner {
         Ok(TxEthSignature::EthereumSignature(pack))
     }
 
+    /// Signs the EIP-712 typed data:
+    /// sign(keccak256("\x19\x01" + domainSeparator + hashStruct(data))).
+    async fn sign_typed_data(
+        &self,
+        domain: &Eip712Domain,
+        data: &(dyn Eip712Struct + Sync),
+    ) -> Result<TxEthSignature, SignerError> {
+        let pack = PackedEthSignature::sign_typed_data(&self.private_key, domain, data)
+            .map_err(|err| SignerError::SigningFailed(err.to_string()))?;
+        Ok(TxEthSignature::EIP712Signature(pack))
+    }
+
     /// Signs and returns the RLP-encoded transaction.
     async fn sign_transaction(&self, raw_tx: RawTransaction) -> Result<Vec<u8>, SignerError> {
         let sig = sign(&self.private_key.into(), &raw_tx.hash().into())
@@ -52,6 +64,7 @@ mod test {
     use super::PrivateKeySigner;
     use super::RawTransaction;
     use crate::EthereumSigner;
+    use zksync_types::tx::{Eip712Domain, TxEthSignature, TxsBatch};
     use zksync_types::{H160, H256, U256};
 
     #[tokio::test]
@@ -82,4 +95,24 @@ mod test {
         ];
         assert_eq!(signature, precalculated_signature);
     }
+
+    #[tokio::test]
+    async fn test_signing_typed_data() {
+        let signer = PrivateKeySigner::new(H256::from([5; 32]));
+        let address = signer.get_address().await.unwrap();
+        let domain = Eip712Domain::new(9, H160::repeat_byte(1));
+        let batch = TxsBatch::new(Vec::new()).unwrap();
+
+        let signature = signer.sign_typed_data(&domain, &batch).await.unwrap();
+        if let TxEthSignature::EIP712Signature(signature) = signature {
+            assert_eq!(
+                signature
+                    .typed_data_recover_signer(&domain, &batch)
+                    .unwrap(),
+                address
+            );
+        } else {
+            panic!("Wrong signature type")
+        }
+    }
 }
@@ -28,6 +28,8 @@ pub struct ProverData {
     pub new_root: Fr,
     #[serde(with = "FrSerde")]
     pub validator_address: Fr,
+    #[serde(with = "FrSerde")]
+    pub block_timestamp: Fr,
     #[serde(with = "VecOptionalFrSerde")]
     pub validator_balances: Vec<Option<Fr>>,
     #[serde(with = "VecOptionalFrSerde")]
@@ -47,6 +49,8 @@ impl From<WitnessBuilder<'_>> for ProverData {
             new_root: witness_builder.root_after_fees.unwrap(),
             validator_address: Fr::from_str(&witness_builder.fee_account_id.to_string())
                 .expect("failed to parse"),
+            block_timestamp: Fr::from_str(&witness_builder.block_timestamp.to_string())
+                .expect("failed to parse"),
             operations: witness_builder.operations,
             validator_balances: witness_builder.fee_account_balances.unwrap(),
             validator_audit_path: witness_builder.fee_account_audit_path.unwrap(),
@@ -64,6 +68,7 @@ impl ProverData {
             initial_used_subtree_root: Some(self.initial_used_subtree_root),
             block_number: Fr::from_str(&block.to_string()),
             validator_address: Some(self.validator_address),
+            block_timestamp: Some(self.block_timestamp),
             pub_data_commitment: Some(self.public_data_commitment),
             operations: self.operations,
             validator_balances: self.validator_balances,
@@ -131,6 +136,10 @@ pub struct OperationArgumentsDef {
     pub eth_address: Option<Fr>,
     #[serde(with = "OptionalFrSerde")]
     pub pub_nonce: Option<Fr>,
+    #[serde(with = "OptionalFrSerde")]
+    pub valid_from: Option<Fr>,
+    #[serde(with = "OptionalFrSerde")]
+    pub valid_until: Option<Fr>,
 }
 
 #[derive(Serialize, Deserialize)]
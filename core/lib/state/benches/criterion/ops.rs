@@ -74,6 +74,7 @@ fn apply_transfer_to_new_op(b: &mut Bencher<'_>) {
         10u32.into(),
         1u32.into(),
         0,
+        Default::default(),
         private_key,
     )
     .expect("failed to sign transfer");
@@ -108,6 +109,7 @@ fn apply_transfer_tx(b: &mut Bencher<'_>) {
         10u32.into(),
         1u32.into(),
         0,
+        Default::default(),
         private_key,
     )
     .expect("failed to sign transfer");
@@ -193,6 +195,7 @@ fn apply_withdraw_tx(b: &mut Bencher<'_>) {
         10u32.into(),
         1u32.into(),
         0,
+        Default::default(),
         private_key,
     )
     .expect("failed to sign withdraw");
@@ -231,6 +234,7 @@ fn apply_change_pubkey_op(b: &mut Bencher<'_>) {
         0,
         Default::default(),
         nonce,
+        Default::default(),
         None,
         None,
     );
@@ -1,9 +1,8 @@
-use anyhow::{ensure, format_err};
 use std::time::Instant;
 use zksync_crypto::params;
 use zksync_types::{
     operations::{ChangePubKeyOp, ZkSyncOp},
-    tx::ChangePubKey,
+    tx::{ChangePubKey, TxExecutionError},
     AccountUpdate, AccountUpdates,
 };
 
@@ -15,32 +14,28 @@ use crate::{
 impl TxHandler<ChangePubKey> for ZkSyncState {
     type Op = ChangePubKeyOp;
 
-    fn create_op(&self, tx: ChangePubKey) -> Result<Self::Op, anyhow::Error> {
+    fn create_op(&self, tx: ChangePubKey) -> Result<Self::Op, TxExecutionError> {
         let (account_id, account) = self
             .get_account_by_address(&tx.account)
-            .ok_or_else(|| format_err!("Account does not exist"))?;
-        ensure!(
-            tx.eth_signature.is_none() || tx.verify_eth_signature() == Some(account.address),
-            "ChangePubKey Ethereum signature is incorrect"
-        );
-        ensure!(
-            tx.verify_signature() == Some(tx.new_pk_hash.clone()),
-            "ChangePubKey zkSync signature is incorrect"
-        );
-        ensure!(
-            account_id == tx.account_id,
-            "ChangePubKey account id is incorrect"
-        );
-        ensure!(
-            account_id <= params::max_account_id(),
-            "ChangePubKey account id is bigger than max supported"
-        );
+            .ok_or(TxExecutionError::AccountNotFound)?;
+        if tx.eth_signature.is_some() && tx.verify_eth_signature() != Some(account.address) {
+            return Err(TxExecutionError::IncorrectEthSignature);
+        }
+        if tx.verify_signature() != Some(tx.new_pk_hash.clone()) {
+            return Err(TxExecutionError::PubKeyHashMismatch);
+        }
+        if account_id != tx.account_id {
+            return Err(TxExecutionError::AccountIdMismatch);
+        }
+        if account_id > params::max_account_id() {
+            return Err(TxExecutionError::AccountIdTooBig);
+        }
         let change_pk_op = ChangePubKeyOp { tx, account_id };
 
         Ok(change_pk_op)
     }
 
-    fn apply_tx(&mut self, tx: ChangePubKey) -> Result<OpSuccess, anyhow::Error> {
+    fn apply_tx(&mut self, tx: ChangePubKey) -> Result<OpSuccess, TxExecutionError> {
         let op = self.create_op(tx)?;
 
         let (fee, updates) = <Self as TxHandler<ChangePubKey>>::apply_op(self, &op)?;
@@ -54,7 +49,7 @@ impl TxHandler<ChangePubKey> for ZkSyncState {
     fn apply_op(
         &mut self,
         op: &Self::Op,
-    ) -> Result<(Option<CollectedFee>, AccountUpdates), anyhow::Error> {
+    ) -> Result<(Option<CollectedFee>, AccountUpdates), TxExecutionError> {
         let start = Instant::now();
         let mut updates = Vec::new();
         let mut account = self.get_account(op.account_id).unwrap();
@@ -65,14 +60,18 @@ impl TxHandler<ChangePubKey> for ZkSyncState {
         let old_nonce = account.nonce;
 
         // Update nonce.
-        ensure!(op.tx.nonce == account.nonce, "Nonce mismatch");
+        if op.tx.nonce != account.nonce {
+            return Err(TxExecutionError::NonceMismatch);
+        }
         account.nonce += 1;
 
         // Update pubkey hash.
         account.pub_key_hash = op.tx.new_pk_hash.clone();
 
         // Subract fees.
-        ensure!(old_balance >= op.tx.fee, "Not enough balance");
+        if old_balance < op.tx.fee {
+            return Err(TxExecutionError::InsufficientBalance);
+        }
         account.sub_balance(op.tx.fee_token, &op.tx.fee);
 
         let new_pub_key_hash = account.pub_key_hash.clone();
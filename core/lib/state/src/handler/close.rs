@@ -1,7 +1,6 @@
-use anyhow::{bail, ensure};
 use num::BigUint;
 use zksync_crypto::params::{self, max_account_id};
-use zksync_types::{AccountUpdate, AccountUpdates, Close, CloseOp, TokenId};
+use zksync_types::{tx::TxExecutionError, AccountUpdate, AccountUpdates, Close, CloseOp, TokenId};
 
 use crate::{
     handler::TxHandler,
@@ -11,33 +10,34 @@ use crate::{
 impl TxHandler<Close> for ZkSyncState {
     type Op = CloseOp;
 
-    fn create_op(&self, _tx: Close) -> Result<Self::Op, anyhow::Error> {
+    fn create_op(&self, _tx: Close) -> Result<Self::Op, TxExecutionError> {
         panic!("Attempt to create disabled closed op");
     }
 
-    fn apply_tx(&mut self, _tx: Close) -> Result<OpSuccess, anyhow::Error> {
-        bail!("Account closing is disabled");
+    fn apply_tx(&mut self, _tx: Close) -> Result<OpSuccess, TxExecutionError> {
+        Err(TxExecutionError::AccountCloseDisabled)
     }
 
     fn apply_op(
         &mut self,
         op: &Self::Op,
-    ) -> Result<(Option<CollectedFee>, AccountUpdates), anyhow::Error> {
-        ensure!(
-            op.account_id <= max_account_id(),
-            "Close account id is bigger than max supported"
-        );
+    ) -> Result<(Option<CollectedFee>, AccountUpdates), TxExecutionError> {
+        if op.account_id > max_account_id() {
+            return Err(TxExecutionError::AccountIdTooBig);
+        }
 
         let mut updates = Vec::new();
         let account = self.get_account(op.account_id).unwrap();
 
         for token in 0..params::total_tokens() {
             if account.get_balance(token as TokenId) != BigUint::from(0u32) {
-                bail!("Account is not empty, token id: {}", token);
+                return Err(TxExecutionError::AccountNotEmpty);
             }
         }
 
-        ensure!(op.tx.nonce == account.nonce, "Nonce mismatch");
+        if op.tx.nonce != account.nonce {
+            return Err(TxExecutionError::NonceMismatch);
+        }
 
         self.remove_account(op.account_id);
 
@@ -1,6 +1,8 @@
 use std::time::Instant;
 use zksync_crypto::params;
-use zksync_types::{Account, AccountUpdate, AccountUpdates, Deposit, DepositOp, ZkSyncOp};
+use zksync_types::{
+    tx::TxExecutionError, Account, AccountUpdate, AccountUpdates, Deposit, DepositOp, ZkSyncOp,
+};
 
 use crate::{
     handler::TxHandler,
@@ -10,7 +12,7 @@ use crate::{
 impl TxHandler<Deposit> for ZkSyncState {
     type Op = DepositOp;
 
-    fn create_op(&self, priority_op: Deposit) -> Result<Self::Op, anyhow::Error> {
+    fn create_op(&self, priority_op: Deposit) -> Result<Self::Op, TxExecutionError> {
         assert!(
             priority_op.token <= params::max_token_id(),
             "Deposit token is out of range, this should be enforced by contract"
@@ -30,7 +32,7 @@ impl TxHandler<Deposit> for ZkSyncState {
         Ok(op)
     }
 
-    fn apply_tx(&mut self, priority_op: Deposit) -> Result<OpSuccess, anyhow::Error> {
+    fn apply_tx(&mut self, priority_op: Deposit) -> Result<OpSuccess, TxExecutionError> {
         let op = self.create_op(priority_op)?;
 
         let (fee, updates) = <Self as TxHandler<Deposit>>::apply_op(self, &op)?;
@@ -46,7 +48,7 @@ impl TxHandler<Deposit> for ZkSyncState {
     fn apply_op(
         &mut self,
         op: &Self::Op,
-    ) -> Result<(Option<CollectedFee>, AccountUpdates), anyhow::Error> {
+    ) -> Result<(Option<CollectedFee>, AccountUpdates), TxExecutionError> {
         let start = Instant::now();
         let mut updates = Vec::new();
 
@@ -1,7 +1,9 @@
-use anyhow::{ensure, format_err};
 use std::time::Instant;
 use zksync_crypto::params;
-use zksync_types::{AccountUpdate, AccountUpdates, ForcedExit, ForcedExitOp, PubKeyHash, ZkSyncOp};
+use zksync_types::{
+    tx::TxExecutionError, AccountUpdate, AccountUpdates, ForcedExit, ForcedExitOp, PubKeyHash,
+    ZkSyncOp,
+};
 use zksync_utils::BigUintSerdeWrapper;
 
 use crate::{
@@ -12,30 +14,27 @@ use crate::{
 impl TxHandler<ForcedExit> for ZkSyncState {
     type Op = ForcedExitOp;
 
-    fn create_op(&self, tx: ForcedExit) -> Result<Self::Op, anyhow::Error> {
+    fn create_op(&self, tx: ForcedExit) -> Result<Self::Op, TxExecutionError> {
         // Check the tx signature.
         let initiator_account = self
             .get_account(tx.initiator_account_id)
-            .ok_or_else(|| format_err!("Initiator account does not exist"))?;
-        ensure!(
-            tx.verify_signature() == Some(initiator_account.pub_key_hash),
-            "ForcedExit signature is incorrect"
-        );
+            .ok_or(TxExecutionError::AccountNotFound)?;
+        if tx.verify_signature() != Some(initiator_account.pub_key_hash) {
+            return Err(TxExecutionError::PubKeyHashMismatch);
+        }
 
         // Check the token ID correctness.
-        ensure!(
-            tx.token <= params::max_token_id(),
-            "Token id is not supported"
-        );
+        if tx.token > params::max_token_id() {
+            return Err(TxExecutionError::InvalidToken);
+        }
 
         // Check that target account does not have an account ID set.
         let (target_account_id, account) = self
             .get_account_by_address(&tx.target)
-            .ok_or_else(|| format_err!("Target account does not exist"))?;
-        ensure!(
-            account.pub_key_hash == PubKeyHash::default(),
-            "Target account is not locked; forced exit is forbidden"
-        );
+            .ok_or(TxExecutionError::TargetAccountNotFound)?;
+        if account.pub_key_hash != PubKeyHash::default() {
+            return Err(TxExecutionError::TargetAccountNotLocked);
+        }
 
         // Obtain the token balance to be withdrawn.
         let account_balance = self
@@ -53,7 +52,7 @@ impl TxHandler<ForcedExit> for ZkSyncState {
         Ok(forced_exit_op)
     }
 
-    fn apply_tx(&mut self, tx: ForcedExit) -> Result<OpSuccess, anyhow::Error> {
+    fn apply_tx(&mut self, tx: ForcedExit) -> Result<OpSuccess, TxExecutionError> {
         let op = self.create_op(tx)?;
 
         let (fee, updates) = <Self as TxHandler<ForcedExit>>::apply_op(self, &op)?;
@@ -67,12 +66,11 @@ impl TxHandler<ForcedExit> for ZkSyncState {
     fn apply_op(
         &mut self,
         op: &Self::Op,
-    ) -> Result<(Option<CollectedFee>, AccountUpdates), anyhow::Error> {
+    ) -> Result<(Option<CollectedFee>, AccountUpdates), TxExecutionError> {
         let start = Instant::now();
-        ensure!(
-            op.tx.initiator_account_id <= params::max_account_id(),
-            "Incorrect initiator account ID"
-        );
+        if op.tx.initiator_account_id > params::max_account_id() {
+            return Err(TxExecutionError::AccountIdTooBig);
+        }
 
         let initiator_account_id = op.tx.initiator_account_id;
         let target_account_id = op.target_account_id;
@@ -92,20 +90,20 @@ impl TxHandler<ForcedExit> for ZkSyncState {
         let initiator_old_balance = initiator_account.get_balance(op.tx.token);
         let initiator_old_nonce = initiator_account.nonce;
 
-        ensure!(op.tx.nonce == initiator_old_nonce, "Nonce mismatch");
-        ensure!(
-            initiator_old_balance >= op.tx.fee,
-            "Initiator account: Not enough balance to cover fees"
-        );
+        if op.tx.nonce != initiator_old_nonce {
+            return Err(TxExecutionError::NonceMismatch);
+        }
+        if initiator_old_balance < op.tx.fee {
+            return Err(TxExecutionError::InsufficientBalance);
+        }
 
         // Check that target account has required amount of tokens to withdraw.
         // (normally, it should, since we're declaring this amount ourselves, but
         // this check is added for additional safety).
         let target_old_balance = target_account.get_balance(op.tx.token);
-        ensure!(
-            target_old_balance == amount,
-            "Target account: Target account balance is not equal to the withdrawal amount"
-        );
+        if target_old_balance != amount {
+            return Err(TxExecutionError::TargetBalanceMismatch);
+        }
 
         // Take fees from the initiator account (and update initiator account nonce).
         initiator_account.sub_balance(op.tx.token, &op.tx.fee);
@@ -1,7 +1,9 @@
 use num::BigUint;
 use std::time::Instant;
 use zksync_crypto::params;
-use zksync_types::{AccountUpdate, AccountUpdates, FullExit, FullExitOp, ZkSyncOp};
+use zksync_types::{
+    tx::TxExecutionError, AccountUpdate, AccountUpdates, FullExit, FullExitOp, ZkSyncOp,
+};
 use zksync_utils::BigUintSerdeWrapper;
 
 use crate::{
@@ -12,7 +14,7 @@ use crate::{
 impl TxHandler<FullExit> for ZkSyncState {
     type Op = FullExitOp;
 
-    fn create_op(&self, priority_op: FullExit) -> Result<Self::Op, anyhow::Error> {
+    fn create_op(&self, priority_op: FullExit) -> Result<Self::Op, TxExecutionError> {
         // NOTE: Authorization of the FullExit is verified on the contract.
         assert!(
             priority_op.token <= params::max_token_id(),
@@ -34,7 +36,7 @@ impl TxHandler<FullExit> for ZkSyncState {
         Ok(op)
     }
 
-    fn apply_tx(&mut self, priority_op: FullExit) -> Result<OpSuccess, anyhow::Error> {
+    fn apply_tx(&mut self, priority_op: FullExit) -> Result<OpSuccess, TxExecutionError> {
         let op = self.create_op(priority_op)?;
 
         let (fee, updates) = <Self as TxHandler<FullExit>>::apply_op(self, &op)?;
@@ -50,7 +52,7 @@ impl TxHandler<FullExit> for ZkSyncState {
     fn apply_op(
         &mut self,
         op: &Self::Op,
-    ) -> Result<(Option<CollectedFee>, AccountUpdates), anyhow::Error> {
+    ) -> Result<(Option<CollectedFee>, AccountUpdates), TxExecutionError> {
         let start = Instant::now();
         let mut updates = Vec::new();
         let amount = if let Some(amount) = &op.withdraw_amount {
@@ -1,11 +1,12 @@
 use crate::state::{CollectedFee, OpSuccess};
-use zksync_types::AccountUpdates;
+use zksync_types::{tx::TxExecutionError, AccountUpdates};
 
 mod change_pubkey;
 mod close;
 mod deposit;
 mod forced_exit;
 mod full_exit;
+mod swap;
 mod transfer;
 mod withdraw;
 
@@ -22,14 +23,14 @@ pub trait TxHandler<Tx> {
     type Op;
 
     /// Creates an operation wrapper from the given transaction.
-    fn create_op(&self, tx: Tx) -> Result<Self::Op, anyhow::Error>;
+    fn create_op(&self, tx: Tx) -> Result<Self::Op, TxExecutionError>;
 
     /// Applies the transaction.
-    fn apply_tx(&mut self, tx: Tx) -> Result<OpSuccess, anyhow::Error>;
+    fn apply_tx(&mut self, tx: Tx) -> Result<OpSuccess, TxExecutionError>;
 
     /// Applies the operation.
     fn apply_op(
         &mut self,
         op: &Self::Op,
-    ) -> Result<(Option<CollectedFee>, AccountUpdates), anyhow::Error>;
+    ) -> Result<(Option<CollectedFee>, AccountUpdates), TxExecutionError>;
 }
@@ -0,0 +1,221 @@
+use num::BigUint;
+use std::collections::{hash_map::Entry, HashMap};
+use std::time::Instant;
+use zksync_crypto::params::{self, max_account_id};
+use zksync_types::{
+    tx::TxExecutionError, Account, AccountId, AccountUpdate, AccountUpdates, Nonce, PubKeyHash,
+    Swap, SwapOp, TokenId, ZkSyncOp,
+};
+
+use crate::{
+    handler::TxHandler,
+    state::{CollectedFee, OpSuccess, ZkSyncState},
+};
+
+impl TxHandler<Swap> for ZkSyncState {
+    type Op = SwapOp;
+
+    fn create_op(&self, tx: Swap) -> Result<Self::Op, TxExecutionError> {
+        if tx.fee_token > params::max_token_id()
+            || tx.orders.0.token_sell > params::max_token_id()
+            || tx.orders.1.token_sell > params::max_token_id()
+        {
+            return Err(TxExecutionError::InvalidToken);
+        }
+
+        // Check the submitter signature.
+        let submitter = self
+            .get_account(tx.submitter_id)
+            .ok_or(TxExecutionError::AccountNotFound)?;
+        if submitter.address != tx.submitter_address {
+            return Err(TxExecutionError::AccountIdMismatch);
+        }
+        if submitter.pub_key_hash == PubKeyHash::default() {
+            return Err(TxExecutionError::AccountLocked);
+        }
+        if tx.verify_signature() != Some(submitter.pub_key_hash) {
+            return Err(TxExecutionError::PubKeyHashMismatch);
+        }
+
+        // Check the orders signatures.
+        for order in &[&tx.orders.0, &tx.orders.1] {
+            let account = self
+                .get_account(order.account_id)
+                .ok_or(TxExecutionError::AccountNotFound)?;
+            if account.pub_key_hash == PubKeyHash::default() {
+                return Err(TxExecutionError::AccountLocked);
+            }
+            if order.verify_signature() != Some(account.pub_key_hash) {
+                return Err(TxExecutionError::PubKeyHashMismatch);
+            }
+        }
+
+        // Check that the swap satisfies both orders.
+        let (order_0, order_1) = &tx.orders;
+        let (amount_0, amount_1) = &tx.amounts;
+        if !order_0.is_amount_acceptable(amount_0) || !order_1.is_amount_acceptable(amount_1) {
+            return Err(TxExecutionError::SwapAmountOutOfBounds);
+        }
+        if !order_0.is_price_acceptable(amount_0, amount_1)
+            || !order_1.is_price_acceptable(amount_1, amount_0)
+        {
+            return Err(TxExecutionError::SwapPriceMismatch);
+        }
+
+        // Unlike transfers, swap can't create new accounts.
+        let (recipient_0, _) = self
+            .get_account_by_address(&order_0.recipient_address)
+            .ok_or(TxExecutionError::TargetAccountNotFound)?;
+        let (recipient_1, _) = self
+            .get_account_by_address(&order_1.recipient_address)
+            .ok_or(TxExecutionError::TargetAccountNotFound)?;
+
+        let swap_op = SwapOp {
+            submitter: tx.submitter_id,
+            accounts: (tx.orders.0.account_id, tx.orders.1.account_id),
+            recipients: (recipient_0, recipient_1),
+            tx,
+        };
+
+        Ok(swap_op)
+    }
+
+    fn apply_tx(&mut self, tx: Swap) -> Result<OpSuccess, TxExecutionError> {
+        let op = self.create_op(tx)?;
+
+        let (fee, updates) = <Self as TxHandler<Swap>>::apply_op(self, &op)?;
+        Ok(OpSuccess {
+            fee,
+            updates,
+            executed_op: ZkSyncOp::Swap(Box::new(op)),
+        })
+    }
+
+    fn apply_op(
+        &mut self,
+        op: &Self::Op,
+    ) -> Result<(Option<CollectedFee>, AccountUpdates), TxExecutionError> {
+        let start = Instant::now();
+        for &account_id in &op.get_updated_account_ids() {
+            if account_id > max_account_id() {
+                return Err(TxExecutionError::AccountIdTooBig);
+            }
+        }
+
+        let (order_0, order_1) = &op.tx.orders;
+        let (amount_0, amount_1) = &op.tx.amounts;
+
+        // Accounts may coincide (e.g. the order owner may be the submitter or the recipient),
+        // so the steps are applied one after another in the same order as in the circuit.
+        // Changes are accumulated aside and stored only if all the steps succeeded.
+        let mut accounts = HashMap::new();
+        let updates = vec![
+            self.swap_debit(
+                &mut accounts,
+                op.submitter,
+                op.tx.nonce,
+                op.tx.fee_token,
+                &op.tx.fee,
+            )?,
+            self.swap_debit(
+                &mut accounts,
+                op.accounts.0,
+                order_0.nonce,
+                order_0.token_sell,
+                amount_0,
+            )?,
+            self.swap_credit(&mut accounts, op.recipients.0, order_1.token_sell, amount_1),
+            self.swap_debit(
+                &mut accounts,
+                op.accounts.1,
+                order_1.nonce,
+                order_1.token_sell,
+                amount_1,
+            )?,
+            self.swap_credit(&mut accounts, op.recipients.1, order_0.token_sell, amount_0),
+        ];
+
+        for (account_id, account) in accounts {
+            self.insert_account(account_id, account);
+        }
+
+        let fee = CollectedFee {
+            token: op.tx.fee_token,
+            amount: op.tx.fee.clone(),
+        };
+
+        metrics::histogram!("state.swap", start.elapsed());
+        Ok((Some(fee), updates))
+    }
+}
+
+impl ZkSyncState {
+    /// Takes `amount` of `token` from the account and increments its nonce.
+    fn swap_debit(
+        &self,
+        accounts: &mut HashMap<AccountId, Account>,
+        account_id: AccountId,
+        nonce: Nonce,
+        token: TokenId,
+        amount: &BigUint,
+    ) -> Result<(AccountId, AccountUpdate), TxExecutionError> {
+        let account = self.swap_account(accounts, account_id)?;
+
+        let old_balance = account.get_balance(token);
+        let old_nonce = account.nonce;
+        if nonce != old_nonce {
+            return Err(TxExecutionError::NonceMismatch);
+        }
+        if &old_balance < amount {
+            return Err(TxExecutionError::InsufficientBalance);
+        }
+
+        account.sub_balance(token, amount);
+        account.nonce += 1;
+
+        let update = AccountUpdate::UpdateBalance {
+            balance_update: (token, old_balance, account.get_balance(token)),
+            old_nonce,
+            new_nonce: account.nonce,
+        };
+        Ok((account_id, update))
+    }
+
+    /// Adds `amount` of `token` to the account. Account nonce is not affected.
+    fn swap_credit(
+        &self,
+        accounts: &mut HashMap<AccountId, Account>,
+        account_id: AccountId,
+        token: TokenId,
+        amount: &BigUint,
+    ) -> (AccountId, AccountUpdate) {
+        let account = self
+            .swap_account(accounts, account_id)
+            .expect("Recipient account existence is checked during the operation creation");
+
+        let old_balance = account.get_balance(token);
+        account.add_balance(token, amount);
+
+        let update = AccountUpdate::UpdateBalance {
+            balance_update: (token, old_balance, account.get_balance(token)),
+            old_nonce: account.nonce,
+            new_nonce: account.nonce,
+        };
+        (account_id, update)
+    }
+
+    fn swap_account<'a>(
+        &self,
+        accounts: &'a mut HashMap<AccountId, Account>,
+        account_id: AccountId,
+    ) -> Result<&'a mut Account, TxExecutionError> {
+        let account = match accounts.entry(account_id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(
+                self.get_account(account_id)
+                    .ok_or(TxExecutionError::AccountNotFound)?,
+            ),
+        };
+        Ok(account)
+    }
+}
@@ -1,9 +1,8 @@
-use anyhow::{ensure, format_err};
 use std::time::Instant;
 use zksync_crypto::params::{self, max_account_id};
 use zksync_types::{
-    Account, AccountUpdate, AccountUpdates, Address, PubKeyHash, Transfer, TransferOp,
-    TransferToNewOp,
+    tx::TxExecutionError, Account, AccountUpdate, AccountUpdates, Address, PubKeyHash, Transfer,
+    TransferOp, TransferToNewOp,
 };
 
 use crate::{
@@ -14,27 +13,25 @@ use crate::{
 impl TxHandler<Transfer> for ZkSyncState {
     type Op = TransferOutcome;
 
-    fn create_op(&self, tx: Transfer) -> Result<Self::Op, anyhow::Error> {
-        ensure!(
-            tx.token <= params::max_token_id(),
-            "Token id is not supported"
-        );
-        ensure!(
-            tx.to != Address::zero(),
-            "Transfer to Account with address 0 is not allowed"
-        );
+    fn create_op(&self, tx: Transfer) -> Result<Self::Op, TxExecutionError> {
+        if tx.token > params::max_token_id() {
+            return Err(TxExecutionError::InvalidToken);
+        }
+        if tx.to == Address::zero() {
+            return Err(TxExecutionError::TransferToZeroAddress);
+        }
         let (from, from_account) = self
             .get_account_by_address(&tx.from)
-            .ok_or_else(|| format_err!("From account does not exist"))?;
-        ensure!(
-            from_account.pub_key_hash != PubKeyHash::default(),
-            "Account is locked"
-        );
-        ensure!(
-            tx.verify_signature() == Some(from_account.pub_key_hash),
-            "Transfer signature is incorrect"
-        );
-        ensure!(from == tx.account_id, "Transfer account id is incorrect");
+            .ok_or(TxExecutionError::AccountNotFound)?;
+        if from_account.pub_key_hash == PubKeyHash::default() {
+            return Err(TxExecutionError::AccountLocked);
+        }
+        if tx.verify_signature() != Some(from_account.pub_key_hash) {
+            return Err(TxExecutionError::PubKeyHashMismatch);
+        }
+        if from != tx.account_id {
+            return Err(TxExecutionError::AccountIdMismatch);
+        }
 
         let outcome = if let Some((to, _)) = self.get_account_by_address(&tx.to) {
             let transfer_op = TransferOp { tx, from, to };
@@ -50,7 +47,7 @@ impl TxHandler<Transfer> for ZkSyncState {
         Ok(outcome)
     }
 
-    fn apply_tx(&mut self, tx: Transfer) -> Result<OpSuccess, anyhow::Error> {
+    fn apply_tx(&mut self, tx: Transfer) -> Result<OpSuccess, TxExecutionError> {
         let op = self.create_op(tx)?;
 
         let (fee, updates) = <Self as TxHandler<Transfer>>::apply_op(self, &op)?;
@@ -64,7 +61,7 @@ impl TxHandler<Transfer> for ZkSyncState {
     fn apply_op(
         &mut self,
         op: &Self::Op,
-    ) -> Result<(Option<CollectedFee>, AccountUpdates), anyhow::Error> {
+    ) -> Result<(Option<CollectedFee>, AccountUpdates), TxExecutionError> {
         match op {
             TransferOutcome::Transfer(transfer_op) => self.apply_transfer_op(&transfer_op),
             TransferOutcome::TransferToNew(transfer_to_new_op) => {
@@ -78,16 +75,14 @@ impl ZkSyncState {
     fn apply_transfer_op(
         &mut self,
         op: &TransferOp,
-    ) -> Result<(Option<CollectedFee>, AccountUpdates), anyhow::Error> {
+    ) -> Result<(Option<CollectedFee>, AccountUpdates), TxExecutionError> {
         let start = Instant::now();
-        ensure!(
-            op.from <= max_account_id(),
-            "Transfer from account id is bigger than max supported"
-        );
-        ensure!(
-            op.to <= max_account_id(),
-            "Transfer to account id is bigger than max supported"
-        );
+        if op.from > max_account_id() {
+            return Err(TxExecutionError::AccountIdTooBig);
+        }
+        if op.to > max_account_id() {
+            return Err(TxExecutionError::AccountIdTooBig);
+        }
 
         if op.from == op.to {
             return self.apply_transfer_op_to_self(op);
@@ -100,11 +95,12 @@ impl ZkSyncState {
         let from_old_balance = from_account.get_balance(op.tx.token);
         let from_old_nonce = from_account.nonce;
 
-        ensure!(op.tx.nonce == from_old_nonce, "Nonce mismatch");
-        ensure!(
-            from_old_balance >= &op.tx.amount + &op.tx.fee,
-            "Not enough balance"
-        );
+        if op.tx.nonce != from_old_nonce {
+            return Err(TxExecutionError::NonceMismatch);
+        }
+        if from_old_balance < &op.tx.amount + &op.tx.fee {
+            return Err(TxExecutionError::InsufficientBalance);
+        }
 
         from_account.sub_balance(op.tx.token, &(&op.tx.amount + &op.tx.fee));
         from_account.nonce += 1;
@@ -152,16 +148,14 @@ impl ZkSyncState {
     fn apply_transfer_op_to_self(
         &mut self,
         op: &TransferOp,
-    ) -> Result<(Option<CollectedFee>, AccountUpdates), anyhow::Error> {
+    ) -> Result<(Option<CollectedFee>, AccountUpdates), TxExecutionError> {
         let start = Instant::now();
-        ensure!(
-            op.from <= max_account_id(),
-            "Transfer to self from account id is bigger than max supported"
-        );
-        ensure!(
-            op.from == op.to,
-            "Bug: transfer to self should not be called."
-        );
+        if op.from > max_account_id() {
+            return Err(TxExecutionError::AccountIdTooBig);
+        }
+        if op.from != op.to {
+            return Err(TxExecutionError::AccountIdMismatch);
+        }
 
         let mut updates = Vec::new();
         let mut account = self.get_account(op.from).unwrap();
@@ -169,11 +163,12 @@ impl ZkSyncState {
         let old_balance = account.get_balance(op.tx.token);
         let old_nonce = account.nonce;
 
-        ensure!(op.tx.nonce == old_nonce, "Nonce mismatch");
-        ensure!(
-            old_balance >= &op.tx.amount + &op.tx.fee,
-            "Not enough balance"
-        );
+        if op.tx.nonce != old_nonce {
+            return Err(TxExecutionError::NonceMismatch);
+        }
+        if old_balance < &op.tx.amount + &op.tx.fee {
+            return Err(TxExecutionError::InsufficientBalance);
+        }
 
         account.sub_balance(op.tx.token, &op.tx.fee);
         account.nonce += 1;
@@ -204,18 +199,16 @@ impl ZkSyncState {
     fn apply_transfer_to_new_op(
         &mut self,
         op: &TransferToNewOp,
-    ) -> Result<(Option<CollectedFee>, AccountUpdates), anyhow::Error> {
+    ) -> Result<(Option<CollectedFee>, AccountUpdates), TxExecutionError> {
         let start = Instant::now();
         let mut updates = Vec::new();
 
-        ensure!(
-            op.from <= max_account_id(),
-            "TransferToNew from account id is bigger than max supported"
-        );
-        ensure!(
-            op.to <= max_account_id(),
-            "TransferToNew to account id is bigger than max supported"
-        );
+        if op.from > max_account_id() {
+            return Err(TxExecutionError::AccountIdTooBig);
+        }
+        if op.to > max_account_id() {
+            return Err(TxExecutionError::AccountIdTooBig);
+        }
 
         assert!(
             self.get_account(op.to).is_none(),
@@ -230,11 +223,12 @@ impl ZkSyncState {
         let mut from_account = self.get_account(op.from).unwrap();
         let from_old_balance = from_account.get_balance(op.tx.token);
         let from_old_nonce = from_account.nonce;
-        ensure!(op.tx.nonce == from_old_nonce, "Nonce mismatch");
-        ensure!(
-            from_old_balance >= &op.tx.amount + &op.tx.fee,
-            "Not enough balance"
-        );
+        if op.tx.nonce != from_old_nonce {
+            return Err(TxExecutionError::NonceMismatch);
+        }
+        if from_old_balance < &op.tx.amount + &op.tx.fee {
+            return Err(TxExecutionError::InsufficientBalance);
+        }
         from_account.sub_balance(op.tx.token, &(&op.tx.amount + &op.tx.fee));
         from_account.nonce += 1;
         let from_new_balance = from_account.get_balance(op.tx.token);
@@ -1,7 +1,8 @@
-use anyhow::{ensure, format_err};
 use std::time::Instant;
 use zksync_crypto::params::{self, max_account_id};
-use zksync_types::{AccountUpdate, AccountUpdates, PubKeyHash, Withdraw, WithdrawOp, ZkSyncOp};
+use zksync_types::{
+    tx::TxExecutionError, AccountUpdate, AccountUpdates, PubKeyHash, Withdraw, WithdrawOp, ZkSyncOp,
+};
 
 use crate::{
     handler::TxHandler,
@@ -11,32 +12,28 @@ use crate::{
 impl TxHandler<Withdraw> for ZkSyncState {
     type Op = WithdrawOp;
 
-    fn create_op(&self, tx: Withdraw) -> Result<Self::Op, anyhow::Error> {
-        ensure!(
-            tx.token <= params::max_token_id(),
-            "Token id is not supported"
-        );
+    fn create_op(&self, tx: Withdraw) -> Result<Self::Op, TxExecutionError> {
+        if tx.token > params::max_token_id() {
+            return Err(TxExecutionError::InvalidToken);
+        }
         let (account_id, account) = self
             .get_account_by_address(&tx.from)
-            .ok_or_else(|| format_err!("Account does not exist"))?;
-        ensure!(
-            account.pub_key_hash != PubKeyHash::default(),
-            "Account is locked"
-        );
-        ensure!(
-            tx.verify_signature() == Some(account.pub_key_hash),
-            "withdraw signature is incorrect"
-        );
-        ensure!(
-            account_id == tx.account_id,
-            "Withdraw account id is incorrect"
-        );
+            .ok_or(TxExecutionError::AccountNotFound)?;
+        if account.pub_key_hash == PubKeyHash::default() {
+            return Err(TxExecutionError::AccountLocked);
+        }
+        if tx.verify_signature() != Some(account.pub_key_hash) {
+            return Err(TxExecutionError::PubKeyHashMismatch);
+        }
+        if account_id != tx.account_id {
+            return Err(TxExecutionError::AccountIdMismatch);
+        }
         let withdraw_op = WithdrawOp { tx, account_id };
 
         Ok(withdraw_op)
     }
 
-    fn apply_tx(&mut self, tx: Withdraw) -> Result<OpSuccess, anyhow::Error> {
+    fn apply_tx(&mut self, tx: Withdraw) -> Result<OpSuccess, TxExecutionError> {
         let op = self.create_op(tx)?;
 
         let (fee, updates) = <Self as TxHandler<Withdraw>>::apply_op(self, &op)?;
@@ -50,12 +47,11 @@ impl TxHandler<Withdraw> for ZkSyncState {
     fn apply_op(
         &mut self,
         op: &Self::Op,
-    ) -> Result<(Option<CollectedFee>, AccountUpdates), anyhow::Error> {
+    ) -> Result<(Option<CollectedFee>, AccountUpdates), TxExecutionError> {
         let start = Instant::now();
-        ensure!(
-            op.account_id <= max_account_id(),
-            "Withdraw account id is bigger than max supported"
-        );
+        if op.account_id > max_account_id() {
+            return Err(TxExecutionError::AccountIdTooBig);
+        }
 
         let mut updates = Vec::new();
         let mut from_account = self.get_account(op.account_id).unwrap();
@@ -63,11 +59,12 @@ impl TxHandler<Withdraw> for ZkSyncState {
         let from_old_balance = from_account.get_balance(op.tx.token);
         let from_old_nonce = from_account.nonce;
 
-        ensure!(op.tx.nonce == from_old_nonce, "Nonce mismatch");
-        ensure!(
-            from_old_balance >= &op.tx.amount + &op.tx.fee,
-            "Not enough balance"
-        );
+        if op.tx.nonce != from_old_nonce {
+            return Err(TxExecutionError::NonceMismatch);
+        }
+        if from_old_balance < &op.tx.amount + &op.tx.fee {
+            return Err(TxExecutionError::InsufficientBalance);
+        }
 
         from_account.sub_balance(op.tx.token, &(&op.tx.amount + &op.tx.fee));
         from_account.nonce += 1;
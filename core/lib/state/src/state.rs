@@ -1,10 +1,10 @@
-use anyhow::Error;
 use num::BigUint;
 use std::collections::HashMap;
 use zksync_crypto::{params, Fr};
 use zksync_types::{
     helpers::reverse_updates,
     operations::{TransferOp, TransferToNewOp, ZkSyncOp},
+    tx::{BatchExecutionError, TxExecutionError},
     Account, AccountId, AccountMap, AccountTree, AccountUpdate, AccountUpdates, Address,
     BlockNumber, SignedZkSyncTx, TokenId, ZkSyncPriorityOp, ZkSyncTx,
 };
@@ -198,10 +198,16 @@ impl ZkSyncState {
         }
     }
 
-    pub fn execute_txs_batch(&mut self, txs: &[SignedZkSyncTx]) -> Vec<Result<OpSuccess, Error>> {
+    /// Executes the transactions batch. If any of the transactions fails, the changes made by
+    /// the batch are reverted, and the index of the failed transaction along with the reason
+    /// of its failure is returned for each transaction.
+    pub fn execute_txs_batch(
+        &mut self,
+        txs: &[SignedZkSyncTx],
+    ) -> Vec<Result<OpSuccess, BatchExecutionError>> {
         let mut successes = Vec::new();
 
-        for (id, tx) in txs.iter().enumerate() {
+        for (index, tx) in txs.iter().enumerate() {
             match self.execute_tx(tx.tx.clone()) {
                 Ok(success) => {
                     successes.push(Ok(success));
@@ -217,17 +223,9 @@ impl ZkSyncState {
                         self.apply_account_updates(updates);
                     }
 
-                    // Create message for an error.
-                    let error_msg = format!(
-                        "Batch execution failed, since tx #{} of batch failed with a reason: {}",
-                        id + 1,
-                        error
-                    );
-
                     // Create the same error for each transaction.
-                    let errors = (0..txs.len())
-                        .map(|_| Err(anyhow::format_err!("{}", error_msg)))
-                        .collect();
+                    let error = BatchExecutionError { index, error };
+                    let errors = (0..txs.len()).map(|_| Err(error)).collect();
 
                     // Stop execution and return an error.
                     return errors;
@@ -238,13 +236,14 @@ impl ZkSyncState {
         successes
     }
 
-    pub fn execute_tx(&mut self, tx: ZkSyncTx) -> Result<OpSuccess, Error> {
+    pub fn execute_tx(&mut self, tx: ZkSyncTx) -> Result<OpSuccess, TxExecutionError> {
         match tx {
             ZkSyncTx::Transfer(tx) => self.apply_tx(*tx),
             ZkSyncTx::Withdraw(tx) => self.apply_tx(*tx),
             ZkSyncTx::Close(tx) => self.apply_tx(*tx),
             ZkSyncTx::ChangePubKey(tx) => self.apply_tx(*tx),
             ZkSyncTx::ForcedExit(tx) => self.apply_tx(*tx),
+            ZkSyncTx::Swap(tx) => self.apply_tx(*tx),
         }
     }
 
@@ -311,13 +310,14 @@ impl ZkSyncState {
     }
 
     /// Converts the `ZkSyncTx` object to a `ZkSyncOp`, without applying it.
-    pub fn zksync_tx_to_zksync_op(&self, tx: ZkSyncTx) -> Result<ZkSyncOp, anyhow::Error> {
+    pub fn zksync_tx_to_zksync_op(&self, tx: ZkSyncTx) -> Result<ZkSyncOp, TxExecutionError> {
         match tx {
             ZkSyncTx::Transfer(tx) => self.create_op(*tx).map(TransferOutcome::into_franklin_op),
             ZkSyncTx::Withdraw(tx) => self.create_op(*tx).map(Into::into),
             ZkSyncTx::ChangePubKey(tx) => self.create_op(*tx).map(Into::into),
-            ZkSyncTx::Close(_) => anyhow::bail!("Close op is disabled"),
+            ZkSyncTx::Close(_) => Err(TxExecutionError::AccountCloseDisabled),
             ZkSyncTx::ForcedExit(tx) => self.create_op(*tx).map(Into::into),
+            ZkSyncTx::Swap(tx) => self.create_op(*tx).map(Into::into),
         }
     }
 
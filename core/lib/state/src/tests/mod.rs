@@ -9,7 +9,7 @@ use zksync_crypto::{
     rand::{Rng, SeedableRng, XorShiftRng},
     PrivateKey,
 };
-use zksync_types::tx::PackedEthSignature;
+use zksync_types::tx::{PackedEthSignature, TxExecutionError};
 use zksync_types::{
     Account, AccountId, AccountUpdate, PubKeyHash, TokenId, ZkSyncPriorityOp, ZkSyncTx,
 };
@@ -86,17 +86,13 @@ impl PlasmaTestBuilder {
         );
     }
 
-    pub fn test_tx_fail(&mut self, tx: ZkSyncTx, expected_error_message: &str) {
+    pub fn test_tx_fail(&mut self, tx: ZkSyncTx, expected_error: TxExecutionError) {
         let error = self
             .state
             .execute_tx(tx)
             .expect_err("transaction didn't fail");
 
-        assert_eq!(
-            error.to_string().as_str(),
-            expected_error_message,
-            "unexpected error message"
-        );
+        assert_eq!(error, expected_error, "unexpected error");
     }
 
     pub fn test_priority_op_success(
@@ -1,6 +1,6 @@
 use crate::tests::{AccountState::*, PlasmaTestBuilder};
 use zksync_types::account::{AccountUpdate, PubKeyHash};
-use zksync_types::tx::ChangePubKey;
+use zksync_types::tx::{ChangePubKey, TxExecutionError};
 
 /// Check ChangePubKey operation on new account
 #[test]
@@ -20,6 +20,7 @@ fn success() {
         token_id,
         balance.into(),
         account.nonce,
+        Default::default(),
         None,
         &sk,
     )
@@ -63,12 +64,13 @@ fn nonce_mismatch() {
         0,
         0u32.into(),
         account.nonce + 1,
+        Default::default(),
         None,
         &sk,
     )
     .expect("Failed to sign ChangePubkey");
 
-    tb.test_tx_fail(change_pub_key.into(), "Nonce mismatch");
+    tb.test_tx_fail(change_pub_key.into(), TxExecutionError::NonceMismatch);
 }
 
 /// Check that ChangePubKey fails if account address
@@ -86,13 +88,11 @@ fn invalid_account_id() {
         0,
         0u32.into(),
         account.nonce + 1,
+        Default::default(),
         None,
         &sk,
     )
     .expect("Failed to sign ChangePubkey");
 
-    tb.test_tx_fail(
-        change_pub_key.into(),
-        "ChangePubKey account id is incorrect",
-    );
+    tb.test_tx_fail(change_pub_key.into(), TxExecutionError::AccountIdMismatch);
 }
@@ -1,5 +1,5 @@
 use crate::tests::{AccountState::*, PlasmaTestBuilder};
-use zksync_types::tx::{Close, TxSignature};
+use zksync_types::tx::{Close, TxExecutionError, TxSignature};
 
 /// Checks that Close operations fails
 /// because it is disabled
@@ -14,5 +14,5 @@ fn expected_fail() {
         signature: TxSignature::default(),
     };
 
-    tb.test_tx_fail(close.into(), "Account closing is disabled");
+    tb.test_tx_fail(close.into(), TxExecutionError::AccountCloseDisabled);
 }
@@ -1,6 +1,9 @@
 use crate::tests::{AccountState::*, PlasmaTestBuilder};
 use num::{BigUint, Zero};
-use zksync_types::{account::AccountUpdate, tx::ForcedExit};
+use zksync_types::{
+    account::AccountUpdate,
+    tx::{ForcedExit, TxExecutionError},
+};
 
 /// Check ForcedExit operation
 #[test]
@@ -23,6 +26,7 @@ fn success() {
         token_id,
         fee.clone(),
         initiator_account.nonce,
+        Default::default(),
         &initiator_sk,
     )
     .unwrap();
@@ -71,14 +75,12 @@ fn unlocked_target() {
         token_id,
         fee,
         initiator_account.nonce,
+        Default::default(),
         &initiator_sk,
     )
     .unwrap();
 
-    tb.test_tx_fail(
-        forced_exit.into(),
-        "Target account is not locked; forced exit is forbidden",
-    );
+    tb.test_tx_fail(forced_exit.into(), TxExecutionError::TargetAccountNotLocked);
 }
 
 /// Check ForcedExit failure if not enough funds
@@ -101,14 +103,12 @@ fn insufficient_funds() {
         token_id,
         fee,
         initiator_account.nonce,
+        Default::default(),
         &initiator_sk,
     )
     .unwrap();
 
-    tb.test_tx_fail(
-        forced_exit.into(),
-        "Initiator account: Not enough balance to cover fees",
-    );
+    tb.test_tx_fail(forced_exit.into(), TxExecutionError::InsufficientBalance);
 }
 
 /// Check ForcedExit failure if nonce is incorrect
@@ -132,11 +132,12 @@ fn nonce_mismatch() {
         token_id,
         fee,
         initiator_account.nonce + 42,
+        Default::default(),
         &initiator_sk,
     )
     .unwrap();
 
-    tb.test_tx_fail(forced_exit.into(), "Nonce mismatch")
+    tb.test_tx_fail(forced_exit.into(), TxExecutionError::NonceMismatch)
 }
 
 /// Check ForcedExit failure if account address
@@ -161,9 +162,10 @@ fn invalid_account_id() {
         token_id,
         fee,
         initiator_account.nonce,
+        Default::default(),
         &initiator_sk,
     )
     .unwrap();
 
-    tb.test_tx_fail(forced_exit.into(), "Initiator account does not exist")
+    tb.test_tx_fail(forced_exit.into(), TxExecutionError::AccountNotFound)
 }
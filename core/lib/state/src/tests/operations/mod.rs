@@ -2,5 +2,6 @@ mod change_pub_key;
 mod close;
 mod forced_exit;
 mod priority_ops;
+mod swap;
 mod transfer;
 mod withdraw;
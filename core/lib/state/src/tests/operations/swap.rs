@@ -0,0 +1,336 @@
+use crate::tests::{AccountState::*, PlasmaTestBuilder};
+use num::{BigUint, Zero};
+use zksync_crypto::PrivateKey;
+use zksync_types::{
+    account::AccountUpdate,
+    tx::{Order, Swap, TxExecutionError},
+    Account, AccountId, Nonce, TokenId,
+};
+
+const TOKEN_A: TokenId = 0;
+const TOKEN_B: TokenId = 1;
+
+/// Creates an order selling from 10 to 100 of `token_sell` for `token_buy`.
+/// Token A is valued as two tokens B by both sides.
+fn sign_order(
+    account_id: AccountId,
+    account: &Account,
+    sk: &PrivateKey,
+    nonce: Nonce,
+    token_sell: TokenId,
+    token_buy: TokenId,
+) -> Order {
+    let price = if token_sell == TOKEN_A {
+        (1u32.into(), 2u32.into())
+    } else {
+        (2u32.into(), 1u32.into())
+    };
+
+    Order::new_signed(
+        account_id,
+        account.address,
+        nonce,
+        token_sell,
+        token_buy,
+        price,
+        10u32.into(),
+        100u32.into(),
+        sk,
+    )
+    .unwrap()
+}
+
+/// Check Swap operation between two accounts paid by a third one
+#[test]
+fn success() {
+    let mut tb = PlasmaTestBuilder::new();
+
+    let (account_0_id, account_0, sk_0) = tb.add_account(Unlocked);
+    let (account_1_id, account_1, sk_1) = tb.add_account(Unlocked);
+    let (submitter_id, submitter, submitter_sk) = tb.add_account(Unlocked);
+
+    tb.set_balance(account_0_id, TOKEN_A, 50u32);
+    tb.set_balance(account_1_id, TOKEN_B, 100u32);
+    tb.set_balance(submitter_id, TOKEN_A, 10u32);
+
+    let order_0 = sign_order(account_0_id, &account_0, &sk_0, 0, TOKEN_A, TOKEN_B);
+    let order_1 = sign_order(account_1_id, &account_1, &sk_1, 0, TOKEN_B, TOKEN_A);
+    let swap = Swap::new_signed(
+        submitter_id,
+        submitter.address,
+        submitter.nonce,
+        (order_0, order_1),
+        (50u32.into(), 100u32.into()),
+        TOKEN_A,
+        10u32.into(),
+        Default::default(),
+        &submitter_sk,
+    )
+    .unwrap();
+
+    tb.test_tx_success(
+        swap.into(),
+        &[
+            (
+                submitter_id,
+                AccountUpdate::UpdateBalance {
+                    old_nonce: 0,
+                    new_nonce: 1,
+                    balance_update: (TOKEN_A, 10u32.into(), BigUint::zero()),
+                },
+            ),
+            (
+                account_0_id,
+                AccountUpdate::UpdateBalance {
+                    old_nonce: 0,
+                    new_nonce: 1,
+                    balance_update: (TOKEN_A, 50u32.into(), BigUint::zero()),
+                },
+            ),
+            (
+                account_0_id,
+                AccountUpdate::UpdateBalance {
+                    old_nonce: 1,
+                    new_nonce: 1,
+                    balance_update: (TOKEN_B, BigUint::zero(), 100u32.into()),
+                },
+            ),
+            (
+                account_1_id,
+                AccountUpdate::UpdateBalance {
+                    old_nonce: 0,
+                    new_nonce: 1,
+                    balance_update: (TOKEN_B, 100u32.into(), BigUint::zero()),
+                },
+            ),
+            (
+                account_1_id,
+                AccountUpdate::UpdateBalance {
+                    old_nonce: 1,
+                    new_nonce: 1,
+                    balance_update: (TOKEN_A, BigUint::zero(), 50u32.into()),
+                },
+            ),
+        ],
+    )
+}
+
+/// Check Swap operation submitted by the owner of one of the orders
+#[test]
+fn success_submitted_by_order_owner() {
+    let mut tb = PlasmaTestBuilder::new();
+
+    let (account_0_id, account_0, sk_0) = tb.add_account(Unlocked);
+    let (account_1_id, account_1, sk_1) = tb.add_account(Unlocked);
+
+    tb.set_balance(account_0_id, TOKEN_A, 60u32);
+    tb.set_balance(account_1_id, TOKEN_B, 100u32);
+
+    // Order nonce is checked after the submitter nonce was incremented.
+    let order_0 = sign_order(account_0_id, &account_0, &sk_0, 1, TOKEN_A, TOKEN_B);
+    let order_1 = sign_order(account_1_id, &account_1, &sk_1, 0, TOKEN_B, TOKEN_A);
+    let swap = Swap::new_signed(
+        account_0_id,
+        account_0.address,
+        0,
+        (order_0, order_1),
+        (50u32.into(), 100u32.into()),
+        TOKEN_A,
+        10u32.into(),
+        Default::default(),
+        &sk_0,
+    )
+    .unwrap();
+
+    tb.test_tx_success(
+        swap.into(),
+        &[
+            (
+                account_0_id,
+                AccountUpdate::UpdateBalance {
+                    old_nonce: 0,
+                    new_nonce: 1,
+                    balance_update: (TOKEN_A, 60u32.into(), 50u32.into()),
+                },
+            ),
+            (
+                account_0_id,
+                AccountUpdate::UpdateBalance {
+                    old_nonce: 1,
+                    new_nonce: 2,
+                    balance_update: (TOKEN_A, 50u32.into(), BigUint::zero()),
+                },
+            ),
+            (
+                account_0_id,
+                AccountUpdate::UpdateBalance {
+                    old_nonce: 2,
+                    new_nonce: 2,
+                    balance_update: (TOKEN_B, BigUint::zero(), 100u32.into()),
+                },
+            ),
+            (
+                account_1_id,
+                AccountUpdate::UpdateBalance {
+                    old_nonce: 0,
+                    new_nonce: 1,
+                    balance_update: (TOKEN_B, 100u32.into(), BigUint::zero()),
+                },
+            ),
+            (
+                account_1_id,
+                AccountUpdate::UpdateBalance {
+                    old_nonce: 1,
+                    new_nonce: 1,
+                    balance_update: (TOKEN_A, BigUint::zero(), 50u32.into()),
+                },
+            ),
+        ],
+    )
+}
+
+/// Check Swap failure if the amount doesn't fit into the order bounds
+#[test]
+fn amount_out_of_bounds() {
+    let mut tb = PlasmaTestBuilder::new();
+
+    let (account_0_id, account_0, sk_0) = tb.add_account(Unlocked);
+    let (account_1_id, account_1, sk_1) = tb.add_account(Unlocked);
+
+    tb.set_balance(account_0_id, TOKEN_A, 500u32);
+    tb.set_balance(account_1_id, TOKEN_B, 1000u32);
+
+    let order_0 = sign_order(account_0_id, &account_0, &sk_0, 1, TOKEN_A, TOKEN_B);
+    let order_1 = sign_order(account_1_id, &account_1, &sk_1, 0, TOKEN_B, TOKEN_A);
+    let swap = Swap::new_signed(
+        account_0_id,
+        account_0.address,
+        0,
+        (order_0, order_1),
+        (500u32.into(), 1000u32.into()),
+        TOKEN_A,
+        BigUint::zero(),
+        Default::default(),
+        &sk_0,
+    )
+    .unwrap();
+
+    tb.test_tx_fail(swap.into(), TxExecutionError::SwapAmountOutOfBounds)
+}
+
+/// Check Swap failure if the amounts don't satisfy the order price
+#[test]
+fn price_mismatch() {
+    let mut tb = PlasmaTestBuilder::new();
+
+    let (account_0_id, account_0, sk_0) = tb.add_account(Unlocked);
+    let (account_1_id, account_1, sk_1) = tb.add_account(Unlocked);
+
+    tb.set_balance(account_0_id, TOKEN_A, 50u32);
+    tb.set_balance(account_1_id, TOKEN_B, 100u32);
+
+    let order_0 = sign_order(account_0_id, &account_0, &sk_0, 1, TOKEN_A, TOKEN_B);
+    let order_1 = sign_order(account_1_id, &account_1, &sk_1, 0, TOKEN_B, TOKEN_A);
+    // First order requires at least 100 B for 50 A.
+    let swap = Swap::new_signed(
+        account_0_id,
+        account_0.address,
+        0,
+        (order_0, order_1),
+        (50u32.into(), 99u32.into()),
+        TOKEN_A,
+        BigUint::zero(),
+        Default::default(),
+        &sk_0,
+    )
+    .unwrap();
+
+    tb.test_tx_fail(swap.into(), TxExecutionError::SwapPriceMismatch)
+}
+
+/// Check Swap failure if the second order owner doesn't have enough funds
+#[test]
+fn insufficient_funds() {
+    let mut tb = PlasmaTestBuilder::new();
+
+    let (account_0_id, account_0, sk_0) = tb.add_account(Unlocked);
+    let (account_1_id, account_1, sk_1) = tb.add_account(Unlocked);
+
+    tb.set_balance(account_0_id, TOKEN_A, 50u32);
+    tb.set_balance(account_1_id, TOKEN_B, 99u32);
+
+    let order_0 = sign_order(account_0_id, &account_0, &sk_0, 1, TOKEN_A, TOKEN_B);
+    let order_1 = sign_order(account_1_id, &account_1, &sk_1, 0, TOKEN_B, TOKEN_A);
+    let swap = Swap::new_signed(
+        account_0_id,
+        account_0.address,
+        0,
+        (order_0, order_1),
+        (50u32.into(), 100u32.into()),
+        TOKEN_A,
+        BigUint::zero(),
+        Default::default(),
+        &sk_0,
+    )
+    .unwrap();
+
+    tb.test_tx_fail(swap.into(), TxExecutionError::InsufficientBalance)
+}
+
+/// Check Swap failure if the order nonce is incorrect
+#[test]
+fn nonce_mismatch() {
+    let mut tb = PlasmaTestBuilder::new();
+
+    let (account_0_id, account_0, sk_0) = tb.add_account(Unlocked);
+    let (account_1_id, account_1, sk_1) = tb.add_account(Unlocked);
+
+    tb.set_balance(account_0_id, TOKEN_A, 50u32);
+    tb.set_balance(account_1_id, TOKEN_B, 100u32);
+
+    let order_0 = sign_order(account_0_id, &account_0, &sk_0, 1, TOKEN_A, TOKEN_B);
+    let order_1 = sign_order(account_1_id, &account_1, &sk_1, 42, TOKEN_B, TOKEN_A);
+    let swap = Swap::new_signed(
+        account_0_id,
+        account_0.address,
+        0,
+        (order_0, order_1),
+        (50u32.into(), 100u32.into()),
+        TOKEN_A,
+        BigUint::zero(),
+        Default::default(),
+        &sk_0,
+    )
+    .unwrap();
+
+    tb.test_tx_fail(swap.into(), TxExecutionError::NonceMismatch)
+}
+
+/// Check Swap failure if the order is signed by another account
+#[test]
+fn invalid_order_signature() {
+    let mut tb = PlasmaTestBuilder::new();
+
+    let (account_0_id, account_0, sk_0) = tb.add_account(Unlocked);
+    let (account_1_id, account_1, _) = tb.add_account(Unlocked);
+
+    tb.set_balance(account_0_id, TOKEN_A, 50u32);
+    tb.set_balance(account_1_id, TOKEN_B, 100u32);
+
+    let order_0 = sign_order(account_0_id, &account_0, &sk_0, 1, TOKEN_A, TOKEN_B);
+    let order_1 = sign_order(account_1_id, &account_1, &sk_0, 0, TOKEN_B, TOKEN_A);
+    let swap = Swap::new_signed(
+        account_0_id,
+        account_0.address,
+        0,
+        (order_0, order_1),
+        (50u32.into(), 100u32.into()),
+        TOKEN_A,
+        BigUint::zero(),
+        Default::default(),
+        &sk_0,
+    )
+    .unwrap();
+
+    tb.test_tx_fail(swap.into(), TxExecutionError::PubKeyHashMismatch)
+}
@@ -1,7 +1,10 @@
 use crate::tests::{AccountState::*, PlasmaTestBuilder};
 use num::{BigUint, Zero};
 use web3::types::H160;
-use zksync_types::{AccountUpdate, Transfer};
+use zksync_types::{
+    tx::{BatchExecutionError, TxExecutionError},
+    AccountUpdate, SignedZkSyncTx, Transfer, ZkSyncTx,
+};
 
 /// Check Transfer operation to existing account
 #[test]
@@ -25,6 +28,7 @@ fn to_existing() {
         amount.clone(),
         fee.clone(),
         from_account.nonce,
+        Default::default(),
         &from_sk,
     )
     .unwrap();
@@ -74,11 +78,12 @@ fn insufficient_funds() {
         amount,
         fee,
         from_account.nonce,
+        Default::default(),
         &from_sk,
     )
     .unwrap();
 
-    tb.test_tx_fail(transfer.into(), "Not enough balance");
+    tb.test_tx_fail(transfer.into(), TxExecutionError::InsufficientBalance);
 }
 
 /// Check Transfer operation to new account
@@ -104,6 +109,7 @@ fn to_new() {
         amount.clone(),
         fee.clone(),
         account.nonce,
+        Default::default(),
         &sk,
     )
     .unwrap();
@@ -158,6 +164,7 @@ fn to_self() {
         amount.clone(),
         fee.clone(),
         account.nonce,
+        Default::default(),
         &sk,
     )
     .unwrap();
@@ -195,11 +202,12 @@ fn nonce_mismatch() {
         amount,
         fee,
         account.nonce + 1,
+        Default::default(),
         &sk,
     )
     .unwrap();
 
-    tb.test_tx_fail(transfer.into(), "Nonce mismatch")
+    tb.test_tx_fail(transfer.into(), TxExecutionError::NonceMismatch)
 }
 
 /// Check Transfer failure if account address
@@ -224,9 +232,58 @@ fn invalid_account_id() {
         amount,
         fee,
         account.nonce,
+        Default::default(),
         &sk,
     )
     .unwrap();
 
-    tb.test_tx_fail(transfer.into(), "Transfer account id is incorrect")
+    tb.test_tx_fail(transfer.into(), TxExecutionError::AccountIdMismatch)
+}
+
+/// Check that the failed batch is reverted and the failed transaction is reported
+#[test]
+fn batch_with_failed_tx() {
+    let token_id = 0;
+    let amount = BigUint::from(100u32);
+    let fee = BigUint::from(10u32);
+
+    let mut tb = PlasmaTestBuilder::new();
+
+    let (account_id, account, sk) = tb.add_account(Unlocked);
+    let (_, to_account, _) = tb.add_account(Locked);
+    tb.set_balance(account_id, token_id, &amount + &fee);
+
+    let transfers: Vec<SignedZkSyncTx> = (0..2)
+        .map(|_| {
+            Transfer::new_signed(
+                account_id,
+                account.address,
+                to_account.address,
+                token_id,
+                amount.clone(),
+                fee.clone(),
+                account.nonce,
+                Default::default(),
+                &sk,
+            )
+            .unwrap()
+        })
+        .map(|transfer| ZkSyncTx::from(transfer).into())
+        .collect();
+
+    let root_hash = tb.state.root_hash();
+    let results = tb.state.execute_txs_batch(&transfers);
+
+    // The first transfer is correct, but the second one reuses its nonce.
+    let expected_error = BatchExecutionError {
+        index: 1,
+        error: TxExecutionError::NonceMismatch,
+    };
+    assert_eq!(results.len(), transfers.len());
+    for result in results {
+        assert_eq!(result.err(), Some(expected_error));
+    }
+    assert_eq!(expected_error.tx_error(0), TxExecutionError::BatchTxFailed);
+    assert_eq!(expected_error.tx_error(1), TxExecutionError::NonceMismatch);
+    assert_eq!(tb.state.root_hash(), root_hash, "batch was not reverted");
 }
@@ -1,6 +1,9 @@
 use crate::tests::{AccountState::*, PlasmaTestBuilder};
 use num::{BigUint, Zero};
-use zksync_types::{account::AccountUpdate, tx::Withdraw};
+use zksync_types::{
+    account::AccountUpdate,
+    tx::{TxExecutionError, Withdraw},
+};
 
 /// Check withdraw operation
 #[test]
@@ -22,6 +25,7 @@ fn success() {
         amount.clone(),
         fee.clone(),
         account.nonce,
+        Default::default(),
         &sk,
     )
     .unwrap();
@@ -59,11 +63,12 @@ fn insufficient_funds() {
         amount,
         fee,
         account.nonce,
+        Default::default(),
         &sk,
     )
     .unwrap();
 
-    tb.test_tx_fail(withdraw.into(), "Not enough balance");
+    tb.test_tx_fail(withdraw.into(), TxExecutionError::InsufficientBalance);
 }
 
 /// Check Withdraw failure if nonce is incorrect
@@ -86,11 +91,12 @@ fn nonce_mismatch() {
         amount,
         fee,
         account.nonce + 1,
+        Default::default(),
         &sk,
     )
     .unwrap();
 
-    tb.test_tx_fail(withdraw.into(), "Nonce mismatch")
+    tb.test_tx_fail(withdraw.into(), TxExecutionError::NonceMismatch)
 }
 
 /// Check Withdraw failure if account address
@@ -115,9 +121,10 @@ fn invalid_account_id() {
         amount,
         fee,
         account.nonce,
+        Default::default(),
         &sk,
     )
     .unwrap();
 
-    tb.test_tx_fail(withdraw.into(), "Withdraw account id is incorrect")
+    tb.test_tx_fail(withdraw.into(), TxExecutionError::AccountIdMismatch)
 }
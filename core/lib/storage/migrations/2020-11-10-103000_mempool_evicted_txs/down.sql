@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS mempool_txs_created_at_index;
+DROP TABLE IF EXISTS mempool_evicted_txs;
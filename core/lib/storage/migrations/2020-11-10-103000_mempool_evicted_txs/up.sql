@@ -0,0 +1,8 @@
+-- Transactions that were removed from the mempool without being executed.
+CREATE TABLE mempool_evicted_txs (
+    tx_hash TEXT PRIMARY KEY,
+    -- Human-readable reason of the eviction
+    reason TEXT NOT NULL,
+    evicted_at TIMESTAMP with time zone NOT NULL
+);
+CREATE INDEX mempool_txs_created_at_index ON mempool_txs (created_at);
@@ -0,0 +1 @@
+ALTER TABLE mempool_evicted_txs DROP COLUMN replaced_by;
@@ -0,0 +1,2 @@
+-- Hash of the transaction that replaced the evicted one by paying higher fee
+ALTER TABLE mempool_evicted_txs ADD COLUMN replaced_by TEXT;
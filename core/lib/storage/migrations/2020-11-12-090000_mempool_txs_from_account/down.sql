@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS mempool_txs_from_account_index;
+ALTER TABLE mempool_txs DROP COLUMN from_account;
@@ -0,0 +1,8 @@
+-- Address of the account affected by the transaction
+ALTER TABLE mempool_txs ADD COLUMN from_account BYTEA;
+UPDATE mempool_txs SET from_account = decode(
+    substring(COALESCE(tx->>'from', tx->>'account', tx->>'target') from 3),
+    'hex'
+);
+ALTER TABLE mempool_txs ALTER COLUMN from_account SET NOT NULL;
+CREATE INDEX mempool_txs_from_account_index ON mempool_txs (from_account);
@@ -0,0 +1,2 @@
+-- Machine-readable code of the transaction execution failure.
+ALTER TABLE executed_transactions ADD COLUMN fail_code INTEGER;
@@ -0,0 +1,2 @@
+-- Criterion which caused the block to be sealed by the state keeper.
+ALTER TABLE blocks ADD COLUMN seal_reason TEXT;
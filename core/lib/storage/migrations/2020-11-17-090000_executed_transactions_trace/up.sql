@@ -0,0 +1,2 @@
+-- Execution details of the successful transaction: account updates, fee, chunks and position in the block.
+ALTER TABLE executed_transactions ADD COLUMN trace JSONB;
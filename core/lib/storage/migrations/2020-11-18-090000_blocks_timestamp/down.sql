@@ -0,0 +1,2 @@
+ALTER TABLE blocks DROP COLUMN IF EXISTS timestamp;
+ALTER TABLE pending_block DROP COLUMN IF EXISTS timestamp;
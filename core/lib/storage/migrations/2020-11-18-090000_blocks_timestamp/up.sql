@@ -0,0 +1,3 @@
+-- Unix timestamp of the block, which is a part of the block commitment.
+ALTER TABLE blocks ADD COLUMN timestamp BIGINT;
+ALTER TABLE pending_block ADD COLUMN timestamp BIGINT;
@@ -0,0 +1,4 @@
+-- Binary encoded transactions can't be represented in the old format.
+DELETE FROM mempool_txs WHERE tx IS NULL;
+ALTER TABLE mempool_txs ALTER COLUMN tx SET NOT NULL;
+ALTER TABLE mempool_txs DROP COLUMN IF EXISTS tx_binary;
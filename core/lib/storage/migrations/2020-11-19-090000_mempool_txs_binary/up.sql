@@ -0,0 +1,4 @@
+-- Transactions encoded with the zkSync binary codec, along with their Ethereum signature data.
+-- JSON columns are kept for the transactions stored before the binary encoding was introduced.
+ALTER TABLE mempool_txs ADD COLUMN tx_binary BYTEA;
+ALTER TABLE mempool_txs ALTER COLUMN tx DROP NOT NULL;
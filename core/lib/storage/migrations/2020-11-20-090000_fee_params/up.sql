@@ -0,0 +1,7 @@
+-- Fee parameters set by the server operator. Every change creates a new version,
+-- the latest one is used by the fee ticker.
+CREATE TABLE fee_params (
+    version BIGSERIAL PRIMARY KEY,
+    params JSONB NOT NULL,
+    created_at TIMESTAMP with time zone NOT NULL DEFAULT now()
+);
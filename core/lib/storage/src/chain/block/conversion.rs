@@ -10,6 +10,7 @@ use zksync_types::{
     Action, ActionType, Operation,
     {
         block::{ExecutedPriorityOp, ExecutedTx},
+        tx::TxExecutionError,
         BlockNumber, PriorityOp, ZkSyncOp, ZkSyncTx,
     },
 };
@@ -60,16 +61,21 @@ impl StoredExecutedTransaction {
         let eth_sign_data = self
             .eth_sign_data
             .map(|value| serde_json::from_value(value).expect("Unparsable EthSignData"));
+        let trace = self
+            .trace
+            .map(|value| serde_json::from_value(value).expect("Unparsable TxExecutionTrace"));
         Ok(ExecutedTx {
             signed_tx: SignedZkSyncTx { tx, eth_sign_data },
             success: self.success,
             op: franklin_op,
             fail_reason: self.fail_reason,
+            fail_code: self.fail_code.and_then(TxExecutionError::from_code),
             block_index: self
                 .block_index
                 .map(|val| u32::try_from(val).expect("Invalid block index")),
             created_at: self.created_at,
             batch_id: self.batch_id,
+            trace,
         })
     }
 }
@@ -162,6 +168,10 @@ impl NewExecutedTransaction {
                     serde_json::from_value(tx["target"].clone()).unwrap(),
                     serde_json::from_value(tx["target"].clone()).unwrap(),
                 ),
+                ZkSyncTx::Swap(_) => (
+                    serde_json::from_value(tx["submitterAddress"].clone()).unwrap(),
+                    None,
+                ),
             };
 
         let from_account: Vec<u8> = hex::decode(cut_prefix(&from_account_hex)).unwrap();
@@ -171,6 +181,10 @@ impl NewExecutedTransaction {
         let eth_sign_data = exec_tx.signed_tx.eth_sign_data.as_ref().map(|sign_data| {
             serde_json::to_value(sign_data).expect("Failed to encode EthSignData")
         });
+        let trace = exec_tx
+            .trace
+            .as_ref()
+            .map(|trace| serde_json::to_value(trace).expect("Failed to encode TxExecutionTrace"));
 
         Self {
             block_number: i64::from(block),
@@ -181,12 +195,14 @@ impl NewExecutedTransaction {
             operation,
             success: exec_tx.success,
             fail_reason: exec_tx.fail_reason,
+            fail_code: exec_tx.fail_code.map(TxExecutionError::code),
             block_index: exec_tx.block_index.map(|idx| idx as i32),
             primary_account_address: exec_tx.signed_tx.account().as_bytes().to_vec(),
             nonce: exec_tx.signed_tx.nonce() as i64,
             created_at: exec_tx.created_at,
             eth_sign_data,
             batch_id: exec_tx.batch_id,
+            trace,
         }
     }
 }
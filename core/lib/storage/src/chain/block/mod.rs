@@ -3,23 +3,26 @@ use std::time::Instant;
 // External imports
 use zksync_basic_types::U256;
 // Workspace imports
-use zksync_crypto::convert::FeConvert;
+use zksync_crypto::{convert::FeConvert, proof::EncodedProofPlonk};
 use zksync_types::{block::PendingBlock, Action, ActionType, Operation};
 use zksync_types::{
     block::{Block, ExecutedOperations},
-    AccountId, BlockNumber, ZkSyncOp,
+    AccountId, AccountUpdate, BlockNumber, ZkSyncOp,
 };
 // Local imports
 use self::records::{
     AccountTreeCache, BlockDetails, BlockTransactionItem, StorageBlock, StoragePendingBlock,
 };
 use crate::{
-    chain::operations::{
-        records::{
-            NewExecutedPriorityOperation, NewExecutedTransaction, NewOperation,
-            StoredExecutedPriorityOperation, StoredExecutedTransaction, StoredOperation,
+    chain::{
+        operations::{
+            records::{
+                NewExecutedPriorityOperation, NewExecutedTransaction, NewOperation,
+                StoredExecutedPriorityOperation, StoredExecutedTransaction, StoredOperation,
+            },
+            OperationsSchema,
         },
-        OperationsSchema,
+        state::StateSchema,
     },
     prover::ProverSchema,
     QueryResult, StorageProcessor,
@@ -142,7 +145,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             FeConvert::from_bytes(&stored_block.root_hash).expect("Unparsable root hash");
 
         // Return the obtained block in the expected format.
-        let result = Some(Block::new(
+        let mut result = Block::new(
             block,
             new_root_hash,
             stored_block.fee_account_id as AccountId,
@@ -154,11 +157,15 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             stored_block.block_size as usize,
             U256::from(stored_block.commit_gas_limit as u64),
             U256::from(stored_block.verify_gas_limit as u64),
-        ));
+            stored_block.timestamp.unwrap_or_default() as u64,
+        );
+        result.seal_reason = stored_block
+            .seal_reason
+            .map(|reason| reason.parse().expect("Unparsable block seal reason"));
 
         metrics::histogram!("sql.chain.block.get_block", start.elapsed());
 
-        Ok(result)
+        Ok(Some(result))
     }
 
     /// Same as `get_block_executed_ops`, but returns a vector of `ZkSyncOp` instead
@@ -325,6 +332,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
                 blocks.number AS "block_number!",
                 blocks.root_hash AS "new_state_root!",
                 blocks.block_size AS "block_size!",
+                blocks.fee_account_id AS "fee_account_id!",
                 committed.tx_hash AS "commit_tx_hash?",
                 verified.tx_hash AS "verify_tx_hash?",
                 committed.created_at AS "committed_at!",
@@ -428,6 +436,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
                 blocks.number AS "block_number!",
                 blocks.root_hash AS "new_state_root!",
                 blocks.block_size AS "block_size!",
+                blocks.fee_account_id AS "fee_account_id!",
                 committed.tx_hash AS "commit_tx_hash?",
                 verified.tx_hash AS "verify_tx_hash?",
                 committed.created_at AS "committed_at!",
@@ -566,6 +575,7 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             pending_block_iteration: block.pending_block_iteration as usize,
             success_operations,
             failed_txs,
+            timestamp: block.timestamp.map(|timestamp| timestamp as u64),
         };
 
         transaction.commit().await?;
@@ -590,17 +600,19 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             chunks_left: pending_block.chunks_left as i64,
             unprocessed_priority_op_before: pending_block.unprocessed_priority_op_before as i64,
             pending_block_iteration: pending_block.pending_block_iteration as i64,
+            timestamp: pending_block.timestamp.map(|timestamp| timestamp as i64),
         };
 
         // Store the pending block header.
         sqlx::query!("
-            INSERT INTO pending_block (number, chunks_left, unprocessed_priority_op_before, pending_block_iteration)
-            VALUES ($1, $2, $3, $4)
+            INSERT INTO pending_block (number, chunks_left, unprocessed_priority_op_before, pending_block_iteration, timestamp)
+            VALUES ($1, $2, $3, $4, $5)
             ON CONFLICT (number)
             DO UPDATE
-              SET chunks_left = $2, unprocessed_priority_op_before = $3, pending_block_iteration = $4
+              SET chunks_left = $2, unprocessed_priority_op_before = $3, pending_block_iteration = $4, timestamp = $5
             ",
             storage_block.number, storage_block.chunks_left, storage_block.unprocessed_priority_op_before, storage_block.pending_block_iteration,
+            storage_block.timestamp,
         ).execute(transaction.conn())
         .await?;
 
@@ -654,6 +666,8 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         let block_size = block.block_chunks_size as i64;
         let commit_gas_limit = block.commit_gas_limit.as_u64() as i64;
         let verify_gas_limit = block.verify_gas_limit.as_u64() as i64;
+        let seal_reason = block.seal_reason.map(|reason| reason.to_string());
+        let timestamp = Some(block.timestamp as i64);
 
         BlockSchema(&mut transaction)
             .save_block_transactions(block.block_number, block.block_transactions)
@@ -668,6 +682,8 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
             block_size,
             commit_gas_limit,
             verify_gas_limit,
+            seal_reason,
+            timestamp,
         };
 
         // Remove pending block (as it's now completed).
@@ -682,11 +698,12 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
 
         // Save new completed block.
         sqlx::query!("
-            INSERT INTO blocks (number, root_hash, fee_account_id, unprocessed_prior_op_before, unprocessed_prior_op_after, block_size, commit_gas_limit, verify_gas_limit)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            INSERT INTO blocks (number, root_hash, fee_account_id, unprocessed_prior_op_before, unprocessed_prior_op_after, block_size, commit_gas_limit, verify_gas_limit, seal_reason, timestamp)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
             ",
             new_block.number, new_block.root_hash, new_block.fee_account_id, new_block.unprocessed_prior_op_before,
             new_block.unprocessed_prior_op_after, new_block.block_size, new_block.commit_gas_limit, new_block.verify_gas_limit,
+            new_block.seal_reason, new_block.timestamp,
         ).execute(transaction.conn())
         .await?;
 
@@ -696,6 +713,58 @@ impl<'a, 'c> BlockSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Stores the block restored from the state snapshot along with its account tree cache.
+    /// `accounts_updated` must create the state of the block from scratch, so the database
+    /// is expected to be empty.
+    ///
+    /// Similarly to the blocks restored from Ethereum, the snapshot block is stored
+    /// as committed and verified, so it's neither proven nor sent to Ethereum once again.
+    pub async fn save_snapshot_block(
+        &mut self,
+        block: Block,
+        accounts_updated: &[(AccountId, AccountUpdate)],
+        tree_cache: serde_json::Value,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+        let block_number = block.block_number;
+
+        StateSchema(&mut transaction)
+            .commit_state_update(block_number, accounts_updated, 0)
+            .await?;
+        BlockSchema(&mut transaction)
+            .execute_operation(Operation {
+                id: None,
+                action: Action::Commit,
+                block: block.clone(),
+            })
+            .await?;
+        BlockSchema(&mut transaction)
+            .execute_operation(Operation {
+                id: None,
+                action: Action::Verify {
+                    proof: Box::new(EncodedProofPlonk::default()),
+                },
+                block,
+            })
+            .await?;
+        StateSchema(&mut transaction)
+            .apply_state_update(block_number)
+            .await?;
+        for action_type in &[ActionType::COMMIT, ActionType::VERIFY] {
+            OperationsSchema(&mut transaction)
+                .confirm_operation(block_number, *action_type)
+                .await?;
+        }
+        BlockSchema(&mut transaction)
+            .store_account_tree_cache(block_number, tree_cache)
+            .await?;
+
+        transaction.commit().await?;
+        metrics::histogram!("sql.chain.block.save_snapshot_block", start.elapsed());
+        Ok(())
+    }
+
     /// Stores account tree cache for a block
     pub async fn store_account_tree_cache(
         &mut self,
@@ -17,6 +17,8 @@ pub struct StorageBlock {
     pub block_size: i64,
     pub commit_gas_limit: i64,
     pub verify_gas_limit: i64,
+    pub seal_reason: Option<String>,
+    pub timestamp: Option<i64>,
 }
 
 #[derive(Debug, FromRow)]
@@ -25,6 +27,7 @@ pub struct StoragePendingBlock {
     pub chunks_left: i64,
     pub unprocessed_priority_op_before: i64,
     pub pending_block_iteration: i64,
+    pub timestamp: Option<i64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow, PartialEq, Clone)]
@@ -36,6 +39,8 @@ pub struct BlockDetails {
 
     pub block_size: i64,
 
+    pub fee_account_id: i64,
+
     #[serde(with = "OptionBytesToHexSerde::<ZeroxPrefix>")]
     pub commit_tx_hash: Option<Vec<u8>>,
 
@@ -1,15 +1,17 @@
 // Built-in deps
 use std::{collections::VecDeque, convert::TryFrom, time::Instant};
 // External imports
+use chrono::{DateTime, Utc};
 use itertools::Itertools;
 // Workspace imports
 use zksync_types::{
+    codec,
     mempool::SignedTxVariant,
     tx::{TxEthSignature, TxHash},
-    SignedZkSyncTx,
+    Address, SignedZkSyncTx,
 };
 // Local imports
-use self::records::MempoolTx;
+use self::records::{MempoolEvictedTx, MempoolTx};
 use crate::{QueryResult, StorageProcessor};
 
 pub mod records;
@@ -26,6 +28,15 @@ pub struct MempoolSchema<'a, 'c>(pub &'a mut StorageProcessor<'c>);
 impl<'a, 'c> MempoolSchema<'a, 'c> {
     /// Loads all the transactions stored in the mempool schema.
     pub async fn load_txs(&mut self) -> QueryResult<VecDeque<SignedTxVariant>> {
+        let txs = self.load_txs_with_creation_time().await?;
+        Ok(txs.into_iter().map(|(tx, _)| tx).collect())
+    }
+
+    /// Loads all the transactions stored in the mempool schema along with the moments
+    /// they were stored at. For batches, the moment of the first batch transaction is used.
+    pub async fn load_txs_with_creation_time(
+        &mut self,
+    ) -> QueryResult<VecDeque<(SignedTxVariant, DateTime<Utc>)>> {
         let start = Instant::now();
         // Load the transactions from mempool along with corresponding batch IDs.
         let txs: Vec<MempoolTx> = sqlx::query_as!(
@@ -58,33 +69,27 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         let mut txs = Vec::new();
 
         for (batch_id, group) in grouped_txs.into_iter() {
-            let deserialized_txs: Vec<SignedZkSyncTx> = group
-                .map(|tx_object| -> QueryResult<SignedZkSyncTx> {
-                    let tx = serde_json::from_value(tx_object.tx)?;
-                    let sign_data = match tx_object.eth_sign_data {
-                        None => None,
-                        Some(sign_data_value) => serde_json::from_value(sign_data_value)?,
-                    };
-
-                    Ok(SignedZkSyncTx {
-                        tx,
-                        eth_sign_data: sign_data,
-                    })
+            let deserialized_txs = group
+                .map(|tx| {
+                    let created_at = tx.created_at;
+                    SignedZkSyncTx::try_from(tx).map(|tx| (tx, created_at))
                 })
-                .collect::<Result<Vec<SignedZkSyncTx>, anyhow::Error>>()?;
+                .collect::<QueryResult<Vec<_>>>()?;
 
             match batch_id {
                 Some(batch_id) => {
                     // Group of batched transactions.
                     // Signatures will be loaded afterwards.
-                    let variant = SignedTxVariant::batch(deserialized_txs, batch_id, None);
-                    txs.push(variant);
+                    let created_at = deserialized_txs[0].1;
+                    let batch_txs = deserialized_txs.into_iter().map(|(tx, _)| tx).collect();
+                    let variant = SignedTxVariant::batch(batch_txs, batch_id, None);
+                    txs.push((variant, created_at));
                 }
                 None => {
                     // Group of non-batched transactions.
                     let mut variants = deserialized_txs
                         .into_iter()
-                        .map(SignedTxVariant::from)
+                        .map(|(tx, created_at)| (SignedTxVariant::from(tx), created_at))
                         .collect();
                     txs.append(&mut variants);
                 }
@@ -92,7 +97,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         }
 
         // Load signatures for batches.
-        for tx in &mut txs {
+        for (tx, _) in &mut txs {
             if let SignedTxVariant::Batch(batch) = tx {
                 let eth_signature = sqlx::query!(
                     "SELECT eth_signature FROM txs_batches_signatures
@@ -112,7 +117,7 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
 
         // Now transactions should be sorted by the nonce (transaction natural order)
         // According to our convention in batch `fee transaction` would be the last one, so we would use nonce from it as a key for sort
-        txs.sort_by_key(|tx| match tx {
+        txs.sort_by_key(|(tx, _)| match tx {
             SignedTxVariant::Tx(tx) => tx.tx.nonce(),
             SignedTxVariant::Batch(batch) => batch
                 .txs
@@ -145,20 +150,16 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         let batch_id = {
             let first_tx_data = txs[0].clone();
             let tx_hash = hex::encode(first_tx_data.hash().as_ref());
-            let tx = serde_json::to_value(&first_tx_data.tx)
-                .expect("Unserializable TX provided to the database");
-            let eth_sign_data = first_tx_data
-                .eth_sign_data
-                .as_ref()
-                .map(|sd| serde_json::to_value(sd).expect("failed to encode EthSignData"));
+            let from_account = first_tx_data.account().as_bytes().to_vec();
+            let tx_binary = codec::encode(&first_tx_data);
 
             sqlx::query!(
-                "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data)
+                "INSERT INTO mempool_txs (tx_hash, tx_binary, created_at, from_account)
                 VALUES ($1, $2, $3, $4)",
                 tx_hash,
-                tx,
+                tx_binary,
                 chrono::Utc::now(),
-                eth_sign_data,
+                from_account,
             )
             .execute(self.0.conn())
             .await?;
@@ -178,21 +179,17 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         // Processing of all batch transactions, except the first
         for tx_data in txs[1..].iter() {
             let tx_hash = hex::encode(tx_data.hash().as_ref());
-            let tx = serde_json::to_value(&tx_data.tx)
-                .expect("Unserializable TX provided to the database");
-            let eth_sign_data = tx_data
-                .eth_sign_data
-                .as_ref()
-                .map(|sd| serde_json::to_value(sd).expect("failed to encode EthSignData"));
+            let from_account = tx_data.account().as_bytes().to_vec();
+            let tx_binary = codec::encode(tx_data);
 
             sqlx::query!(
-                "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data, batch_id)
+                "INSERT INTO mempool_txs (tx_hash, tx_binary, created_at, batch_id, from_account)
                 VALUES ($1, $2, $3, $4, $5)",
                 tx_hash,
-                tx,
+                tx_binary,
                 chrono::Utc::now(),
-                eth_sign_data,
                 batch_id,
+                from_account,
             )
             .execute(self.0.conn())
             .await?;
@@ -218,22 +215,18 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
     pub async fn insert_tx(&mut self, tx_data: &SignedZkSyncTx) -> QueryResult<()> {
         let start = Instant::now();
         let tx_hash = hex::encode(tx_data.tx.hash().as_ref());
-        let tx = serde_json::to_value(&tx_data.tx)?;
+        let from_account = tx_data.tx.account().as_bytes().to_vec();
+        let tx_binary = codec::encode(tx_data);
         let batch_id = 0; // Special case: batch_id == 0 <==> transaction is not a part of some batch
 
-        let eth_sign_data = tx_data
-            .eth_sign_data
-            .as_ref()
-            .map(|sd| serde_json::to_value(sd).expect("failed to encode EthSignData"));
-
         sqlx::query!(
-            "INSERT INTO mempool_txs (tx_hash, tx, created_at, eth_sign_data, batch_id)
+            "INSERT INTO mempool_txs (tx_hash, tx_binary, created_at, batch_id, from_account)
             VALUES ($1, $2, $3, $4, $5)",
             tx_hash,
-            tx,
+            tx_binary,
             chrono::Utc::now(),
-            eth_sign_data,
             batch_id,
+            from_account,
         )
         .execute(self.0.conn())
         .await?;
@@ -274,6 +267,134 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         Ok(())
     }
 
+    /// Removes transactions from the mempool schema without executing them.
+    /// Removed transactions are stored along with the reason of eviction, so
+    /// their status can be reported to users.
+    pub async fn evict_txs(&mut self, txs: &[TxHash], reason: &str) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        MempoolSchema(&mut transaction).remove_txs(txs).await?;
+
+        let evicted_at = chrono::Utc::now();
+        for tx_hash in txs {
+            let tx_hash = hex::encode(tx_hash.as_ref());
+            sqlx::query!(
+                "INSERT INTO mempool_evicted_txs (tx_hash, reason, evicted_at)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (tx_hash)
+                DO UPDATE SET reason = $2, evicted_at = $3, replaced_by = NULL",
+                tx_hash,
+                reason,
+                evicted_at,
+            )
+            .execute(transaction.conn())
+            .await?;
+        }
+
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.chain.mempool.evict_txs", start.elapsed());
+        Ok(())
+    }
+
+    /// Removes the transaction from the mempool schema in favor of the transaction with
+    /// the same nonce paying higher fee. The new transaction must be inserted separately.
+    pub async fn replace_tx(
+        &mut self,
+        old_tx: TxHash,
+        new_tx: TxHash,
+        reason: &str,
+    ) -> QueryResult<()> {
+        let start = Instant::now();
+        let mut transaction = self.0.start_transaction().await?;
+
+        MempoolSchema(&mut transaction)
+            .remove_txs(&[old_tx])
+            .await?;
+
+        let old_tx = hex::encode(old_tx.as_ref());
+        let new_tx = hex::encode(new_tx.as_ref());
+        sqlx::query!(
+            "INSERT INTO mempool_evicted_txs (tx_hash, reason, evicted_at, replaced_by)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (tx_hash)
+            DO UPDATE SET reason = $2, evicted_at = $3, replaced_by = $4",
+            old_tx,
+            reason,
+            chrono::Utc::now(),
+            new_tx,
+        )
+        .execute(transaction.conn())
+        .await?;
+
+        transaction.commit().await?;
+
+        metrics::histogram!("sql.chain.mempool.replace_tx", start.elapsed());
+        Ok(())
+    }
+
+    /// Evicts transactions that were added to the mempool schema before the `deadline`.
+    /// Batch is evicted as a whole if any of its transactions is expired.
+    /// Returns the hashes of evicted transactions.
+    pub async fn evict_expired_txs(
+        &mut self,
+        deadline: DateTime<Utc>,
+        reason: &str,
+    ) -> QueryResult<Vec<TxHash>> {
+        let start = Instant::now();
+
+        let tx_hashes = sqlx::query!(
+            "SELECT tx_hash FROM mempool_txs
+            WHERE created_at < $1 OR batch_id IN (
+                SELECT batch_id FROM mempool_txs
+                WHERE created_at < $1 AND batch_id != 0
+            )",
+            deadline
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|row| -> QueryResult<TxHash> {
+            let bytes = hex::decode(&row.tx_hash)?;
+            TxHash::from_slice(&bytes)
+                .ok_or_else(|| anyhow::format_err!("Incorrect tx hash stored: {}", row.tx_hash))
+        })
+        .collect::<QueryResult<Vec<_>>>()?;
+
+        if !tx_hashes.is_empty() {
+            self.evict_txs(&tx_hashes, reason).await?;
+        }
+
+        metrics::histogram!("sql.chain.mempool.evict_expired_txs", start.elapsed());
+        Ok(tx_hashes)
+    }
+
+    /// Returns the eviction details if the transaction with the given hash was
+    /// removed from the mempool without being executed.
+    /// Transactions that were evicted and then sent again are not reported as evicted.
+    pub async fn get_evicted_tx(
+        &mut self,
+        tx_hash: TxHash,
+    ) -> QueryResult<Option<MempoolEvictedTx>> {
+        let start = Instant::now();
+
+        let tx_hash = hex::encode(tx_hash.as_ref());
+
+        let evicted_tx = sqlx::query_as!(
+            MempoolEvictedTx,
+            "SELECT * FROM mempool_evicted_txs
+            WHERE tx_hash = $1
+            AND NOT EXISTS (SELECT 1 FROM mempool_txs WHERE tx_hash = $1)",
+            &tx_hash
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.chain.mempool.get_evicted_tx", start.elapsed());
+        Ok(evicted_tx)
+    }
+
     /// Checks if the memory pool contains transaction with the given hash.
     pub async fn contains_tx(&mut self, tx_hash: TxHash) -> QueryResult<bool> {
         let start = Instant::now();
@@ -311,10 +432,32 @@ impl<'a, 'c> MempoolSchema<'a, 'c> {
         .await?;
 
         metrics::histogram!("sql.chain", start.elapsed(), "mempool" => "get_tx");
-        mempool_tx
+        mempool_tx.map(SignedZkSyncTx::try_from).transpose()
+    }
+
+    /// Returns the transactions of the given account stored in the mempool schema,
+    /// sorted by nonce.
+    pub async fn get_account_txs(&mut self, address: Address) -> QueryResult<Vec<SignedZkSyncTx>> {
+        let start = Instant::now();
+
+        let mempool_txs = sqlx::query_as!(
+            MempoolTx,
+            "SELECT * FROM mempool_txs
+            WHERE from_account = $1
+            ORDER BY created_at",
+            address.as_bytes()
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        let mut txs = mempool_txs
+            .into_iter()
             .map(SignedZkSyncTx::try_from)
-            .transpose()
-            .map_err(anyhow::Error::from)
+            .collect::<Result<Vec<_>, _>>()?;
+        txs.sort_by_key(|tx| tx.nonce());
+
+        metrics::histogram!("sql.chain.mempool.get_account_txs", start.elapsed());
+        Ok(txs)
     }
 
     /// Removes transactions that are already committed.
@@ -6,7 +6,7 @@ use chrono::{DateTime, Utc};
 use sqlx::FromRow;
 
 // Workspace imports
-use zksync_types::SignedZkSyncTx;
+use zksync_types::{codec, tx::TxHash, SignedZkSyncTx};
 
 // Local imports
 
@@ -14,18 +14,50 @@ use zksync_types::SignedZkSyncTx;
 pub struct MempoolTx {
     pub id: i64,
     pub tx_hash: String,
-    pub tx: serde_json::Value,
+    /// JSON representation of the transaction, only set for the transactions
+    /// stored before the binary encoding was introduced.
+    pub tx: Option<serde_json::Value>,
     pub created_at: DateTime<Utc>,
     pub eth_sign_data: Option<serde_json::Value>,
     pub batch_id: i64,
+    pub from_account: Vec<u8>,
+    /// `SignedZkSyncTx` encoded with the zkSync binary codec.
+    pub tx_binary: Option<Vec<u8>>,
+}
+
+/// Transaction that was removed from the mempool without being executed.
+#[derive(Debug, Clone, FromRow)]
+pub struct MempoolEvictedTx {
+    pub tx_hash: String,
+    pub reason: String,
+    pub evicted_at: DateTime<Utc>,
+    /// Hash of the transaction with the same nonce that replaced the evicted one.
+    pub replaced_by: Option<String>,
+}
+
+impl MempoolEvictedTx {
+    /// Returns the hash of the transaction that replaced the evicted one, if any.
+    pub fn replacement_hash(&self) -> Option<TxHash> {
+        let replaced_by = self.replaced_by.as_ref()?;
+        hex::decode(replaced_by)
+            .ok()
+            .and_then(|bytes| TxHash::from_slice(&bytes))
+    }
 }
 
 impl TryFrom<MempoolTx> for SignedZkSyncTx {
-    type Error = serde_json::Error;
+    type Error = anyhow::Error;
 
     fn try_from(value: MempoolTx) -> Result<Self, Self::Error> {
+        if let Some(tx_binary) = value.tx_binary {
+            return Ok(codec::decode(&tx_binary)?);
+        }
+
+        let tx = value
+            .tx
+            .ok_or_else(|| anyhow::format_err!("Mempool tx {} has no body", value.tx_hash))?;
         Ok(Self {
-            tx: serde_json::from_value(value.tx)?,
+            tx: serde_json::from_value(tx)?,
             eth_sign_data: value
                 .eth_sign_data
                 .map(serde_json::from_value)
@@ -3,7 +3,7 @@ use std::time::Instant;
 // External imports
 use anyhow::format_err;
 // Workspace imports
-use zksync_types::{ethereum::CompleteWithdrawalsTx, tx::TxHash, ActionType, BlockNumber};
+use zksync_types::{ethereum::CompleteWithdrawalsTx, tx::TxHash, ActionType, Address, BlockNumber};
 // Local imports
 use self::records::{
     NewExecutedPriorityOperation, NewExecutedTransaction, NewOperation,
@@ -126,6 +126,36 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
         Ok(op)
     }
 
+    /// Loads the executed priority operations related to the account, starting from the
+    /// newest one. If `before` is provided, only operations with the lesser serial ID are loaded.
+    pub async fn get_account_executed_priority_operations(
+        &mut self,
+        address: &Address,
+        before: Option<u64>,
+        limit: u64,
+    ) -> QueryResult<Vec<StoredExecutedPriorityOperation>> {
+        let start = Instant::now();
+        let before = before.map(|id| id as i64).unwrap_or(i64::max_value());
+        let ops = sqlx::query_as!(
+            StoredExecutedPriorityOperation,
+            "SELECT * FROM executed_priority_operations
+            WHERE (from_account = $1 OR to_account = $1) AND priority_op_serialid < $2
+            ORDER BY priority_op_serialid DESC
+            LIMIT $3",
+            address.as_bytes(),
+            before,
+            limit as i64
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!(
+            "sql.chain.operations.get_account_executed_priority_operations",
+            start.elapsed()
+        );
+        Ok(ops)
+    }
+
     pub(crate) async fn store_operation(
         &mut self,
         operation: NewOperation,
@@ -184,11 +214,11 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
             // sent the same transfer again.
 
             sqlx::query!(
-                "INSERT INTO executed_transactions (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+                "INSERT INTO executed_transactions (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id, fail_code, trace)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
                 ON CONFLICT (tx_hash)
                 DO UPDATE
-                SET block_number = $1, block_index = $2, tx = $3, operation = $4, tx_hash = $5, from_account = $6, to_account = $7, success = $8, fail_reason = $9, primary_account_address = $10, nonce = $11, created_at = $12, eth_sign_data = $13, batch_id = $14",
+                SET block_number = $1, block_index = $2, tx = $3, operation = $4, tx_hash = $5, from_account = $6, to_account = $7, success = $8, fail_reason = $9, primary_account_address = $10, nonce = $11, created_at = $12, eth_sign_data = $13, batch_id = $14, fail_code = $15, trace = $16",
                 operation.block_number,
                 operation.block_index,
                 operation.tx,
@@ -203,14 +233,16 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
                 operation.created_at,
                 operation.eth_sign_data,
                 operation.batch_id,
+                operation.fail_code,
+                operation.trace,
             )
             .execute(transaction.conn())
             .await?;
         } else {
             // If transaction failed, we do nothing on conflict.
             sqlx::query!(
-                "INSERT INTO executed_transactions (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id)
-                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+                "INSERT INTO executed_transactions (block_number, block_index, tx, operation, tx_hash, from_account, to_account, success, fail_reason, primary_account_address, nonce, created_at, eth_sign_data, batch_id, fail_code, trace)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
                 ON CONFLICT (tx_hash)
                 DO NOTHING",
                 operation.block_number,
@@ -227,6 +259,8 @@ impl<'a, 'c> OperationsSchema<'a, 'c> {
                 operation.created_at,
                 operation.eth_sign_data,
                 operation.batch_id,
+                operation.fail_code,
+                operation.trace,
             )
             .execute(transaction.conn())
             .await?;
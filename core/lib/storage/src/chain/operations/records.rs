@@ -39,11 +39,13 @@ pub struct StoredExecutedTransaction {
     pub to_account: Option<Vec<u8>>,
     pub success: bool,
     pub fail_reason: Option<String>,
+    pub fail_code: Option<i32>,
     pub primary_account_address: Vec<u8>,
     pub nonce: i64,
     pub created_at: DateTime<Utc>,
     pub eth_sign_data: Option<serde_json::Value>,
     pub batch_id: Option<i64>,
+    pub trace: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone)]
@@ -77,11 +79,13 @@ pub struct NewExecutedTransaction {
     pub to_account: Option<Vec<u8>>,
     pub success: bool,
     pub fail_reason: Option<String>,
+    pub fail_code: Option<i32>,
     pub primary_account_address: Vec<u8>,
     pub nonce: i64,
     pub created_at: DateTime<Utc>,
     pub eth_sign_data: Option<serde_json::Value>,
     pub batch_id: Option<i64>,
+    pub trace: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone)]
@@ -3,7 +3,7 @@ use std::time::Instant;
 // External imports
 use chrono::{DateTime, Utc};
 // Workspace imports
-use zksync_types::ActionType;
+use zksync_types::{tx::TxExecutionError, ActionType};
 use zksync_types::{Address, TokenId};
 // Local imports
 use self::records::{
@@ -62,6 +62,7 @@ impl<'a, 'c> OperationsExtSchema<'a, 'c> {
                 success: tx.success,
                 verified,
                 fail_reason: tx.fail_reason,
+                fail_code: tx.fail_code.and_then(TxExecutionError::from_code),
                 prover_run,
             }))
         } else {
@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::value::Value;
 use sqlx::FromRow;
 // Workspace imports
+use zksync_types::tx::TxExecutionError;
 // Local imports
 use crate::prover::records::ProverRun;
 
@@ -43,6 +44,7 @@ pub struct TxReceiptResponse {
     pub success: bool,
     pub verified: bool,
     pub fail_reason: Option<String>,
+    pub fail_code: Option<TxExecutionError>,
     pub prover_run: Option<ProverRun>,
 }
 
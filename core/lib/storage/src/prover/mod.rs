@@ -1,12 +1,13 @@
 // Built-in deps
 use std::time::{self, Instant};
 // External imports
+use chrono::{DateTime, Utc};
 use sqlx::Done;
 // Workspace imports
 use zksync_crypto::proof::EncodedProofPlonk;
 use zksync_types::BlockNumber;
 // Local imports
-use self::records::{ActiveProver, ProverRun, StoredProof};
+use self::records::{ActiveProver, ProverRun, ProvingTimeStats, StoredProof};
 use crate::prover::records::StorageBlockWitness;
 use crate::{chain::block::BlockSchema, QueryResult, StorageProcessor};
 
@@ -86,6 +87,47 @@ impl<'a, 'c> ProverSchema<'a, 'c> {
         Ok(block_without_proofs as u32)
     }
 
+    /// Returns the statistics of the proving jobs finished since the given moment,
+    /// grouped by the block size.
+    ///
+    /// Proving time of the block is measured from the start of its latest prover run
+    /// until the proof is stored.
+    pub async fn proving_time_stats(
+        &mut self,
+        since: DateTime<Utc>,
+    ) -> QueryResult<Vec<ProvingTimeStats>> {
+        let start = Instant::now();
+        let stats = sqlx::query!(
+            r#"
+            SELECT blocks.block_size, COUNT(*) AS jobs_count,
+                EXTRACT(EPOCH FROM AVG(proofs.created_at - runs.started_at)) AS avg_proving_secs
+            FROM proofs
+            INNER JOIN (
+                SELECT block_number, MAX(created_at) AS started_at FROM prover_runs
+                GROUP BY block_number
+            ) runs ON runs.block_number = proofs.block_number
+            INNER JOIN blocks ON blocks.number = proofs.block_number
+            WHERE proofs.created_at > $1
+            GROUP BY blocks.block_size
+            "#,
+            since
+        )
+        .fetch_all(self.0.conn())
+        .await?
+        .into_iter()
+        .map(|record| ProvingTimeStats {
+            block_size: record.block_size as usize,
+            jobs_count: record.jobs_count.unwrap_or(0) as u64,
+            avg_proving_time: time::Duration::from_secs_f64(
+                record.avg_proving_secs.unwrap_or(0.0).max(0.0),
+            ),
+        })
+        .collect();
+
+        metrics::histogram!("sql.prover.proving_time_stats", start.elapsed());
+        Ok(stats)
+    }
+
     /// Attempts to obtain an existing prover run given block number.
     pub async fn get_existing_prover_run(
         &mut self,
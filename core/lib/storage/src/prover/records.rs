@@ -1,3 +1,5 @@
+// Built-in imports
+use std::time::Duration;
 // External imports
 use chrono::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -37,6 +39,16 @@ pub struct ProverRun {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Proving jobs statistics for the blocks of a certain size.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProvingTimeStats {
+    pub block_size: usize,
+    /// Amount of the proven blocks.
+    pub jobs_count: u64,
+    /// Average time it took to prove a block.
+    pub avg_proving_time: Duration,
+}
+
 #[derive(Debug, FromRow)]
 pub struct IntegerNumber {
     pub integer_value: i64,
@@ -3,8 +3,8 @@
 // Workspace imports
 use zksync_crypto::{convert::FeConvert, rand::XorShiftRng};
 use zksync_types::{
-    ethereum::OperationType, helpers::apply_updates, AccountMap, AccountUpdate, AccountUpdates,
-    Action, ActionType, BlockNumber,
+    block::BlockSealReason, ethereum::OperationType, helpers::apply_updates, AccountMap,
+    AccountUpdate, AccountUpdates, Action, ActionType, BlockNumber,
 };
 // Local imports
 use super::utils::{get_operation, get_operation_with_txs};
@@ -214,6 +214,7 @@ async fn find_block_by_height_or_hash(mut storage: StorageProcessor<'_>) -> Quer
             block_number: 0,
             new_state_root: Default::default(),
             block_size: 0,
+            fee_account_id: 0,
             commit_tx_hash: None,
             verify_tx_hash: None,
             committed_at: chrono::DateTime::from_utc(
@@ -262,6 +263,7 @@ async fn find_block_by_height_or_hash(mut storage: StorageProcessor<'_>) -> Quer
         current_block_detail.block_number = operation.block.block_number as i64;
         current_block_detail.new_state_root = operation.block.new_root_hash.to_bytes();
         current_block_detail.block_size = operation.block.block_transactions.len() as i64;
+        current_block_detail.fee_account_id = i64::from(operation.block.fee_account);
         current_block_detail.commit_tx_hash = Some(eth_tx_hash.as_ref().to_vec());
 
         // Add verification for the block if required.
@@ -605,9 +607,11 @@ async fn pending_block_workflow(mut storage: StorageProcessor<'_>) -> QueryResul
             success: true,
             op: Some(change_pubkey_op),
             fail_reason: None,
+            fail_code: None,
             block_index: None,
             created_at: chrono::Utc::now(),
             batch_id: None,
+            trace: None,
         };
 
         (
@@ -639,9 +643,11 @@ async fn pending_block_workflow(mut storage: StorageProcessor<'_>) -> QueryResul
             success: true,
             op: Some(transfer_to_new_op),
             fail_reason: None,
+            fail_code: None,
             block_index: None,
             created_at: chrono::Utc::now(),
             batch_id: None,
+            trace: None,
         };
 
         (
@@ -663,6 +669,7 @@ async fn pending_block_workflow(mut storage: StorageProcessor<'_>) -> QueryResul
         pending_block_iteration: 1,
         success_operations: txs_1,
         failed_txs: Vec::new(),
+        timestamp: Some(1_600_000_000),
     };
     let pending_block_2 = PendingBlock {
         number: 2,
@@ -671,6 +678,7 @@ async fn pending_block_workflow(mut storage: StorageProcessor<'_>) -> QueryResul
         pending_block_iteration: 2,
         success_operations: txs_2,
         failed_txs: Vec::new(),
+        timestamp: None,
     };
 
     // Save pending block
@@ -693,6 +701,7 @@ async fn pending_block_workflow(mut storage: StorageProcessor<'_>) -> QueryResul
         pending_block.pending_block_iteration,
         pending_block_1.pending_block_iteration
     );
+    assert_eq!(pending_block.timestamp, pending_block_1.timestamp);
     assert_eq!(
         pending_block.success_operations.len(),
         pending_block_1.success_operations.len()
@@ -945,3 +954,75 @@ async fn test_operations_counter(mut storage: StorageProcessor<'_>) -> QueryResu
 
     Ok(())
 }
+
+/// Checks that the reason of the block sealing and the block timestamp are stored along with the block.
+#[db_test]
+async fn block_seal_reason(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut operation = gen_unique_operation(1, Action::Commit, BLOCK_SIZE_CHUNKS);
+    operation.block.seal_reason = Some(BlockSealReason::GasLimit);
+    operation.block.timestamp = 1_600_000_000;
+    BlockSchema(&mut storage)
+        .execute_operation(operation)
+        .await?;
+    BlockSchema(&mut storage)
+        .execute_operation(gen_unique_operation(2, Action::Commit, BLOCK_SIZE_CHUNKS))
+        .await?;
+
+    let block = BlockSchema(&mut storage).get_block(1).await?.unwrap();
+    assert_eq!(block.seal_reason, Some(BlockSealReason::GasLimit));
+    assert_eq!(block.timestamp, 1_600_000_000);
+    let block = BlockSchema(&mut storage).get_block(2).await?.unwrap();
+    assert_eq!(block.seal_reason, None);
+
+    Ok(())
+}
+
+/// Checks that the block restored from the state snapshot is stored into the empty database
+/// as the last committed and verified one, along with its state and account tree cache.
+#[db_test]
+async fn snapshot_block_import(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let mut rng = create_rng();
+    let (accounts, updates) = apply_random_updates(AccountMap::default(), &mut rng);
+    let mut operation = gen_unique_operation(5, Action::Commit, BLOCK_SIZE_CHUNKS);
+    operation.block.processed_priority_ops = (3, 3);
+    let tree_cache = serde_json::json!({ "cache": "dummy" });
+
+    BlockSchema(&mut storage)
+        .save_snapshot_block(operation.block.clone(), &updates, tree_cache.clone())
+        .await?;
+
+    assert_eq!(
+        StateSchema(&mut storage).load_committed_state(None).await?,
+        (5, accounts.clone())
+    );
+    assert_eq!(
+        StateSchema(&mut storage).load_verified_state().await?,
+        (5, accounts)
+    );
+    assert_eq!(
+        BlockSchema(&mut storage).get_account_tree_cache().await?,
+        Some((5, tree_cache))
+    );
+
+    let block = BlockSchema(&mut storage).get_block(5).await?.unwrap();
+    assert_eq!(block.new_root_hash, operation.block.new_root_hash);
+    assert_eq!(block.processed_priority_ops, (3, 3));
+    assert_eq!(
+        BlockSchema(&mut storage).get_last_committed_block().await?,
+        5
+    );
+    assert_eq!(
+        BlockSchema(&mut storage)
+            .get_last_verified_confirmed_block()
+            .await?,
+        5
+    );
+
+    // Snapshot block must not be sent to Ethereum.
+    assert!(EthereumSchema(&mut storage)
+        .load_unprocessed_operations()
+        .await?
+        .is_empty());
+
+    Ok(())
+}
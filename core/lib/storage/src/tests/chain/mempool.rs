@@ -28,6 +28,7 @@ fn franklin_txs() -> Vec<SignedZkSyncTx> {
         100u32.into(),
         10u32.into(),
         10,
+        Default::default(),
         None,
     );
 
@@ -39,6 +40,7 @@ fn franklin_txs() -> Vec<SignedZkSyncTx> {
         500u32.into(),
         20u32.into(),
         11,
+        Default::default(),
         None,
     );
 
@@ -50,6 +52,7 @@ fn franklin_txs() -> Vec<SignedZkSyncTx> {
         100u32.into(),
         10u32.into(),
         12,
+        Default::default(),
         None,
     );
 
@@ -60,6 +63,7 @@ fn franklin_txs() -> Vec<SignedZkSyncTx> {
         0,
         Default::default(),
         13,
+        Default::default(),
         None,
         None,
     );
@@ -99,6 +103,7 @@ fn gen_transfers(n: usize) -> Vec<SignedZkSyncTx> {
                 100u32.into(),
                 10u32.into(),
                 10,
+                Default::default(),
                 None,
             );
 
@@ -261,12 +266,14 @@ async fn collect_garbage(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
         to_account: None,
         success: true,
         fail_reason: None,
+        fail_code: None,
         block_index: None,
         primary_account_address: Default::default(),
         nonce: Default::default(),
         created_at: chrono::Utc::now(),
         eth_sign_data: None,
         batch_id: None,
+        trace: None,
     };
     OperationsSchema(&mut storage)
         .store_executed_tx(executed_tx)
@@ -337,3 +344,158 @@ async fn contains_and_get_tx(mut storage: StorageProcessor<'_>) -> QueryResult<(
 
     Ok(())
 }
+
+/// Checks that transactions can be loaded by the account address.
+#[db_test]
+async fn get_account_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let address = Address::random();
+    // Transactions of the same account are inserted in the reversed nonce order.
+    let account_txs: Vec<_> = (0..3)
+        .rev()
+        .map(|nonce| {
+            let transfer = Transfer::new(
+                1,
+                address,
+                Address::random(),
+                0,
+                100u32.into(),
+                10u32.into(),
+                nonce,
+                Default::default(),
+                None,
+            );
+            SignedZkSyncTx::from(ZkSyncTx::from(transfer))
+        })
+        .collect();
+    let other_txs = gen_transfers(2);
+
+    {
+        let mut mempool = MempoolSchema(&mut storage);
+        mempool.insert_tx(&other_txs[0]).await?;
+        mempool.insert_tx(&account_txs[0]).await?;
+        mempool.insert_batch(&account_txs[1..], None).await?;
+        mempool.insert_tx(&other_txs[1]).await?;
+    }
+
+    let loaded_txs = MempoolSchema(&mut storage).get_account_txs(address).await?;
+    let loaded_hashes: Vec<_> = loaded_txs.iter().map(|tx| tx.hash()).collect();
+    let expected_hashes: Vec<_> = account_txs.iter().rev().map(|tx| tx.hash()).collect();
+    assert_eq!(loaded_hashes, expected_hashes);
+
+    // Account without pending transactions.
+    assert!(MempoolSchema(&mut storage)
+        .get_account_txs(Address::random())
+        .await?
+        .is_empty());
+
+    Ok(())
+}
+
+/// Checks that evicted transactions are removed from the mempool and their
+/// eviction reason can be loaded.
+#[db_test]
+async fn evict_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = gen_transfers(3);
+    for tx in &txs {
+        MempoolSchema(&mut storage).insert_tx(tx).await?;
+    }
+
+    let evicted_hash = txs[0].hash();
+    MempoolSchema(&mut storage)
+        .evict_txs(&[evicted_hash], "test reason")
+        .await?;
+
+    assert_eq!(
+        MempoolSchema(&mut storage)
+            .contains_tx(evicted_hash)
+            .await?,
+        false
+    );
+    let evicted_tx = MempoolSchema(&mut storage)
+        .get_evicted_tx(evicted_hash)
+        .await?
+        .expect("Evicted tx must be stored");
+    assert_eq!(evicted_tx.reason, "test reason");
+
+    // Retained transactions aren't marked as evicted.
+    for tx in &txs[1..] {
+        assert!(MempoolSchema(&mut storage).contains_tx(tx.hash()).await?);
+        assert!(MempoolSchema(&mut storage)
+            .get_evicted_tx(tx.hash())
+            .await?
+            .is_none());
+    }
+
+    Ok(())
+}
+
+/// Checks that transactions added before the deadline are evicted, and batches
+/// are evicted as a whole.
+#[db_test]
+async fn evict_expired_txs(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = gen_transfers(4);
+    {
+        let mut mempool = MempoolSchema(&mut storage);
+        mempool.insert_tx(&txs[0]).await?;
+        mempool.insert_batch(&txs[1..3], None).await?;
+    }
+
+    // All the transactions inserted above are older than the deadline.
+    let deadline = chrono::Utc::now();
+    MempoolSchema(&mut storage).insert_tx(&txs[3]).await?;
+
+    let mut evicted = MempoolSchema(&mut storage)
+        .evict_expired_txs(deadline, "expired")
+        .await?;
+    evicted.sort();
+    let mut expected: Vec<_> = txs[..3].iter().map(|tx| tx.hash()).collect();
+    expected.sort();
+    assert_eq!(evicted, expected);
+
+    let txs_from_db = MempoolSchema(&mut storage).load_txs().await?;
+    assert_eq!(txs_from_db.len(), 1);
+    assert_eq!(
+        unwrap_tx(txs_from_db.into_iter().next().unwrap()).hash(),
+        txs[3].hash()
+    );
+
+    Ok(())
+}
+
+/// Checks that replaced transaction is removed from the mempool and refers
+/// to the transaction that replaced it.
+#[db_test]
+async fn replace_tx(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let txs = gen_transfers(2);
+    MempoolSchema(&mut storage).insert_tx(&txs[0]).await?;
+
+    let (old_hash, new_hash) = (txs[0].hash(), txs[1].hash());
+    {
+        let mut mempool = MempoolSchema(&mut storage);
+        mempool
+            .replace_tx(old_hash, new_hash, "Replaced by fee")
+            .await?;
+        mempool.insert_tx(&txs[1]).await?;
+    }
+
+    assert!(!MempoolSchema(&mut storage).contains_tx(old_hash).await?);
+    assert!(MempoolSchema(&mut storage).contains_tx(new_hash).await?);
+
+    let replaced_tx = MempoolSchema(&mut storage)
+        .get_evicted_tx(old_hash)
+        .await?
+        .expect("Replaced tx must be stored");
+    assert_eq!(replaced_tx.replacement_hash(), Some(new_hash));
+
+    // Evicting the transaction for another reason resets the replacement.
+    MempoolSchema(&mut storage)
+        .evict_txs(&[old_hash], "test reason")
+        .await?;
+    let evicted_tx = MempoolSchema(&mut storage)
+        .get_evicted_tx(old_hash)
+        .await?
+        .expect("Evicted tx must be stored");
+    assert_eq!(evicted_tx.replacement_hash(), None);
+
+    Ok(())
+}
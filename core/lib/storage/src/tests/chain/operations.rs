@@ -1,13 +1,16 @@
 // External imports
 // Workspace imports
-use zksync_types::ActionType;
+use zksync_types::{tx::TxExecutionError, ActionType, Address};
 // Local imports
 use crate::tests::db_test;
 use crate::{
     chain::{
         block::BlockSchema,
         operations::{
-            records::{NewExecutedPriorityOperation, NewExecutedTransaction, NewOperation},
+            records::{
+                NewExecutedPriorityOperation, NewExecutedTransaction, NewOperation,
+                StoredExecutedPriorityOperation,
+            },
             OperationsSchema,
         },
     },
@@ -50,12 +53,14 @@ async fn executed_operations(mut storage: StorageProcessor<'_>) -> QueryResult<(
         to_account: None,
         success: true,
         fail_reason: None,
+        fail_code: None,
         block_index: None,
         primary_account_address: Default::default(),
         nonce: Default::default(),
         created_at: chrono::Utc::now(),
         eth_sign_data: None,
         batch_id: Some(10),
+        trace: Some(serde_json::json!({ "blockIndex": 0, "chunks": 1 })),
     };
 
     OperationsSchema(&mut storage)
@@ -75,6 +80,7 @@ async fn executed_operations(mut storage: StorageProcessor<'_>) -> QueryResult<(
     assert_eq!(stored_operation.to_account, executed_tx.to_account);
     assert_eq!(stored_operation.success, executed_tx.success);
     assert_eq!(stored_operation.fail_reason, executed_tx.fail_reason);
+    assert_eq!(stored_operation.fail_code, executed_tx.fail_code);
     assert_eq!(stored_operation.block_index, executed_tx.block_index);
     assert_eq!(stored_operation.nonce, executed_tx.nonce);
     assert_eq!(
@@ -82,6 +88,7 @@ async fn executed_operations(mut storage: StorageProcessor<'_>) -> QueryResult<(
         executed_tx.primary_account_address
     );
     assert_eq!(stored_operation.batch_id, executed_tx.batch_id);
+    assert_eq!(stored_operation.trace, executed_tx.trace);
 
     Ok(())
 }
@@ -125,6 +132,62 @@ async fn executed_priority_operations(mut storage: StorageProcessor<'_>) -> Quer
     Ok(())
 }
 
+/// Checks that the executed priority operations can be loaded for the account.
+#[db_test]
+async fn account_executed_priority_operations(
+    mut storage: StorageProcessor<'_>,
+) -> QueryResult<()> {
+    let address = Address::repeat_byte(0x11);
+    let another_address = Address::repeat_byte(0x22);
+
+    for serial_id in 0..4 {
+        // Every second operation is sent to another account.
+        let to_account = if serial_id % 2 == 0 {
+            address
+        } else {
+            another_address
+        };
+
+        OperationsSchema(&mut storage)
+            .store_executed_priority_op(NewExecutedPriorityOperation {
+                block_number: 1,
+                block_index: serial_id as i32,
+                operation: Default::default(),
+                from_account: another_address.as_bytes().to_vec(),
+                to_account: to_account.as_bytes().to_vec(),
+                priority_op_serialid: serial_id,
+                deadline_block: 100,
+                eth_hash: vec![0xDE, 0xAD, 0xBE, serial_id as u8],
+                eth_block: 10,
+                created_at: chrono::Utc::now(),
+            })
+            .await?;
+    }
+
+    let serial_ids = |ops: Vec<StoredExecutedPriorityOperation>| {
+        ops.into_iter()
+            .map(|op| op.priority_op_serialid)
+            .collect::<Vec<_>>()
+    };
+
+    let ops = OperationsSchema(&mut storage)
+        .get_account_executed_priority_operations(&address, None, 10)
+        .await?;
+    assert_eq!(serial_ids(ops), vec![2, 0]);
+
+    let ops = OperationsSchema(&mut storage)
+        .get_account_executed_priority_operations(&another_address, None, 3)
+        .await?;
+    assert_eq!(serial_ids(ops), vec![3, 2, 1]);
+
+    let ops = OperationsSchema(&mut storage)
+        .get_account_executed_priority_operations(&another_address, Some(2), 10)
+        .await?;
+    assert_eq!(serial_ids(ops), vec![1, 0]);
+
+    Ok(())
+}
+
 /// Checks that attempt to save the duplicate txs is ignored by the DB.
 #[db_test]
 async fn duplicated_operations(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
@@ -139,12 +202,14 @@ async fn duplicated_operations(mut storage: StorageProcessor<'_>) -> QueryResult
         to_account: None,
         success: true,
         fail_reason: None,
+        fail_code: None,
         block_index: None,
         primary_account_address: Default::default(),
         nonce: Default::default(),
         created_at: chrono::Utc::now(),
         eth_sign_data: None,
         batch_id: None,
+        trace: None,
     };
 
     let executed_priority_op = NewExecutedPriorityOperation {
@@ -207,13 +272,15 @@ async fn transaction_resent(mut storage: StorageProcessor<'_>) -> QueryResult<()
         from_account: Default::default(),
         to_account: None,
         success: false, // <- Note that success is false. We'll replace this tx with succeeded one.
-        fail_reason: None,
+        fail_reason: Some(TxExecutionError::InsufficientBalance.to_string()),
+        fail_code: Some(TxExecutionError::InsufficientBalance.code()),
         block_index: None,
         primary_account_address: Default::default(),
         nonce: Default::default(),
         created_at: chrono::Utc::now(),
         eth_sign_data: None,
         batch_id: None,
+        trace: None,
     };
 
     // Save the failed operation.
@@ -221,14 +288,20 @@ async fn transaction_resent(mut storage: StorageProcessor<'_>) -> QueryResult<()
         .store_executed_tx(executed_tx.clone())
         .await?;
 
-    // Check that we can still load it.
-    assert!(OperationsSchema(&mut storage)
+    // Check that we can still load it along with the failure code.
+    let loaded_tx = OperationsSchema(&mut storage)
         .get_executed_operation(executed_tx.tx_hash.as_ref())
         .await?
-        .is_some());
+        .unwrap();
+    assert_eq!(
+        loaded_tx.fail_code.and_then(TxExecutionError::from_code),
+        Some(TxExecutionError::InsufficientBalance)
+    );
 
     // Replace failed tx with a successfull one.
     executed_tx.success = true;
+    executed_tx.fail_reason = None;
+    executed_tx.fail_code = None;
 
     OperationsSchema(&mut storage)
         .store_executed_tx(executed_tx.clone())
@@ -241,6 +314,7 @@ async fn transaction_resent(mut storage: StorageProcessor<'_>) -> QueryResult<()
         .unwrap();
     assert_eq!(loaded_tx.tx_hash, executed_tx.tx_hash);
     assert_eq!(loaded_tx.success, true);
+    assert_eq!(loaded_tx.fail_code, None);
 
     // Get the block transactions and check if there is exactly 1 tx (failed tx not copied but replaced).
     let block_txs = BlockSchema(&mut storage)
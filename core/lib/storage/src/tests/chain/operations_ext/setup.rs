@@ -88,6 +88,7 @@ impl TransactionsHistoryTestSetup {
             100,
             1_000_000.into(), // Not important
             1_500_000.into(), // Not important
+            0,                // Not important
         );
 
         self.blocks.push(block);
@@ -179,9 +180,11 @@ impl TransactionsHistoryTestSetup {
             success: true,
             op: Some(transfer_to_new_op),
             fail_reason: None,
+            fail_code: None,
             block_index,
             created_at: self.get_tx_time(),
             batch_id: None,
+            trace: None,
         };
 
         ExecutedOperations::Tx(Box::new(executed_transfer_to_new_op))
@@ -210,9 +213,11 @@ impl TransactionsHistoryTestSetup {
             success: true,
             op: Some(transfer_op),
             fail_reason: None,
+            fail_code: None,
             block_index,
             created_at: self.get_tx_time(),
             batch_id: None,
+            trace: None,
         };
 
         ExecutedOperations::Tx(Box::new(executed_transfer_op))
@@ -240,9 +245,11 @@ impl TransactionsHistoryTestSetup {
             success: true,
             op: Some(withdraw_op),
             fail_reason: None,
+            fail_code: None,
             block_index,
             created_at: self.get_tx_time(),
             batch_id: None,
+            trace: None,
         };
 
         ExecutedOperations::Tx(Box::new(executed_withdraw_op))
@@ -259,9 +266,11 @@ impl TransactionsHistoryTestSetup {
             success: true,
             op: Some(close_op),
             fail_reason: None,
+            fail_code: None,
             block_index,
             created_at: self.get_tx_time(),
             batch_id: None,
+            trace: None,
         };
 
         ExecutedOperations::Tx(Box::new(executed_close_op))
@@ -284,9 +293,11 @@ impl TransactionsHistoryTestSetup {
             success: true,
             op: Some(change_pubkey_op),
             fail_reason: None,
+            fail_code: None,
             block_index,
             created_at: self.get_tx_time(),
             batch_id: None,
+            trace: None,
         };
 
         ExecutedOperations::Tx(Box::new(executed_change_pubkey_op))
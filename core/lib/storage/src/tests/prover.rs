@@ -1,6 +1,7 @@
 /// Std imports
 use std::time::Duration;
 // External imports
+use chrono::Utc;
 // Workspace imports
 use zksync_config::ConfigurationOptions;
 use zksync_crypto::proof::EncodedProofPlonk;
@@ -297,6 +298,7 @@ async fn unstarted_prover_jobs_count(mut storage: StorageProcessor<'_>) -> Query
             pending_block_iteration: 1,
             success_operations: vec![],
             failed_txs: Vec::new(),
+            timestamp: None,
         })
         .await?;
     let blocks_count = ProverSchema(&mut storage).unstarted_jobs_count().await?;
@@ -304,3 +306,56 @@ async fn unstarted_prover_jobs_count(mut storage: StorageProcessor<'_>) -> Query
 
     Ok(())
 }
+
+/// Checks that `proving_time_stats` method of schema takes into account
+/// only the proven blocks, grouping them by the block size.
+#[db_test]
+async fn proving_time_stats(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    let prover_name = "prover_10";
+    let block_sizes = ConfigurationOptions::from_env().available_block_chunk_sizes;
+    let (small_block, big_block) = (block_sizes[0], block_sizes[block_sizes.len() - 1]);
+    let since = Utc::now() - chrono::Duration::hours(1);
+
+    // Initially there are no proven blocks.
+    let stats = ProverSchema(&mut storage).proving_time_stats(since).await?;
+    assert!(stats.is_empty());
+
+    // Create blocks of both sizes and prove all of them except the last one.
+    for (block_number, block_size) in [
+        (1, small_block),
+        (2, small_block),
+        (3, big_block),
+        (4, big_block),
+    ]
+    .iter()
+    .copied()
+    {
+        BlockSchema(&mut storage)
+            .execute_operation(get_operation(block_number, Action::Commit, block_size))
+            .await?;
+        ProverSchema(&mut storage)
+            .prover_run_for_next_commit(prover_name, Duration::from_secs(1), block_size)
+            .await?;
+        if block_number != 4 {
+            ProverSchema(&mut storage)
+                .store_proof(block_number, &EncodedProofPlonk::default())
+                .await?;
+        }
+    }
+
+    let mut stats = ProverSchema(&mut storage).proving_time_stats(since).await?;
+    stats.sort_by_key(|stats| stats.block_size);
+    let jobs: Vec<_> = stats
+        .iter()
+        .map(|stats| (stats.block_size, stats.jobs_count))
+        .collect();
+    assert_eq!(jobs, vec![(small_block, 2), (big_block, 1)]);
+
+    // Proofs stored before the given moment are not taken into account.
+    let stats = ProverSchema(&mut storage)
+        .proving_time_stats(Utc::now() + chrono::Duration::hours(1))
+        .await?;
+    assert!(stats.is_empty());
+
+    Ok(())
+}
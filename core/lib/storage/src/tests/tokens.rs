@@ -1,7 +1,7 @@
 // External imports
 use num::{rational::Ratio, BigUint};
 // Workspace imports
-use zksync_types::{Token, TokenId, TokenLike, TokenPrice};
+use zksync_types::{FeeParams, Token, TokenId, TokenLike, TokenPrice};
 use zksync_utils::{big_decimal_to_ratio, ratio_to_big_decimal};
 // Local imports
 use crate::tests::db_test;
@@ -150,3 +150,49 @@ async fn test_ticker_price(mut storage: StorageProcessor<'_>) -> QueryResult<()>
 
     Ok(())
 }
+
+/// Checks that every change of the fee parameters creates a new version.
+#[db_test]
+async fn test_fee_params_versions(mut storage: StorageProcessor<'_>) -> QueryResult<()> {
+    // Parameters are not set by default.
+    assert!(storage.tokens_schema().load_fee_params().await?.is_none());
+
+    let first_params = FeeParams {
+        zkp_cost_chunk_usd: Ratio::new(BigUint::from(1u32), BigUint::from(1000u32)),
+        tokens_risk_factors: vec![(1, Ratio::new(BigUint::from(5u32), BigUint::from(2u32)))]
+            .into_iter()
+            .collect(),
+    };
+    let second_params = FeeParams {
+        zkp_cost_chunk_usd: Ratio::new(BigUint::from(1u32), BigUint::from(500u32)),
+        tokens_risk_factors: Default::default(),
+    };
+
+    let first = storage
+        .tokens_schema()
+        .store_fee_params(&first_params)
+        .await?;
+    let second = storage
+        .tokens_schema()
+        .store_fee_params(&second_params)
+        .await?;
+    assert!(second.version > first.version);
+    assert_eq!(first.params, first_params);
+    assert_eq!(second.params, second_params);
+
+    // The latest version is used.
+    let loaded = storage
+        .tokens_schema()
+        .load_fee_params()
+        .await?
+        .expect("couldn't load fee params");
+    assert_eq!(loaded, second);
+
+    // History is returned starting from the newest version.
+    let history = storage.tokens_schema().load_fee_params_history(10).await?;
+    assert_eq!(history, vec![second.clone(), first]);
+    let history = storage.tokens_schema().load_fee_params_history(1).await?;
+    assert_eq!(history, vec![second]);
+
+    Ok(())
+}
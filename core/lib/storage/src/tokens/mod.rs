@@ -3,10 +3,10 @@ use std::collections::HashMap;
 use std::time::Instant;
 // External imports
 // Workspace imports
-use zksync_types::{Token, TokenId, TokenLike, TokenPrice};
+use zksync_types::{FeeParams, FeeParamsVersion, Token, TokenId, TokenLike, TokenPrice};
 use zksync_utils::ratio_to_big_decimal;
 // Local imports
-use self::records::{DbTickerPrice, DbToken};
+use self::records::{DbFeeParams, DbTickerPrice, DbToken};
 use crate::tokens::utils::address_to_stored_string;
 use crate::{QueryResult, StorageProcessor};
 
@@ -183,4 +183,63 @@ impl<'a, 'c> TokensSchema<'a, 'c> {
         metrics::histogram!("sql.token.update_historical_ticker_price", start.elapsed());
         Ok(())
     }
+
+    /// Stores the new version of the fee parameters and returns it.
+    pub async fn store_fee_params(&mut self, params: &FeeParams) -> QueryResult<FeeParamsVersion> {
+        let start = Instant::now();
+        let db_params = sqlx::query_as!(
+            DbFeeParams,
+            r#"
+            INSERT INTO fee_params ( params )
+            VALUES ( $1 )
+            RETURNING *
+            "#,
+            serde_json::to_value(params)?
+        )
+        .fetch_one(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.store_fee_params", start.elapsed());
+        Ok(db_params.into())
+    }
+
+    /// Loads the latest version of the fee parameters, if they were ever set.
+    pub async fn load_fee_params(&mut self) -> QueryResult<Option<FeeParamsVersion>> {
+        let start = Instant::now();
+        let db_params = sqlx::query_as!(
+            DbFeeParams,
+            r#"
+            SELECT * FROM fee_params
+            ORDER BY version DESC
+            LIMIT 1
+            "#,
+        )
+        .fetch_optional(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.load_fee_params", start.elapsed());
+        Ok(db_params.map(|params| params.into()))
+    }
+
+    /// Loads up to `limit` latest versions of the fee parameters, starting from the newest one.
+    pub async fn load_fee_params_history(
+        &mut self,
+        limit: u32,
+    ) -> QueryResult<Vec<FeeParamsVersion>> {
+        let start = Instant::now();
+        let db_params = sqlx::query_as!(
+            DbFeeParams,
+            r#"
+            SELECT * FROM fee_params
+            ORDER BY version DESC
+            LIMIT $1
+            "#,
+            i64::from(limit)
+        )
+        .fetch_all(self.0.conn())
+        .await?;
+
+        metrics::histogram!("sql.token.load_fee_params_history", start.elapsed());
+        Ok(db_params.into_iter().map(|params| params.into()).collect())
+    }
 }
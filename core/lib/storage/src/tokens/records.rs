@@ -5,7 +5,7 @@ use sqlx::{types::BigDecimal, FromRow};
 // Local imports
 use crate::tokens::utils::{address_to_stored_string, stored_str_address_to_address};
 use chrono::{DateTime, Utc};
-use zksync_types::tokens::TokenPrice;
+use zksync_types::tokens::{FeeParamsVersion, TokenPrice};
 use zksync_types::{Token, TokenId};
 use zksync_utils::big_decimal_to_ratio;
 
@@ -54,3 +54,20 @@ impl Into<TokenPrice> for DbTickerPrice {
         }
     }
 }
+
+#[derive(Debug, Clone, FromRow)]
+pub struct DbFeeParams {
+    pub version: i64,
+    pub params: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Into<FeeParamsVersion> for DbFeeParams {
+    fn into(self) -> FeeParamsVersion {
+        FeeParamsVersion {
+            version: self.version,
+            params: serde_json::from_value(self.params).expect("Unparsable FeeParams in db"),
+            created_at: self.created_at,
+        }
+    }
+}
@@ -3,10 +3,14 @@
 use super::PriorityOp;
 use super::ZkSyncOp;
 use super::{AccountId, BlockNumber, Fr};
-use crate::SignedZkSyncTx;
+use crate::{
+    tx::{TxExecutionError, TxExecutionTrace},
+    SignedZkSyncTx,
+};
 use chrono::DateTime;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
 use zksync_basic_types::{H256, U256};
 use zksync_crypto::franklin_crypto::bellman::pairing::ff::{PrimeField, PrimeFieldRepr};
 use zksync_crypto::params::CHUNK_BIT_WIDTH;
@@ -32,6 +36,9 @@ pub struct PendingBlock {
     pub success_operations: Vec<ExecutedOperations>,
     /// Lit of failed operations.
     pub failed_txs: Vec<ExecutedTx>,
+    /// Unix timestamp (in seconds) of the block. It's not set until the first
+    /// operation is included into the block.
+    pub timestamp: Option<u64>,
 }
 
 /// Executed L2 transaction.
@@ -41,9 +48,15 @@ pub struct ExecutedTx {
     pub success: bool,
     pub op: Option<ZkSyncOp>,
     pub fail_reason: Option<String>,
+    /// Machine-readable reason of the failure.
+    #[serde(default)]
+    pub fail_code: Option<TxExecutionError>,
     pub block_index: Option<u32>,
     pub created_at: DateTime<Utc>,
     pub batch_id: Option<i64>,
+    /// Execution details of the successful transaction.
+    #[serde(default)]
+    pub trace: Option<TxExecutionTrace>,
 }
 
 /// Executed L1 priority operation.
@@ -103,6 +116,73 @@ impl ExecutedOperations {
     }
 }
 
+/// Criterion which caused the block to be sealed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BlockSealReason {
+    /// The block has no space for the next operation or has reached the configured fill level.
+    ChunksFill,
+    /// The next operation would make the commit or verify Ethereum transaction too expensive.
+    GasLimit,
+    /// The oldest operation of the block has been waiting for too long.
+    BlockAge,
+    /// The block has reached the limit of the withdrawal operations.
+    WithdrawalsLimit,
+    /// The block has reached the maximum number of miniblock iterations.
+    MiniblockIterations,
+    /// The block was sealed on an explicit request.
+    Forced,
+}
+
+impl BlockSealReason {
+    /// Returns the string representation of the reason, which is used in the database and metrics.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ChunksFill => "chunks_fill",
+            Self::GasLimit => "gas_limit",
+            Self::BlockAge => "block_age",
+            Self::WithdrawalsLimit => "withdrawals_limit",
+            Self::MiniblockIterations => "miniblock_iterations",
+            Self::Forced => "forced",
+        }
+    }
+}
+
+impl fmt::Display for BlockSealReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for BlockSealReason {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let reason = match s {
+            "chunks_fill" => Self::ChunksFill,
+            "gas_limit" => Self::GasLimit,
+            "block_age" => Self::BlockAge,
+            "withdrawals_limit" => Self::WithdrawalsLimit,
+            "miniblock_iterations" => Self::MiniblockIterations,
+            "forced" => Self::Forced,
+            _ => anyhow::bail!("Unknown block seal reason: {}", s),
+        };
+
+        Ok(reason)
+    }
+}
+
+/// Mode of the block production.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BlockProductionMode {
+    /// Transactions from the mempool are being included into the new blocks.
+    Active,
+    /// Block proposer doesn't propose new blocks. Transactions are still accepted by the mempool,
+    /// but aren't executed until the block production is resumed.
+    Paused,
+}
+
 /// zkSync network block.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Block {
@@ -126,6 +206,14 @@ pub struct Block {
     pub commit_gas_limit: U256,
     /// Gas limit to be set for the Verify Ethereum transaction.
     pub verify_gas_limit: U256,
+    /// Criterion which caused the block to be sealed. Not known for the blocks
+    /// that were not created by the state keeper (e.g. restored ones).
+    #[serde(default)]
+    pub seal_reason: Option<BlockSealReason>,
+    /// Unix timestamp (in seconds) of the block. Transactions of the block must be valid
+    /// at this moment, and the smart contract checks it against the Ethereum block timestamp.
+    #[serde(default)]
+    pub timestamp: u64,
 }
 
 impl Block {
@@ -140,6 +228,7 @@ impl Block {
         block_chunks_size: usize,
         commit_gas_limit: U256,
         verify_gas_limit: U256,
+        timestamp: u64,
     ) -> Self {
         Self {
             block_number,
@@ -150,6 +239,8 @@ impl Block {
             block_chunks_size,
             commit_gas_limit,
             verify_gas_limit,
+            seal_reason: None,
+            timestamp,
         }
     }
 
@@ -169,6 +260,7 @@ impl Block {
         available_block_chunks_sizes: &[usize],
         commit_gas_limit: U256,
         verify_gas_limit: U256,
+        timestamp: u64,
     ) -> Self {
         let mut block = Self {
             block_number,
@@ -179,6 +271,8 @@ impl Block {
             block_chunks_size: 0,
             commit_gas_limit,
             verify_gas_limit,
+            seal_reason: None,
+            timestamp,
         };
         block.block_chunks_size = block.smallest_block_size(available_block_chunks_sizes);
         block
@@ -194,6 +288,15 @@ impl Block {
         H256::from(be_bytes)
     }
 
+    /// Returns the block info for the Ethereum Commit operation: the new state root hash
+    /// followed by the block timestamp.
+    pub fn get_eth_new_block_info(&self) -> Vec<H256> {
+        vec![
+            self.get_eth_encoded_root(),
+            H256::from_low_u64_be(self.timestamp),
+        ]
+    }
+
     /// Returns the public data for the Ethereum Commit operation.
     pub fn get_eth_public_data(&self) -> Vec<u8> {
         let mut executed_tx_pub_data = self
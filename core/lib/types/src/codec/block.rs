@@ -0,0 +1,538 @@
+//! Encoding of the operations and blocks.
+
+// Local uses
+use super::{BinaryCodec, CodecError, Decoder, Encoder};
+use crate::{
+    block::{Block, BlockSealReason, ExecutedOperations, ExecutedPriorityOp, ExecutedTx},
+    operations::NoopOp,
+    tx::{TracedFee, TxExecutionError, TxExecutionTrace},
+    AccountUpdate, ChangePubKeyOp, CloseOp, Deposit, DepositOp, ForcedExitOp, FullExit, FullExitOp,
+    PriorityOp, SwapOp, TransferOp, TransferToNewOp, WithdrawOp, ZkSyncOp, ZkSyncPriorityOp,
+};
+
+impl BinaryCodec for Deposit {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.from);
+        encoder.put(&self.token);
+        encoder.put(&self.amount);
+        encoder.put(&self.to);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(Deposit {
+            from: decoder.get()?,
+            token: decoder.get()?,
+            amount: decoder.get()?,
+            to: decoder.get()?,
+        })
+    }
+}
+
+impl BinaryCodec for FullExit {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.account_id);
+        encoder.put(&self.eth_address);
+        encoder.put(&self.token);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(FullExit {
+            account_id: decoder.get()?,
+            eth_address: decoder.get()?,
+            token: decoder.get()?,
+        })
+    }
+}
+
+impl BinaryCodec for ZkSyncPriorityOp {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            ZkSyncPriorityOp::Deposit(op) => {
+                encoder.put(&DepositOp::OP_CODE);
+                encoder.put(op);
+            }
+            ZkSyncPriorityOp::FullExit(op) => {
+                encoder.put(&FullExitOp::OP_CODE);
+                encoder.put(op);
+            }
+        }
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        match decoder.get()? {
+            DepositOp::OP_CODE => Ok(ZkSyncPriorityOp::Deposit(decoder.get()?)),
+            FullExitOp::OP_CODE => Ok(ZkSyncPriorityOp::FullExit(decoder.get()?)),
+            tag => Err(CodecError::UnknownTag {
+                type_name: "ZkSyncPriorityOp",
+                tag,
+            }),
+        }
+    }
+}
+
+impl BinaryCodec for PriorityOp {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.serial_id);
+        encoder.put(&self.data);
+        encoder.put(&self.deadline_block);
+        encoder.put_var_bytes(&self.eth_hash);
+        encoder.put(&self.eth_block);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(PriorityOp {
+            serial_id: decoder.get()?,
+            data: decoder.get()?,
+            deadline_block: decoder.get()?,
+            eth_hash: decoder.get_var_bytes()?.to_vec(),
+            eth_block: decoder.get()?,
+        })
+    }
+}
+
+impl BinaryCodec for TransferOp {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.tx);
+        encoder.put(&self.from);
+        encoder.put(&self.to);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(TransferOp {
+            tx: decoder.get()?,
+            from: decoder.get()?,
+            to: decoder.get()?,
+        })
+    }
+}
+
+impl BinaryCodec for TransferToNewOp {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.tx);
+        encoder.put(&self.from);
+        encoder.put(&self.to);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(TransferToNewOp {
+            tx: decoder.get()?,
+            from: decoder.get()?,
+            to: decoder.get()?,
+        })
+    }
+}
+
+impl BinaryCodec for WithdrawOp {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.tx);
+        encoder.put(&self.account_id);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(WithdrawOp {
+            tx: decoder.get()?,
+            account_id: decoder.get()?,
+        })
+    }
+}
+
+impl BinaryCodec for CloseOp {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.tx);
+        encoder.put(&self.account_id);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(CloseOp {
+            tx: decoder.get()?,
+            account_id: decoder.get()?,
+        })
+    }
+}
+
+impl BinaryCodec for ChangePubKeyOp {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.tx);
+        encoder.put(&self.account_id);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(ChangePubKeyOp {
+            tx: decoder.get()?,
+            account_id: decoder.get()?,
+        })
+    }
+}
+
+impl BinaryCodec for DepositOp {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.priority_op);
+        encoder.put(&self.account_id);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(DepositOp {
+            priority_op: decoder.get()?,
+            account_id: decoder.get()?,
+        })
+    }
+}
+
+impl BinaryCodec for FullExitOp {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.priority_op);
+        encoder.put(&self.withdraw_amount);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(FullExitOp {
+            priority_op: decoder.get()?,
+            withdraw_amount: decoder.get()?,
+        })
+    }
+}
+
+impl BinaryCodec for ForcedExitOp {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.tx);
+        encoder.put(&self.target_account_id);
+        encoder.put(&self.withdraw_amount);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(ForcedExitOp {
+            tx: decoder.get()?,
+            target_account_id: decoder.get()?,
+            withdraw_amount: decoder.get()?,
+        })
+    }
+}
+
+impl BinaryCodec for SwapOp {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.tx);
+        encoder.put(&self.submitter);
+        encoder.put(&self.accounts);
+        encoder.put(&self.recipients);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(SwapOp {
+            tx: decoder.get()?,
+            submitter: decoder.get()?,
+            accounts: decoder.get()?,
+            recipients: decoder.get()?,
+        })
+    }
+}
+
+impl BinaryCodec for ZkSyncOp {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            ZkSyncOp::Deposit(op) => {
+                encoder.put(&DepositOp::OP_CODE);
+                encoder.put(op);
+            }
+            ZkSyncOp::Transfer(op) => {
+                encoder.put(&TransferOp::OP_CODE);
+                encoder.put(op);
+            }
+            ZkSyncOp::TransferToNew(op) => {
+                encoder.put(&TransferToNewOp::OP_CODE);
+                encoder.put(op);
+            }
+            ZkSyncOp::Withdraw(op) => {
+                encoder.put(&WithdrawOp::OP_CODE);
+                encoder.put(op);
+            }
+            ZkSyncOp::Close(op) => {
+                encoder.put(&CloseOp::OP_CODE);
+                encoder.put(op);
+            }
+            ZkSyncOp::FullExit(op) => {
+                encoder.put(&FullExitOp::OP_CODE);
+                encoder.put(op);
+            }
+            ZkSyncOp::ChangePubKeyOffchain(op) => {
+                encoder.put(&ChangePubKeyOp::OP_CODE);
+                encoder.put(op);
+            }
+            ZkSyncOp::ForcedExit(op) => {
+                encoder.put(&ForcedExitOp::OP_CODE);
+                encoder.put(op);
+            }
+            ZkSyncOp::Swap(op) => {
+                encoder.put(&SwapOp::OP_CODE);
+                encoder.put(op);
+            }
+            ZkSyncOp::Noop(_) => {
+                encoder.put(&NoopOp::OP_CODE);
+            }
+        }
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        match decoder.get()? {
+            DepositOp::OP_CODE => Ok(ZkSyncOp::Deposit(decoder.get()?)),
+            TransferOp::OP_CODE => Ok(ZkSyncOp::Transfer(decoder.get()?)),
+            TransferToNewOp::OP_CODE => Ok(ZkSyncOp::TransferToNew(decoder.get()?)),
+            WithdrawOp::OP_CODE => Ok(ZkSyncOp::Withdraw(decoder.get()?)),
+            CloseOp::OP_CODE => Ok(ZkSyncOp::Close(decoder.get()?)),
+            FullExitOp::OP_CODE => Ok(ZkSyncOp::FullExit(decoder.get()?)),
+            ChangePubKeyOp::OP_CODE => Ok(ZkSyncOp::ChangePubKeyOffchain(decoder.get()?)),
+            ForcedExitOp::OP_CODE => Ok(ZkSyncOp::ForcedExit(decoder.get()?)),
+            SwapOp::OP_CODE => Ok(ZkSyncOp::Swap(decoder.get()?)),
+            NoopOp::OP_CODE => Ok(ZkSyncOp::Noop(NoopOp {})),
+            tag => Err(CodecError::UnknownTag {
+                type_name: "ZkSyncOp",
+                tag,
+            }),
+        }
+    }
+}
+
+impl BinaryCodec for TxExecutionError {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&(self.code() as u8));
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        let tag: u8 = decoder.get()?;
+        TxExecutionError::from_code(tag.into()).ok_or(CodecError::UnknownTag {
+            type_name: "TxExecutionError",
+            tag,
+        })
+    }
+}
+
+impl BinaryCodec for AccountUpdate {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            AccountUpdate::Create { address, nonce } => {
+                encoder.put(&0u8);
+                encoder.put(address);
+                encoder.put(nonce);
+            }
+            AccountUpdate::Delete { address, nonce } => {
+                encoder.put(&1u8);
+                encoder.put(address);
+                encoder.put(nonce);
+            }
+            AccountUpdate::UpdateBalance {
+                old_nonce,
+                new_nonce,
+                balance_update: (token, old_balance, new_balance),
+            } => {
+                encoder.put(&2u8);
+                encoder.put(old_nonce);
+                encoder.put(new_nonce);
+                encoder.put(token);
+                encoder.put(old_balance);
+                encoder.put(new_balance);
+            }
+            AccountUpdate::ChangePubKeyHash {
+                old_pub_key_hash,
+                new_pub_key_hash,
+                old_nonce,
+                new_nonce,
+            } => {
+                encoder.put(&3u8);
+                encoder.put(old_pub_key_hash);
+                encoder.put(new_pub_key_hash);
+                encoder.put(old_nonce);
+                encoder.put(new_nonce);
+            }
+        }
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        match decoder.get()? {
+            0u8 => Ok(AccountUpdate::Create {
+                address: decoder.get()?,
+                nonce: decoder.get()?,
+            }),
+            1 => Ok(AccountUpdate::Delete {
+                address: decoder.get()?,
+                nonce: decoder.get()?,
+            }),
+            2 => Ok(AccountUpdate::UpdateBalance {
+                old_nonce: decoder.get()?,
+                new_nonce: decoder.get()?,
+                balance_update: (decoder.get()?, decoder.get()?, decoder.get()?),
+            }),
+            3 => Ok(AccountUpdate::ChangePubKeyHash {
+                old_pub_key_hash: decoder.get()?,
+                new_pub_key_hash: decoder.get()?,
+                old_nonce: decoder.get()?,
+                new_nonce: decoder.get()?,
+            }),
+            tag => Err(CodecError::UnknownTag {
+                type_name: "AccountUpdate",
+                tag,
+            }),
+        }
+    }
+}
+
+impl BinaryCodec for TracedFee {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.token);
+        encoder.put(&self.amount);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(TracedFee {
+            token: decoder.get()?,
+            amount: decoder.get()?,
+        })
+    }
+}
+
+impl BinaryCodec for TxExecutionTrace {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.block_index);
+        encoder.put(&self.chunks);
+        encoder.put(&self.fee);
+        encoder.put(&self.updates);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(TxExecutionTrace {
+            block_index: decoder.get()?,
+            chunks: decoder.get()?,
+            fee: decoder.get()?,
+            updates: decoder.get()?,
+        })
+    }
+}
+
+impl BinaryCodec for ExecutedTx {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.signed_tx);
+        encoder.put(&self.success);
+        encoder.put(&self.op);
+        encoder.put(&self.fail_reason);
+        encoder.put(&self.fail_code);
+        encoder.put(&self.block_index);
+        encoder.put(&self.created_at);
+        encoder.put(&self.batch_id);
+        encoder.put(&self.trace);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(ExecutedTx {
+            signed_tx: decoder.get()?,
+            success: decoder.get()?,
+            op: decoder.get()?,
+            fail_reason: decoder.get()?,
+            fail_code: decoder.get()?,
+            block_index: decoder.get()?,
+            created_at: decoder.get()?,
+            batch_id: decoder.get()?,
+            trace: decoder.get()?,
+        })
+    }
+}
+
+impl BinaryCodec for ExecutedPriorityOp {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.priority_op);
+        encoder.put(&self.op);
+        encoder.put(&self.block_index);
+        encoder.put(&self.created_at);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(ExecutedPriorityOp {
+            priority_op: decoder.get()?,
+            op: decoder.get()?,
+            block_index: decoder.get()?,
+            created_at: decoder.get()?,
+        })
+    }
+}
+
+impl BinaryCodec for ExecutedOperations {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            ExecutedOperations::Tx(tx) => {
+                encoder.put(&0u8);
+                encoder.put(tx);
+            }
+            ExecutedOperations::PriorityOp(op) => {
+                encoder.put(&1u8);
+                encoder.put(op);
+            }
+        }
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        match decoder.get()? {
+            0u8 => Ok(ExecutedOperations::Tx(decoder.get()?)),
+            1 => Ok(ExecutedOperations::PriorityOp(decoder.get()?)),
+            tag => Err(CodecError::UnknownTag {
+                type_name: "ExecutedOperations",
+                tag,
+            }),
+        }
+    }
+}
+
+impl BinaryCodec for BlockSealReason {
+    fn encode(&self, encoder: &mut Encoder) {
+        let tag: u8 = match self {
+            BlockSealReason::ChunksFill => 0,
+            BlockSealReason::GasLimit => 1,
+            BlockSealReason::BlockAge => 2,
+            BlockSealReason::WithdrawalsLimit => 3,
+            BlockSealReason::MiniblockIterations => 4,
+            BlockSealReason::Forced => 5,
+        };
+        encoder.put(&tag);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        match decoder.get()? {
+            0u8 => Ok(BlockSealReason::ChunksFill),
+            1 => Ok(BlockSealReason::GasLimit),
+            2 => Ok(BlockSealReason::BlockAge),
+            3 => Ok(BlockSealReason::WithdrawalsLimit),
+            4 => Ok(BlockSealReason::MiniblockIterations),
+            5 => Ok(BlockSealReason::Forced),
+            tag => Err(CodecError::UnknownTag {
+                type_name: "BlockSealReason",
+                tag,
+            }),
+        }
+    }
+}
+
+impl BinaryCodec for Block {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.block_number);
+        encoder.put(&self.new_root_hash);
+        encoder.put(&self.fee_account);
+        encoder.put(&self.block_transactions);
+        encoder.put(&self.processed_priority_ops);
+        encoder.put(&self.block_chunks_size);
+        encoder.put(&self.commit_gas_limit);
+        encoder.put(&self.verify_gas_limit);
+        encoder.put(&self.seal_reason);
+        encoder.put(&self.timestamp);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(Block {
+            block_number: decoder.get()?,
+            new_root_hash: decoder.get()?,
+            fee_account: decoder.get()?,
+            block_transactions: decoder.get()?,
+            processed_priority_ops: decoder.get()?,
+            block_chunks_size: decoder.get()?,
+            commit_gas_limit: decoder.get()?,
+            verify_gas_limit: decoder.get()?,
+            seal_reason: decoder.get()?,
+            timestamp: decoder.get()?,
+        })
+    }
+}
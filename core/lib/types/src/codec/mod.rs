@@ -0,0 +1,211 @@
+//! Canonical compact binary encoding of the zkSync types.
+//!
+//! JSON representation of transactions and blocks is convenient for the public API, but
+//! it's slow and bulky for the internal communication and storage: addresses and signatures
+//! become hex strings, and amounts become decimal strings. This module provides an
+//! alternative binary codec for the types passed between the zkSync components.
+//!
+//! Encoded value is prefixed with the codec version byte (see `CODEC_VERSION`), so the
+//! format can be changed without breaking the already stored data.
+//!
+//! Encoding rules:
+//!
+//! - fixed-width integers are encoded in the big-endian byte order;
+//! - lengths of the variable-size items (`usize` values included) are encoded as
+//!   the unsigned LEB128 numbers;
+//! - `BigUint` is encoded as the length-prefixed big-endian bytes without leading zeroes;
+//! - `bool` is a single `0` or `1` byte, `Option` is a `bool` flag followed by the value;
+//! - enums are encoded as the one-byte tag followed by the variant contents, where the
+//!   transactions and the operations use their protocol type codes as tags;
+//! - structures are encoded as the sequence of their fields.
+//!
+//! Every value has exactly one encoding: decoder rejects non-minimal lengths, numbers
+//! with leading zeroes, unknown tags and trailing bytes.
+
+// Built-in deps
+use std::convert::TryInto;
+// External uses
+use thiserror::Error;
+
+mod block;
+mod primitives;
+mod tx;
+
+#[cfg(test)]
+mod tests;
+
+/// Version of the format produced by `encode`.
+pub const CODEC_VERSION: u8 = 1;
+
+/// HTTP content type of the binary encoded values.
+pub const CONTENT_TYPE: &str = "application/x-zksync-binary";
+
+#[derive(Debug, Error, PartialEq)]
+pub enum CodecError {
+    #[error("Encoding version {0} is not supported")]
+    UnsupportedVersion(u8),
+
+    #[error("Unexpected end of input")]
+    UnexpectedEnd,
+
+    #[error("{0} bytes left after the end of the encoded value")]
+    TrailingBytes(usize),
+
+    #[error("Unknown {type_name} tag: {tag}")]
+    UnknownTag { type_name: &'static str, tag: u8 },
+
+    #[error("Non-canonical encoding of {0}")]
+    NonCanonical(&'static str),
+
+    #[error("Invalid {type_name}: {reason}")]
+    InvalidValue {
+        type_name: &'static str,
+        reason: String,
+    },
+}
+
+impl CodecError {
+    pub(crate) fn invalid_value(type_name: &'static str, reason: impl ToString) -> Self {
+        Self::InvalidValue {
+            type_name,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// Type which can be encoded with the zkSync binary codec.
+pub trait BinaryCodec: Sized {
+    /// Appends the encoded value to the encoder output.
+    fn encode(&self, encoder: &mut Encoder);
+
+    /// Reads the value from the decoder input.
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError>;
+}
+
+/// Encodes the value with the current codec version.
+pub fn encode<T: BinaryCodec>(value: &T) -> Vec<u8> {
+    let mut encoder = Encoder::default();
+    encoder.put(&CODEC_VERSION);
+    encoder.put(value);
+    encoder.finish()
+}
+
+/// Decodes the value, checking the codec version and that the whole input was consumed.
+pub fn decode<T: BinaryCodec>(bytes: &[u8]) -> Result<T, CodecError> {
+    let mut decoder = Decoder::new(bytes);
+    let version: u8 = decoder.get()?;
+    if version != CODEC_VERSION {
+        return Err(CodecError::UnsupportedVersion(version));
+    }
+
+    let value = decoder.get()?;
+    decoder.finish()?;
+    Ok(value)
+}
+
+/// Output of the binary encoding.
+#[derive(Debug, Default)]
+pub struct Encoder {
+    bytes: Vec<u8>,
+}
+
+impl Encoder {
+    pub fn put<T: BinaryCodec>(&mut self, value: &T) {
+        value.encode(self);
+    }
+
+    pub fn put_bytes(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    /// Writes the length of the variable-size item.
+    pub fn put_len(&mut self, len: usize) {
+        let mut value = len as u64;
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                self.bytes.push(byte);
+                break;
+            }
+            self.bytes.push(byte | 0x80);
+        }
+    }
+
+    /// Writes the length-prefixed byte sequence.
+    pub fn put_var_bytes(&mut self, bytes: &[u8]) {
+        self.put_len(bytes.len());
+        self.put_bytes(bytes);
+    }
+
+    pub fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+/// Input of the binary decoding.
+#[derive(Debug)]
+pub struct Decoder<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    pub fn get<T: BinaryCodec>(&mut self) -> Result<T, CodecError> {
+        T::decode(self)
+    }
+
+    /// Returns the number of bytes left in the input.
+    pub fn remaining(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn get_bytes(&mut self, len: usize) -> Result<&'a [u8], CodecError> {
+        if self.bytes.len() < len {
+            return Err(CodecError::UnexpectedEnd);
+        }
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(bytes)
+    }
+
+    /// Reads the length of the variable-size item.
+    pub fn get_len(&mut self) -> Result<usize, CodecError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.get_bytes(1)?[0];
+            if shift == 63 && byte > 1 {
+                return Err(CodecError::NonCanonical("length"));
+            }
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                // The last byte may be zero only for the zero value itself.
+                if byte == 0 && shift > 0 {
+                    return Err(CodecError::NonCanonical("length"));
+                }
+                return value
+                    .try_into()
+                    .map_err(|_| CodecError::invalid_value("length", value));
+            }
+        }
+        Err(CodecError::NonCanonical("length"))
+    }
+
+    /// Reads the length-prefixed byte sequence.
+    pub fn get_var_bytes(&mut self) -> Result<&'a [u8], CodecError> {
+        let len = self.get_len()?;
+        self.get_bytes(len)
+    }
+
+    /// Checks that the whole input was consumed.
+    pub fn finish(self) -> Result<(), CodecError> {
+        if self.bytes.is_empty() {
+            Ok(())
+        } else {
+            Err(CodecError::TrailingBytes(self.bytes.len()))
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! Encoding of the primitive and generic types.
+
+// Built-in deps
+use std::convert::TryInto;
+// External uses
+use chrono::{DateTime, TimeZone, Utc};
+use num::BigUint;
+// Workspace uses
+use zksync_basic_types::{Address, H256, U256};
+use zksync_crypto::{convert::FeConvert, Fr};
+use zksync_utils::BigUintSerdeWrapper;
+// Local uses
+use super::{BinaryCodec, CodecError, Decoder, Encoder};
+
+macro_rules! impl_codec_for_int {
+    ($($int:ty),*) => {
+        $(
+            impl BinaryCodec for $int {
+                fn encode(&self, encoder: &mut Encoder) {
+                    encoder.put_bytes(&self.to_be_bytes());
+                }
+
+                fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+                    let bytes = decoder.get_bytes(std::mem::size_of::<$int>())?;
+                    Ok(<$int>::from_be_bytes(bytes.try_into().unwrap()))
+                }
+            }
+        )*
+    };
+}
+
+impl_codec_for_int!(u8, u16, u32, u64, i64);
+
+impl BinaryCodec for usize {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put_len(*self);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        decoder.get_len()
+    }
+}
+
+impl BinaryCodec for bool {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&(*self as u8));
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        match decoder.get::<u8>()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(CodecError::NonCanonical("bool")),
+        }
+    }
+}
+
+impl BinaryCodec for String {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put_var_bytes(self.as_bytes());
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        let bytes = decoder.get_var_bytes()?;
+        String::from_utf8(bytes.to_vec()).map_err(|err| CodecError::invalid_value("string", err))
+    }
+}
+
+impl<T: BinaryCodec> BinaryCodec for Option<T> {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.is_some());
+        if let Some(value) = self {
+            encoder.put(value);
+        }
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        if decoder.get()? {
+            Ok(Some(decoder.get()?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<T: BinaryCodec> BinaryCodec for Vec<T> {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put_len(self.len());
+        for item in self {
+            encoder.put(item);
+        }
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        let len = decoder.get_len()?;
+        // Every item takes at least one byte, so the length can't exceed the input size.
+        // It prevents allocating huge vectors for the malformed input.
+        let mut items = Vec::with_capacity(len.min(decoder.remaining()));
+        for _ in 0..len {
+            items.push(decoder.get()?);
+        }
+        Ok(items)
+    }
+}
+
+impl<T: BinaryCodec> BinaryCodec for Box<T> {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(self.as_ref());
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(Box::new(decoder.get()?))
+    }
+}
+
+impl<A: BinaryCodec, B: BinaryCodec> BinaryCodec for (A, B) {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.0);
+        encoder.put(&self.1);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok((decoder.get()?, decoder.get()?))
+    }
+}
+
+impl BinaryCodec for Address {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put_bytes(self.as_bytes());
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(Address::from_slice(decoder.get_bytes(20)?))
+    }
+}
+
+impl BinaryCodec for H256 {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put_bytes(self.as_bytes());
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(H256::from_slice(decoder.get_bytes(32)?))
+    }
+}
+
+impl BinaryCodec for U256 {
+    fn encode(&self, encoder: &mut Encoder) {
+        let mut bytes = [0u8; 32];
+        self.to_big_endian(&mut bytes);
+        encoder.put_bytes(&bytes);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(U256::from_big_endian(decoder.get_bytes(32)?))
+    }
+}
+
+impl BinaryCodec for BigUint {
+    fn encode(&self, encoder: &mut Encoder) {
+        // `to_bytes_be` returns `[0]` for zero, which is encoded as an empty sequence instead.
+        let bytes = self.to_bytes_be();
+        let bytes = if bytes == [0] { &[][..] } else { &bytes[..] };
+        encoder.put_var_bytes(bytes);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        let bytes = decoder.get_var_bytes()?;
+        if bytes.first() == Some(&0) {
+            return Err(CodecError::NonCanonical("BigUint"));
+        }
+        Ok(BigUint::from_bytes_be(bytes))
+    }
+}
+
+impl BinaryCodec for BigUintSerdeWrapper {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.0);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(BigUintSerdeWrapper(decoder.get()?))
+    }
+}
+
+impl BinaryCodec for Fr {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put_bytes(&self.to_bytes());
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Fr::from_bytes(decoder.get_bytes(32)?).map_err(|err| CodecError::invalid_value("Fr", err))
+    }
+}
+
+impl BinaryCodec for DateTime<Utc> {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.timestamp());
+        encoder.put(&self.timestamp_subsec_nanos());
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        let secs = decoder.get()?;
+        let nanos = decoder.get()?;
+        Utc.timestamp_opt(secs, nanos)
+            .single()
+            .ok_or_else(|| CodecError::invalid_value("timestamp", format!("{}.{}", secs, nanos)))
+    }
+}
@@ -0,0 +1,528 @@
+//! Property tests of the binary codec: randomly generated values must survive the
+//! encoding round trip, and malformed inputs must be rejected.
+
+use chrono::{TimeZone, Utc};
+use num::BigUint;
+use serde::Serialize;
+use zksync_basic_types::{Address, H256, U256};
+use zksync_crypto::franklin_crypto::eddsa::PrivateKey;
+use zksync_crypto::params::{max_account_id, max_token_id};
+use zksync_crypto::rand::{Rng, SeedableRng, XorShiftRng};
+use zksync_crypto::Fr;
+use zksync_utils::BigUintSerdeWrapper;
+
+use super::*;
+use crate::{
+    block::{Block, BlockSealReason, ExecutedOperations, ExecutedPriorityOp, ExecutedTx},
+    mempool::SignedTxsBatch,
+    operations::NoopOp,
+    tx::{
+        ChangePubKey, Close, EIP1271Signature, EthSignData, ForcedExit, Order, PackedEthSignature,
+        SignedZkSyncTx, Swap, TimeRange, TracedFee, Transfer, TxEthSignature, TxExecutionError,
+        TxExecutionTrace, TxSignature, Withdraw, ZkSyncTx,
+    },
+    AccountUpdate, ChangePubKeyOp, CloseOp, Deposit, DepositOp, ForcedExitOp, FullExit, FullExitOp,
+    PriorityOp, PubKeyHash, SwapOp, TransferOp, TransferToNewOp, WithdrawOp, ZkSyncOp,
+    ZkSyncPriorityOp,
+};
+
+const ITERATIONS: usize = 50;
+
+/// Checks that the value survives the round trip, the encoding is deterministic and
+/// every truncated encoding is rejected.
+fn check_round_trip<T: BinaryCodec + Serialize>(value: &T) {
+    let bytes = encode(value);
+    let decoded: T = decode(&bytes).expect("failed to decode value");
+    assert_eq!(
+        serde_json::to_value(value).unwrap(),
+        serde_json::to_value(&decoded).unwrap()
+    );
+    assert_eq!(encode(&decoded), bytes);
+
+    for len in 0..bytes.len() {
+        assert!(
+            decode::<T>(&bytes[..len]).is_err(),
+            "prefix of length {} was decoded",
+            len
+        );
+    }
+}
+
+fn gen_biguint<R: Rng>(rng: &mut R) -> BigUint {
+    match rng.gen_range(0, 3) {
+        0 => BigUint::from(0u32),
+        1 => BigUint::from(rng.gen::<u64>()),
+        _ => BigUint::from_bytes_be(&rng.gen::<[u8; 16]>()),
+    }
+}
+
+fn gen_address<R: Rng>(rng: &mut R) -> Address {
+    Address::from(rng.gen::<[u8; 20]>())
+}
+
+fn gen_time_range<R: Rng>(rng: &mut R) -> TimeRange {
+    if rng.gen() {
+        TimeRange::default()
+    } else {
+        TimeRange::new(rng.gen(), rng.gen())
+    }
+}
+
+fn gen_signature<R: Rng>(rng: &mut R) -> TxSignature {
+    TxSignature::sign_musig(&PrivateKey(rng.gen()), &rng.gen::<[u8; 32]>())
+}
+
+fn gen_eth_signature<R: Rng>(rng: &mut R) -> PackedEthSignature {
+    PackedEthSignature::sign(&H256::from(rng.gen::<[u8; 32]>()), &rng.gen::<[u8; 32]>())
+        .expect("failed to sign message")
+}
+
+fn gen_tx_eth_signature<R: Rng>(rng: &mut R) -> TxEthSignature {
+    match rng.gen_range(0, 3) {
+        0 => TxEthSignature::EthereumSignature(gen_eth_signature(rng)),
+        1 => {
+            let len = rng.gen_range(0, 100);
+            TxEthSignature::EIP1271Signature(EIP1271Signature(
+                (0..len).map(|_| rng.gen()).collect(),
+            ))
+        }
+        _ => TxEthSignature::EIP712Signature(gen_eth_signature(rng)),
+    }
+}
+
+fn gen_order<R: Rng>(rng: &mut R) -> Order {
+    let mut order = Order::new(
+        rng.gen::<u32>().min(max_account_id()),
+        gen_address(rng),
+        rng.gen(),
+        rng.gen::<u16>().min(max_token_id()),
+        rng.gen::<u16>().min(max_token_id()),
+        (gen_biguint(rng), gen_biguint(rng)),
+        gen_biguint(rng),
+        gen_biguint(rng),
+        None,
+    );
+    order.signature = gen_signature(rng);
+    order
+}
+
+fn gen_tx<R: Rng>(rng: &mut R) -> ZkSyncTx {
+    let account_id = rng.gen::<u32>().min(max_account_id());
+    let token = rng.gen::<u16>().min(max_token_id());
+    let mut tx = match rng.gen_range(0, 6) {
+        0 => ZkSyncTx::Transfer(Box::new(Transfer::new(
+            account_id,
+            gen_address(rng),
+            gen_address(rng),
+            token,
+            gen_biguint(rng),
+            gen_biguint(rng),
+            rng.gen(),
+            gen_time_range(rng),
+            None,
+        ))),
+        1 => {
+            let mut tx = Withdraw::new(
+                account_id,
+                gen_address(rng),
+                gen_address(rng),
+                token,
+                gen_biguint(rng),
+                gen_biguint(rng),
+                rng.gen(),
+                gen_time_range(rng),
+                None,
+            );
+            tx.fast = rng.gen();
+            ZkSyncTx::Withdraw(Box::new(tx))
+        }
+        2 => ZkSyncTx::Close(Box::new(Close {
+            account: gen_address(rng),
+            nonce: rng.gen(),
+            signature: TxSignature::default(),
+        })),
+        3 => {
+            let eth_signature = if rng.gen() {
+                Some(gen_eth_signature(rng))
+            } else {
+                None
+            };
+            ZkSyncTx::ChangePubKey(Box::new(ChangePubKey::new(
+                account_id,
+                gen_address(rng),
+                PubKeyHash::from_bytes(&rng.gen::<[u8; 20]>()).unwrap(),
+                token,
+                gen_biguint(rng),
+                rng.gen(),
+                gen_time_range(rng),
+                None,
+                eth_signature,
+            )))
+        }
+        4 => ZkSyncTx::ForcedExit(Box::new(ForcedExit::new(
+            account_id,
+            gen_address(rng),
+            token,
+            gen_biguint(rng),
+            rng.gen(),
+            gen_time_range(rng),
+            None,
+        ))),
+        _ => ZkSyncTx::Swap(Box::new(Swap::new(
+            account_id,
+            gen_address(rng),
+            rng.gen(),
+            (gen_order(rng), gen_order(rng)),
+            (gen_biguint(rng), gen_biguint(rng)),
+            token,
+            gen_biguint(rng),
+            gen_time_range(rng),
+            None,
+        ))),
+    };
+
+    let signature = gen_signature(rng);
+    match &mut tx {
+        ZkSyncTx::Transfer(tx) => tx.signature = signature,
+        ZkSyncTx::Withdraw(tx) => tx.signature = signature,
+        ZkSyncTx::Close(tx) => tx.signature = signature,
+        ZkSyncTx::ChangePubKey(tx) => tx.signature = signature,
+        ZkSyncTx::ForcedExit(tx) => tx.signature = signature,
+        ZkSyncTx::Swap(tx) => tx.signature = signature,
+    }
+    tx
+}
+
+fn gen_signed_tx<R: Rng>(rng: &mut R) -> SignedZkSyncTx {
+    let eth_sign_data = if rng.gen() {
+        let len = rng.gen_range(0, 200);
+        Some(EthSignData {
+            signature: gen_tx_eth_signature(rng),
+            message: (0..len).map(|_| rng.gen()).collect(),
+        })
+    } else {
+        None
+    };
+    SignedZkSyncTx {
+        tx: gen_tx(rng),
+        eth_sign_data,
+    }
+}
+
+fn gen_withdraw_amount<R: Rng>(rng: &mut R) -> Option<BigUintSerdeWrapper> {
+    if rng.gen() {
+        Some(BigUintSerdeWrapper(gen_biguint(rng)))
+    } else {
+        None
+    }
+}
+
+fn gen_tx_op<R: Rng>(rng: &mut R, tx: &ZkSyncTx) -> ZkSyncOp {
+    match tx {
+        ZkSyncTx::Transfer(tx) => {
+            if rng.gen() {
+                ZkSyncOp::Transfer(Box::new(TransferOp {
+                    tx: *tx.clone(),
+                    from: rng.gen(),
+                    to: rng.gen(),
+                }))
+            } else {
+                ZkSyncOp::TransferToNew(Box::new(TransferToNewOp {
+                    tx: *tx.clone(),
+                    from: rng.gen(),
+                    to: rng.gen(),
+                }))
+            }
+        }
+        ZkSyncTx::Withdraw(tx) => ZkSyncOp::Withdraw(Box::new(WithdrawOp {
+            tx: *tx.clone(),
+            account_id: rng.gen(),
+        })),
+        ZkSyncTx::Close(tx) => {
+            if rng.gen() {
+                ZkSyncOp::Close(Box::new(CloseOp {
+                    tx: *tx.clone(),
+                    account_id: rng.gen(),
+                }))
+            } else {
+                ZkSyncOp::Noop(NoopOp {})
+            }
+        }
+        ZkSyncTx::ChangePubKey(tx) => ZkSyncOp::ChangePubKeyOffchain(Box::new(ChangePubKeyOp {
+            tx: *tx.clone(),
+            account_id: rng.gen(),
+        })),
+        ZkSyncTx::ForcedExit(tx) => ZkSyncOp::ForcedExit(Box::new(ForcedExitOp {
+            tx: *tx.clone(),
+            target_account_id: rng.gen(),
+            withdraw_amount: gen_withdraw_amount(rng),
+        })),
+        ZkSyncTx::Swap(tx) => ZkSyncOp::Swap(Box::new(SwapOp {
+            tx: *tx.clone(),
+            submitter: rng.gen(),
+            accounts: (rng.gen(), rng.gen()),
+            recipients: (rng.gen(), rng.gen()),
+        })),
+    }
+}
+
+fn gen_account_update<R: Rng>(rng: &mut R) -> AccountUpdate {
+    match rng.gen_range(0, 4) {
+        0 => AccountUpdate::Create {
+            address: gen_address(rng),
+            nonce: rng.gen(),
+        },
+        1 => AccountUpdate::Delete {
+            address: gen_address(rng),
+            nonce: rng.gen(),
+        },
+        2 => AccountUpdate::UpdateBalance {
+            old_nonce: rng.gen(),
+            new_nonce: rng.gen(),
+            balance_update: (rng.gen(), gen_biguint(rng), gen_biguint(rng)),
+        },
+        _ => AccountUpdate::ChangePubKeyHash {
+            old_pub_key_hash: PubKeyHash::from_bytes(&rng.gen::<[u8; 20]>()).unwrap(),
+            new_pub_key_hash: PubKeyHash::from_bytes(&rng.gen::<[u8; 20]>()).unwrap(),
+            old_nonce: rng.gen(),
+            new_nonce: rng.gen(),
+        },
+    }
+}
+
+fn gen_executed_tx<R: Rng>(rng: &mut R) -> ExecutedTx {
+    let signed_tx = gen_signed_tx(rng);
+    let success = rng.gen();
+    let (op, fail_reason, fail_code, trace) = if success {
+        let fee = if rng.gen() {
+            Some(TracedFee {
+                token: rng.gen(),
+                amount: gen_biguint(rng),
+            })
+        } else {
+            None
+        };
+        let updates_count = rng.gen_range(0, 4);
+        let trace = TxExecutionTrace {
+            block_index: rng.gen(),
+            chunks: rng.gen_range(0, 1000),
+            fee,
+            updates: (0..updates_count)
+                .map(|_| (rng.gen(), gen_account_update(rng)))
+                .collect(),
+        };
+        (Some(gen_tx_op(rng, &signed_tx.tx)), None, None, Some(trace))
+    } else {
+        let error = TxExecutionError::InsufficientBalance;
+        (None, Some(error.to_string()), Some(error), None)
+    };
+
+    ExecutedTx {
+        signed_tx,
+        success,
+        op,
+        fail_reason,
+        fail_code,
+        block_index: if success { Some(rng.gen()) } else { None },
+        created_at: Utc.timestamp(rng.gen_range(0, 1 << 40), rng.gen_range(0, 1_000_000_000)),
+        batch_id: if rng.gen() { Some(rng.gen()) } else { None },
+        trace,
+    }
+}
+
+fn gen_executed_priority_op<R: Rng>(rng: &mut R) -> ExecutedPriorityOp {
+    let (data, op) = if rng.gen() {
+        let deposit = Deposit {
+            from: gen_address(rng),
+            token: rng.gen(),
+            amount: gen_biguint(rng),
+            to: gen_address(rng),
+        };
+        let op = ZkSyncOp::Deposit(Box::new(DepositOp {
+            priority_op: deposit.clone(),
+            account_id: rng.gen(),
+        }));
+        (ZkSyncPriorityOp::Deposit(deposit), op)
+    } else {
+        let full_exit = FullExit {
+            account_id: rng.gen(),
+            eth_address: gen_address(rng),
+            token: rng.gen(),
+        };
+        let op = ZkSyncOp::FullExit(Box::new(FullExitOp {
+            priority_op: full_exit.clone(),
+            withdraw_amount: gen_withdraw_amount(rng),
+        }));
+        (ZkSyncPriorityOp::FullExit(full_exit), op)
+    };
+
+    ExecutedPriorityOp {
+        priority_op: PriorityOp {
+            serial_id: rng.gen(),
+            data,
+            deadline_block: rng.gen(),
+            eth_hash: rng.gen::<[u8; 32]>().to_vec(),
+            eth_block: rng.gen(),
+        },
+        op,
+        block_index: rng.gen(),
+        created_at: Utc.timestamp(rng.gen_range(0, 1 << 40), 0),
+    }
+}
+
+fn gen_block<R: Rng>(rng: &mut R) -> Block {
+    let ops_count = rng.gen_range(0, 5);
+    let block_transactions = (0..ops_count)
+        .map(|_| {
+            if rng.gen() {
+                ExecutedOperations::Tx(Box::new(gen_executed_tx(rng)))
+            } else {
+                ExecutedOperations::PriorityOp(Box::new(gen_executed_priority_op(rng)))
+            }
+        })
+        .collect();
+    let seal_reason = match rng.gen_range(0, 7) {
+        0 => None,
+        1 => Some(BlockSealReason::ChunksFill),
+        2 => Some(BlockSealReason::GasLimit),
+        3 => Some(BlockSealReason::BlockAge),
+        4 => Some(BlockSealReason::WithdrawalsLimit),
+        5 => Some(BlockSealReason::MiniblockIterations),
+        _ => Some(BlockSealReason::Forced),
+    };
+
+    let mut block = Block::new(
+        rng.gen(),
+        rng.gen::<Fr>(),
+        rng.gen(),
+        block_transactions,
+        (rng.gen(), rng.gen()),
+        rng.gen_range(0, 1000),
+        U256::from(rng.gen::<u64>()),
+        U256::from_big_endian(&rng.gen::<[u8; 32]>()),
+        rng.gen(),
+    );
+    block.seal_reason = seal_reason;
+    block
+}
+
+#[test]
+fn tx_round_trip() {
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    for _ in 0..ITERATIONS {
+        check_round_trip(&gen_tx(&mut rng));
+    }
+}
+
+#[test]
+fn signed_tx_round_trip() {
+    let mut rng = XorShiftRng::from_seed([2, 2, 3, 4]);
+    for _ in 0..ITERATIONS {
+        let tx = gen_signed_tx(&mut rng);
+        check_round_trip(&tx);
+
+        // `fast` flag of the withdrawal is not serialized to JSON.
+        if let ZkSyncTx::Withdraw(withdraw) = &tx.tx {
+            let decoded: SignedZkSyncTx = decode(&encode(&tx)).unwrap();
+            match decoded.tx {
+                ZkSyncTx::Withdraw(decoded) => assert_eq!(decoded.fast, withdraw.fast),
+                _ => panic!("decoded transaction has a different type"),
+            }
+        }
+    }
+}
+
+#[test]
+fn txs_batch_round_trip() {
+    let mut rng = XorShiftRng::from_seed([3, 2, 3, 4]);
+    for _ in 0..ITERATIONS / 5 {
+        let txs_count = rng.gen_range(0, 6);
+        let batch = SignedTxsBatch {
+            txs: (0..txs_count).map(|_| gen_signed_tx(&mut rng)).collect(),
+            batch_id: rng.gen(),
+            eth_signature: if rng.gen() {
+                Some(gen_tx_eth_signature(&mut rng))
+            } else {
+                None
+            },
+        };
+        check_round_trip(&batch);
+    }
+}
+
+#[test]
+fn block_round_trip() {
+    let mut rng = XorShiftRng::from_seed([4, 2, 3, 4]);
+    for _ in 0..ITERATIONS / 5 {
+        check_round_trip(&gen_block(&mut rng));
+    }
+}
+
+#[test]
+fn malformed_input() {
+    let mut rng = XorShiftRng::from_seed([6, 2, 3, 4]);
+    let tx = gen_signed_tx(&mut rng);
+    let bytes = encode(&tx);
+
+    // Unknown version.
+    let mut wrong_version = bytes.clone();
+    wrong_version[0] = CODEC_VERSION + 1;
+    assert_eq!(
+        decode::<SignedZkSyncTx>(&wrong_version).unwrap_err(),
+        CodecError::UnsupportedVersion(CODEC_VERSION + 1)
+    );
+
+    // Trailing bytes.
+    let mut trailing = bytes.clone();
+    trailing.extend_from_slice(&[0, 0]);
+    assert_eq!(
+        decode::<SignedZkSyncTx>(&trailing).unwrap_err(),
+        CodecError::TrailingBytes(2)
+    );
+
+    // Unknown transaction type.
+    let mut unknown_tag = bytes;
+    unknown_tag[1] = 0xff;
+    assert_eq!(
+        decode::<SignedZkSyncTx>(&unknown_tag).unwrap_err(),
+        CodecError::UnknownTag {
+            type_name: "ZkSyncTx",
+            tag: 0xff
+        }
+    );
+
+    // Empty input.
+    assert_eq!(
+        decode::<SignedZkSyncTx>(&[]).unwrap_err(),
+        CodecError::UnexpectedEnd
+    );
+}
+
+#[test]
+fn non_canonical_encoding() {
+    // Length with the redundant zero continuation byte.
+    assert_eq!(
+        decode::<Vec<u8>>(&[CODEC_VERSION, 0x81, 0x00, 1]).unwrap_err(),
+        CodecError::NonCanonical("length")
+    );
+    assert_eq!(
+        decode::<Vec<u8>>(&[CODEC_VERSION, 0x01, 1]).unwrap(),
+        vec![1]
+    );
+
+    // Number with the leading zero.
+    assert_eq!(
+        decode::<BigUint>(&[CODEC_VERSION, 2, 0, 1]).unwrap_err(),
+        CodecError::NonCanonical("BigUint")
+    );
+    assert_eq!(
+        decode::<BigUint>(&[CODEC_VERSION, 0]).unwrap(),
+        BigUint::from(0u32)
+    );
+
+    // Boolean other than 0 or 1.
+    assert_eq!(
+        decode::<bool>(&[CODEC_VERSION, 2]).unwrap_err(),
+        CodecError::NonCanonical("bool")
+    );
+}
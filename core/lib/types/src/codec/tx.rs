@@ -0,0 +1,394 @@
+//! Encoding of the transactions.
+
+// Workspace uses
+use zksync_crypto::params::FR_ADDRESS_LEN;
+// Local uses
+use super::{BinaryCodec, CodecError, Decoder, Encoder};
+use crate::{
+    mempool::SignedTxsBatch,
+    tx::{
+        ChangePubKey, Close, EIP1271Signature, EthSignData, ForcedExit, Order, PackedEthSignature,
+        PackedPublicKey, PackedSignature, SignedZkSyncTx, Swap, TimeRange, Transfer,
+        TxEthSignature, TxSignature, Withdraw, ZkSyncTx,
+    },
+    PubKeyHash,
+};
+
+const ETHEREUM_SIGNATURE_TAG: u8 = 0;
+const EIP1271_SIGNATURE_TAG: u8 = 1;
+const EIP712_SIGNATURE_TAG: u8 = 2;
+
+impl BinaryCodec for TimeRange {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.valid_from);
+        encoder.put(&self.valid_until);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(TimeRange::new(decoder.get()?, decoder.get()?))
+    }
+}
+
+impl BinaryCodec for PubKeyHash {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put_bytes(&self.data);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        PubKeyHash::from_bytes(decoder.get_bytes(FR_ADDRESS_LEN)?)
+            .map_err(|err| CodecError::invalid_value("PubKeyHash", err))
+    }
+}
+
+impl BinaryCodec for TxSignature {
+    fn encode(&self, encoder: &mut Encoder) {
+        let pub_key = self
+            .pub_key
+            .serialize_packed()
+            .expect("failed to pack public key");
+        let signature = self
+            .signature
+            .serialize_packed()
+            .expect("failed to pack signature");
+        encoder.put_bytes(&pub_key);
+        encoder.put_bytes(&signature);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        let pub_key = PackedPublicKey::deserialize_packed(decoder.get_bytes(32)?)
+            .map_err(|err| CodecError::invalid_value("public key", err))?;
+        let signature = PackedSignature::deserialize_packed(decoder.get_bytes(64)?)
+            .map_err(|err| CodecError::invalid_value("signature", err))?;
+        Ok(TxSignature { pub_key, signature })
+    }
+}
+
+impl BinaryCodec for PackedEthSignature {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put_bytes(&self.serialize_packed());
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        PackedEthSignature::deserialize_packed(decoder.get_bytes(65)?)
+            .map_err(|err| CodecError::invalid_value("Ethereum signature", err))
+    }
+}
+
+impl BinaryCodec for TxEthSignature {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            TxEthSignature::EthereumSignature(signature) => {
+                encoder.put(&ETHEREUM_SIGNATURE_TAG);
+                encoder.put(signature);
+            }
+            TxEthSignature::EIP1271Signature(signature) => {
+                encoder.put(&EIP1271_SIGNATURE_TAG);
+                encoder.put_var_bytes(&signature.0);
+            }
+            TxEthSignature::EIP712Signature(signature) => {
+                encoder.put(&EIP712_SIGNATURE_TAG);
+                encoder.put(signature);
+            }
+        }
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        match decoder.get()? {
+            ETHEREUM_SIGNATURE_TAG => Ok(TxEthSignature::EthereumSignature(decoder.get()?)),
+            EIP1271_SIGNATURE_TAG => Ok(TxEthSignature::EIP1271Signature(EIP1271Signature(
+                decoder.get_var_bytes()?.to_vec(),
+            ))),
+            EIP712_SIGNATURE_TAG => Ok(TxEthSignature::EIP712Signature(decoder.get()?)),
+            tag => Err(CodecError::UnknownTag {
+                type_name: "TxEthSignature",
+                tag,
+            }),
+        }
+    }
+}
+
+impl BinaryCodec for EthSignData {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.signature);
+        encoder.put_var_bytes(&self.message);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(EthSignData {
+            signature: decoder.get()?,
+            message: decoder.get_var_bytes()?.to_vec(),
+        })
+    }
+}
+
+impl BinaryCodec for Transfer {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.account_id);
+        encoder.put(&self.from);
+        encoder.put(&self.to);
+        encoder.put(&self.token);
+        encoder.put(&self.amount);
+        encoder.put(&self.fee);
+        encoder.put(&self.nonce);
+        encoder.put(&self.time_range);
+        encoder.put(&self.signature);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        // Signature is set separately, so it's not verified during the decoding.
+        let mut tx = Transfer::new(
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            None,
+        );
+        tx.signature = decoder.get()?;
+        Ok(tx)
+    }
+}
+
+impl BinaryCodec for Withdraw {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.account_id);
+        encoder.put(&self.from);
+        encoder.put(&self.to);
+        encoder.put(&self.token);
+        encoder.put(&self.amount);
+        encoder.put(&self.fee);
+        encoder.put(&self.nonce);
+        encoder.put(&self.time_range);
+        encoder.put(&self.signature);
+        encoder.put(&self.fast);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        let mut tx = Withdraw::new(
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            None,
+        );
+        tx.signature = decoder.get()?;
+        tx.fast = decoder.get()?;
+        Ok(tx)
+    }
+}
+
+impl BinaryCodec for Close {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.account);
+        encoder.put(&self.nonce);
+        encoder.put(&self.signature);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(Close {
+            account: decoder.get()?,
+            nonce: decoder.get()?,
+            signature: decoder.get()?,
+        })
+    }
+}
+
+impl BinaryCodec for ChangePubKey {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.account_id);
+        encoder.put(&self.account);
+        encoder.put(&self.new_pk_hash);
+        encoder.put(&self.fee_token);
+        encoder.put(&self.fee);
+        encoder.put(&self.nonce);
+        encoder.put(&self.time_range);
+        encoder.put(&self.signature);
+        encoder.put(&self.eth_signature);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        let mut tx = ChangePubKey::new(
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            None,
+            None,
+        );
+        tx.signature = decoder.get()?;
+        tx.eth_signature = decoder.get()?;
+        Ok(tx)
+    }
+}
+
+impl BinaryCodec for ForcedExit {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.initiator_account_id);
+        encoder.put(&self.target);
+        encoder.put(&self.token);
+        encoder.put(&self.fee);
+        encoder.put(&self.nonce);
+        encoder.put(&self.time_range);
+        encoder.put(&self.signature);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        let mut tx = ForcedExit::new(
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            None,
+        );
+        tx.signature = decoder.get()?;
+        Ok(tx)
+    }
+}
+
+impl BinaryCodec for Order {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.account_id);
+        encoder.put(&self.recipient_address);
+        encoder.put(&self.nonce);
+        encoder.put(&self.token_sell);
+        encoder.put(&self.token_buy);
+        encoder.put(&self.price);
+        encoder.put(&self.min_amount);
+        encoder.put(&self.max_amount);
+        encoder.put(&self.signature);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        let mut order = Order::new(
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            None,
+        );
+        order.signature = decoder.get()?;
+        Ok(order)
+    }
+}
+
+impl BinaryCodec for Swap {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.submitter_id);
+        encoder.put(&self.submitter_address);
+        encoder.put(&self.nonce);
+        encoder.put(&self.orders);
+        encoder.put(&self.amounts);
+        encoder.put(&self.fee_token);
+        encoder.put(&self.fee);
+        encoder.put(&self.time_range);
+        encoder.put(&self.signature);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        let mut tx = Swap::new(
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            decoder.get()?,
+            None,
+        );
+        tx.signature = decoder.get()?;
+        Ok(tx)
+    }
+}
+
+impl BinaryCodec for ZkSyncTx {
+    fn encode(&self, encoder: &mut Encoder) {
+        match self {
+            ZkSyncTx::Transfer(tx) => {
+                encoder.put(&Transfer::TX_TYPE);
+                encoder.put(tx);
+            }
+            ZkSyncTx::Withdraw(tx) => {
+                encoder.put(&Withdraw::TX_TYPE);
+                encoder.put(tx);
+            }
+            ZkSyncTx::Close(tx) => {
+                encoder.put(&Close::TX_TYPE);
+                encoder.put(tx);
+            }
+            ZkSyncTx::ChangePubKey(tx) => {
+                encoder.put(&ChangePubKey::TX_TYPE);
+                encoder.put(tx);
+            }
+            ZkSyncTx::ForcedExit(tx) => {
+                encoder.put(&ForcedExit::TX_TYPE);
+                encoder.put(tx);
+            }
+            ZkSyncTx::Swap(tx) => {
+                encoder.put(&Swap::TX_TYPE);
+                encoder.put(tx);
+            }
+        }
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        match decoder.get()? {
+            Transfer::TX_TYPE => Ok(ZkSyncTx::Transfer(decoder.get()?)),
+            Withdraw::TX_TYPE => Ok(ZkSyncTx::Withdraw(decoder.get()?)),
+            Close::TX_TYPE => Ok(ZkSyncTx::Close(decoder.get()?)),
+            ChangePubKey::TX_TYPE => Ok(ZkSyncTx::ChangePubKey(decoder.get()?)),
+            ForcedExit::TX_TYPE => Ok(ZkSyncTx::ForcedExit(decoder.get()?)),
+            Swap::TX_TYPE => Ok(ZkSyncTx::Swap(decoder.get()?)),
+            tag => Err(CodecError::UnknownTag {
+                type_name: "ZkSyncTx",
+                tag,
+            }),
+        }
+    }
+}
+
+impl BinaryCodec for SignedZkSyncTx {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.tx);
+        encoder.put(&self.eth_sign_data);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(SignedZkSyncTx {
+            tx: decoder.get()?,
+            eth_sign_data: decoder.get()?,
+        })
+    }
+}
+
+impl BinaryCodec for SignedTxsBatch {
+    fn encode(&self, encoder: &mut Encoder) {
+        encoder.put(&self.txs);
+        encoder.put(&self.batch_id);
+        encoder.put(&self.eth_signature);
+    }
+
+    fn decode(decoder: &mut Decoder<'_>) -> Result<Self, CodecError> {
+        Ok(SignedTxsBatch {
+            txs: decoder.get()?,
+            batch_id: decoder.get()?,
+            eth_signature: decoder.get()?,
+        })
+    }
+}
@@ -32,6 +32,7 @@ impl CommitCost {
     pub const FULL_EXIT_COST: u64 = 10_165;
     pub const WITHDRAW_COST: u64 = 2_167;
     pub const FORCED_EXIT_COST: u64 = Self::WITHDRAW_COST; // TODO: Verify value (ZKS-109).
+    pub const SWAP_COST: u64 = 2 * Self::TRANSFER_COST; // TODO: Verify value (ZKS-109).
 
     pub fn base_cost() -> U256 {
         U256::from(Self::BASE_COST)
@@ -53,6 +54,7 @@ impl CommitCost {
             ZkSyncOp::FullExit(_) => Self::FULL_EXIT_COST,
             ZkSyncOp::Withdraw(_) => Self::WITHDRAW_COST,
             ZkSyncOp::ForcedExit(_) => Self::FORCED_EXIT_COST,
+            ZkSyncOp::Swap(_) => Self::SWAP_COST,
             ZkSyncOp::Close(_) => unreachable!("Close operations are disabled"),
         };
 
@@ -78,6 +80,7 @@ impl VerifyCost {
     pub const FULL_EXIT_COST: u64 = 2_499;
     pub const WITHDRAW_COST: u64 = 45_668;
     pub const FORCED_EXIT_COST: u64 = Self::WITHDRAW_COST; // TODO: Verify value (ZKS-109).
+    pub const SWAP_COST: u64 = 0;
 
     pub fn base_cost() -> U256 {
         U256::from(Self::BASE_COST)
@@ -93,6 +96,7 @@ impl VerifyCost {
             ZkSyncOp::FullExit(_) => Self::FULL_EXIT_COST,
             ZkSyncOp::Withdraw(_) => Self::WITHDRAW_COST,
             ZkSyncOp::ForcedExit(_) => Self::FORCED_EXIT_COST,
+            ZkSyncOp::Swap(_) => Self::SWAP_COST,
             ZkSyncOp::Close(_) => unreachable!("Close operations are disabled"),
         };
 
@@ -197,6 +201,7 @@ mod tests {
                 0,
                 Default::default(),
                 Default::default(),
+                Default::default(),
                 None,
                 None,
             ),
@@ -225,6 +230,7 @@ mod tests {
                 0,
                 Default::default(),
                 Default::default(),
+                Default::default(),
                 None,
                 None,
             ),
@@ -253,6 +259,7 @@ mod tests {
                 0,
                 Default::default(),
                 Default::default(),
+                Default::default(),
                 None,
                 None,
             ),
@@ -6,7 +6,7 @@
 //! zkSync operations are split into the following categories:
 //!
 //! - **transactions**: operations of zkSync network existing purely in the L2.
-//!   Currently includes [`Transfer`], [`Withdraw`], [`ChangePubKey`] and [`ForcedExit`].
+//!   Currently includes [`Transfer`], [`Withdraw`], [`ChangePubKey`], [`ForcedExit`] and [`Swap`].
 //!   All the transactions form an enum named [`ZkSyncTx`].
 //! - **priority operations**: operations of zkSync network which are triggered by
 //!   invoking the zkSync smart contract method in L1. These operations are disovered by
@@ -24,6 +24,7 @@
 //! [`Withdraw`]: ./tx/struct.Withdraw.html
 //! [`ChangePubKey`]: ./tx/struct.ChangePubKey.html
 //! [`ForcedExit`]: ./tx/struct.ForcedExit.html
+//! [`Swap`]: ./tx/struct.Swap.html
 //! [`ZkSyncTx`]: ./tx/enum.ZkSyncTx.html
 //! [`Deposit`]: ./priority_ops/struct.Deposit.html
 //! [`FullExit`]: ./priority_ops/struct.FullExit.html
@@ -38,6 +39,7 @@
 
 pub mod account;
 pub mod block;
+pub mod codec;
 pub mod config;
 pub mod ethereum;
 pub mod gas_counter;
@@ -55,12 +57,14 @@ mod tests;
 pub use self::account::{Account, AccountUpdate, PubKeyHash};
 pub use self::block::{ExecutedOperations, ExecutedPriorityOp, ExecutedTx};
 pub use self::operations::{
-    ChangePubKeyOp, DepositOp, ForcedExitOp, FullExitOp, TransferOp, TransferToNewOp, WithdrawOp,
-    ZkSyncOp,
+    ChangePubKeyOp, DepositOp, ForcedExitOp, FullExitOp, SwapOp, TransferOp, TransferToNewOp,
+    WithdrawOp, ZkSyncOp,
 };
 pub use self::priority_ops::{Deposit, FullExit, PriorityOp, ZkSyncPriorityOp};
-pub use self::tokens::{Token, TokenGenesisListItem, TokenLike, TokenPrice, TxFeeTypes};
-pub use self::tx::{ForcedExit, SignedZkSyncTx, Transfer, Withdraw, ZkSyncTx};
+pub use self::tokens::{
+    FeeParams, FeeParamsVersion, Token, TokenGenesisListItem, TokenLike, TokenPrice, TxFeeTypes,
+};
+pub use self::tx::{ForcedExit, Order, SignedZkSyncTx, Swap, Transfer, Withdraw, ZkSyncTx};
 
 #[doc(hidden)]
 pub use self::{operations::CloseOp, tx::Close};
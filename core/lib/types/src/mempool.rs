@@ -40,6 +40,14 @@ impl SignedTxVariant {
         })
     }
 
+    /// Returns the transactions contained in the element.
+    pub fn txs(&self) -> &[SignedZkSyncTx] {
+        match self {
+            Self::Tx(tx) => std::slice::from_ref(tx),
+            Self::Batch(batch) => &batch.txs,
+        }
+    }
+
     pub fn hashes(&self) -> Vec<TxHash> {
         match self {
             Self::Tx(tx) => vec![tx.hash()],
@@ -76,6 +76,7 @@ impl ChangePubKeyOp {
                 fee_token,
                 fee,
                 nonce,
+                Default::default(),
                 None,
                 None,
             ),
@@ -0,0 +1,155 @@
+use crate::AccountId;
+use crate::{
+    helpers::{pack_fee_amount, pack_token_amount, unpack_fee_amount, unpack_token_amount},
+    Order, Swap,
+};
+use anyhow::{ensure, format_err};
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+use zksync_basic_types::Address;
+use zksync_crypto::params::{
+    ACCOUNT_ID_BIT_WIDTH, AMOUNT_EXPONENT_BIT_WIDTH, AMOUNT_MANTISSA_BIT_WIDTH, CHUNK_BYTES,
+    FEE_EXPONENT_BIT_WIDTH, FEE_MANTISSA_BIT_WIDTH, TOKEN_BIT_WIDTH,
+};
+use zksync_crypto::primitives::FromBytes;
+
+/// Swap operation. For details, see the documentation of [`ZkSyncOp`](./operations/enum.ZkSyncOp.html).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapOp {
+    pub tx: Swap,
+    /// Account ID of the transaction submitter.
+    pub submitter: AccountId,
+    /// Account IDs of the first and the second orders owners.
+    pub accounts: (AccountId, AccountId),
+    /// Account IDs of the first and the second orders recipients.
+    pub recipients: (AccountId, AccountId),
+}
+
+impl SwapOp {
+    pub const CHUNKS: usize = 5;
+    pub const OP_CODE: u8 = 0x09;
+
+    pub(crate) fn get_public_data(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.push(Self::OP_CODE); // opcode
+        data.extend_from_slice(&self.accounts.0.to_be_bytes());
+        data.extend_from_slice(&self.recipients.0.to_be_bytes());
+        data.extend_from_slice(&self.accounts.1.to_be_bytes());
+        data.extend_from_slice(&self.recipients.1.to_be_bytes());
+        data.extend_from_slice(&self.submitter.to_be_bytes());
+        data.extend_from_slice(&self.tx.orders.0.token_sell.to_be_bytes());
+        data.extend_from_slice(&self.tx.orders.1.token_sell.to_be_bytes());
+        data.extend_from_slice(&self.tx.fee_token.to_be_bytes());
+        data.extend_from_slice(&pack_token_amount(&self.tx.amounts.0));
+        data.extend_from_slice(&pack_token_amount(&self.tx.amounts.1));
+        data.extend_from_slice(&pack_fee_amount(&self.tx.fee));
+        data.resize(Self::CHUNKS * CHUNK_BYTES, 0x00);
+        data
+    }
+
+    pub fn from_public_data(bytes: &[u8]) -> Result<Self, anyhow::Error> {
+        ensure!(
+            bytes.len() == Self::CHUNKS * CHUNK_BYTES,
+            "Wrong bytes length for swap pubdata"
+        );
+
+        const ACCOUNT_ID_BYTES: usize = ACCOUNT_ID_BIT_WIDTH / 8;
+        const TOKEN_BYTES: usize = TOKEN_BIT_WIDTH / 8;
+        const AMOUNT_BYTES: usize = (AMOUNT_EXPONENT_BIT_WIDTH + AMOUNT_MANTISSA_BIT_WIDTH) / 8;
+        const FEE_BYTES: usize = (FEE_EXPONENT_BIT_WIDTH + FEE_MANTISSA_BIT_WIDTH) / 8;
+
+        let account_0_offset = 1;
+        let recipient_0_offset = account_0_offset + ACCOUNT_ID_BYTES;
+        let account_1_offset = recipient_0_offset + ACCOUNT_ID_BYTES;
+        let recipient_1_offset = account_1_offset + ACCOUNT_ID_BYTES;
+        let submitter_offset = recipient_1_offset + ACCOUNT_ID_BYTES;
+        let token_0_offset = submitter_offset + ACCOUNT_ID_BYTES;
+        let token_1_offset = token_0_offset + TOKEN_BYTES;
+        let fee_token_offset = token_1_offset + TOKEN_BYTES;
+        let amount_0_offset = fee_token_offset + TOKEN_BYTES;
+        let amount_1_offset = amount_0_offset + AMOUNT_BYTES;
+        let fee_offset = amount_1_offset + AMOUNT_BYTES;
+
+        let read_account_id = |offset: usize, name: &str| {
+            u32::from_bytes(&bytes[offset..offset + ACCOUNT_ID_BYTES])
+                .ok_or_else(|| format_err!("Cant get {} account id from swap pubdata", name))
+        };
+        let read_token_id = |offset: usize, name: &str| {
+            u16::from_bytes(&bytes[offset..offset + TOKEN_BYTES])
+                .ok_or_else(|| format_err!("Cant get {} token id from swap pubdata", name))
+        };
+        let read_amount = |offset: usize, name: &str| {
+            unpack_token_amount(&bytes[offset..offset + AMOUNT_BYTES])
+                .ok_or_else(|| format_err!("Cant get {} amount from swap pubdata", name))
+        };
+
+        let account_0 = read_account_id(account_0_offset, "first order")?;
+        let recipient_0 = read_account_id(recipient_0_offset, "first recipient")?;
+        let account_1 = read_account_id(account_1_offset, "second order")?;
+        let recipient_1 = read_account_id(recipient_1_offset, "second recipient")?;
+        let submitter = read_account_id(submitter_offset, "submitter")?;
+        let token_0 = read_token_id(token_0_offset, "first order")?;
+        let token_1 = read_token_id(token_1_offset, "second order")?;
+        let fee_token = read_token_id(fee_token_offset, "fee")?;
+        let amount_0 = read_amount(amount_0_offset, "first order")?;
+        let amount_1 = read_amount(amount_1_offset, "second order")?;
+        let fee = unpack_fee_amount(&bytes[fee_offset..fee_offset + FEE_BYTES])
+            .ok_or_else(|| format_err!("Cant get fee from swap pubdata"))?;
+
+        // Addresses, nonces and prices are unknown from pubdata, so the orders
+        // are restored with the executed amounts as their bounds.
+        let nonce = 0;
+        let order_0 = Order::new(
+            account_0,
+            Address::zero(),
+            nonce,
+            token_0,
+            token_1,
+            (BigUint::default(), BigUint::default()),
+            amount_0.clone(),
+            amount_0.clone(),
+            None,
+        );
+        let order_1 = Order::new(
+            account_1,
+            Address::zero(),
+            nonce,
+            token_1,
+            token_0,
+            (BigUint::default(), BigUint::default()),
+            amount_1.clone(),
+            amount_1.clone(),
+            None,
+        );
+
+        Ok(Self {
+            tx: Swap::new(
+                submitter,
+                Address::zero(),
+                nonce,
+                (order_0, order_1),
+                (amount_0, amount_1),
+                fee_token,
+                fee,
+                Default::default(),
+                None,
+            ),
+            submitter,
+            accounts: (account_0, account_1),
+            recipients: (recipient_0, recipient_1),
+        })
+    }
+
+    pub fn get_updated_account_ids(&self) -> Vec<AccountId> {
+        let mut ids = vec![
+            self.accounts.0,
+            self.recipients.0,
+            self.accounts.1,
+            self.recipients.1,
+            self.submitter,
+        ];
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+}
@@ -77,6 +77,7 @@ impl TransferOp {
                 amount,
                 fee,
                 nonce,
+                Default::default(),
                 None,
             ),
             from: from_id,
@@ -71,7 +71,17 @@ impl TransferToNewOp {
         let nonce = 0; // It is unknown from pubdata
 
         Ok(Self {
-            tx: Transfer::new(from_id, from, to, token, amount, fee, nonce, None),
+            tx: Transfer::new(
+                from_id,
+                from,
+                to,
+                token,
+                amount,
+                fee,
+                nonce,
+                Default::default(),
+                None,
+            ),
             from: from_id,
             to: to_id,
         })
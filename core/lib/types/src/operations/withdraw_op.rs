@@ -79,7 +79,17 @@ impl WithdrawOp {
         let nonce = 0; // From pubdata it is unknown
 
         Ok(Self {
-            tx: Withdraw::new(account_id, from, to, token, amount, fee, nonce, None),
+            tx: Withdraw::new(
+                account_id,
+                from,
+                to,
+                token,
+                amount,
+                fee,
+                nonce,
+                Default::default(),
+                None,
+            ),
             account_id,
         })
     }
@@ -3,7 +3,7 @@ use zksync_crypto::ff::Field;
 use zksync_crypto::Fr;
 
 use super::utils::*;
-use crate::block::Block;
+use crate::block::{Block, BlockSealReason};
 
 /// Checks that we cannot create a block with invalid block sizes provided.
 #[test]
@@ -18,6 +18,7 @@ fn no_supported_block_size() {
         &[0],
         1_000_000.into(),
         1_500_000.into(),
+        0,
     );
 }
 
@@ -33,6 +34,7 @@ fn test_get_eth_encoded_root() {
         1,
         1_000_000.into(),
         1_500_000.into(),
+        0,
     );
 
     let mut bytes = [0u8; 32];
@@ -42,6 +44,29 @@ fn test_get_eth_encoded_root() {
     assert_eq!(block.get_eth_encoded_root(), H256::from(bytes));
 }
 
+#[test]
+fn test_get_eth_new_block_info() {
+    let block = Block::new(
+        0,
+        Fr::one(),
+        0,
+        vec![],
+        (0, 0),
+        1,
+        1_000_000.into(),
+        1_500_000.into(),
+        0x1234,
+    );
+
+    let mut timestamp = [0u8; 32];
+    timestamp[30..].copy_from_slice(&[0x12, 0x34]);
+
+    assert_eq!(
+        block.get_eth_new_block_info(),
+        vec![block.get_eth_encoded_root(), H256::from(timestamp)]
+    );
+}
+
 #[test]
 fn test_get_eth_public_data() {
     let mut block = Block::new(
@@ -57,6 +82,7 @@ fn test_get_eth_public_data() {
         100,
         1_000_000.into(),
         1_500_000.into(),
+        0,
     );
 
     let expected = {
@@ -96,6 +122,7 @@ fn test_get_eth_witness_data() {
         100,
         1_000_000.into(),
         1_500_000.into(),
+        0,
     );
 
     let witness = change_pubkey_tx
@@ -137,6 +164,7 @@ fn test_get_withdrawals_data() {
         100,
         1_000_000.into(),
         1_500_000.into(),
+        0,
     );
 
     let expected = {
@@ -156,3 +184,22 @@ fn test_get_withdrawals_data() {
     // No more corresponding operations left.
     assert!(block.get_withdrawals_data().is_empty());
 }
+
+/// Checks that the seal reason can be restored from its string representation.
+#[test]
+fn seal_reason_roundtrip() {
+    for reason in &[
+        BlockSealReason::ChunksFill,
+        BlockSealReason::GasLimit,
+        BlockSealReason::BlockAge,
+        BlockSealReason::WithdrawalsLimit,
+        BlockSealReason::MiniblockIterations,
+        BlockSealReason::Forced,
+    ] {
+        assert_eq!(
+            reason.to_string().parse::<BlockSealReason>().unwrap(),
+            *reason
+        );
+    }
+    assert!("unknown".parse::<BlockSealReason>().is_err());
+}
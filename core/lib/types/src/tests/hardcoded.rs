@@ -77,6 +77,7 @@ pub mod operations_test {
                 BigUint::from(42u32),
                 BigUint::from(42u32),
                 42,
+                Default::default(),
                 None,
             );
             let (from, to) = (1u32, 2u32);
@@ -112,6 +113,7 @@ pub mod operations_test {
                 BigUint::from(42u32),
                 BigUint::from(42u32),
                 42,
+                Default::default(),
                 None,
             );
             let account_id = 42u32;
@@ -156,6 +158,7 @@ pub mod operations_test {
                 42,
                 BigUint::from(42u32),
                 42,
+                Default::default(),
                 None,
                 Some(PackedEthSignature::deserialize_packed(
                     &hex::decode("2a0a81e257a2f5d6ed4f07b81dbda09f107bd026dbda09f107bd026f5d6ed4f02a0a81e257a2f5d6ed4f07b81dbda09f107bd026dbda09f107bd026f5d6ed4f0d4").unwrap(),
@@ -181,6 +184,7 @@ pub mod operations_test {
                 42,
                 BigUint::from(42u32),
                 42,
+                Default::default(),
                 None,
             );
             let target_account_id = 42u32;
@@ -267,12 +271,13 @@ pub mod tx_conversion_test {
             TOKEN_ID,
             (*FEE).clone(),
             NONCE,
+            Default::default(),
             None,
             None,
         );
 
         let bytes = change_pubkey.get_bytes();
-        assert_eq!(hex::encode(bytes), "07000000642a0a81e257a2f5d6ed4f07b81dbda09f107bd0263cfb9a39096d9e02b24187355f628f9a6331511b00057d0300000014");
+        assert_eq!(hex::encode(bytes), "07000000642a0a81e257a2f5d6ed4f07b81dbda09f107bd0263cfb9a39096d9e02b24187355f628f9a6331511b00057d03000000140000000000000000ffffffffffffffff");
     }
 
     #[test]
@@ -285,22 +290,30 @@ pub mod tx_conversion_test {
             (*AMOUNT).clone(),
             (*FEE).clone(),
             NONCE,
+            Default::default(),
             None,
         );
 
         let bytes = transfer.get_bytes();
-        assert_eq!(hex::encode(bytes), "05000000642a0a81e257a2f5d6ed4f07b81dbda09f107bd02621abaed8712072e918632259780e587698ef58da000500178c29c07d0300000014");
+        assert_eq!(hex::encode(bytes), "05000000642a0a81e257a2f5d6ed4f07b81dbda09f107bd02621abaed8712072e918632259780e587698ef58da000500178c29c07d03000000140000000000000000ffffffffffffffff");
     }
 
     #[test]
     fn test_convert_to_bytes_forced_exit() {
-        let forced_exit =
-            ForcedExit::new(ACCOUNT_ID, *ALICE, TOKEN_ID, (*FEE).clone(), NONCE, None);
+        let forced_exit = ForcedExit::new(
+            ACCOUNT_ID,
+            *ALICE,
+            TOKEN_ID,
+            (*FEE).clone(),
+            NONCE,
+            Default::default(),
+            None,
+        );
 
         let bytes = forced_exit.get_bytes();
         assert_eq!(
             hex::encode(bytes),
-            "08000000642a0a81e257a2f5d6ed4f07b81dbda09f107bd02600057d0300000014"
+            "08000000642a0a81e257a2f5d6ed4f07b81dbda09f107bd02600057d03000000140000000000000000ffffffffffffffff"
         );
     }
 
@@ -314,11 +327,12 @@ pub mod tx_conversion_test {
             (*AMOUNT).clone(),
             (*FEE).clone(),
             NONCE,
+            Default::default(),
             None,
         );
 
         let bytes = withdraw.get_bytes();
-        assert_eq!(hex::encode(bytes), "03000000642a0a81e257a2f5d6ed4f07b81dbda09f107bd02621abaed8712072e918632259780e587698ef58da000500000000000000000000000000bc614e7d0300000014");
+        assert_eq!(hex::encode(bytes), "03000000642a0a81e257a2f5d6ed4f07b81dbda09f107bd02621abaed8712072e918632259780e587698ef58da000500000000000000000000000000bc614e7d03000000140000000000000000ffffffffffffffff");
     }
 }
 
@@ -35,6 +35,7 @@ pub fn create_withdraw_tx() -> ExecutedOperations {
             100u32.into(),
             10u32.into(),
             12,
+            Default::default(),
             None,
         ),
         account_id: 0,
@@ -45,9 +46,11 @@ pub fn create_withdraw_tx() -> ExecutedOperations {
         success: true,
         op: Some(withdraw_op),
         fail_reason: None,
+        fail_code: None,
         block_index: None,
         created_at: Utc::now(),
         batch_id: None,
+        trace: None,
     };
 
     ExecutedOperations::Tx(Box::new(executed_withdraw_op))
@@ -62,6 +65,7 @@ pub fn create_change_pubkey_tx() -> ExecutedOperations {
             0,
             Default::default(),
             Default::default(),
+            Default::default(),
             None,
             None,
         ),
@@ -73,9 +77,11 @@ pub fn create_change_pubkey_tx() -> ExecutedOperations {
         success: true,
         op: Some(change_pubkey_op),
         fail_reason: None,
+        fail_code: None,
         block_index: None,
         created_at: Utc::now(),
         batch_id: None,
+        trace: None,
     };
 
     ExecutedOperations::Tx(Box::new(executed_change_pubkey_op))
@@ -2,9 +2,9 @@ use crate::{Address, TokenId};
 use chrono::{DateTime, Utc};
 use num::{rational::Ratio, BigUint};
 use serde::{Deserialize, Serialize};
-use std::{fmt, fs::read_to_string, path::PathBuf, str::FromStr};
+use std::{collections::HashMap, fmt, fs::read_to_string, path::PathBuf, str::FromStr};
 use zksync_utils::parse_env;
-use zksync_utils::UnsignedRatioSerializeAsDecimal;
+use zksync_utils::{UnsignedRatioMapSerializeAsDecimal, UnsignedRatioSerializeAsDecimal};
 
 // Order of the fields is important (from more specific types to less specific types)
 /// Set of values that can be interpreted as a token descriptor.
@@ -135,6 +135,35 @@ pub enum TxFeeTypes {
         #[serde(rename = "onchainPubkeyAuth")]
         onchain_pubkey_auth: bool,
     },
+    /// Fee for the `Swap` operation.
+    Swap,
+}
+
+/// Fee parameters which can be adjusted by the server operator at runtime.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeParams {
+    /// Cost of proving a single chunk of the block, in USD.
+    /// If the ZKP cost is derived from the prover statistics, this value is only
+    /// used while there are not enough of them.
+    #[serde(with = "UnsignedRatioSerializeAsDecimal")]
+    pub zkp_cost_chunk_usd: Ratio<BigUint>,
+    /// Multipliers of the fees paid in the corresponding tokens.
+    /// Tokens which aren't listed have the risk factor of 1.
+    #[serde(with = "UnsignedRatioMapSerializeAsDecimal")]
+    pub tokens_risk_factors: HashMap<TokenId, Ratio<BigUint>>,
+}
+
+/// Version of the fee parameters stored in the database.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeParamsVersion {
+    /// Version number, increased every time the parameters are changed.
+    pub version: i64,
+    #[serde(flatten)]
+    pub params: FeeParams,
+    /// Time when the parameters were set.
+    pub created_at: DateTime<Utc>,
 }
 
 #[cfg(test)]
@@ -0,0 +1,50 @@
+use crate::account::PubKeyHash;
+use crate::Engine;
+use serde::{Deserialize, Serialize};
+use zksync_crypto::franklin_crypto::eddsa::PrivateKey;
+
+use super::{TxHash, TxSignature};
+
+/// `TxCancelRequest` is a request to remove the pending transaction from the mempool.
+///
+/// Request must be signed with the zkSync key of the account which has sent the transaction.
+/// Once the transaction is proposed for a block, it can't be cancelled anymore.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxCancelRequest {
+    /// Hash of the transaction to be cancelled.
+    pub tx_hash: TxHash,
+    /// zkSync signature of the request.
+    pub signature: TxSignature,
+}
+
+impl TxCancelRequest {
+    /// Marker of the signed message. Its value doesn't match any of the transaction types,
+    /// so the signature of the request can't be used as a signature of some transaction.
+    pub const MSG_MARKER: u8 = 0xff;
+
+    /// Creates a signed request to cancel the transaction.
+    pub fn new_signed(tx_hash: TxHash, private_key: &PrivateKey<Engine>) -> Self {
+        let mut request = Self {
+            tx_hash,
+            signature: TxSignature::default(),
+        };
+        request.signature = TxSignature::sign_musig(private_key, &request.get_bytes());
+        request
+    }
+
+    /// Encodes the request data as the byte sequence to be signed.
+    pub fn get_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&[Self::MSG_MARKER]);
+        out.extend_from_slice(self.tx_hash.as_ref());
+        out
+    }
+
+    /// Restores the `PubKeyHash` from the request signature.
+    pub fn verify_signature(&self) -> Option<PubKeyHash> {
+        self.signature
+            .verify_musig(&self.get_bytes())
+            .map(|pub_key| PubKeyHash::from_pubkey(&pub_key))
+    }
+}
@@ -14,7 +14,7 @@ use zksync_crypto::{
 };
 use zksync_utils::BigUintSerdeAsRadix10Str;
 
-use super::{PackedEthSignature, TxSignature, VerifiedSignatureCache};
+use super::{PackedEthSignature, TimeRange, TxSignature, VerifiedSignatureCache};
 
 /// `ChangePubKey` transaction is used to set the owner's public key hash
 /// associated with the account.
@@ -37,6 +37,9 @@ pub struct ChangePubKey {
     pub fee: BigUint,
     /// Current account nonce.
     pub nonce: Nonce,
+    /// Time range when the transaction is valid.
+    #[serde(flatten)]
+    pub time_range: TimeRange,
     /// Transaction zkSync signature. Must be signed with the key corresponding to the
     /// `new_pk_hash` value. This signature is required to ensure that `fee_token` and `fee`
     /// fields can't be changed by an attacker.
@@ -66,6 +69,7 @@ impl ChangePubKey {
         fee_token: TokenId,
         fee: BigUint,
         nonce: Nonce,
+        time_range: TimeRange,
         signature: Option<TxSignature>,
         eth_signature: Option<PackedEthSignature>,
     ) -> Self {
@@ -76,6 +80,7 @@ impl ChangePubKey {
             fee_token,
             fee,
             nonce,
+            time_range,
             signature: signature.clone().unwrap_or_default(),
             eth_signature,
             cached_signer: VerifiedSignatureCache::NotCached,
@@ -96,6 +101,7 @@ impl ChangePubKey {
         fee_token: TokenId,
         fee: BigUint,
         nonce: Nonce,
+        time_range: TimeRange,
         eth_signature: Option<PackedEthSignature>,
         private_key: &PrivateKey,
     ) -> Result<Self, anyhow::Error> {
@@ -106,6 +112,7 @@ impl ChangePubKey {
             fee_token,
             fee,
             nonce,
+            time_range,
             None,
             eth_signature,
         );
@@ -137,6 +144,7 @@ impl ChangePubKey {
         out.extend_from_slice(&self.fee_token.to_be_bytes());
         out.extend_from_slice(&pack_fee_amount(&self.fee));
         out.extend_from_slice(&self.nonce.to_be_bytes());
+        out.extend_from_slice(&self.time_range.to_be_bytes());
         out
     }
 
@@ -191,11 +199,13 @@ impl ChangePubKey {
     /// - `account_id` field must be within supported range.
     /// - `fee_token` field must be within supported range.
     /// - `fee` field must represent a packable value.
+    /// - `time_range` field must represent a non-empty range.
     pub fn check_correctness(&self) -> bool {
         (self.eth_signature.is_none() || self.verify_eth_signature() == Some(self.account))
             && self.verify_signature() == Some(self.new_pk_hash.clone())
             && self.account_id <= max_account_id()
             && self.fee_token <= max_token_id()
             && is_fee_amount_packable(&self.fee)
+            && self.time_range.check_correctness()
     }
 }
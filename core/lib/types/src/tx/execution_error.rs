@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Reason of the transaction execution failure.
+///
+/// Every variant has a stable numeric code (see `TxExecutionError::code`), which is stored
+/// along with the executed transaction, so the clients don't have to parse the error messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Error)]
+#[serde(rename_all = "camelCase")]
+pub enum TxExecutionError {
+    #[error("Token id is not supported")]
+    InvalidToken = 1,
+    #[error("Transfer to Account with address 0 is not allowed")]
+    TransferToZeroAddress = 2,
+    #[error("Account does not exist")]
+    AccountNotFound = 3,
+    #[error("Target account does not exist")]
+    TargetAccountNotFound = 4,
+    #[error("Account is locked")]
+    AccountLocked = 5,
+    #[error("zkSync signature doesn't match the account public key hash")]
+    PubKeyHashMismatch = 6,
+    #[error("Ethereum signature is incorrect")]
+    IncorrectEthSignature = 7,
+    #[error("Account id is incorrect")]
+    AccountIdMismatch = 8,
+    #[error("Account id is bigger than max supported")]
+    AccountIdTooBig = 9,
+    #[error("Nonce mismatch")]
+    NonceMismatch = 10,
+    #[error("Not enough balance")]
+    InsufficientBalance = 11,
+    #[error("Target account is not locked; forced exit is forbidden")]
+    TargetAccountNotLocked = 12,
+    #[error("Target account balance is not equal to the withdrawal amount")]
+    TargetBalanceMismatch = 13,
+    #[error("Account is not empty")]
+    AccountNotEmpty = 14,
+    #[error("Account closing is disabled")]
+    AccountCloseDisabled = 15,
+    #[error("Transaction is not valid at the block timestamp")]
+    InvalidTimeRange = 16,
+    #[error("Swap amount doesn't fit into the order amount bounds")]
+    SwapAmountOutOfBounds = 17,
+    #[error("Swap amounts don't satisfy the order price")]
+    SwapPriceMismatch = 18,
+    #[error("Another transaction of the batch has failed")]
+    BatchTxFailed = 19,
+}
+
+impl TxExecutionError {
+    pub(crate) const ALL: [Self; 19] = [
+        Self::InvalidToken,
+        Self::TransferToZeroAddress,
+        Self::AccountNotFound,
+        Self::TargetAccountNotFound,
+        Self::AccountLocked,
+        Self::PubKeyHashMismatch,
+        Self::IncorrectEthSignature,
+        Self::AccountIdMismatch,
+        Self::AccountIdTooBig,
+        Self::NonceMismatch,
+        Self::InsufficientBalance,
+        Self::TargetAccountNotLocked,
+        Self::TargetBalanceMismatch,
+        Self::AccountNotEmpty,
+        Self::AccountCloseDisabled,
+        Self::InvalidTimeRange,
+        Self::SwapAmountOutOfBounds,
+        Self::SwapPriceMismatch,
+        Self::BatchTxFailed,
+    ];
+
+    /// Returns the numeric code of the error.
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+
+    /// Restores the error from its numeric code.
+    pub fn from_code(code: i32) -> Option<Self> {
+        Self::ALL.iter().copied().find(|error| error.code() == code)
+    }
+}
+
+/// Reason of the transactions batch execution failure.
+///
+/// Batch is executed atomically, so the failure of a single transaction fails the whole batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error(
+    "Batch execution failed, since tx #{} of batch failed with a reason: {}",
+    .index + 1,
+    .error
+)]
+pub struct BatchExecutionError {
+    /// Index of the failed transaction in the batch.
+    pub index: usize,
+    /// Reason of the transaction failure.
+    pub error: TxExecutionError,
+}
+
+impl BatchExecutionError {
+    /// Returns the reason of the failure for the transaction of the batch with the given index:
+    /// it's the actual reason for the failed transaction and `BatchTxFailed` for the rest ones.
+    pub fn tx_error(&self, tx_index: usize) -> TxExecutionError {
+        if tx_index == self.index {
+            self.error
+        } else {
+            TxExecutionError::BatchTxFailed
+        }
+    }
+}
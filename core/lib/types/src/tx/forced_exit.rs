@@ -13,7 +13,7 @@ use zksync_crypto::franklin_crypto::eddsa::PrivateKey;
 use zksync_crypto::params::{max_account_id, max_token_id};
 use zksync_utils::BigUintSerdeAsRadix10Str;
 
-use super::{TxSignature, VerifiedSignatureCache};
+use super::{TimeRange, TxSignature, VerifiedSignatureCache};
 
 /// `ForcedExit` transaction is used to withdraw funds from an unowned
 /// account to its corresponding L1 address.
@@ -40,6 +40,9 @@ pub struct ForcedExit {
     pub fee: BigUint,
     /// Current initiator account nonce.
     pub nonce: Nonce,
+    /// Time range when the transaction is valid.
+    #[serde(flatten)]
+    pub time_range: TimeRange,
     /// Transaction zkSync signature.
     pub signature: TxSignature,
     #[serde(skip)]
@@ -60,6 +63,7 @@ impl ForcedExit {
         token: TokenId,
         fee: BigUint,
         nonce: Nonce,
+        time_range: TimeRange,
         signature: Option<TxSignature>,
     ) -> Self {
         let mut tx = Self {
@@ -68,6 +72,7 @@ impl ForcedExit {
             token,
             fee,
             nonce,
+            time_range,
             signature: signature.clone().unwrap_or_default(),
             cached_signer: VerifiedSignatureCache::NotCached,
         };
@@ -85,9 +90,18 @@ impl ForcedExit {
         token: TokenId,
         fee: BigUint,
         nonce: Nonce,
+        time_range: TimeRange,
         private_key: &PrivateKey<Engine>,
     ) -> Result<Self, anyhow::Error> {
-        let mut tx = Self::new(initiator_account_id, target, token, fee, nonce, None);
+        let mut tx = Self::new(
+            initiator_account_id,
+            target,
+            token,
+            fee,
+            nonce,
+            time_range,
+            None,
+        );
         tx.signature = TxSignature::sign_musig(private_key, &tx.get_bytes());
         if !tx.check_correctness() {
             bail!("Transfer is incorrect, check amounts");
@@ -104,6 +118,7 @@ impl ForcedExit {
         out.extend_from_slice(&self.token.to_be_bytes());
         out.extend_from_slice(&pack_fee_amount(&self.fee));
         out.extend_from_slice(&self.nonce.to_be_bytes());
+        out.extend_from_slice(&self.time_range.to_be_bytes());
         out
     }
 
@@ -112,11 +127,13 @@ impl ForcedExit {
     /// - `initiator_account_id` field must be within supported range.
     /// - `token` field must be within supported range.
     /// - `fee` field must represent a packable value.
+    /// - `time_range` field must represent a non-empty range.
     /// - zkSync signature must correspond to the PubKeyHash of the account.
     pub fn check_correctness(&mut self) -> bool {
         let mut valid = is_fee_amount_packable(&self.fee)
             && self.initiator_account_id <= max_account_id()
-            && self.token <= max_token_id();
+            && self.token <= max_token_id()
+            && self.time_range.check_correctness();
 
         if valid {
             let signer = self.verify_signature();
@@ -1,9 +1,15 @@
 //! zkSync network L2 transactions.
 
+mod cancel;
 mod change_pubkey;
 mod close;
+mod execution_error;
 mod forced_exit;
+mod order;
 mod primitives;
+mod simulation;
+mod swap;
+mod trace;
 mod transfer;
 mod utils;
 mod withdraw;
@@ -16,8 +22,14 @@ mod tests;
 #[doc(hidden)]
 pub use self::close::Close;
 pub use self::{
+    cancel::TxCancelRequest,
     change_pubkey::ChangePubKey,
+    execution_error::{BatchExecutionError, TxExecutionError},
     forced_exit::ForcedExit,
+    order::Order,
+    simulation::{SimulatedFee, TxSimulationResult},
+    swap::Swap,
+    trace::{TracedFee, TxExecutionTrace},
     transfer::Transfer,
     withdraw::Withdraw,
     zksync_tx::{EthSignData, SignedZkSyncTx, ZkSyncTx},
@@ -25,9 +37,17 @@ pub use self::{
 
 // Re-export primitives associated with transactions.
 pub use self::primitives::{
-    eip1271_signature::EIP1271Signature, eth_signature::TxEthSignature,
-    packed_eth_signature::PackedEthSignature, packed_public_key::PackedPublicKey,
-    packed_signature::PackedSignature, signature::TxSignature, tx_hash::TxHash,
+    eip1271_signature::EIP1271Signature,
+    eip712_signature::{
+        Eip712Domain, Eip712Member, Eip712Struct, Eip712StructValue, Eip712Value, TxsBatch,
+    },
+    eth_signature::TxEthSignature,
+    packed_eth_signature::PackedEthSignature,
+    packed_public_key::PackedPublicKey,
+    packed_signature::PackedSignature,
+    signature::TxSignature,
+    time_range::TimeRange,
+    tx_hash::TxHash,
 };
 
 pub(crate) use self::primitives::signature_cache::VerifiedSignatureCache;
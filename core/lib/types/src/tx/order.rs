@@ -0,0 +1,200 @@
+use crate::{
+    helpers::{is_token_amount_packable, pack_token_amount},
+    AccountId, Nonce, TokenId,
+};
+use num::{BigUint, Zero};
+
+use crate::account::PubKeyHash;
+use crate::Engine;
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+use zksync_basic_types::Address;
+use zksync_crypto::franklin_crypto::eddsa::PrivateKey;
+use zksync_crypto::params::{max_account_id, max_token_id, PRICE_BIT_WIDTH};
+use zksync_utils::{BigUintPairSerdeAsRadix10Str, BigUintSerdeAsRadix10Str};
+
+use super::{TxSignature, VerifiedSignatureCache};
+
+/// `Order` is a signed intention of the account to exchange one token for another.
+///
+/// Order is not a transaction by itself: two matching orders are executed
+/// atomically by the `Swap` transaction. Order binds the amount of `token_sell`
+/// that can be sold to the `[min_amount, max_amount]` range, and the exchange
+/// rate to the `price` ratio: for every `price.0` units of `token_sell` the account
+/// wants to receive at least `price.1` units of `token_buy`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    /// zkSync network account ID of the order owner.
+    pub account_id: AccountId,
+    /// Address of the account which receives the bought tokens.
+    pub recipient_address: Address,
+    /// Current nonce of the order owner account.
+    pub nonce: Nonce,
+    /// Token to be sold.
+    pub token_sell: TokenId,
+    /// Token to be bought.
+    pub token_buy: TokenId,
+    /// Exchange rate as the `(token_sell, token_buy)` amounts ratio.
+    #[serde(with = "BigUintPairSerdeAsRadix10Str")]
+    pub price: (BigUint, BigUint),
+    /// Minimal amount of `token_sell` to be sold.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub min_amount: BigUint,
+    /// Maximal amount of `token_sell` to be sold.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub max_amount: BigUint,
+    /// Order zkSync signature.
+    pub signature: TxSignature,
+    #[serde(skip)]
+    cached_signer: VerifiedSignatureCache,
+}
+
+impl Order {
+    /// Identifier of the signed order message. It differs from all the transaction types,
+    /// so the order signature can't be reused as a transaction signature and vice versa.
+    pub const MSG_TYPE: u8 = b'o';
+
+    /// Creates order from all the required fields.
+    ///
+    /// While `signature` field is mandatory for new orders, it may be `None`
+    /// in some cases (e.g. when restoring the network state from the L1 contract data).
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        account_id: AccountId,
+        recipient_address: Address,
+        nonce: Nonce,
+        token_sell: TokenId,
+        token_buy: TokenId,
+        price: (BigUint, BigUint),
+        min_amount: BigUint,
+        max_amount: BigUint,
+        signature: Option<TxSignature>,
+    ) -> Self {
+        let mut order = Self {
+            account_id,
+            recipient_address,
+            nonce,
+            token_sell,
+            token_buy,
+            price,
+            min_amount,
+            max_amount,
+            signature: signature.clone().unwrap_or_default(),
+            cached_signer: VerifiedSignatureCache::NotCached,
+        };
+        if signature.is_some() {
+            order.cached_signer = VerifiedSignatureCache::Cached(order.verify_signature());
+        }
+        order
+    }
+
+    /// Creates a signed order using private key and
+    /// checks for the order correcteness.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_signed(
+        account_id: AccountId,
+        recipient_address: Address,
+        nonce: Nonce,
+        token_sell: TokenId,
+        token_buy: TokenId,
+        price: (BigUint, BigUint),
+        min_amount: BigUint,
+        max_amount: BigUint,
+        private_key: &PrivateKey<Engine>,
+    ) -> Result<Self, anyhow::Error> {
+        let mut order = Self::new(
+            account_id,
+            recipient_address,
+            nonce,
+            token_sell,
+            token_buy,
+            price,
+            min_amount,
+            max_amount,
+            None,
+        );
+        order.signature = TxSignature::sign_musig(private_key, &order.get_bytes());
+        if !order.check_correctness() {
+            bail!("Order is incorrect, check tokens, price and amounts");
+        }
+        Ok(order)
+    }
+
+    /// Encodes the order data as the byte sequence according to the zkSync protocol.
+    pub fn get_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&[Self::MSG_TYPE]);
+        out.extend_from_slice(&self.account_id.to_be_bytes());
+        out.extend_from_slice(&self.recipient_address.as_bytes());
+        out.extend_from_slice(&self.nonce.to_be_bytes());
+        out.extend_from_slice(&self.token_sell.to_be_bytes());
+        out.extend_from_slice(&self.token_buy.to_be_bytes());
+        out.extend_from_slice(&price_to_be_bytes(&self.price.0));
+        out.extend_from_slice(&price_to_be_bytes(&self.price.1));
+        out.extend_from_slice(&pack_token_amount(&self.min_amount));
+        out.extend_from_slice(&pack_token_amount(&self.max_amount));
+        out
+    }
+
+    /// Verifies the order correctness:
+    ///
+    /// - `account_id` field must be within supported range.
+    /// - `token_sell` and `token_buy` fields must be different and within supported range.
+    /// - both `price` components must be non-zero and fit into `PRICE_BIT_WIDTH` bits.
+    /// - `min_amount` and `max_amount` must represent packable values and a non-empty range.
+    /// - `recipient_address` must be a non-zero address.
+    /// - zkSync signature must correspond to the PubKeyHash of the account.
+    pub fn check_correctness(&mut self) -> bool {
+        let mut valid = self.account_id <= max_account_id()
+            && self.token_sell <= max_token_id()
+            && self.token_buy <= max_token_id()
+            && self.token_sell != self.token_buy
+            && is_price_correct(&self.price.0)
+            && is_price_correct(&self.price.1)
+            && is_token_amount_packable(&self.min_amount)
+            && is_token_amount_packable(&self.max_amount)
+            && self.min_amount <= self.max_amount
+            && self.recipient_address != Address::zero();
+
+        if valid {
+            let signer = self.verify_signature();
+            valid = valid && signer.is_some();
+            self.cached_signer = VerifiedSignatureCache::Cached(signer);
+        }
+        valid
+    }
+
+    /// Checks whether the amount of `token_sell` to be sold fits into the order bounds.
+    pub fn is_amount_acceptable(&self, amount_sell: &BigUint) -> bool {
+        &self.min_amount <= amount_sell && amount_sell <= &self.max_amount
+    }
+
+    /// Checks whether selling `amount_sell` for `amount_buy` is not worse than the order price.
+    pub fn is_price_acceptable(&self, amount_sell: &BigUint, amount_buy: &BigUint) -> bool {
+        amount_buy * &self.price.0 >= amount_sell * &self.price.1
+    }
+
+    /// Restores the `PubKeyHash` from the order signature.
+    pub fn verify_signature(&self) -> Option<PubKeyHash> {
+        if let VerifiedSignatureCache::Cached(cached_signer) = &self.cached_signer {
+            cached_signer.clone()
+        } else if let Some(pub_key) = self.signature.verify_musig(&self.get_bytes()) {
+            Some(PubKeyHash::from_pubkey(&pub_key))
+        } else {
+            None
+        }
+    }
+}
+
+fn is_price_correct(price: &BigUint) -> bool {
+    !price.is_zero() && price.bits() <= PRICE_BIT_WIDTH
+}
+
+fn price_to_be_bytes(price: &BigUint) -> Vec<u8> {
+    let bytes = price.to_bytes_be();
+    let width = PRICE_BIT_WIDTH / 8;
+    let mut out = vec![0u8; width.saturating_sub(bytes.len())];
+    out.extend_from_slice(&bytes);
+    out
+}
@@ -0,0 +1,393 @@
+//! Typed structured data of the transactions according to EIP-712.
+//!
+//! Unlike the human-readable messages signed via `personal_sign`, typed data
+//! is displayed by the wallets (including the hardware ones) field by field.
+//! Signed hash is bound to the chain id and the zkSync contract address through
+//! the domain separator, so the signature can't be replayed on another network.
+
+use std::collections::BTreeMap;
+
+use num::BigUint;
+use parity_crypto::Keccak256;
+use serde_json::{json, Map, Value};
+use zksync_basic_types::{Address, H256, U256};
+
+use crate::tx::{ChangePubKey, ForcedExit, Transfer, Withdraw, ZkSyncTx};
+
+/// Value of the typed struct member.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Eip712Value {
+    /// Any of the `uint<N>` types.
+    Uint(U256),
+    Address(Address),
+    /// Any of the `bytes<N>` types.
+    FixedBytes(Vec<u8>),
+    String(String),
+    /// Array of the structs of the same type, e.g. `Transfer[]`.
+    StructArray(Vec<Eip712StructValue>),
+}
+
+impl Eip712Value {
+    /// Encodes the value as the 32-byte word according to the `encodeData` rules.
+    fn encode(&self) -> [u8; 32] {
+        let mut word = [0u8; 32];
+        match self {
+            Eip712Value::Uint(value) => value.to_big_endian(&mut word),
+            Eip712Value::Address(address) => word[12..].copy_from_slice(address.as_bytes()),
+            Eip712Value::FixedBytes(bytes) => word[..bytes.len()].copy_from_slice(bytes),
+            Eip712Value::String(value) => word = value.as_bytes().keccak256(),
+            Eip712Value::StructArray(items) => {
+                word = items
+                    .iter()
+                    .flat_map(|item| item.hash_struct().to_fixed_bytes().to_vec())
+                    .collect::<Vec<u8>>()
+                    .keccak256()
+            }
+        }
+        word
+    }
+
+    fn to_json(&self) -> Value {
+        match self {
+            Eip712Value::Uint(value) => Value::String(value.to_string()),
+            Eip712Value::Address(address) => json!(address),
+            Eip712Value::FixedBytes(bytes) => Value::String(format!("0x{}", hex::encode(bytes))),
+            Eip712Value::String(value) => Value::String(value.clone()),
+            Eip712Value::StructArray(items) => {
+                items.iter().map(|item| message_json(item)).collect()
+            }
+        }
+    }
+}
+
+impl From<&BigUint> for Eip712Value {
+    fn from(value: &BigUint) -> Self {
+        Eip712Value::Uint(U256::from_big_endian(&value.to_bytes_be()))
+    }
+}
+
+/// Member of the typed struct: its name, Solidity type and value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Eip712Member {
+    pub name: &'static str,
+    pub kind: &'static str,
+    pub value: Eip712Value,
+}
+
+impl Eip712Member {
+    pub fn new(name: &'static str, kind: &'static str, value: Eip712Value) -> Self {
+        Self { name, kind, value }
+    }
+
+    fn uint(name: &'static str, kind: &'static str, value: impl Into<U256>) -> Self {
+        Self::new(name, kind, Eip712Value::Uint(value.into()))
+    }
+
+    fn address(name: &'static str, value: Address) -> Self {
+        Self::new(name, "address", Eip712Value::Address(value))
+    }
+}
+
+/// Values of the struct members, i.e. the `message` of the typed data.
+fn message_json(data: &dyn Eip712Struct) -> Value {
+    data.members()
+        .into_iter()
+        .map(|member| (member.name.to_owned(), member.value.to_json()))
+        .collect::<Map<_, _>>()
+        .into()
+}
+
+/// Encodes the single struct type without the referenced ones, e.g. `Mail(address from,address to)`.
+fn type_string(type_name: &str, members: &[Eip712Member]) -> String {
+    let members = members
+        .iter()
+        .map(|member| format!("{} {}", member.kind, member.name))
+        .collect::<Vec<_>>();
+    format!("{}({})", type_name, members.join(","))
+}
+
+/// Structure which can be signed as the EIP-712 typed data.
+///
+/// Members are either atomic values or arrays of other structs, which is enough
+/// for the zkSync transactions and their batches.
+pub trait Eip712Struct {
+    /// Name of the struct type, e.g. `Transfer`.
+    fn type_name(&self) -> &'static str;
+
+    /// Members of the struct in the order of their declaration.
+    fn members(&self) -> Vec<Eip712Member>;
+
+    /// Returns all the struct types referenced by the members, directly or not, sorted by name.
+    fn referenced_types(&self) -> BTreeMap<&'static str, Eip712StructValue> {
+        let mut types = BTreeMap::new();
+        for member in self.members() {
+            if let Eip712Value::StructArray(items) = member.value {
+                for item in items {
+                    types.extend(item.referenced_types());
+                    types.insert(item.type_name, item);
+                }
+            }
+        }
+        types
+    }
+
+    /// Returns the `encodeType` of the struct, e.g. `Mail(address from,address to)`.
+    /// Referenced struct types are appended in the alphabetical order.
+    fn encode_type(&self) -> String {
+        let mut encoded = type_string(self.type_name(), &self.members());
+        for referenced in self.referenced_types().values() {
+            encoded.push_str(&type_string(referenced.type_name, &referenced.members));
+        }
+        encoded
+    }
+
+    /// Returns the `hashStruct` of the struct.
+    fn hash_struct(&self) -> H256 {
+        let members = self.members();
+        let mut bytes = Vec::with_capacity(32 * (members.len() + 1));
+        bytes.extend_from_slice(&self.encode_type().as_bytes().keccak256());
+        for member in members {
+            bytes.extend_from_slice(&member.value.encode());
+        }
+        bytes.keccak256().into()
+    }
+}
+
+/// Type name and members of the struct, captured to be used as the array element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Eip712StructValue {
+    pub type_name: &'static str,
+    pub members: Vec<Eip712Member>,
+}
+
+impl Eip712StructValue {
+    pub fn new(data: &dyn Eip712Struct) -> Self {
+        Self {
+            type_name: data.type_name(),
+            members: data.members(),
+        }
+    }
+}
+
+impl Eip712Struct for Eip712StructValue {
+    fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    fn members(&self) -> Vec<Eip712Member> {
+        self.members.clone()
+    }
+}
+
+/// Domain of the zkSync typed data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Eip712Domain {
+    pub chain_id: u64,
+    /// Address of the zkSync contract.
+    pub verifying_contract: Address,
+}
+
+impl Eip712Domain {
+    pub const NAME: &'static str = "zkSync";
+    pub const VERSION: &'static str = "1";
+
+    pub fn new(chain_id: u64, verifying_contract: Address) -> Self {
+        Self {
+            chain_id,
+            verifying_contract,
+        }
+    }
+
+    /// Returns the domain separator.
+    pub fn separator(&self) -> H256 {
+        self.hash_struct()
+    }
+
+    /// Returns the hash which is actually signed by the `eth_signTypedData` method:
+    /// `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(data))`.
+    pub fn typed_data_hash(&self, data: &dyn Eip712Struct) -> H256 {
+        let mut bytes = Vec::with_capacity(2 + 32 + 32);
+        bytes.extend_from_slice(b"\x19\x01");
+        bytes.extend_from_slice(self.separator().as_bytes());
+        bytes.extend_from_slice(data.hash_struct().as_bytes());
+        bytes.keccak256().into()
+    }
+
+    /// Returns the typed data in the JSON format expected by the `eth_signTypedData_v4` method.
+    pub fn typed_data_json(&self, data: &dyn Eip712Struct) -> Value {
+        fn type_json(data: &dyn Eip712Struct) -> Value {
+            data.members()
+                .iter()
+                .map(|member| json!({ "name": member.name, "type": member.kind }))
+                .collect()
+        }
+
+        let mut types = Map::new();
+        types.insert(self.type_name().to_owned(), type_json(self));
+        types.insert(data.type_name().to_owned(), type_json(data));
+        for (type_name, referenced) in data.referenced_types() {
+            types.insert(type_name.to_owned(), type_json(&referenced));
+        }
+
+        json!({
+            "types": types,
+            "primaryType": data.type_name(),
+            "domain": message_json(self),
+            "message": message_json(data),
+        })
+    }
+}
+
+impl Eip712Struct for Eip712Domain {
+    fn type_name(&self) -> &'static str {
+        "EIP712Domain"
+    }
+
+    fn members(&self) -> Vec<Eip712Member> {
+        vec![
+            Eip712Member::new("name", "string", Eip712Value::String(Self::NAME.into())),
+            Eip712Member::new(
+                "version",
+                "string",
+                Eip712Value::String(Self::VERSION.into()),
+            ),
+            Eip712Member::uint("chainId", "uint256", self.chain_id),
+            Eip712Member::address("verifyingContract", self.verifying_contract),
+        ]
+    }
+}
+
+/// Batch of transactions signed with the single Ethereum signature.
+///
+/// EIP-712 arrays can't hold the structs of different types, so the transactions
+/// are grouped into the per-type arrays. All the transactions of the signed batch
+/// belong to the signer, so their order is still determined by the nonces.
+/// Only the arrays of the types present in the batch are the members of the struct.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TxsBatch {
+    pub change_pub_keys: Vec<Eip712StructValue>,
+    pub forced_exits: Vec<Eip712StructValue>,
+    pub transfers: Vec<Eip712StructValue>,
+    pub withdraws: Vec<Eip712StructValue>,
+}
+
+impl TxsBatch {
+    /// Returns `None` if any of the transactions doesn't support typed data signatures.
+    pub fn new<'a>(txs: impl IntoIterator<Item = &'a ZkSyncTx>) -> Option<Self> {
+        let mut batch = Self::default();
+        for tx in txs {
+            let typed_tx = Eip712StructValue::new(tx.eip712_struct()?);
+            match tx {
+                ZkSyncTx::ChangePubKey(_) => batch.change_pub_keys.push(typed_tx),
+                ZkSyncTx::ForcedExit(_) => batch.forced_exits.push(typed_tx),
+                ZkSyncTx::Transfer(_) => batch.transfers.push(typed_tx),
+                ZkSyncTx::Withdraw(_) => batch.withdraws.push(typed_tx),
+                ZkSyncTx::Close(_) | ZkSyncTx::Swap(_) => return None,
+            }
+        }
+        Some(batch)
+    }
+}
+
+impl Eip712Struct for TxsBatch {
+    fn type_name(&self) -> &'static str {
+        "Batch"
+    }
+
+    fn members(&self) -> Vec<Eip712Member> {
+        let arrays = [
+            ("changePubKeys", "ChangePubKey[]", &self.change_pub_keys),
+            ("forcedExits", "ForcedExit[]", &self.forced_exits),
+            ("transfers", "Transfer[]", &self.transfers),
+            ("withdraws", "Withdraw[]", &self.withdraws),
+        ];
+        arrays
+            .iter()
+            .filter(|(_, _, txs)| !txs.is_empty())
+            .map(|(name, kind, txs)| {
+                Eip712Member::new(*name, *kind, Eip712Value::StructArray(txs.to_vec()))
+            })
+            .collect()
+    }
+}
+
+impl Eip712Struct for Transfer {
+    fn type_name(&self) -> &'static str {
+        "Transfer"
+    }
+
+    fn members(&self) -> Vec<Eip712Member> {
+        vec![
+            Eip712Member::uint("accountId", "uint32", self.account_id),
+            Eip712Member::address("from", self.from),
+            Eip712Member::address("to", self.to),
+            Eip712Member::uint("token", "uint16", self.token),
+            Eip712Member::new("amount", "uint256", (&self.amount).into()),
+            Eip712Member::new("fee", "uint256", (&self.fee).into()),
+            Eip712Member::uint("nonce", "uint32", self.nonce),
+            Eip712Member::uint("validFrom", "uint64", self.time_range.valid_from),
+            Eip712Member::uint("validUntil", "uint64", self.time_range.valid_until),
+        ]
+    }
+}
+
+impl Eip712Struct for Withdraw {
+    fn type_name(&self) -> &'static str {
+        "Withdraw"
+    }
+
+    fn members(&self) -> Vec<Eip712Member> {
+        vec![
+            Eip712Member::uint("accountId", "uint32", self.account_id),
+            Eip712Member::address("from", self.from),
+            Eip712Member::address("to", self.to),
+            Eip712Member::uint("token", "uint16", self.token),
+            Eip712Member::new("amount", "uint256", (&self.amount).into()),
+            Eip712Member::new("fee", "uint256", (&self.fee).into()),
+            Eip712Member::uint("nonce", "uint32", self.nonce),
+            Eip712Member::uint("validFrom", "uint64", self.time_range.valid_from),
+            Eip712Member::uint("validUntil", "uint64", self.time_range.valid_until),
+        ]
+    }
+}
+
+impl Eip712Struct for ForcedExit {
+    fn type_name(&self) -> &'static str {
+        "ForcedExit"
+    }
+
+    fn members(&self) -> Vec<Eip712Member> {
+        vec![
+            Eip712Member::uint("initiatorAccountId", "uint32", self.initiator_account_id),
+            Eip712Member::address("target", self.target),
+            Eip712Member::uint("token", "uint16", self.token),
+            Eip712Member::new("fee", "uint256", (&self.fee).into()),
+            Eip712Member::uint("nonce", "uint32", self.nonce),
+            Eip712Member::uint("validFrom", "uint64", self.time_range.valid_from),
+            Eip712Member::uint("validUntil", "uint64", self.time_range.valid_until),
+        ]
+    }
+}
+
+impl Eip712Struct for ChangePubKey {
+    fn type_name(&self) -> &'static str {
+        "ChangePubKey"
+    }
+
+    fn members(&self) -> Vec<Eip712Member> {
+        vec![
+            Eip712Member::uint("accountId", "uint32", self.account_id),
+            Eip712Member::address("account", self.account),
+            Eip712Member::new(
+                "newPkHash",
+                "bytes20",
+                Eip712Value::FixedBytes(self.new_pk_hash.data.to_vec()),
+            ),
+            Eip712Member::uint("feeToken", "uint16", self.fee_token),
+            Eip712Member::new("fee", "uint256", (&self.fee).into()),
+            Eip712Member::uint("nonce", "uint32", self.nonce),
+            Eip712Member::uint("validFrom", "uint64", self.time_range.valid_from),
+            Eip712Member::uint("validUntil", "uint64", self.time_range.valid_until),
+        ]
+    }
+}
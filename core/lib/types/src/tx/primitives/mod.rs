@@ -1,8 +1,10 @@
 pub mod eip1271_signature;
+pub mod eip712_signature;
 pub mod eth_signature;
 pub mod packed_eth_signature;
 pub mod packed_public_key;
 pub mod packed_signature;
 pub mod signature;
 pub mod signature_cache;
+pub mod time_range;
 pub mod tx_hash;
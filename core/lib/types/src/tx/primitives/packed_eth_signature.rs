@@ -7,6 +7,8 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use zksync_basic_types::{Address, H256};
 use zksync_utils::ZeroPrefixHexSerde;
 
+use crate::tx::{Eip712Domain, Eip712Struct};
+
 /// Struct used for working with ethereum signatures created using eth_sign (using geth, ethers.js, etc)
 /// message is serialized as 65 bytes long `0x` prefixed string.
 ///
@@ -71,6 +73,28 @@ impl PackedEthSignature {
         Ok(public_to_address(&public_key))
     }
 
+    /// Signs the EIP-712 typed data, results are identical to signature created
+    /// using `eth_signTypedData_v4` method of the Ethereum clients.
+    pub fn sign_typed_data(
+        private_key: &H256,
+        domain: &Eip712Domain,
+        data: &dyn Eip712Struct,
+    ) -> Result<PackedEthSignature, anyhow::Error> {
+        let secret_key = (*private_key).into();
+        let signature = sign(&secret_key, &domain.typed_data_hash(data))?;
+        Ok(PackedEthSignature(signature))
+    }
+
+    /// Checks the signature of the EIP-712 typed data and returns ethereum address of the signer.
+    pub fn typed_data_recover_signer(
+        &self,
+        domain: &Eip712Domain,
+        data: &dyn Eip712Struct,
+    ) -> Result<Address, anyhow::Error> {
+        let public_key = recover(&self.0, &domain.typed_data_hash(data))?;
+        Ok(public_to_address(&public_key))
+    }
+
     /// Get Ethereum address from private key.
     pub fn address_from_private_key(private_key: &H256) -> Result<Address, anyhow::Error> {
         Ok(KeyPair::from_secret((*private_key).into())?.address())
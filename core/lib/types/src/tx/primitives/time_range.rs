@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+
+/// Time window (UNIX timestamps in seconds, both bounds inclusive) within which
+/// the transaction can be executed.
+///
+/// Time range is a part of the signed transaction data, so it can't be changed
+/// without invalidating the signature. The default range doesn't restrict the
+/// execution time, which keeps the transactions without time range valid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TimeRange {
+    /// The earliest moment when the transaction can be executed.
+    pub valid_from: u64,
+    /// The latest moment when the transaction can be executed.
+    pub valid_until: u64,
+}
+
+impl Default for TimeRange {
+    fn default() -> Self {
+        Self {
+            valid_from: 0,
+            valid_until: u64::max_value(),
+        }
+    }
+}
+
+impl TimeRange {
+    pub fn new(valid_from: u64, valid_until: u64) -> Self {
+        Self {
+            valid_from,
+            valid_until,
+        }
+    }
+
+    /// Encodes the time range as the byte sequence according to the zkSync protocol.
+    pub fn to_be_bytes(&self) -> [u8; 16] {
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&self.valid_from.to_be_bytes());
+        bytes[8..].copy_from_slice(&self.valid_until.to_be_bytes());
+        bytes
+    }
+
+    /// Checks that the range is not empty.
+    pub fn check_correctness(&self) -> bool {
+        self.valid_from <= self.valid_until
+    }
+
+    /// Checks whether the transaction can be executed at the given moment.
+    pub fn is_valid(&self, timestamp: u64) -> bool {
+        self.valid_from <= timestamp && timestamp <= self.valid_until
+    }
+
+    /// Checks whether the transaction can't be executed at the given moment or later.
+    pub fn is_expired(&self, timestamp: u64) -> bool {
+        self.valid_until < timestamp
+    }
+}
@@ -0,0 +1,58 @@
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+use zksync_utils::BigUintSerdeAsRadix10Str;
+
+use crate::{tx::TxExecutionError, AccountUpdates, TokenId};
+
+/// Fee which is charged for the transaction execution.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedFee {
+    pub token: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+}
+
+/// Outcome of the transaction execution against the current (including the pending block)
+/// network state. Changes made by the transaction are never applied to the state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxSimulationResult {
+    pub success: bool,
+    /// Account changes which would be made by the transaction.
+    pub updates: AccountUpdates,
+    /// Number of block chunks required for the transaction.
+    pub chunks: usize,
+    /// Fee which would be charged, if the transaction pays any.
+    pub fee: Option<SimulatedFee>,
+    /// Reason of the failure, if the transaction can't be executed.
+    pub fail_reason: Option<String>,
+    /// Machine-readable reason of the failure.
+    pub fail_code: Option<TxExecutionError>,
+}
+
+impl TxSimulationResult {
+    /// Creates a result of the successful execution.
+    pub fn success(updates: AccountUpdates, chunks: usize, fee: Option<SimulatedFee>) -> Self {
+        Self {
+            success: true,
+            updates,
+            chunks,
+            fee,
+            fail_reason: None,
+            fail_code: None,
+        }
+    }
+
+    /// Creates a result of the failed execution.
+    pub fn failure(fail_reason: String, fail_code: TxExecutionError) -> Self {
+        Self {
+            success: false,
+            updates: AccountUpdates::new(),
+            chunks: 0,
+            fee: None,
+            fail_reason: Some(fail_reason),
+            fail_code: Some(fail_code),
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{
+    helpers::{
+        is_fee_amount_packable, is_token_amount_packable, pack_fee_amount, pack_token_amount,
+    },
+    AccountId, Nonce, TokenId,
+};
+use num::BigUint;
+
+use crate::account::PubKeyHash;
+use crate::Engine;
+use anyhow::bail;
+use serde::{Deserialize, Serialize};
+use zksync_basic_types::Address;
+use zksync_crypto::franklin_crypto::eddsa::PrivateKey;
+use zksync_crypto::params::{max_account_id, max_token_id};
+use zksync_utils::{BigUintPairSerdeAsRadix10Str, BigUintSerdeAsRadix10Str};
+
+use super::{Order, TimeRange, TxSignature, VerifiedSignatureCache};
+
+/// `Swap` transaction atomically exchanges tokens between the owners of two matching orders.
+///
+/// Owner of the first order sells `amounts.0` of its `token_sell` to the owner of the
+/// second order, and the owner of the second order sells `amounts.1` of its `token_sell`
+/// in return. Bought tokens are credited to the recipients specified in the orders.
+/// Either both transfers are executed, or none of them.
+///
+/// Transaction is signed and paid by the submitter, which may be an owner of one of
+/// the orders or a third party (e.g. an exchange matching the orders).
+/// Submitter nonce is incremented before the orders nonces are checked,
+/// so if the submitter owns one of the orders, the order nonce must be `nonce` plus one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Swap {
+    /// zkSync network account ID of the transaction submitter.
+    pub submitter_id: AccountId,
+    /// Address of the transaction submitter.
+    pub submitter_address: Address,
+    /// Current submitter account nonce.
+    pub nonce: Nonce,
+    /// Orders to be matched.
+    pub orders: (Order, Order),
+    /// Amounts of `token_sell` sold by the owners of the first and the second orders.
+    #[serde(with = "BigUintPairSerdeAsRadix10Str")]
+    pub amounts: (BigUint, BigUint),
+    /// Token in which fee is paid.
+    pub fee_token: TokenId,
+    /// Fee for the transaction.
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub fee: BigUint,
+    /// Time range when the transaction is valid.
+    #[serde(flatten)]
+    pub time_range: TimeRange,
+    /// Transaction zkSync signature.
+    pub signature: TxSignature,
+    #[serde(skip)]
+    cached_signer: VerifiedSignatureCache,
+}
+
+impl Swap {
+    /// Unique identifier of the transaction type in zkSync network.
+    pub const TX_TYPE: u8 = 9;
+
+    /// Creates transaction from all the required fields.
+    ///
+    /// While `signature` field is mandatory for new transactions, it may be `None`
+    /// in some cases (e.g. when restoring the network state from the L1 contract data).
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        submitter_id: AccountId,
+        submitter_address: Address,
+        nonce: Nonce,
+        orders: (Order, Order),
+        amounts: (BigUint, BigUint),
+        fee_token: TokenId,
+        fee: BigUint,
+        time_range: TimeRange,
+        signature: Option<TxSignature>,
+    ) -> Self {
+        let mut tx = Self {
+            submitter_id,
+            submitter_address,
+            nonce,
+            orders,
+            amounts,
+            fee_token,
+            fee,
+            time_range,
+            signature: signature.clone().unwrap_or_default(),
+            cached_signer: VerifiedSignatureCache::NotCached,
+        };
+        if signature.is_some() {
+            tx.cached_signer = VerifiedSignatureCache::Cached(tx.verify_signature());
+        }
+        tx
+    }
+
+    /// Creates a signed transaction using private key and
+    /// checks for the transaction correcteness.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_signed(
+        submitter_id: AccountId,
+        submitter_address: Address,
+        nonce: Nonce,
+        orders: (Order, Order),
+        amounts: (BigUint, BigUint),
+        fee_token: TokenId,
+        fee: BigUint,
+        time_range: TimeRange,
+        private_key: &PrivateKey<Engine>,
+    ) -> Result<Self, anyhow::Error> {
+        let mut tx = Self::new(
+            submitter_id,
+            submitter_address,
+            nonce,
+            orders,
+            amounts,
+            fee_token,
+            fee,
+            time_range,
+            None,
+        );
+        tx.signature = TxSignature::sign_musig(private_key, &tx.get_bytes());
+        if !tx.check_correctness() {
+            bail!("Swap is incorrect, check orders and amounts");
+        }
+        Ok(tx)
+    }
+
+    /// Encodes the transaction data as the byte sequence according to the zkSync protocol.
+    ///
+    /// Orders are not included into the message, since they are signed by their owners:
+    /// submitter only commits to the matched accounts, tokens and amounts.
+    pub fn get_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&[Self::TX_TYPE]);
+        out.extend_from_slice(&self.submitter_id.to_be_bytes());
+        out.extend_from_slice(&self.submitter_address.as_bytes());
+        out.extend_from_slice(&self.nonce.to_be_bytes());
+        out.extend_from_slice(&self.orders.0.account_id.to_be_bytes());
+        out.extend_from_slice(&self.orders.1.account_id.to_be_bytes());
+        out.extend_from_slice(&self.orders.0.token_sell.to_be_bytes());
+        out.extend_from_slice(&self.orders.1.token_sell.to_be_bytes());
+        out.extend_from_slice(&pack_token_amount(&self.amounts.0));
+        out.extend_from_slice(&pack_token_amount(&self.amounts.1));
+        out.extend_from_slice(&self.fee_token.to_be_bytes());
+        out.extend_from_slice(&pack_fee_amount(&self.fee));
+        out.extend_from_slice(&self.time_range.to_be_bytes());
+        out
+    }
+
+    /// Verifies the transaction correctness:
+    ///
+    /// - `submitter_id` field must be within supported range.
+    /// - `fee_token` field must be within supported range.
+    /// - both orders must be correct and belong to the different accounts.
+    /// - token sold in one order must be the token bought in the other one.
+    /// - `amounts` fields must represent packable values.
+    /// - `fee` field must represent a packable value.
+    /// - `time_range` field must represent a non-empty range.
+    /// - zkSync signature must correspond to the PubKeyHash of the submitter account.
+    pub fn check_correctness(&mut self) -> bool {
+        let mut valid = self.orders.0.check_correctness()
+            && self.orders.1.check_correctness()
+            && self.orders.0.account_id != self.orders.1.account_id
+            && self.orders.0.token_sell == self.orders.1.token_buy
+            && self.orders.0.token_buy == self.orders.1.token_sell
+            && is_token_amount_packable(&self.amounts.0)
+            && is_token_amount_packable(&self.amounts.1)
+            && is_fee_amount_packable(&self.fee)
+            && self.submitter_id <= max_account_id()
+            && self.fee_token <= max_token_id()
+            && self.time_range.check_correctness();
+
+        if valid {
+            let signer = self.verify_signature();
+            valid = valid && signer.is_some();
+            self.cached_signer = VerifiedSignatureCache::Cached(signer);
+        }
+        valid
+    }
+
+    /// Restores the `PubKeyHash` of the submitter from the transaction signature.
+    pub fn verify_signature(&self) -> Option<PubKeyHash> {
+        if let VerifiedSignatureCache::Cached(cached_signer) = &self.cached_signer {
+            cached_signer.clone()
+        } else if let Some(pub_key) = self.signature.verify_musig(&self.get_bytes()) {
+            Some(PubKeyHash::from_pubkey(&pub_key))
+        } else {
+            None
+        }
+    }
+}
@@ -1,4 +1,5 @@
-use zksync_basic_types::Address;
+use parity_crypto::Keccak256;
+use zksync_basic_types::{Address, H256};
 use zksync_crypto::franklin_crypto::{
     eddsa::{PrivateKey, PublicKey},
     jubjub::FixedGenerators,
@@ -13,7 +14,7 @@ use serde::{Deserialize, Serialize};
 use super::*;
 use crate::{
     helpers::{pack_fee_amount, pack_token_amount},
-    AccountId, Engine, TokenId,
+    AccountId, Engine, PubKeyHash, TokenId,
 };
 
 fn gen_pk_and_msg() -> (PrivateKey<Engine>, Vec<Vec<u8>>) {
@@ -48,6 +49,7 @@ fn test_print_transfer_for_protocol() {
         BigUint::from(12_340_000_000_000u64),
         BigUint::from(56_700_000_000u64),
         rng.gen(),
+        TimeRange::new(1_600_000_000, 1_700_000_000),
         &key,
     )
     .expect("failed to sign transfer");
@@ -71,6 +73,14 @@ fn test_print_transfer_for_protocol() {
         ("amount", pack_token_amount(&transfer.amount)),
         ("fee", pack_fee_amount(&transfer.fee)),
         ("nonce", transfer.nonce.to_be_bytes().to_vec()),
+        (
+            "validFrom",
+            transfer.time_range.valid_from.to_be_bytes().to_vec(),
+        ),
+        (
+            "validUntil",
+            transfer.time_range.valid_until.to_be_bytes().to_vec(),
+        ),
     ];
     println!("Signed transaction fields:");
     let mut field_concat = Vec::new();
@@ -98,6 +108,7 @@ fn test_print_withdraw_for_protocol() {
         BigUint::from(12_340_000_000_000u64),
         BigUint::from(56_700_000_000u64),
         rng.gen(),
+        TimeRange::new(1_600_000_000, 1_700_000_000),
         &key,
     )
     .expect("failed to sign withdraw");
@@ -124,6 +135,14 @@ fn test_print_withdraw_for_protocol() {
         ),
         ("fee", pack_fee_amount(&withdraw.fee)),
         ("nonce", withdraw.nonce.to_be_bytes().to_vec()),
+        (
+            "validFrom",
+            withdraw.time_range.valid_from.to_be_bytes().to_vec(),
+        ),
+        (
+            "validUntil",
+            withdraw.time_range.valid_until.to_be_bytes().to_vec(),
+        ),
     ];
     println!("Signed transaction fields:");
     let mut field_concat = Vec::new();
@@ -294,3 +313,197 @@ fn test_check_signature() {
 
     assert_eq!(hex::encode(signature), "4e3298ac8cc13868dbbc94ad6fb41085ffe05b3c2eee22f88b05e69b7a5126aea723d7a3e7282ef5a32d9479c9c8dde52b3e3c462dd445dcd8158ebb6edb6000");
 }
+
+#[test]
+fn test_cancel_request_signature() {
+    let (pk, _) = gen_pk_and_msg();
+    let tx_hash = TxHash::from_slice(&[1u8; 32]).unwrap();
+    let request = TxCancelRequest::new_signed(tx_hash, &pk);
+
+    let expected_pub_key_hash = PubKeyHash::from_privkey(&pk);
+    assert_eq!(
+        request.verify_signature(),
+        Some(expected_pub_key_hash.clone())
+    );
+
+    // Signature must not be valid for another transaction.
+    let mut forged_request = request;
+    forged_request.tx_hash = TxHash::from_slice(&[2u8; 32]).unwrap();
+    assert_ne!(
+        forged_request.verify_signature(),
+        Some(expected_pub_key_hash)
+    );
+}
+
+#[test]
+fn execution_error_codes_roundtrip() {
+    for &error in TxExecutionError::ALL.iter() {
+        assert_eq!(TxExecutionError::from_code(error.code()), Some(error));
+    }
+    assert_eq!(TxExecutionError::from_code(0), None);
+}
+
+#[test]
+fn time_range_bounds() {
+    let time_range = TimeRange::new(10, 20);
+    assert!(time_range.check_correctness());
+    assert!(!TimeRange::new(20, 10).check_correctness());
+
+    assert!(!time_range.is_valid(9));
+    assert!(time_range.is_valid(10));
+    assert!(time_range.is_valid(20));
+    assert!(!time_range.is_valid(21));
+
+    assert!(!time_range.is_expired(20));
+    assert!(time_range.is_expired(21));
+    assert!(!TimeRange::default().is_expired(u64::max_value()));
+}
+
+#[test]
+fn time_range_serde() {
+    // Missing bounds don't restrict the execution time.
+    let time_range: TimeRange = serde_json::from_str(r#"{"validUntil":20}"#).unwrap();
+    assert_eq!(time_range, TimeRange::new(0, 20));
+
+    assert_eq!(
+        hex::encode(TimeRange::new(1, u64::max_value()).to_be_bytes()),
+        "0000000000000001ffffffffffffffff"
+    );
+}
+
+fn eip712_test_transfer() -> Transfer {
+    Transfer::new(
+        12,
+        Address::repeat_byte(0x11),
+        Address::repeat_byte(0x22),
+        1,
+        1_000_000u32.into(),
+        2000u32.into(),
+        7,
+        Default::default(),
+        None,
+    )
+}
+
+#[test]
+fn test_eip712_hashes() {
+    // Reference values are calculated according to the EIP-712 specification.
+    let domain = Eip712Domain::new(
+        9,
+        "5e6d086f5ec079adff4fb3774cdf3e8d6a34f7e9".parse().unwrap(),
+    );
+    assert_eq!(
+        hex::encode(domain.separator()),
+        "3e9dcb4d900f7c261ca348f561f776dda468fb09cc7a576593d7314151900141"
+    );
+
+    let transfer = eip712_test_transfer();
+    assert_eq!(
+        transfer.encode_type(),
+        "Transfer(uint32 accountId,address from,address to,uint16 token,uint256 amount,\
+         uint256 fee,uint32 nonce,uint64 validFrom,uint64 validUntil)"
+    );
+    assert_eq!(
+        hex::encode(transfer.hash_struct()),
+        "1375e09232eab4bc5a2ddac9398ecde5fc5597d0740389882d521cbe5529a0ca"
+    );
+    assert_eq!(
+        hex::encode(domain.typed_data_hash(&transfer)),
+        "3e281ea54161b5853bae07fcc7d658af4ee90a722e1778ced50387a241456db3"
+    );
+
+    let typed_data = domain.typed_data_json(&transfer);
+    assert_eq!(typed_data["primaryType"], "Transfer");
+    assert_eq!(typed_data["domain"]["chainId"], "9");
+    assert_eq!(typed_data["message"]["amount"], "1000000");
+    assert_eq!(
+        typed_data["types"]["EIP712Domain"]
+            .as_array()
+            .unwrap()
+            .len(),
+        4
+    );
+}
+
+#[test]
+fn test_eip712_batch() {
+    let transfer = eip712_test_transfer();
+    let mut other_transfer = eip712_test_transfer();
+    other_transfer.nonce += 1;
+    let txs = vec![
+        ZkSyncTx::from(transfer.clone()),
+        other_transfer.clone().into(),
+    ];
+    let batch = TxsBatch::new(&txs).unwrap();
+
+    // Transactions are encoded as the typed array, the referenced type follows the primary one.
+    assert_eq!(
+        batch.encode_type(),
+        format!("Batch(Transfer[] transfers){}", transfer.encode_type())
+    );
+    let mut encoded_txs = Vec::new();
+    encoded_txs.extend_from_slice(transfer.hash_struct().as_bytes());
+    encoded_txs.extend_from_slice(other_transfer.hash_struct().as_bytes());
+    let mut encoded_batch = Vec::new();
+    encoded_batch.extend_from_slice(&batch.encode_type().as_bytes().keccak256());
+    encoded_batch.extend_from_slice(&encoded_txs.keccak256());
+    assert_eq!(batch.hash_struct(), H256::from(encoded_batch.keccak256()));
+
+    let domain = Eip712Domain::new(9, Address::repeat_byte(0x33));
+    let typed_data = domain.typed_data_json(&batch);
+    assert_eq!(typed_data["primaryType"], "Batch");
+    assert_eq!(typed_data["types"]["Batch"][0]["type"], "Transfer[]");
+    assert_eq!(typed_data["types"]["Transfer"].as_array().unwrap().len(), 9);
+    assert_eq!(typed_data["message"]["transfers"][1]["nonce"], "8");
+}
+
+#[test]
+fn test_eip712_signature() {
+    let private_key = "0b43c0f5b5a13a7047408d1f8c8ad32ba5879902ea6212184e0a5d1157281d76"
+        .parse()
+        .unwrap();
+    let address = PackedEthSignature::address_from_private_key(&private_key).unwrap();
+    let domain = Eip712Domain::new(9, Address::repeat_byte(0x33));
+    let tx = ZkSyncTx::from(eip712_test_transfer());
+    let data = tx.eip712_struct().unwrap();
+
+    let signature = PackedEthSignature::sign_typed_data(&private_key, &domain, data).unwrap();
+    assert_eq!(
+        signature.typed_data_recover_signer(&domain, data).unwrap(),
+        address
+    );
+
+    // Signature is bound to the network and the contract.
+    let other_domain = Eip712Domain::new(1, domain.verifying_contract);
+    assert_ne!(
+        signature
+            .typed_data_recover_signer(&other_domain, data)
+            .unwrap(),
+        address
+    );
+
+    // Batch signature is bound to the contents of the batch.
+    let batch = TxsBatch::new(vec![&tx]).unwrap();
+    let signature = PackedEthSignature::sign_typed_data(&private_key, &domain, &batch).unwrap();
+    let other_batch = TxsBatch::new(vec![&tx, &tx]).unwrap();
+    assert_eq!(
+        signature
+            .typed_data_recover_signer(&domain, &batch)
+            .unwrap(),
+        address
+    );
+    assert_ne!(
+        signature
+            .typed_data_recover_signer(&domain, &other_batch)
+            .unwrap(),
+        address
+    );
+
+    let signature = TxEthSignature::EIP712Signature(signature);
+    let value = serde_json::to_value(&signature).unwrap();
+    assert_eq!(value["type"], "EIP712Signature");
+    assert_eq!(
+        serde_json::from_value::<TxEthSignature>(value).unwrap(),
+        signature
+    );
+}
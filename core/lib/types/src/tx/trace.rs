@@ -0,0 +1,29 @@
+use num::BigUint;
+use serde::{Deserialize, Serialize};
+use zksync_utils::BigUintSerdeAsRadix10Str;
+
+use crate::{AccountUpdates, TokenId};
+
+/// Fee collected from the executed transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TracedFee {
+    pub token: TokenId,
+    #[serde(with = "BigUintSerdeAsRadix10Str")]
+    pub amount: BigUint,
+}
+
+/// Details of the successful transaction execution recorded by the state keeper,
+/// which explain the changes made by the transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxExecutionTrace {
+    /// Position of the transaction among the operations of the block.
+    pub block_index: u32,
+    /// Number of block chunks used by the transaction.
+    pub chunks: usize,
+    /// Fee collected from the transaction, if it pays any.
+    pub fee: Option<TracedFee>,
+    /// Account changes made by the transaction, in the order of application.
+    pub updates: AccountUpdates,
+}
@@ -16,7 +16,7 @@ use zksync_crypto::params::{max_account_id, max_token_id};
 use zksync_utils::format_units;
 use zksync_utils::BigUintSerdeAsRadix10Str;
 
-use super::{TxSignature, VerifiedSignatureCache};
+use super::{TimeRange, TxSignature, VerifiedSignatureCache};
 
 /// `Transfer` transaction performs a move of funds from one zkSync account to another.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -38,6 +38,9 @@ pub struct Transfer {
     pub fee: BigUint,
     /// Current account nonce.
     pub nonce: Nonce,
+    /// Time range when the transaction is valid.
+    #[serde(flatten)]
+    pub time_range: TimeRange,
     /// Transaction zkSync signature.
     pub signature: TxSignature,
     #[serde(skip)]
@@ -61,6 +64,7 @@ impl Transfer {
         amount: BigUint,
         fee: BigUint,
         nonce: Nonce,
+        time_range: TimeRange,
         signature: Option<TxSignature>,
     ) -> Self {
         let mut tx = Self {
@@ -71,6 +75,7 @@ impl Transfer {
             amount,
             fee,
             nonce,
+            time_range,
             signature: signature.clone().unwrap_or_default(),
             cached_signer: VerifiedSignatureCache::NotCached,
         };
@@ -91,9 +96,12 @@ impl Transfer {
         amount: BigUint,
         fee: BigUint,
         nonce: Nonce,
+        time_range: TimeRange,
         private_key: &PrivateKey<Engine>,
     ) -> Result<Self, anyhow::Error> {
-        let mut tx = Self::new(account_id, from, to, token, amount, fee, nonce, None);
+        let mut tx = Self::new(
+            account_id, from, to, token, amount, fee, nonce, time_range, None,
+        );
         tx.signature = TxSignature::sign_musig(private_key, &tx.get_bytes());
         if !tx.check_correctness() {
             bail!("Transfer is incorrect, check amounts");
@@ -112,6 +120,7 @@ impl Transfer {
         out.extend_from_slice(&pack_token_amount(&self.amount));
         out.extend_from_slice(&pack_fee_amount(&self.fee));
         out.extend_from_slice(&self.nonce.to_be_bytes());
+        out.extend_from_slice(&self.time_range.to_be_bytes());
         out
     }
 
@@ -122,6 +131,7 @@ impl Transfer {
     /// - `amount` field must represent a packable value.
     /// - `fee` field must represent a packable value.
     /// - transfer recipient must not be `Adddress::zero()`.
+    /// - `time_range` field must represent a non-empty range.
     /// - zkSync signature must correspond to the PubKeyHash of the account.
     pub fn check_correctness(&mut self) -> bool {
         let mut valid = self.amount <= BigUint::from(u128::max_value())
@@ -130,7 +140,8 @@ impl Transfer {
             && is_fee_amount_packable(&self.fee)
             && self.account_id <= max_account_id()
             && self.token <= max_token_id()
-            && self.to != Address::zero();
+            && self.to != Address::zero()
+            && self.time_range.check_correctness();
         if valid {
             let signer = self.verify_signature();
             valid = valid && signer.is_some();
@@ -14,7 +14,7 @@ use zksync_crypto::params::{max_account_id, max_token_id};
 use zksync_utils::format_units;
 use zksync_utils::BigUintSerdeAsRadix10Str;
 
-use super::{TxSignature, VerifiedSignatureCache};
+use super::{TimeRange, TxSignature, VerifiedSignatureCache};
 
 /// `Withdraw` transaction performs a withdrawal of funds from zkSync account to L1 account.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,6 +36,9 @@ pub struct Withdraw {
     pub fee: BigUint,
     /// Current account nonce.
     pub nonce: Nonce,
+    /// Time range when the transaction is valid.
+    #[serde(flatten)]
+    pub time_range: TimeRange,
     /// Transaction zkSync signature.
     pub signature: TxSignature,
     #[serde(skip)]
@@ -65,6 +68,7 @@ impl Withdraw {
         amount: BigUint,
         fee: BigUint,
         nonce: Nonce,
+        time_range: TimeRange,
         signature: Option<TxSignature>,
     ) -> Self {
         let mut tx = Self {
@@ -75,6 +79,7 @@ impl Withdraw {
             amount,
             fee,
             nonce,
+            time_range,
             signature: signature.clone().unwrap_or_default(),
             cached_signer: VerifiedSignatureCache::NotCached,
             fast: false,
@@ -96,9 +101,12 @@ impl Withdraw {
         amount: BigUint,
         fee: BigUint,
         nonce: Nonce,
+        time_range: TimeRange,
         private_key: &PrivateKey<Engine>,
     ) -> Result<Self, anyhow::Error> {
-        let mut tx = Self::new(account_id, from, to, token, amount, fee, nonce, None);
+        let mut tx = Self::new(
+            account_id, from, to, token, amount, fee, nonce, time_range, None,
+        );
         tx.signature = TxSignature::sign_musig(private_key, &tx.get_bytes());
         if !tx.check_correctness() {
             bail!("Transfer is incorrect, check amounts");
@@ -117,6 +125,7 @@ impl Withdraw {
         out.extend_from_slice(&self.amount.to_u128().unwrap().to_be_bytes());
         out.extend_from_slice(&pack_fee_amount(&self.fee));
         out.extend_from_slice(&self.nonce.to_be_bytes());
+        out.extend_from_slice(&self.time_range.to_be_bytes());
         out
     }
 
@@ -126,12 +135,14 @@ impl Withdraw {
     /// - `token` field must be within supported range.
     /// - `amount` field must represent a packable value.
     /// - `fee` field must represent a packable value.
+    /// - `time_range` field must represent a non-empty range.
     /// - zkSync signature must correspond to the PubKeyHash of the account.
     pub fn check_correctness(&mut self) -> bool {
         let mut valid = self.amount <= BigUint::from(u128::max_value())
             && is_fee_amount_packable(&self.fee)
             && self.account_id <= max_account_id()
-            && self.token <= max_token_id();
+            && self.token <= max_token_id()
+            && self.time_range.check_correctness();
 
         if valid {
             let signer = self.verify_signature();
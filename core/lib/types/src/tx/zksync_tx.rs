@@ -1,8 +1,11 @@
 use crate::Nonce;
 
 use crate::{
-    tx::{ChangePubKey, Close, ForcedExit, Transfer, TxEthSignature, TxHash, Withdraw},
-    CloseOp, ForcedExitOp, TokenLike, TransferOp, TxFeeTypes, WithdrawOp,
+    tx::{
+        ChangePubKey, Close, Eip712Struct, ForcedExit, Swap, TimeRange, Transfer, TxEthSignature,
+        TxHash, Withdraw,
+    },
+    CloseOp, ForcedExitOp, PubKeyHash, SwapOp, TokenLike, TransferOp, TxFeeTypes, WithdrawOp,
 };
 use num::BigUint;
 use parity_crypto::digest::sha256;
@@ -40,6 +43,7 @@ pub enum ZkSyncTx {
     Close(Box<Close>),
     ChangePubKey(Box<ChangePubKey>),
     ForcedExit(Box<ForcedExit>),
+    Swap(Box<Swap>),
 }
 
 impl From<Transfer> for ZkSyncTx {
@@ -72,6 +76,12 @@ impl From<ForcedExit> for ZkSyncTx {
     }
 }
 
+impl From<Swap> for ZkSyncTx {
+    fn from(tx: Swap) -> Self {
+        Self::Swap(Box::new(tx))
+    }
+}
+
 impl From<ZkSyncTx> for SignedZkSyncTx {
     fn from(tx: ZkSyncTx) -> Self {
         Self {
@@ -98,6 +108,7 @@ impl ZkSyncTx {
             ZkSyncTx::Close(tx) => tx.get_bytes(),
             ZkSyncTx::ChangePubKey(tx) => tx.get_bytes(),
             ZkSyncTx::ForcedExit(tx) => tx.get_bytes(),
+            ZkSyncTx::Swap(tx) => tx.get_bytes(),
         };
 
         let hash = sha256(&bytes);
@@ -114,6 +125,7 @@ impl ZkSyncTx {
             ZkSyncTx::Close(tx) => tx.account,
             ZkSyncTx::ChangePubKey(tx) => tx.account,
             ZkSyncTx::ForcedExit(tx) => tx.target,
+            ZkSyncTx::Swap(tx) => tx.submitter_address,
         }
     }
 
@@ -125,6 +137,31 @@ impl ZkSyncTx {
             ZkSyncTx::Close(tx) => tx.nonce,
             ZkSyncTx::ChangePubKey(tx) => tx.nonce,
             ZkSyncTx::ForcedExit(tx) => tx.nonce,
+            ZkSyncTx::Swap(tx) => tx.nonce,
+        }
+    }
+
+    /// Returns the time range when the transaction can be executed.
+    pub fn time_range(&self) -> TimeRange {
+        match self {
+            ZkSyncTx::Transfer(tx) => tx.time_range,
+            ZkSyncTx::Withdraw(tx) => tx.time_range,
+            ZkSyncTx::Close(_) => TimeRange::default(),
+            ZkSyncTx::ChangePubKey(tx) => tx.time_range,
+            ZkSyncTx::ForcedExit(tx) => tx.time_range,
+            ZkSyncTx::Swap(tx) => tx.time_range,
+        }
+    }
+
+    /// Restores the `PubKeyHash` of the key that signed the transaction.
+    pub fn verify_signature(&self) -> Option<PubKeyHash> {
+        match self {
+            ZkSyncTx::Transfer(tx) => tx.verify_signature(),
+            ZkSyncTx::Withdraw(tx) => tx.verify_signature(),
+            ZkSyncTx::Close(tx) => tx.verify_signature(),
+            ZkSyncTx::ChangePubKey(tx) => tx.verify_signature(),
+            ZkSyncTx::ForcedExit(tx) => tx.verify_signature(),
+            ZkSyncTx::Swap(tx) => tx.verify_signature(),
         }
     }
 
@@ -139,6 +176,7 @@ impl ZkSyncTx {
             ZkSyncTx::Close(tx) => tx.check_correctness(),
             ZkSyncTx::ChangePubKey(tx) => tx.check_correctness(),
             ZkSyncTx::ForcedExit(tx) => tx.check_correctness(),
+            ZkSyncTx::Swap(tx) => tx.check_correctness(),
         }
     }
 
@@ -150,6 +188,20 @@ impl ZkSyncTx {
             ZkSyncTx::Close(tx) => tx.get_bytes(),
             ZkSyncTx::ChangePubKey(tx) => tx.get_bytes(),
             ZkSyncTx::ForcedExit(tx) => tx.get_bytes(),
+            ZkSyncTx::Swap(tx) => tx.get_bytes(),
+        }
+    }
+
+    /// Returns the typed structured data of the transaction which can be signed according to EIP-712.
+    /// Returns `None` if the transaction doesn't support the typed data signatures.
+    pub fn eip712_struct(&self) -> Option<&(dyn Eip712Struct + Sync)> {
+        match self {
+            ZkSyncTx::Transfer(tx) => Some(tx.as_ref()),
+            ZkSyncTx::Withdraw(tx) => Some(tx.as_ref()),
+            ZkSyncTx::Close(_) => None,
+            ZkSyncTx::ChangePubKey(tx) => Some(tx.as_ref()),
+            ZkSyncTx::ForcedExit(tx) => Some(tx.as_ref()),
+            ZkSyncTx::Swap(_) => None,
         }
     }
 
@@ -163,6 +215,7 @@ impl ZkSyncTx {
             ZkSyncTx::Close(_) => CloseOp::CHUNKS,
             ZkSyncTx::ChangePubKey(_) => ChangePubKeyOp::CHUNKS,
             ZkSyncTx::ForcedExit(_) => ForcedExitOp::CHUNKS,
+            ZkSyncTx::Swap(_) => SwapOp::CHUNKS,
         }
     }
 
@@ -223,6 +276,12 @@ impl ZkSyncTx {
                 change_pubkey.account,
                 change_pubkey.fee.clone(),
             )),
+            ZkSyncTx::Swap(swap) => Some((
+                TxFeeTypes::Swap,
+                TokenLike::Id(swap.fee_token),
+                swap.submitter_address,
+                swap.fee.clone(),
+            )),
             _ => None,
         }
     }
@@ -5,8 +5,10 @@ mod env_tools;
 mod format;
 pub mod panic_notify;
 mod serde_wrappers;
+mod time;
 
 pub use convert::*;
 pub use env_tools::*;
 pub use format::*;
 pub use serde_wrappers::*;
+pub use time::*;
@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{collections::HashMap, hash::Hash, str::FromStr};
 
 use bigdecimal::BigDecimal;
 use num::{bigint::ToBigInt, rational::Ratio, BigUint};
@@ -77,6 +77,65 @@ impl From<BigUint> for BigUintSerdeWrapper {
     }
 }
 
+/// Used to serialize a pair of BigUint values as a pair of radix 10 strings.
+#[derive(Clone, Debug)]
+pub struct BigUintPairSerdeAsRadix10Str;
+
+impl BigUintPairSerdeAsRadix10Str {
+    pub fn serialize<S>(val: &(BigUint, BigUint), serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let pair = (
+            BigUintSerdeWrapper(val.0.clone()),
+            BigUintSerdeWrapper(val.1.clone()),
+        );
+        pair.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<(BigUint, BigUint), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (first, second) =
+            <(BigUintSerdeWrapper, BigUintSerdeWrapper)>::deserialize(deserializer)?;
+        Ok((first.0, second.0))
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct UnsignedRatioSerdeWrapper(#[serde(with = "UnsignedRatioSerializeAsDecimal")] Ratio<BigUint>);
+
+/// Used to serialize a map with `Ratio` values as a map with decimal values.
+#[derive(Clone, Debug)]
+pub struct UnsignedRatioMapSerializeAsDecimal;
+
+impl UnsignedRatioMapSerializeAsDecimal {
+    pub fn serialize<K, S>(
+        val: &HashMap<K, Ratio<BigUint>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize + Eq + Hash,
+        S: Serializer,
+    {
+        let map: HashMap<_, _> = val
+            .iter()
+            .map(|(key, value)| (key, UnsignedRatioSerdeWrapper(value.clone())))
+            .collect();
+        map.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, K, D>(deserializer: D) -> Result<HashMap<K, Ratio<BigUint>>, D::Error>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        D: Deserializer<'de>,
+    {
+        let map = HashMap::<K, UnsignedRatioSerdeWrapper>::deserialize(deserializer)?;
+        Ok(map.into_iter().map(|(key, value)| (key, value.0)).collect())
+    }
+}
+
 /// Trait for specifying prefix for bytes to hex serialization
 pub trait Prefix {
     fn prefix() -> &'static str;
@@ -212,6 +271,27 @@ mod test {
         assert_eq!(expected.0, ratio.0);
     }
 
+    /// Tests that the map of `Ratio` values is serialized as the map of decimals.
+    #[test]
+    fn test_ratio_map_serialize_as_decimal() {
+        #[derive(Clone, Serialize, Deserialize)]
+        struct RatioMapSerdeWrapper(
+            #[serde(with = "UnsignedRatioMapSerializeAsDecimal")] pub HashMap<u16, Ratio<BigUint>>,
+        );
+
+        let mut expected = HashMap::new();
+        expected.insert(1, Ratio::new(BigUint::from(5u32), BigUint::from(2u32)));
+        expected.insert(7, Ratio::from_integer(BigUint::from(3u32)));
+        let expected = RatioMapSerdeWrapper(expected);
+
+        let value = serde_json::to_value(expected.clone())
+            .expect("cannot serialize map of Ratio as map of Decimal");
+        assert!(value["1"].is_string());
+        let map: RatioMapSerdeWrapper = serde_json::from_value(value)
+            .expect("cannot deserialize map of Ratio from map of Decimal");
+        assert_eq!(expected.0, map.0);
+    }
+
     /// Tests that `BigUint` serializer works correctly.
     #[test]
     fn test_serde_big_uint_wrapper() {
@@ -8,7 +8,8 @@ use zksync_crypto::rand::{thread_rng, Rng};
 use zksync_crypto::{priv_key_from_fs, PrivateKey};
 use zksync_types::tx::{ChangePubKey, PackedEthSignature, TxSignature};
 use zksync_types::{
-    AccountId, Address, Close, ForcedExit, Nonce, PubKeyHash, TokenId, Transfer, Withdraw,
+    AccountId, Address, Close, ForcedExit, Nonce, Order, PubKeyHash, Swap, TokenId, Transfer,
+    Withdraw,
 };
 
 /// Structure used to sign ZKSync transactions, keeps tracks of its nonce internally
@@ -122,6 +123,7 @@ impl ZkSyncAccount {
             amount,
             fee,
             nonce.unwrap_or_else(|| *stored_nonce),
+            Default::default(),
             &self.private_key,
         )
         .expect("Failed to sign transfer");
@@ -154,6 +156,7 @@ impl ZkSyncAccount {
             token_id,
             fee,
             nonce.unwrap_or_else(|| *stored_nonce),
+            Default::default(),
             &self.private_key,
         )
         .expect("Failed to sign forced exit");
@@ -165,6 +168,75 @@ impl ZkSyncAccount {
         forced_exit
     }
 
+    #[allow(clippy::too_many_arguments)]
+    pub fn sign_order(
+        &self,
+        token_sell: TokenId,
+        token_buy: TokenId,
+        price: (BigUint, BigUint),
+        min_amount: BigUint,
+        max_amount: BigUint,
+        recipient: &Address,
+        nonce: Option<Nonce>,
+        increment_nonce: bool,
+    ) -> Order {
+        let mut stored_nonce = self.nonce.lock().unwrap();
+        let order = Order::new_signed(
+            self.account_id
+                .lock()
+                .unwrap()
+                .expect("can't sign order without account id"),
+            *recipient,
+            nonce.unwrap_or_else(|| *stored_nonce),
+            token_sell,
+            token_buy,
+            price,
+            min_amount,
+            max_amount,
+            &self.private_key,
+        )
+        .expect("Failed to sign order");
+
+        if increment_nonce {
+            *stored_nonce += 1;
+        }
+
+        order
+    }
+
+    pub fn sign_swap(
+        &self,
+        orders: (Order, Order),
+        amounts: (BigUint, BigUint),
+        fee_token: TokenId,
+        fee: BigUint,
+        nonce: Option<Nonce>,
+        increment_nonce: bool,
+    ) -> Swap {
+        let mut stored_nonce = self.nonce.lock().unwrap();
+        let swap = Swap::new_signed(
+            self.account_id
+                .lock()
+                .unwrap()
+                .expect("can't sign tx without account id"),
+            self.address,
+            nonce.unwrap_or_else(|| *stored_nonce),
+            orders,
+            amounts,
+            fee_token,
+            fee,
+            Default::default(),
+            &self.private_key,
+        )
+        .expect("Failed to sign swap");
+
+        if increment_nonce {
+            *stored_nonce += 1;
+        }
+
+        swap
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn sign_withdraw(
         &self,
@@ -188,6 +260,7 @@ impl ZkSyncAccount {
             amount,
             fee,
             nonce.unwrap_or_else(|| *stored_nonce),
+            Default::default(),
             &self.private_key,
         )
         .expect("Failed to sign withdraw");
@@ -240,6 +313,7 @@ impl ZkSyncAccount {
             fee_token,
             fee,
             nonce,
+            Default::default(),
             None,
             &self.private_key,
         )
@@ -370,7 +370,7 @@ impl<T: Transport> EthereumAccount<T> {
                 (
                     u64::from(block.block_number),
                     u64::from(block.fee_account),
-                    vec![block.get_eth_encoded_root()],
+                    block.get_eth_new_block_info(),
                     block.get_eth_public_data(),
                     witness_data.0,
                     witness_data.1,
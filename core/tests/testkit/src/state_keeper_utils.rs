@@ -2,8 +2,9 @@ use futures::{
     channel::{mpsc, oneshot},
     SinkExt,
 };
-use std::thread::JoinHandle;
+use std::{thread::JoinHandle, time::Duration};
 use tokio::runtime::Runtime;
+use zksync_config::{BlockSealingOptions, FeeRoutingOptions, FeeRoutingPolicy};
 use zksync_core::committer::CommitRequest;
 use zksync_core::state_keeper::{start_state_keeper, StateKeeperRequest, ZkSyncStateKeeper};
 use zksync_types::{
@@ -61,6 +62,17 @@ pub fn spawn_state_keeper(
         max_miniblock_iterations,
         max_miniblock_iterations,
         super::MAX_WITHDRAWALS_PER_BLOCK as usize,
+        // Blocks are sealed only when they are full or on the explicit request.
+        BlockSealingOptions {
+            chunks_fill_percent: 100,
+            gas_limit_percent: 100,
+            max_block_age: Duration::from_secs(u64::MAX),
+        },
+        // All the fees go to the operator fee account.
+        FeeRoutingOptions {
+            policy: FeeRoutingPolicy::FirstMatch,
+            routes: Vec::new(),
+        },
     );
 
     let (stop_state_keeper_sender, stop_state_keeper_receiver) = oneshot::channel::<()>();
@@ -1,7 +1,10 @@
 use crate::eth_account::{get_executed_tx_fee, ETHExecResult, EthereumAccount};
 use crate::external_commands::Contracts;
 use anyhow::bail;
-use futures::{channel::mpsc, SinkExt, StreamExt};
+use futures::{
+    channel::{mpsc, oneshot},
+    SinkExt, StreamExt,
+};
 use num::BigUint;
 use std::collections::HashMap;
 use web3::transports::Http;
@@ -622,7 +625,7 @@ impl TestSetup {
     pub async fn execute_commit_block(&mut self) -> (ETHExecResult, Block) {
         self.state_keeper_request_sender
             .clone()
-            .send(StateKeeperRequest::SealBlock)
+            .send(StateKeeperRequest::SealBlock(oneshot::channel().0))
             .await
             .expect("sk receiver dropped");
 
@@ -654,7 +657,7 @@ impl TestSetup {
     ) -> Result<BlockExecutionResult, anyhow::Error> {
         self.state_keeper_request_sender
             .clone()
-            .send(StateKeeperRequest::SealBlock)
+            .send(StateKeeperRequest::SealBlock(oneshot::channel().0))
             .await
             .expect("sk receiver dropped");
 
@@ -24,6 +24,8 @@ pub enum ClientError {
 
     #[error("Operation timeout")]
     OperationTimeout,
+    #[error("Transaction was evicted from the mempool: {0}")]
+    TransactionEvicted(String),
     #[error("Polling interval is too small")]
     PollingIntervalIsTooSmall,
 
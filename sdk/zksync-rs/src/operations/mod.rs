@@ -7,10 +7,14 @@ use zksync_types::tx::TxHash;
 use crate::{error::ClientError, provider::Provider, types::TransactionInfo};
 
 pub use self::{
-    change_pubkey::ChangePubKeyBuilder, transfer::TransferBuilder, withdraw::WithdrawBuilder,
+    change_pubkey::ChangePubKeyBuilder,
+    swap::{OrderBuilder, SwapBuilder},
+    transfer::TransferBuilder,
+    withdraw::WithdrawBuilder,
 };
 
 mod change_pubkey;
+mod swap;
 mod transfer;
 mod withdraw;
 
@@ -86,6 +90,11 @@ impl<P: Provider> SyncTransactionHandle<P> {
             }
 
             let response = self.provider.tx_info(self.hash).await?;
+            if response.evicted {
+                return Err(ClientError::TransactionEvicted(
+                    response.fail_reason.unwrap_or_default(),
+                ));
+            }
             if let Some(block) = &response.block {
                 if block.committed {
                     return Ok(response);
@@ -109,6 +118,11 @@ impl<P: Provider> SyncTransactionHandle<P> {
             }
 
             let response = self.provider.tx_info(self.hash).await?;
+            if response.evicted {
+                return Err(ClientError::TransactionEvicted(
+                    response.fail_reason.unwrap_or_default(),
+                ));
+            }
             if let Some(block) = &response.block {
                 if block.verified {
                     return Ok(response);
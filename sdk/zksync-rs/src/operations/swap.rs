@@ -0,0 +1,289 @@
+use num::BigUint;
+use zksync_eth_signer::EthereumSigner;
+use zksync_types::{
+    helpers::{closest_packable_fee_amount, is_fee_amount_packable, is_token_amount_packable},
+    tx::PackedEthSignature,
+    Address, Nonce, Order, Token, TokenLike, TxFeeTypes, ZkSyncTx,
+};
+
+use crate::{
+    error::ClientError, operations::SyncTransactionHandle, provider::Provider, wallet::Wallet,
+};
+
+#[derive(Debug)]
+pub struct OrderBuilder<'a, S: EthereumSigner, P: Provider> {
+    wallet: &'a Wallet<S, P>,
+    token_sell: Option<Token>,
+    token_buy: Option<Token>,
+    price: Option<(BigUint, BigUint)>,
+    amount_bounds: Option<(BigUint, BigUint)>,
+    recipient: Option<Address>,
+    nonce: Option<Nonce>,
+}
+
+impl<'a, S, P> OrderBuilder<'a, S, P>
+where
+    S: EthereumSigner + Clone,
+    P: Provider + Clone,
+{
+    /// Initializes an order building process.
+    pub fn new(wallet: &'a Wallet<S, P>) -> Self {
+        Self {
+            wallet,
+            token_sell: None,
+            token_buy: None,
+            price: None,
+            amount_bounds: None,
+            recipient: None,
+            nonce: None,
+        }
+    }
+
+    /// Returns the signed order to be passed to the `Swap` transaction submitter.
+    pub async fn order(self) -> Result<Order, ClientError> {
+        let token_sell = self
+            .token_sell
+            .ok_or_else(|| ClientError::MissingRequiredField("token_sell".into()))?;
+        let token_buy = self
+            .token_buy
+            .ok_or_else(|| ClientError::MissingRequiredField("token_buy".into()))?;
+        let price = self
+            .price
+            .ok_or_else(|| ClientError::MissingRequiredField("price".into()))?;
+        let (min_amount, max_amount) = self
+            .amount_bounds
+            .ok_or_else(|| ClientError::MissingRequiredField("amount_bounds".into()))?;
+        let recipient = self.recipient.unwrap_or_else(|| self.wallet.address());
+
+        let nonce = match self.nonce {
+            Some(nonce) => nonce,
+            None => {
+                let account_info = self
+                    .wallet
+                    .provider
+                    .account_info(self.wallet.address())
+                    .await?;
+                account_info.committed.nonce
+            }
+        };
+
+        self.wallet
+            .signer
+            .sign_order(
+                token_sell, token_buy, price, min_amount, max_amount, recipient, nonce,
+            )
+            .await
+            .map_err(ClientError::SigningError)
+    }
+
+    /// Sets the token to be sold. Returns an error if token is not supported by zkSync.
+    pub fn token_sell(mut self, token: impl Into<TokenLike>) -> Result<Self, ClientError> {
+        self.token_sell = Some(self.resolve_token(token)?);
+
+        Ok(self)
+    }
+
+    /// Sets the token to be bought. Returns an error if token is not supported by zkSync.
+    pub fn token_buy(mut self, token: impl Into<TokenLike>) -> Result<Self, ClientError> {
+        self.token_buy = Some(self.resolve_token(token)?);
+
+        Ok(self)
+    }
+
+    /// Sets the exchange rate: for every `sell` units of the sold token
+    /// at least `buy` units of the bought token must be received.
+    pub fn price(mut self, sell: impl Into<BigUint>, buy: impl Into<BigUint>) -> Self {
+        self.price = Some((sell.into(), buy.into()));
+        self
+    }
+
+    /// Sets the range of the amount of the sold token. If any of the provided
+    /// amounts is not packable, returns an error.
+    ///
+    /// For more details, see [utils](../utils/index.html) functions.
+    pub fn amount_bounds(
+        mut self,
+        min_amount: impl Into<BigUint>,
+        max_amount: impl Into<BigUint>,
+    ) -> Result<Self, ClientError> {
+        let (min_amount, max_amount) = (min_amount.into(), max_amount.into());
+        if !is_token_amount_packable(&min_amount) || !is_token_amount_packable(&max_amount) {
+            return Err(ClientError::NotPackableValue);
+        }
+        self.amount_bounds = Some((min_amount, max_amount));
+
+        Ok(self)
+    }
+
+    /// Sets the recipient of the bought tokens. By default it's the wallet address.
+    pub fn recipient(mut self, recipient: Address) -> Self {
+        self.recipient = Some(recipient);
+        self
+    }
+
+    /// Sets the order nonce.
+    pub fn nonce(mut self, nonce: Nonce) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    fn resolve_token(&self, token: impl Into<TokenLike>) -> Result<Token, ClientError> {
+        self.wallet
+            .tokens
+            .resolve(token.into())
+            .ok_or(ClientError::UnknownToken)
+    }
+}
+
+#[derive(Debug)]
+pub struct SwapBuilder<'a, S: EthereumSigner, P: Provider> {
+    wallet: &'a Wallet<S, P>,
+    orders: Option<(Order, Order)>,
+    amounts: Option<(BigUint, BigUint)>,
+    fee_token: Option<Token>,
+    fee: Option<BigUint>,
+    nonce: Option<Nonce>,
+}
+
+impl<'a, S, P> SwapBuilder<'a, S, P>
+where
+    S: EthereumSigner + Clone,
+    P: Provider + Clone,
+{
+    /// Initializes a swap transaction building process.
+    pub fn new(wallet: &'a Wallet<S, P>) -> Self {
+        Self {
+            wallet,
+            orders: None,
+            amounts: None,
+            fee_token: None,
+            fee: None,
+            nonce: None,
+        }
+    }
+
+    /// Directly returns the signed swap transaction for the subsequent usage.
+    /// Swap transaction doesn't require an Ethereum signature.
+    pub async fn tx(self) -> Result<(ZkSyncTx, Option<PackedEthSignature>), ClientError> {
+        let orders = self
+            .orders
+            .ok_or_else(|| ClientError::MissingRequiredField("orders".into()))?;
+        let amounts = self
+            .amounts
+            .ok_or_else(|| ClientError::MissingRequiredField("amounts".into()))?;
+        let fee_token = self
+            .fee_token
+            .ok_or_else(|| ClientError::MissingRequiredField("fee_token".into()))?;
+
+        let nonce = match self.nonce {
+            Some(nonce) => nonce,
+            None => {
+                let account_info = self
+                    .wallet
+                    .provider
+                    .account_info(self.wallet.address())
+                    .await?;
+                account_info.committed.nonce
+            }
+        };
+
+        let fee = match self.fee {
+            Some(fee) => fee,
+            None => {
+                let fee = self
+                    .wallet
+                    .provider
+                    .get_tx_fee(TxFeeTypes::Swap, self.wallet.address(), fee_token.id)
+                    .await?;
+                fee.total_fee
+            }
+        };
+
+        self.wallet
+            .signer
+            .sign_swap(orders, amounts, fee_token, fee, nonce)
+            .await
+            .map(|tx| (ZkSyncTx::Swap(Box::new(tx)), None))
+            .map_err(ClientError::SigningError)
+    }
+
+    /// Sends the transaction, returning the handle for its awaiting.
+    pub async fn send(self) -> Result<SyncTransactionHandle<P>, ClientError> {
+        let provider = self.wallet.provider.clone();
+
+        let (tx, eth_signature) = self.tx().await?;
+        let tx_hash = provider.send_tx(tx, eth_signature).await?;
+
+        Ok(SyncTransactionHandle::new(tx_hash, provider))
+    }
+
+    /// Sets the orders to be matched.
+    pub fn orders(mut self, first: Order, second: Order) -> Self {
+        self.orders = Some((first, second));
+        self
+    }
+
+    /// Sets the amounts sold by the owners of the first and the second orders.
+    /// If any of the provided amounts is not packable, returns an error.
+    ///
+    /// Unlike the transfer amount, swap amounts are never rounded,
+    /// since rounding may violate the orders price.
+    pub fn amounts(
+        mut self,
+        first: impl Into<BigUint>,
+        second: impl Into<BigUint>,
+    ) -> Result<Self, ClientError> {
+        let (first, second) = (first.into(), second.into());
+        if !is_token_amount_packable(&first) || !is_token_amount_packable(&second) {
+            return Err(ClientError::NotPackableValue);
+        }
+        self.amounts = Some((first, second));
+
+        Ok(self)
+    }
+
+    /// Sets the fee token. Returns an error if token is not supported by zkSync.
+    pub fn fee_token(mut self, token: impl Into<TokenLike>) -> Result<Self, ClientError> {
+        let token_like = token.into();
+        let token = self
+            .wallet
+            .tokens
+            .resolve(token_like)
+            .ok_or(ClientError::UnknownToken)?;
+
+        self.fee_token = Some(token);
+
+        Ok(self)
+    }
+
+    /// Set the fee amount. If the provided fee is not packable,
+    /// rounds it to the closest packable fee amount.
+    ///
+    /// For more details, see [utils](../utils/index.html) functions.
+    pub fn fee(mut self, fee: impl Into<BigUint>) -> Self {
+        let fee = closest_packable_fee_amount(&fee.into());
+        self.fee = Some(fee);
+
+        self
+    }
+
+    /// Set the fee amount. If the provided fee is not packable,
+    /// returns an error.
+    ///
+    /// For more details, see [utils](../utils/index.html) functions.
+    pub fn fee_exact(mut self, fee: impl Into<BigUint>) -> Result<Self, ClientError> {
+        let fee = fee.into();
+        if !is_fee_amount_packable(&fee) {
+            return Err(ClientError::NotPackableValue);
+        }
+        self.fee = Some(fee);
+
+        Ok(self)
+    }
+
+    /// Sets the transaction nonce.
+    pub fn nonce(mut self, nonce: Nonce) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+}
@@ -2,13 +2,16 @@
 use std::fmt;
 use zksync_eth_signer::error::SignerError;
 use zksync_eth_signer::EthereumSigner;
-use zksync_types::tx::TxEthSignature;
+use zksync_types::tx::{Eip712Domain, TxEthSignature, TxsBatch};
 // External uses
 use num::BigUint;
 // Workspace uses
 use zksync_crypto::PrivateKey;
 use zksync_types::tx::{ChangePubKey, PackedEthSignature};
-use zksync_types::{AccountId, Address, ForcedExit, Nonce, PubKeyHash, Token, Transfer, Withdraw};
+use zksync_types::{
+    AccountId, Address, ForcedExit, Nonce, Order, PubKeyHash, Swap, Token, Transfer, Withdraw,
+    ZkSyncTx,
+};
 // Local imports
 use crate::WalletCredentials;
 
@@ -87,6 +90,7 @@ impl<S: EthereumSigner> Signer<S> {
             fee_token.id,
             fee,
             nonce,
+            Default::default(),
             None,
             &self.private_key,
         )
@@ -145,6 +149,7 @@ impl<S: EthereumSigner> Signer<S> {
             amount,
             fee,
             nonce,
+            Default::default(),
             &self.private_key,
         )
         .map_err(signing_failed_error)?;
@@ -184,6 +189,7 @@ impl<S: EthereumSigner> Signer<S> {
             amount,
             fee,
             nonce,
+            Default::default(),
             &self.private_key,
         )
         .map_err(signing_failed_error)?;
@@ -214,7 +220,106 @@ impl<S: EthereumSigner> Signer<S> {
     ) -> Result<ForcedExit, SignerError> {
         let account_id = self.account_id.ok_or(SignerError::NoSigningKey)?;
 
-        ForcedExit::new_signed(account_id, target, token.id, fee, nonce, &self.private_key)
-            .map_err(signing_failed_error)
+        ForcedExit::new_signed(
+            account_id,
+            target,
+            token.id,
+            fee,
+            nonce,
+            Default::default(),
+            &self.private_key,
+        )
+        .map_err(signing_failed_error)
+    }
+
+    /// Signs the order to sell `token_sell` for `token_buy`.
+    /// Order doesn't require an Ethereum signature, since it doesn't move funds by itself.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn sign_order(
+        &self,
+        token_sell: Token,
+        token_buy: Token,
+        price: (BigUint, BigUint),
+        min_amount: BigUint,
+        max_amount: BigUint,
+        recipient: Address,
+        nonce: Nonce,
+    ) -> Result<Order, SignerError> {
+        let account_id = self.account_id.ok_or(SignerError::NoSigningKey)?;
+
+        Order::new_signed(
+            account_id,
+            recipient,
+            nonce,
+            token_sell.id,
+            token_buy.id,
+            price,
+            min_amount,
+            max_amount,
+            &self.private_key,
+        )
+        .map_err(signing_failed_error)
+    }
+
+    pub async fn sign_swap(
+        &self,
+        orders: (Order, Order),
+        amounts: (BigUint, BigUint),
+        fee_token: Token,
+        fee: BigUint,
+        nonce: Nonce,
+    ) -> Result<Swap, SignerError> {
+        let account_id = self.account_id.ok_or(SignerError::NoSigningKey)?;
+
+        Swap::new_signed(
+            account_id,
+            self.address,
+            nonce,
+            orders,
+            amounts,
+            fee_token.id,
+            fee,
+            Default::default(),
+            &self.private_key,
+        )
+        .map_err(signing_failed_error)
+    }
+
+    /// Signs the transaction as the EIP-712 typed data.
+    /// Such signature can be provided instead of the signature of the text message.
+    pub async fn sign_tx_typed_data(
+        &self,
+        domain: &Eip712Domain,
+        tx: &ZkSyncTx,
+    ) -> Result<TxEthSignature, SignerError> {
+        let eth_signer = self
+            .eth_signer
+            .as_ref()
+            .ok_or(SignerError::MissingEthSigner)?;
+        let typed_data = tx.eip712_struct().ok_or_else(|| {
+            signing_failed_error("Transaction doesn't support typed data signatures")
+        })?;
+
+        eth_signer.sign_typed_data(domain, typed_data).await
+    }
+
+    /// Signs the batch of transactions as the EIP-712 typed data.
+    pub async fn sign_batch_typed_data(
+        &self,
+        domain: &Eip712Domain,
+        txs: &[ZkSyncTx],
+    ) -> Result<TxEthSignature, SignerError> {
+        let eth_signer = self
+            .eth_signer
+            .as_ref()
+            .ok_or(SignerError::MissingEthSigner)?;
+
+        let batch = TxsBatch::new(txs).ok_or_else(|| {
+            signing_failed_error(
+                "Batch contains transactions which don't support typed data signatures",
+            )
+        })?;
+
+        eth_signer.sign_typed_data(domain, &batch).await
     }
 }
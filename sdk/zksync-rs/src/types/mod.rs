@@ -1,7 +1,7 @@
 use num::BigUint;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use zksync_types::{AccountId, Address, Nonce, PubKeyHash, Token};
+use zksync_types::{tx::TxHash, AccountId, Address, Nonce, PubKeyHash, Token};
 use zksync_utils::{BigUintSerdeAsRadix10Str, BigUintSerdeWrapper};
 
 pub type Tokens = HashMap<String, Token>;
@@ -60,6 +60,12 @@ pub struct TransactionInfo {
     pub success: Option<bool>,
     pub fail_reason: Option<String>,
     pub block: Option<BlockInfo>,
+    /// Transaction was removed from the mempool without being executed.
+    #[serde(default)]
+    pub evicted: bool,
+    /// Hash of the transaction with the same nonce that replaced this one.
+    #[serde(default)]
+    pub replaced_by: Option<TxHash>,
 }
 
 impl TransactionInfo {
@@ -143,6 +149,7 @@ pub enum OutputFeeType {
         #[serde(rename = "onchainPubkeyAuth")]
         onchain_pubkey_auth: bool,
     },
+    Swap,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -10,7 +10,9 @@ import {
     serializeAmountPacked,
     serializeFeePacked,
     serializeNonce,
-    serializeAmountFull
+    serializeTimestamp,
+    serializeAmountFull,
+    MAX_TIMESTAMP
 } from './utils';
 import { Address, EthSignerType, PubKeyHash, Transfer, Withdraw, ForcedExit, ChangePubKey } from './types';
 
@@ -33,6 +35,8 @@ export class Signer {
         amount: BigNumberish;
         fee: BigNumberish;
         nonce: number;
+        validFrom?: number;
+        validUntil?: number;
     }): Uint8Array {
         const type = new Uint8Array([5]); // tx type
         const accountId = serializeAccountId(transfer.accountId);
@@ -42,7 +46,20 @@ export class Signer {
         const amount = serializeAmountPacked(transfer.amount);
         const fee = serializeFeePacked(transfer.fee);
         const nonce = serializeNonce(transfer.nonce);
-        const msgBytes = ethers.utils.concat([type, accountId, from, to, token, amount, fee, nonce]);
+        const validFrom = serializeTimestamp(transfer.validFrom || 0);
+        const validUntil = serializeTimestamp(transfer.validUntil || MAX_TIMESTAMP);
+        const msgBytes = ethers.utils.concat([
+            type,
+            accountId,
+            from,
+            to,
+            token,
+            amount,
+            fee,
+            nonce,
+            validFrom,
+            validUntil
+        ]);
 
         return msgBytes;
     }
@@ -55,6 +72,8 @@ export class Signer {
         amount: BigNumberish;
         fee: BigNumberish;
         nonce: number;
+        validFrom?: number;
+        validUntil?: number;
     }): Promise<Transfer> {
         const msgBytes = this.transferSignBytes(transfer);
         const signature = await signTransactionBytes(this.#privateKey, msgBytes);
@@ -68,6 +87,8 @@ export class Signer {
             amount: BigNumber.from(transfer.amount).toString(),
             fee: BigNumber.from(transfer.fee).toString(),
             nonce: transfer.nonce,
+            validFrom: transfer.validFrom || 0,
+            validUntil: transfer.validUntil || MAX_TIMESTAMP,
             signature
         };
     }
@@ -80,6 +101,8 @@ export class Signer {
         amount: BigNumberish;
         fee: BigNumberish;
         nonce: number;
+        validFrom?: number;
+        validUntil?: number;
     }): Uint8Array {
         const typeBytes = new Uint8Array([3]);
         const accountId = serializeAccountId(withdraw.accountId);
@@ -89,6 +112,8 @@ export class Signer {
         const amountBytes = serializeAmountFull(withdraw.amount);
         const feeBytes = serializeFeePacked(withdraw.fee);
         const nonceBytes = serializeNonce(withdraw.nonce);
+        const validFromBytes = serializeTimestamp(withdraw.validFrom || 0);
+        const validUntilBytes = serializeTimestamp(withdraw.validUntil || MAX_TIMESTAMP);
         const msgBytes = ethers.utils.concat([
             typeBytes,
             accountId,
@@ -97,7 +122,9 @@ export class Signer {
             tokenIdBytes,
             amountBytes,
             feeBytes,
-            nonceBytes
+            nonceBytes,
+            validFromBytes,
+            validUntilBytes
         ]);
 
         return msgBytes;
@@ -111,6 +138,8 @@ export class Signer {
         amount: BigNumberish;
         fee: BigNumberish;
         nonce: number;
+        validFrom?: number;
+        validUntil?: number;
     }): Promise<Withdraw> {
         const msgBytes = this.withdrawSignBytes(withdraw);
         const signature = await signTransactionBytes(this.#privateKey, msgBytes);
@@ -124,6 +153,8 @@ export class Signer {
             amount: BigNumber.from(withdraw.amount).toString(),
             fee: BigNumber.from(withdraw.fee).toString(),
             nonce: withdraw.nonce,
+            validFrom: withdraw.validFrom || 0,
+            validUntil: withdraw.validUntil || MAX_TIMESTAMP,
             signature
         };
     }
@@ -134,6 +165,8 @@ export class Signer {
         tokenId: number;
         fee: BigNumberish;
         nonce: number;
+        validFrom?: number;
+        validUntil?: number;
     }): Uint8Array {
         const typeBytes = new Uint8Array([8]);
         const initiatorAccountIdBytes = serializeAccountId(forcedExit.initiatorAccountId);
@@ -141,13 +174,17 @@ export class Signer {
         const tokenIdBytes = serializeTokenId(forcedExit.tokenId);
         const feeBytes = serializeFeePacked(forcedExit.fee);
         const nonceBytes = serializeNonce(forcedExit.nonce);
+        const validFromBytes = serializeTimestamp(forcedExit.validFrom || 0);
+        const validUntilBytes = serializeTimestamp(forcedExit.validUntil || MAX_TIMESTAMP);
         const msgBytes = ethers.utils.concat([
             typeBytes,
             initiatorAccountIdBytes,
             targetBytes,
             tokenIdBytes,
             feeBytes,
-            nonceBytes
+            nonceBytes,
+            validFromBytes,
+            validUntilBytes
         ]);
 
         return msgBytes;
@@ -159,6 +196,8 @@ export class Signer {
         tokenId: number;
         fee: BigNumberish;
         nonce: number;
+        validFrom?: number;
+        validUntil?: number;
     }): Promise<ForcedExit> {
         const msgBytes = this.forcedExitSignBytes(forcedExit);
         const signature = await signTransactionBytes(this.#privateKey, msgBytes);
@@ -169,6 +208,8 @@ export class Signer {
             token: forcedExit.tokenId,
             fee: BigNumber.from(forcedExit.fee).toString(),
             nonce: forcedExit.nonce,
+            validFrom: forcedExit.validFrom || 0,
+            validUntil: forcedExit.validUntil || MAX_TIMESTAMP,
             signature
         };
     }
@@ -180,6 +221,8 @@ export class Signer {
         feeTokenId: number;
         fee: BigNumberish;
         nonce: number;
+        validFrom?: number;
+        validUntil?: number;
     }): Uint8Array {
         const typeBytes = new Uint8Array([7]); // Tx type (1 byte)
         const accountIdBytes = serializeAccountId(changePubKey.accountId);
@@ -188,6 +231,8 @@ export class Signer {
         const tokenIdBytes = serializeTokenId(changePubKey.feeTokenId);
         const feeBytes = serializeFeePacked(changePubKey.fee);
         const nonceBytes = serializeNonce(changePubKey.nonce);
+        const validFromBytes = serializeTimestamp(changePubKey.validFrom || 0);
+        const validUntilBytes = serializeTimestamp(changePubKey.validUntil || MAX_TIMESTAMP);
         const msgBytes = ethers.utils.concat([
             typeBytes,
             accountIdBytes,
@@ -195,7 +240,9 @@ export class Signer {
             pubKeyHashBytes,
             tokenIdBytes,
             feeBytes,
-            nonceBytes
+            nonceBytes,
+            validFromBytes,
+            validUntilBytes
         ]);
 
         return msgBytes;
@@ -208,6 +255,8 @@ export class Signer {
         feeTokenId: number;
         fee: BigNumberish;
         nonce: number;
+        validFrom?: number;
+        validUntil?: number;
     }): Promise<ChangePubKey> {
         const msgBytes = this.changePubKeySignBytes(changePubKey);
         const signature = await signTransactionBytes(this.#privateKey, msgBytes);
@@ -219,6 +268,8 @@ export class Signer {
             feeToken: changePubKey.feeTokenId,
             fee: BigNumber.from(changePubKey.fee).toString(),
             nonce: changePubKey.nonce,
+            validFrom: changePubKey.validFrom || 0,
+            validUntil: changePubKey.validUntil || MAX_TIMESTAMP,
             signature,
             ethSignature: null
         };
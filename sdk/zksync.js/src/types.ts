@@ -57,7 +57,7 @@ export type EthSignerType = {
 };
 
 export interface TxEthSignature {
-    type: 'EthereumSignature' | 'EIP1271Signature';
+    type: 'EthereumSignature' | 'EIP1271Signature' | 'EIP712Signature';
     signature: string;
 }
 
@@ -75,6 +75,8 @@ export interface Transfer {
     amount: BigNumberish;
     fee: BigNumberish;
     nonce: number;
+    validFrom: number;
+    validUntil: number;
     signature: Signature;
 }
 
@@ -87,6 +89,8 @@ export interface Withdraw {
     amount: BigNumberish;
     fee: BigNumberish;
     nonce: number;
+    validFrom: number;
+    validUntil: number;
     signature: Signature;
 }
 
@@ -97,6 +101,8 @@ export interface ForcedExit {
     token: number;
     fee: BigNumberish;
     nonce: number;
+    validFrom: number;
+    validUntil: number;
     signature: Signature;
 }
 
@@ -108,6 +114,8 @@ export interface ChangePubKey {
     feeToken: number;
     fee: BigNumberish;
     nonce: number;
+    validFrom: number;
+    validUntil: number;
     signature: Signature;
     ethSignature: string;
 }
@@ -23,6 +23,9 @@ export const ERC20_APPROVE_TRESHOLD = BigNumber.from(
 
 export const ERC20_DEPOSIT_GAS_LIMIT = BigNumber.from('300000'); // 300k
 
+// Default upper bound of the transactions time range.
+export const MAX_TIMESTAMP = 4294967295; // 2^32 - 1
+
 const AMOUNT_EXPONENT_BIT_WIDTH = 5;
 const AMOUNT_MANTISSA_BIT_WIDTH = 35;
 const FEE_EXPONENT_BIT_WIDTH = 5;
@@ -430,6 +433,13 @@ export function serializeNonce(nonce: number): Uint8Array {
     return numberToBytesBE(nonce, 4);
 }
 
+export function serializeTimestamp(time: number): Uint8Array {
+    if (time < 0) {
+        throw new Error('Negative timestamp');
+    }
+    return ethers.utils.zeroPad(ethers.utils.arrayify(BigNumber.from(time)), 8);
+}
+
 export function serializeTransfer(transfer: Transfer): Uint8Array {
     const type = new Uint8Array([5]); // tx type
     const accountId = serializeAccountId(transfer.accountId);
@@ -439,7 +449,9 @@ export function serializeTransfer(transfer: Transfer): Uint8Array {
     const amount = serializeAmountPacked(transfer.amount);
     const fee = serializeFeePacked(transfer.fee);
     const nonce = serializeNonce(transfer.nonce);
-    return ethers.utils.concat([type, accountId, from, to, token, amount, fee, nonce]);
+    const validFrom = serializeTimestamp(transfer.validFrom);
+    const validUntil = serializeTimestamp(transfer.validUntil);
+    return ethers.utils.concat([type, accountId, from, to, token, amount, fee, nonce, validFrom, validUntil]);
 }
 
 function numberToBytesBE(number: number, bytes: number): Uint8Array {